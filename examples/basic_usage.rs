@@ -0,0 +1,29 @@
+//! Cover a circle with hexagons and print their IDs.
+//!
+//! ```sh
+//! cargo run --example basic_usage -- 33.4484 -112.0740 500 9
+//! ```
+
+use h3o::{LatLng, Resolution};
+use smallest_enclosing_h3::{error::Result, SmallestEnclosingH3Builder};
+use std::env;
+
+fn main() -> Result<()> {
+    // lat, lng, radius in meters and resolution, defaulting to downtown Phoenix
+    let args: Vec<String> = env::args().skip(1).collect();
+    let arg = |i: usize, default: f64| {
+        args.get(i).map_or(default, |arg| {
+            arg.parse().expect("arguments must be numbers")
+        })
+    };
+    let center = LatLng::new(arg(0, 33.4484), arg(1, -112.0740))?;
+    let radius_meters = arg(2, 500.0);
+    let resolution = Resolution::try_from(arg(3, 9.0) as u8)?;
+
+    let smallest_enclosing_h3 =
+        SmallestEnclosingH3Builder::new(center, radius_meters, resolution).build()?;
+    for cell in smallest_enclosing_h3.hexagons()? {
+        println!("{cell}");
+    }
+    Ok(())
+}
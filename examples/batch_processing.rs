@@ -0,0 +1,45 @@
+//! Cover a row of circles at once with the batch API.
+//!
+//! ```sh
+//! cargo run --example batch_processing -- 33.4484 -112.0740 500 9
+//! ```
+//!
+//! With `--features parallel` the circles are covered across threads.
+
+use h3o::{LatLng, Resolution};
+use smallest_enclosing_h3::{batch, error::Result};
+use std::env;
+
+fn main() -> Result<()> {
+    // lat, lng, radius in meters and resolution of the first circle,
+    // defaulting to downtown Phoenix. Nine more follow it east, growing.
+    let args: Vec<String> = env::args().skip(1).collect();
+    let arg = |i: usize, default: f64| {
+        args.get(i).map_or(default, |arg| {
+            arg.parse().expect("arguments must be numbers")
+        })
+    };
+    let (lat, lng) = (arg(0, 33.4484), arg(1, -112.0740));
+    let radius_meters = arg(2, 500.0);
+    let resolution = Resolution::try_from(arg(3, 9.0) as u8)?;
+
+    let inputs = (0..10)
+        .map(|i| {
+            let center = LatLng::new(lat, lng + i as f64 * 0.01)?;
+            Ok((center, radius_meters * (1.0 + i as f64 / 10.0)))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    for ((center, radius_meters), hexagons) in inputs
+        .iter()
+        .zip(batch::compute_all(inputs.clone(), resolution))
+    {
+        println!(
+            "{:.4}, {:.4} within {radius_meters:.0} m: {} hexagons",
+            center.lat(),
+            center.lng(),
+            hexagons?.len()
+        );
+    }
+    Ok(())
+}
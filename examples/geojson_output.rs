@@ -0,0 +1,30 @@
+//! Write the hexagons and the circle they cover to a GeoJSON file.
+//!
+//! ```sh
+//! cargo run --example geojson_output --features geojson_export -- 33.4484 -112.0740 500 9 phoenix.geojson
+//! ```
+
+use h3o::{LatLng, Resolution};
+use smallest_enclosing_h3::{error::Result, SmallestEnclosingH3Builder};
+use std::{env, fs};
+
+fn main() -> Result<()> {
+    // lat, lng, radius in meters, resolution and output path, defaulting to
+    // downtown Phoenix
+    let args: Vec<String> = env::args().skip(1).collect();
+    let arg = |i: usize, default: f64| {
+        args.get(i).map_or(default, |arg| {
+            arg.parse().expect("arguments must be numbers")
+        })
+    };
+    let center = LatLng::new(arg(0, 33.4484), arg(1, -112.0740))?;
+    let radius_meters = arg(2, 500.0);
+    let resolution = Resolution::try_from(arg(3, 9.0) as u8)?;
+    let path = args.get(4).map_or("coverage.geojson", String::as_str);
+
+    let smallest_enclosing_h3 =
+        SmallestEnclosingH3Builder::new(center, radius_meters, resolution).build()?;
+    fs::write(path, smallest_enclosing_h3.to_geojson_string_pretty()?)?;
+    println!("Wrote {path}");
+    Ok(())
+}
@@ -0,0 +1,16 @@
+//! Arbitrary `(lat, lng, radius_meters, resolution)` through the builder, the
+//! cells and the GeoJSON export: any input may fail, but never panic.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use smallest_enclosing_h3::SmallestEnclosingH3Builder;
+
+fuzz_target!(|builder: SmallestEnclosingH3Builder| {
+    let Ok(coverage) = builder.max_cells(100_000).build() else {
+        return;
+    };
+    let _ = coverage.hexagons();
+    let _ = coverage.disk();
+    let _ = coverage.to_geojson();
+});
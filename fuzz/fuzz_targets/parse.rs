@@ -0,0 +1,19 @@
+//! Arbitrary bytes through every parser of untrusted input: errors only,
+//! never a panic.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use smallest_enclosing_h3::{golden, polyline, postcard, wkt, SmallestEnclosingH3Builder};
+
+fuzz_target!(|bytes: &[u8]| {
+    let _ = postcard::from_postcard(bytes);
+    let _ = golden::verify(bytes);
+
+    let Ok(text) = std::str::from_utf8(bytes) else {
+        return;
+    };
+    let _ = wkt::parse_wkt_point(text);
+    let _ = polyline::decode_polyline(text, 5);
+    let _ = SmallestEnclosingH3Builder::from_json_config(text);
+});
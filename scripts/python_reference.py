@@ -0,0 +1,62 @@
+#!/usr/bin/env python3
+"""Reference coverage for tests/python_compat.rs, computed with h3-py 4.
+
+Reads one case per line from stdin, as `lat lng radius_meters resolution`,
+and prints the hex ids of its coverage on the matching line of stdout,
+space separated, in any order. A case the reference can't compute prints
+`! <reason>` instead.
+
+The coverage is the grid ring at k around the center cell, where k is the
+largest grid distance from the center cell to the cells of the circle's
+edge due north, east, south and west.
+"""
+
+import math
+import sys
+
+import h3
+
+EARTH_RADIUS = 6371000.0
+
+
+def destination_point(lat, lng, distance, bearing):
+    """The point `distance` meters from (lat, lng) along `bearing` radians."""
+    lat1, lng1 = math.radians(lat), math.radians(lng)
+    angular = distance / EARTH_RADIUS
+    lat2 = math.asin(
+        math.sin(lat1) * math.cos(angular)
+        + math.cos(lat1) * math.sin(angular) * math.cos(bearing)
+    )
+    lng2 = lng1 + math.atan2(
+        math.sin(bearing) * math.sin(angular) * math.cos(lat1),
+        math.cos(angular) - math.sin(lat1) * math.sin(lat2),
+    )
+    lng2 = math.degrees(lng2)
+    if not -180.0 <= lng2 <= 180.0:
+        lng2 = (lng2 + 180.0) % 360.0 - 180.0
+    return math.degrees(lat2), lng2
+
+
+def coverage(lat, lng, radius_meters, resolution):
+    center = h3.latlng_to_cell(lat, lng, resolution)
+    k = 0
+    for bearing in (0.0, 90.0, 180.0, 270.0):
+        edge = destination_point(lat, lng, radius_meters, math.radians(bearing))
+        edge_cell = h3.latlng_to_cell(*edge, resolution)
+        k = max(k, h3.grid_distance(center, edge_cell))
+    return h3.grid_ring(center, k)
+
+
+def main():
+    for line in sys.stdin:
+        lat, lng, radius_meters, resolution = line.split()
+        try:
+            cells = coverage(float(lat), float(lng), float(radius_meters), int(resolution))
+            print(" ".join(sorted(cells)))
+        except Exception as error:  # noqa: BLE001, reported to the test
+            print(f"! {error}")
+        sys.stdout.flush()
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,142 @@
+use crate::error::{Result, SmallestEnclosingH3Error};
+use h3o::Resolution;
+use std::fmt;
+use std::sync::{Mutex, PoisonError};
+
+/// A change the crate would make to what the caller asked for.
+///
+/// Lenient builders apply these silently; strict builders (see
+/// [`crate::SmallestEnclosingH3Builder::strict`]) turn each one into
+/// [`SmallestEnclosingH3Error::StrictModeViolation`], except for those the
+/// caller asked for explicitly (see [`Adjustment::is_requested`]). Either way
+/// the adjustments applied are listed in
+/// [`crate::EffectiveParameters::adjustments`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Adjustment {
+    /// A center latitude beyond a pole is folded back over it
+    NormalizeLatitude { from: f64, to: f64 },
+    /// A center longitude outside [-180, 180] is wrapped into range
+    NormalizeLongitude { from: f64, to: f64 },
+    /// The exact grid distance to the circle's edge can't be computed (pentagon
+    /// distortion), so the circle is flood-filled as a large spherical cap is,
+    /// and its ring distance estimated
+    ApproximateRingDistance { estimate: u32 },
+    /// The center, as `(lat, lng)`, is snapped to the centroid of its cell,
+    /// see [`crate::SmallestEnclosingH3Builder::snap_center_to_resolution`]
+    SnapCenter { from: (f64, f64), to: (f64, f64) },
+    /// The cells are computed at a coarser resolution to fit within the
+    /// cell limit, see
+    /// [`crate::SmallestEnclosingH3Builder::resolution_downgrade_fallback`]
+    CoarsenResolution { from: Resolution, to: Resolution },
+    /// The ring is moved out past the grid distance to the circle's edge until
+    /// it covers the whole boundary, see
+    /// [`crate::SmallestEnclosingH3Builder::ensure_full_coverage`]
+    ExpandRingDistance { from: u32, to: u32 },
+}
+
+impl Adjustment {
+    /// Whether the caller asked for the adjustment, so strict mode allows it
+    pub fn is_requested(&self) -> bool {
+        matches!(
+            self,
+            Adjustment::SnapCenter { .. }
+                | Adjustment::CoarsenResolution { .. }
+                | Adjustment::ExpandRingDistance { .. }
+        )
+    }
+
+    /// Whether the adjustment moves the center
+    pub fn moves_center(&self) -> bool {
+        !matches!(
+            self,
+            Adjustment::ApproximateRingDistance { .. }
+                | Adjustment::CoarsenResolution { .. }
+                | Adjustment::ExpandRingDistance { .. }
+        )
+    }
+}
+
+impl fmt::Display for Adjustment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Adjustment::NormalizeLatitude { from, to } => {
+                write!(f, "would normalize latitude from {from} to {to}")
+            }
+            Adjustment::NormalizeLongitude { from, to } => {
+                write!(f, "would normalize longitude from {from} to {to}")
+            }
+            Adjustment::ApproximateRingDistance { estimate } => {
+                write!(f, "would approximate the ring distance as {estimate}")
+            }
+            Adjustment::SnapCenter { from, to } => {
+                write!(f, "would snap the center from {from:?} to {to:?}")
+            }
+            Adjustment::CoarsenResolution { from, to } => {
+                write!(f, "would coarsen the resolution from {from} to {to}")
+            }
+            Adjustment::ExpandRingDistance { from, to } => {
+                write!(f, "would expand the ring distance from {from} to {to}")
+            }
+        }
+    }
+}
+
+/// The single place deciding whether an adjustment may be applied, which
+/// records the ones that are
+#[derive(Debug, Default)]
+pub(crate) struct AdjustmentPolicy {
+    pub(crate) strict: bool,
+    applied: Mutex<Vec<Adjustment>>,
+}
+
+impl AdjustmentPolicy {
+    pub(crate) fn allow(&self, adjustment: Adjustment) -> Result<()> {
+        if self.strict && !adjustment.is_requested() {
+            return Err(SmallestEnclosingH3Error::StrictModeViolation(adjustment));
+        }
+        // Cached results may be computed twice by racing threads
+        let mut applied = self.applied.lock().unwrap_or_else(PoisonError::into_inner);
+        if !applied.contains(&adjustment) {
+            applied.push(adjustment);
+        }
+        Ok(())
+    }
+
+    /// The adjustments applied so far, in order
+    pub(crate) fn applied(&self) -> Vec<Adjustment> {
+        self.applied
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    /// A policy for covering new inputs, equally strict but with nothing
+    /// applied yet
+    pub(crate) fn for_new_inputs(&self) -> Self {
+        Self {
+            strict: self.strict,
+            applied: Mutex::default(),
+        }
+    }
+
+    /// A policy for covering the same inputs afresh, keeping the adjustments
+    /// made to the inputs but not those made while covering
+    pub(crate) fn for_new_coverage(&self) -> Self {
+        let applied = self
+            .applied()
+            .into_iter()
+            .filter(|adjustment| {
+                !matches!(
+                    adjustment,
+                    Adjustment::ApproximateRingDistance { .. }
+                        | Adjustment::CoarsenResolution { .. }
+                        | Adjustment::ExpandRingDistance { .. }
+                )
+            })
+            .collect();
+        Self {
+            strict: self.strict,
+            applied: Mutex::new(applied),
+        }
+    }
+}
@@ -0,0 +1,143 @@
+//! Thresholded coverage difference alerts with hysteresis.
+
+use h3o::CellIndex;
+use std::collections::HashSet;
+
+/// The outcome of one [`CoverageComparator::evaluate`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ComparisonVerdict {
+    /// No alert is raised
+    Quiet,
+    /// The difference rose above the trigger level, raising the alert
+    Triggered,
+    /// The alert raised earlier is still active
+    Alerting,
+    /// The difference fell below the clear level, clearing the alert
+    Cleared,
+}
+
+/// Alerts when a recomputed coverage differs from the stored one by more than
+/// a threshold of cells, without flapping around it.
+///
+/// The alert triggers once the number of differing cells exceeds
+/// `threshold_cells + hysteresis_cells`, and clears once it drops below
+/// `threshold_cells - hysteresis_cells`. With the `serde` feature the
+/// comparator, alert state included, can be persisted between runs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CoverageComparator {
+    threshold_cells: usize,
+    hysteresis_cells: usize,
+    alerting: bool,
+}
+
+impl CoverageComparator {
+    pub fn new(threshold_cells: usize, hysteresis_cells: usize) -> Self {
+        Self {
+            threshold_cells,
+            hysteresis_cells,
+            alerting: false,
+        }
+    }
+
+    /// Whether an alert is currently raised
+    pub fn is_alerting(&self) -> bool {
+        self.alerting
+    }
+
+    /// Compare `old` and `new` by the number of cells in only one of them,
+    /// updating the alert state
+    pub fn evaluate(&mut self, old: &[CellIndex], new: &[CellIndex]) -> ComparisonVerdict {
+        self.evaluate_difference(symmetric_difference(old, new))
+    }
+
+    /// Like [`Self::evaluate`], for an already computed number of differing
+    /// cells
+    pub fn evaluate_difference(&mut self, difference: usize) -> ComparisonVerdict {
+        let trigger = self.threshold_cells.saturating_add(self.hysteresis_cells);
+        let clear = self.threshold_cells.saturating_sub(self.hysteresis_cells);
+
+        match self.alerting {
+            false if difference > trigger => {
+                self.alerting = true;
+                ComparisonVerdict::Triggered
+            }
+            false => ComparisonVerdict::Quiet,
+            true if difference < clear => {
+                self.alerting = false;
+                ComparisonVerdict::Cleared
+            }
+            true => ComparisonVerdict::Alerting,
+        }
+    }
+}
+
+// Number of cells in exactly one of `old` and `new`
+fn symmetric_difference(old: &[CellIndex], new: &[CellIndex]) -> usize {
+    let old: HashSet<CellIndex> = old.iter().copied().collect();
+    let new: HashSet<CellIndex> = new.iter().copied().collect();
+    old.symmetric_difference(&new).count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SmallestEnclosingH3Builder;
+    use h3o::{LatLng, Resolution};
+
+    #[test]
+    fn test_flapping_triggers_and_clears_once() {
+        let mut comparator = CoverageComparator::new(10, 3);
+
+        // Flapping around the threshold, then a real change that settles back
+        let differences = [
+            0, 9, 11, 12, 13, 14, 12, 9, 11, 13, 8, 7, 6, 8, 11, 13, 2, 0,
+        ];
+        let verdicts: Vec<ComparisonVerdict> = differences
+            .iter()
+            .map(|&difference| comparator.evaluate_difference(difference))
+            .collect();
+
+        let count = |verdict| verdicts.iter().filter(|v| **v == verdict).count();
+        assert_eq!(count(ComparisonVerdict::Triggered), 1);
+        assert_eq!(count(ComparisonVerdict::Cleared), 1);
+        assert_eq!(verdicts[5], ComparisonVerdict::Triggered);
+        assert_eq!(verdicts[12], ComparisonVerdict::Cleared);
+        assert!(!comparator.is_alerting());
+    }
+
+    #[test]
+    fn test_evaluate_coverages() {
+        let center = LatLng::new(33.4484, -112.0740).unwrap();
+        let coverage = |radius_meters| {
+            SmallestEnclosingH3Builder::new(center, radius_meters, Resolution::Twelve)
+                .build()
+                .unwrap()
+                .hexagons()
+                .unwrap()
+        };
+        let (old, new) = (coverage(50.0), coverage(500.0));
+
+        let mut comparator = CoverageComparator::new(5, 1);
+        assert_eq!(comparator.evaluate(&old, &old), ComparisonVerdict::Quiet);
+        assert_eq!(
+            comparator.evaluate(&old, &new),
+            ComparisonVerdict::Triggered
+        );
+        assert_eq!(comparator.evaluate(&new, &new), ComparisonVerdict::Cleared);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_comparator_state_round_trip() {
+        let mut comparator = CoverageComparator::new(10, 3);
+        comparator.evaluate_difference(20);
+
+        let json = serde_json::to_string(&comparator).unwrap();
+        let mut restored: CoverageComparator = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored, comparator);
+        assert!(restored.is_alerting());
+        assert_eq!(restored.evaluate_difference(9), ComparisonVerdict::Alerting);
+    }
+}
@@ -0,0 +1,154 @@
+use crate::{parallel, Result, SmallestEnclosingH3, SmallestEnclosingH3Builder};
+use h3o::{CellIndex, LatLng, Resolution};
+use std::collections::HashSet;
+
+/// Compute the enclosing hexagons of every circle in `circles`.
+///
+/// Results are returned in input order. With the `parallel` feature enabled the
+/// circles are processed across threads.
+pub fn hexagons_for_all(circles: &[SmallestEnclosingH3]) -> Vec<Result<Vec<CellIndex>>> {
+    parallel::map(circles, |circle| circle.hexagons())
+}
+
+/// Compute the enclosing hexagons of a circle for every `(center, radius in
+/// meters)` pair in `inputs`, all at `resolution`.
+///
+/// Like [`hexagons_for_all`], results are returned in input order and the
+/// `parallel` feature spreads the circles across threads. An invalid input
+/// only fails its own entry.
+pub fn compute_all(
+    inputs: Vec<(LatLng, f64)>,
+    resolution: Resolution,
+) -> Vec<Result<Vec<CellIndex>>> {
+    parallel::map(&inputs, |&(center, radius_meters)| {
+        SmallestEnclosingH3Builder::new(center, radius_meters, resolution)
+            .build()?
+            .hexagons()
+    })
+}
+
+impl SmallestEnclosingH3 {
+    /// The cells within `radius_meters` of any of `centers`: the union of
+    /// every center's [`SmallestEnclosingH3::disk`] at `resolution`, sorted
+    /// and without duplicates.
+    ///
+    /// The circles are covered one at a time, so memory grows with the number
+    /// of unique cells rather than the number of centers. No centers give no
+    /// cells, and any invalid circle fails the whole call.
+    pub fn from_multiple_centers(
+        centers: Vec<LatLng>,
+        radius_meters: f64,
+        resolution: Resolution,
+    ) -> Result<Vec<CellIndex>> {
+        let mut cells = HashSet::new();
+        for center in centers {
+            let disk = SmallestEnclosingH3Builder::new(center, radius_meters, resolution)
+                .build()?
+                .disk()?;
+            cells.extend(disk);
+        }
+        let mut cells: Vec<CellIndex> = cells.into_iter().collect();
+        cells.sort_unstable();
+        Ok(cells)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::SmallestEnclosingH3Error;
+
+    #[test]
+    fn test_hexagons_for_all_matches_individual() {
+        let circles: Vec<SmallestEnclosingH3> = (0..100)
+            .map(|i| {
+                let center = LatLng::new(33.4484 + i as f64 * 0.01, -112.0740).unwrap();
+                SmallestEnclosingH3Builder::new(center, 50.0 + i as f64, Resolution::Twelve)
+                    .build()
+                    .unwrap()
+            })
+            .collect();
+
+        let results = hexagons_for_all(&circles);
+        assert_eq!(results.len(), circles.len());
+        for (circle, result) in circles.iter().zip(results) {
+            assert_eq!(result.unwrap(), circle.hexagons().unwrap());
+        }
+    }
+
+    #[test]
+    fn test_compute_all() {
+        let mut inputs: Vec<(LatLng, f64)> = (0..100)
+            .map(|i| {
+                let center = LatLng::new(33.4484, -112.0740 + i as f64 * 0.01).unwrap();
+                (center, 100.0 + i as f64)
+            })
+            .collect();
+        inputs[7].1 = -1.0;
+
+        let results = compute_all(inputs.clone(), Resolution::Eleven);
+        assert_eq!(results.len(), inputs.len());
+        for (i, ((center, radius_meters), result)) in inputs.into_iter().zip(results).enumerate() {
+            if i == 7 {
+                assert!(matches!(
+                    result,
+                    Err(SmallestEnclosingH3Error::InvalidRadius(_))
+                ));
+                continue;
+            }
+            let expected =
+                SmallestEnclosingH3Builder::new(center, radius_meters, Resolution::Eleven)
+                    .build()
+                    .unwrap()
+                    .hexagons()
+                    .unwrap();
+            assert_eq!(result.unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn test_from_multiple_centers() {
+        let stores: Vec<LatLng> = [(33.4484, -112.0740), (33.4490, -112.0700), (33.5, -112.0)]
+            .into_iter()
+            .map(|(lat, lng)| LatLng::new(lat, lng).unwrap())
+            .collect();
+        let cells =
+            SmallestEnclosingH3::from_multiple_centers(stores.clone(), 500.0, Resolution::Ten)
+                .unwrap();
+
+        let disks: Vec<Vec<CellIndex>> = stores
+            .iter()
+            .map(|center| {
+                SmallestEnclosingH3Builder::new(*center, 500.0, Resolution::Ten)
+                    .build()
+                    .unwrap()
+                    .disk()
+                    .unwrap()
+            })
+            .collect();
+        let mut expected: Vec<CellIndex> = disks.iter().flatten().copied().collect();
+        expected.sort_unstable();
+        expected.dedup();
+        assert_eq!(cells, expected);
+        // The first two circles overlap
+        assert!(cells.len() < disks.iter().map(Vec::len).sum());
+
+        // A repeated center adds nothing
+        let mut repeated = stores.clone();
+        repeated.push(stores[0]);
+        assert_eq!(
+            SmallestEnclosingH3::from_multiple_centers(repeated, 500.0, Resolution::Ten).unwrap(),
+            cells
+        );
+
+        assert!(
+            SmallestEnclosingH3::from_multiple_centers(Vec::new(), 500.0, Resolution::Ten)
+                .unwrap()
+                .is_empty()
+        );
+        assert!(matches!(
+            SmallestEnclosingH3::from_multiple_centers(stores, -1.0, Resolution::Ten),
+            Err(SmallestEnclosingH3Error::InvalidRadius(_))
+        ));
+    }
+}
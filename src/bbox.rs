@@ -0,0 +1,445 @@
+use crate::{
+    error::SmallestEnclosingH3Error, geodesy, haversine_distance, CoverageMode, Result,
+    SmallestEnclosingH3, SmallestEnclosingH3Builder,
+};
+use geo::{coord, Rect};
+use h3o::geom::{ContainmentMode, PolyfillConfig, ToCells};
+use h3o::{CellIndex, LatLng, Resolution};
+use std::collections::HashSet;
+
+/// A latitude/longitude bounding box, in degrees
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GeoBBox {
+    pub min_lat: f64,
+    pub min_lng: f64,
+    pub max_lat: f64,
+    pub max_lng: f64,
+}
+
+impl GeoBBox {
+    /// Boxes crossing the antimeridian (`min_lng > max_lng`) aren't supported
+    pub fn new(min_lat: f64, min_lng: f64, max_lat: f64, max_lng: f64) -> Result<Self> {
+        Self::checked(min_lat, min_lng, max_lat, max_lng, false)
+    }
+
+    fn checked(
+        min_lat: f64,
+        min_lng: f64,
+        max_lat: f64,
+        max_lng: f64,
+        across_antimeridian: bool,
+    ) -> Result<Self> {
+        let finite = [min_lat, min_lng, max_lat, max_lng]
+            .iter()
+            .all(|v| v.is_finite());
+        if !finite
+            || !(-90.0..=90.0).contains(&min_lat)
+            || !(-90.0..=90.0).contains(&max_lat)
+            || !(-180.0..=180.0).contains(&min_lng)
+            || !(-180.0..=180.0).contains(&max_lng)
+            || min_lat > max_lat
+            || (min_lng > max_lng && !across_antimeridian)
+        {
+            return Err(SmallestEnclosingH3Error::UnsupportedGeometry(format!(
+                "invalid bounding box ({min_lat}, {min_lng}) to ({max_lat}, {max_lng})"
+            )));
+        }
+
+        Ok(Self {
+            min_lat,
+            min_lng,
+            max_lat,
+            max_lng,
+        })
+    }
+
+    /// Whether the box wraps across the antimeridian, from `min_lng` east to
+    /// `max_lng`
+    pub fn crosses_antimeridian(&self) -> bool {
+        self.min_lng > self.max_lng
+    }
+
+    // The box as rectangles within [-180, 180], split at the antimeridian and
+    // into pieces narrower than 180°, which h3o would otherwise read as
+    // crossing the antimeridian
+    fn to_rects(self) -> Vec<Rect<f64>> {
+        let pieces = if self.crosses_antimeridian() {
+            vec![(self.min_lng, 180.0), (-180.0, self.max_lng)]
+        } else {
+            vec![(self.min_lng, self.max_lng)]
+        };
+        pieces
+            .into_iter()
+            .flat_map(|(min_lng, max_lng)| {
+                let count = ((max_lng - min_lng) / 180.0).floor() as usize + 1;
+                let width = (max_lng - min_lng) / count as f64;
+                (0..count).map(move |i| {
+                    let east = if i + 1 == count {
+                        max_lng
+                    } else {
+                        min_lng + (i + 1) as f64 * width
+                    };
+                    Rect::new(
+                        coord! { x: min_lng + i as f64 * width, y: self.min_lat },
+                        coord! { x: east, y: self.max_lat },
+                    )
+                })
+            })
+            .collect()
+    }
+
+    // Degrees of longitude spanned, eastward from `min_lng`
+    fn lng_span(&self) -> f64 {
+        if self.crosses_antimeridian() {
+            self.max_lng - self.min_lng + 360.0
+        } else {
+            self.max_lng - self.min_lng
+        }
+    }
+
+    fn center(&self) -> (f64, f64) {
+        (
+            (self.min_lat + self.max_lat) / 2.0,
+            geodesy::normalize_lng(self.min_lng + self.lng_span() / 2.0),
+        )
+    }
+
+    // Closed counterclockwise ring of `[lng, lat]` corners
+    pub(crate) fn ring(&self) -> Vec<Vec<f64>> {
+        vec![
+            vec![self.min_lng, self.min_lat],
+            vec![self.max_lng, self.min_lat],
+            vec![self.max_lng, self.max_lat],
+            vec![self.min_lng, self.max_lat],
+            vec![self.min_lng, self.min_lat],
+        ]
+    }
+}
+
+impl SmallestEnclosingH3Builder {
+    /// A builder covering a bounding box, such as a map viewport, instead of a
+    /// circle.
+    ///
+    /// [`SmallestEnclosingH3::disk`] is then every cell covering the box and
+    /// [`SmallestEnclosingH3::hexagons`] the outline of those cells, mirroring
+    /// the circle's [`CoverageMode`]s. The box is exported as the reference
+    /// polygon, tagged `"type": "bbox"`. A box with `min_lng > max_lng`
+    /// crosses the antimeridian and is covered in two parts. The center is the
+    /// box's center and the radius reaches its farthest corner.
+    pub fn from_bbox(
+        min_lat: f64,
+        min_lng: f64,
+        max_lat: f64,
+        max_lng: f64,
+        resolution: Resolution,
+    ) -> Result<Self> {
+        let bbox = GeoBBox::checked(min_lat, min_lng, max_lat, max_lng, true)?;
+        if min_lat == max_lat || min_lng == max_lng {
+            return Err(SmallestEnclosingH3Error::UnsupportedGeometry(format!(
+                "empty bounding box ({min_lat}, {min_lng}) to ({max_lat}, {max_lng})"
+            )));
+        }
+
+        let (lat, lng) = bbox.center();
+        let center = LatLng::new(lat, lng)?;
+        let mut radius_meters: f64 = 0.0;
+        for lat in [min_lat, max_lat] {
+            for lng in [min_lng, max_lng] {
+                let corner = LatLng::new(lat, lng)?;
+                radius_meters = radius_meters.max(haversine_distance(center, corner));
+            }
+        }
+
+        let mut builder = Self::new(center, radius_meters, resolution);
+        builder.rectangle = Some(bbox);
+        Ok(builder)
+    }
+}
+
+impl SmallestEnclosingH3 {
+    /// The bounding box covered instead of a circle, if built with
+    /// [`SmallestEnclosingH3Builder::from_bbox`]
+    pub fn rectangle(&self) -> Option<GeoBBox> {
+        self.rectangle
+    }
+
+    // Every cell covering `bbox`, in index order, or for `CoverageMode::Ring`
+    // only those with a neighbour outside the cover
+    pub(crate) fn rectangle_cells(
+        &self,
+        bbox: GeoBBox,
+        mode: CoverageMode,
+    ) -> Result<Vec<CellIndex>> {
+        let config = PolyfillConfig::new(self.resolution).containment_mode(ContainmentMode::Covers);
+        let rects = bbox
+            .to_rects()
+            .into_iter()
+            .map(h3o::geom::Rect::from_degrees)
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+
+        let estimated = rects.iter().map(|rect| rect.max_cells_count(config)).sum();
+        if estimated > self.max_cells {
+            return Err(SmallestEnclosingH3Error::TooManyCells {
+                estimated,
+                limit: self.max_cells,
+            });
+        }
+
+        let mut cells: Vec<CellIndex> = rects
+            .iter()
+            .flat_map(|rect| rect.to_cells(config))
+            .collect();
+        cells.sort_unstable();
+        cells.dedup();
+
+        if mode == CoverageMode::Ring {
+            let cover: HashSet<CellIndex> = cells.iter().copied().collect();
+            cells.retain(|cell| {
+                cell.grid_disk::<Vec<_>>(1)
+                    .iter()
+                    .any(|neighbor| !cover.contains(neighbor))
+            });
+        }
+        Ok(cells)
+    }
+
+    /// All cells of the bounding box, at the circle's resolution, except those
+    /// within the circle.
+    ///
+    /// The box is tiled by cell centroid. Every cell of [`Self::disk`], the ring
+    /// and everything inside it, is subtracted, so the complement and the disk
+    /// together make up the tiled box wherever the disk lies inside it.
+    pub fn complement_within_bbox(&self, bbox: GeoBBox) -> Result<Vec<CellIndex>> {
+        let rects = bbox
+            .to_rects()
+            .into_iter()
+            .map(h3o::geom::Rect::from_degrees)
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        let config = PolyfillConfig::new(self.resolution);
+
+        let estimated = rects.iter().map(|rect| rect.max_cells_count(config)).sum();
+        if estimated > self.max_cells {
+            return Err(SmallestEnclosingH3Error::TooManyCells {
+                estimated,
+                limit: self.max_cells,
+            });
+        }
+
+        let disk: HashSet<CellIndex> = self.disk()?.into_iter().collect();
+        let mut complement: Vec<CellIndex> = rects
+            .iter()
+            .flat_map(|rect| rect.to_cells(config))
+            .filter(|cell| !disk.contains(cell))
+            .collect();
+        complement.sort_unstable();
+        complement.dedup();
+        Ok(complement)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SmallestEnclosingH3Builder;
+    use h3o::{LatLng, Resolution};
+
+    #[test]
+    fn test_complement_within_bbox() {
+        let center = LatLng::new(33.4484, -112.0740).unwrap();
+        let smallest_enclosing_h3 =
+            SmallestEnclosingH3Builder::new(center, 500.0, Resolution::Eleven)
+                .build()
+                .unwrap();
+
+        // Twice the circle's extent in each direction
+        let lat_delta = 2.0 * 500.0 / 111_320.0;
+        let lng_delta = lat_delta / center.lat().to_radians().cos();
+        let bbox = GeoBBox::new(
+            center.lat() - lat_delta,
+            center.lng() - lng_delta,
+            center.lat() + lat_delta,
+            center.lng() + lng_delta,
+        )
+        .unwrap();
+
+        let complement = smallest_enclosing_h3.complement_within_bbox(bbox).unwrap();
+        let disk = smallest_enclosing_h3.disk().unwrap();
+        let tiled: HashSet<CellIndex> = bbox
+            .to_rects()
+            .into_iter()
+            .flat_map(|rect| {
+                h3o::geom::Rect::from_degrees(rect)
+                    .unwrap()
+                    .to_cells(PolyfillConfig::new(Resolution::Eleven))
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+
+        assert!(disk.iter().all(|cell| tiled.contains(cell)));
+        assert!(complement.iter().all(|cell| !disk.contains(cell)));
+        assert_eq!(tiled.len(), complement.len() + disk.len());
+    }
+
+    #[test]
+    fn test_complement_rejects_huge_bbox() {
+        let center = LatLng::new(33.4484, -112.0740).unwrap();
+        let smallest_enclosing_h3 =
+            SmallestEnclosingH3Builder::new(center, 500.0, Resolution::Fifteen)
+                .build()
+                .unwrap();
+        let bbox = GeoBBox::new(30.0, -115.0, 36.0, -109.0).unwrap();
+
+        assert!(matches!(
+            smallest_enclosing_h3.complement_within_bbox(bbox),
+            Err(SmallestEnclosingH3Error::TooManyCells { .. })
+        ));
+    }
+
+    // Those of `candidates` whose centroid lies in the box
+    fn brute_force_centroids_in(
+        bbox: GeoBBox,
+        candidates: impl IntoIterator<Item = CellIndex>,
+    ) -> HashSet<CellIndex> {
+        candidates
+            .into_iter()
+            .filter(|cell| {
+                let centroid = LatLng::from(*cell);
+                let in_lng = if bbox.crosses_antimeridian() {
+                    centroid.lng() >= bbox.min_lng || centroid.lng() <= bbox.max_lng
+                } else {
+                    (bbox.min_lng..=bbox.max_lng).contains(&centroid.lng())
+                };
+                in_lng && (bbox.min_lat..=bbox.max_lat).contains(&centroid.lat())
+            })
+            .collect()
+    }
+
+    // Checks the cover of `bbox` at `resolution` against the cells of
+    // `candidates` centered in it, and returns the coverage
+    fn assert_covers_centroids(
+        bbox: GeoBBox,
+        resolution: Resolution,
+        candidates: impl IntoIterator<Item = CellIndex>,
+    ) -> SmallestEnclosingH3 {
+        let smallest_enclosing_h3 = SmallestEnclosingH3Builder::from_bbox(
+            bbox.min_lat,
+            bbox.min_lng,
+            bbox.max_lat,
+            bbox.max_lng,
+            resolution,
+        )
+        .unwrap()
+        .build()
+        .unwrap();
+        let cover: HashSet<CellIndex> = smallest_enclosing_h3.disk().unwrap().into_iter().collect();
+        let centroids = brute_force_centroids_in(bbox, candidates);
+
+        // Every cell centered in the box is covered, and every covering cell
+        // touches the box
+        assert!(!centroids.is_empty());
+        assert!(centroids.is_subset(&cover));
+        let near = |cell: &CellIndex| {
+            cell.boundary().iter().any(|vertex| {
+                let in_lng = if bbox.crosses_antimeridian() {
+                    vertex.lng() >= bbox.min_lng - 0.01 || vertex.lng() <= bbox.max_lng + 0.01
+                } else {
+                    (bbox.min_lng - 0.01..=bbox.max_lng + 0.01).contains(&vertex.lng())
+                };
+                in_lng && (bbox.min_lat - 0.01..=bbox.max_lat + 0.01).contains(&vertex.lat())
+            })
+        };
+        assert!(cover.iter().all(near));
+        smallest_enclosing_h3
+    }
+
+    fn assert_covers(bbox: GeoBBox) {
+        // A disk large enough to hold the box
+        let (lat, lng) = bbox.center();
+        let candidates = LatLng::new(lat, lng)
+            .unwrap()
+            .to_cell(Resolution::Eight)
+            .grid_disk::<Vec<_>>(40);
+        let smallest_enclosing_h3 = assert_covers_centroids(bbox, Resolution::Eight, candidates);
+        let cover = smallest_enclosing_h3.disk().unwrap();
+
+        // The outline is the cover's edge
+        let outline = smallest_enclosing_h3.hexagons().unwrap();
+        assert!(!outline.is_empty() && outline.len() < cover.len());
+        assert!(outline.iter().all(|cell| cover.contains(cell)));
+        assert!(!outline.contains(&smallest_enclosing_h3.center.to_cell(Resolution::Eight)));
+    }
+
+    #[test]
+    fn test_from_bbox() {
+        assert_covers(GeoBBox::new(33.40, -112.12, 33.50, -112.02).unwrap());
+    }
+
+    #[test]
+    fn test_from_bbox_across_antimeridian() {
+        let bbox = GeoBBox::checked(-17.1, 179.9, -16.9, -179.9, true).unwrap();
+        assert!(bbox.crosses_antimeridian());
+        assert_covers(bbox);
+
+        let smallest_enclosing_h3 =
+            SmallestEnclosingH3Builder::from_bbox(-17.1, 179.9, -16.9, -179.9, Resolution::Eight)
+                .unwrap()
+                .build()
+                .unwrap();
+        let center = smallest_enclosing_h3.center;
+        assert!((center.lat() + 17.0).abs() < 1e-9);
+        assert!((center.lng().abs() - 180.0).abs() < 1e-9);
+        assert_eq!(smallest_enclosing_h3.rectangle(), Some(bbox));
+    }
+
+    #[test]
+    fn test_from_wide_bbox() {
+        // Boxes of 180° of longitude or more, against every cell of the globe
+        let globe = || CellIndex::base_cells().flat_map(|cell| cell.children(Resolution::Two));
+        let wide = GeoBBox::new(0.0, -179.0, 20.0, 179.0).unwrap();
+        assert_covers_centroids(wide, Resolution::Two, globe());
+
+        let whole = GeoBBox::new(-90.0, -180.0, 90.0, 180.0).unwrap();
+        let coverage = assert_covers_centroids(whole, Resolution::Two, globe());
+        assert_eq!(coverage.disk().unwrap().len(), globe().count());
+    }
+
+    #[test]
+    fn test_from_bbox_validation() {
+        let inverted =
+            SmallestEnclosingH3Builder::from_bbox(34.0, -112.0, 33.0, -111.0, Resolution::Eight);
+        assert!(matches!(
+            inverted,
+            Err(SmallestEnclosingH3Error::UnsupportedGeometry(_))
+        ));
+        assert!(SmallestEnclosingH3Builder::from_bbox(
+            33.0,
+            -112.0,
+            33.0,
+            -111.0,
+            Resolution::Eight
+        )
+        .is_err());
+        assert!(SmallestEnclosingH3Builder::from_bbox(
+            f64::NAN,
+            -112.0,
+            33.0,
+            -111.0,
+            Resolution::Eight
+        )
+        .is_err());
+
+        let annulus =
+            SmallestEnclosingH3Builder::from_bbox(33.0, -112.0, 34.0, -111.0, Resolution::Eight)
+                .unwrap()
+                .inner_radius_meters(100.0)
+                .unwrap();
+        assert!(annulus.build().is_err());
+    }
+
+    #[test]
+    fn test_invalid_bbox() {
+        assert!(GeoBBox::new(10.0, 0.0, 5.0, 1.0).is_err());
+        assert!(GeoBBox::new(0.0, 170.0, 1.0, -170.0).is_err());
+        assert!(GeoBBox::new(0.0, 0.0, 91.0, 1.0).is_err());
+    }
+}
@@ -0,0 +1,148 @@
+//! Dump the coverage of one circle, for one-off checks.
+//!
+//! Exits with 2 on invalid arguments or inputs and 3 when the output can't
+//! be written.
+
+use h3o::{LatLng, Resolution};
+use smallest_enclosing_h3::error::{Result, SmallestEnclosingH3Error};
+use smallest_enclosing_h3::SmallestEnclosingH3Builder;
+use std::fs::File;
+use std::io::{self, Write};
+use std::process::ExitCode;
+
+const USAGE: &str = "\
+Usage: smallest-enclosing-h3 --lat <DEG> --lng <DEG> --radius-m <METERS> --resolution <0-15>
+                             [--format geojson|csv|txt] [--output <FILE>]
+
+Writes the H3 cells enclosing the circle to FILE, or stdout by default, as a
+GeoJSON FeatureCollection (the default), CSV or one cell per line.";
+
+const EXIT_INVALID: u8 = 2;
+const EXIT_IO: u8 = 3;
+
+#[derive(Debug, Clone, Copy)]
+enum Format {
+    GeoJson,
+    Csv,
+    Txt,
+}
+
+struct Args {
+    lat: f64,
+    lng: f64,
+    radius_meters: f64,
+    resolution: i64,
+    format: Format,
+    output: Option<String>,
+}
+
+fn main() -> ExitCode {
+    let args = match parse_args(std::env::args().skip(1)) {
+        Ok(Some(args)) => args,
+        Ok(None) => {
+            println!("{USAGE}");
+            return ExitCode::SUCCESS;
+        }
+        Err(message) => {
+            eprintln!("error: {message}\n\n{USAGE}");
+            return ExitCode::from(EXIT_INVALID);
+        }
+    };
+
+    match run(&args) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("error: {error}");
+            match error {
+                SmallestEnclosingH3Error::IoError(_) => ExitCode::from(EXIT_IO),
+                _ => ExitCode::from(EXIT_INVALID),
+            }
+        }
+    }
+}
+
+// `None` when help was asked for
+fn parse_args(mut args: impl Iterator<Item = String>) -> std::result::Result<Option<Args>, String> {
+    let (mut lat, mut lng, mut radius_meters, mut resolution) = (None, None, None, None);
+    let mut format = Format::GeoJson;
+    let mut output = None;
+
+    while let Some(flag) = args.next() {
+        if flag == "--help" || flag == "-h" {
+            return Ok(None);
+        }
+        let value = args.next().ok_or_else(|| format!("{flag} needs a value"))?;
+        let number = |value: &str| {
+            value
+                .parse::<f64>()
+                .map_err(|_| format!("{flag} must be a number, got {value:?}"))
+        };
+        match flag.as_str() {
+            "--lat" => lat = Some(number(&value)?),
+            "--lng" => lng = Some(number(&value)?),
+            "--radius-m" => radius_meters = Some(number(&value)?),
+            "--resolution" => {
+                resolution = Some(
+                    value
+                        .parse::<i64>()
+                        .map_err(|_| format!("{flag} must be an integer, got {value:?}"))?,
+                )
+            }
+            "--format" => {
+                format = match value.as_str() {
+                    "geojson" => Format::GeoJson,
+                    "csv" => Format::Csv,
+                    "txt" => Format::Txt,
+                    _ => return Err(format!("unknown format {value:?}")),
+                }
+            }
+            "--output" => output = Some(value),
+            _ => return Err(format!("unknown argument {flag:?}")),
+        }
+    }
+
+    Ok(Some(Args {
+        lat: required(lat, "--lat")?,
+        lng: required(lng, "--lng")?,
+        radius_meters: required(radius_meters, "--radius-m")?,
+        resolution: required(resolution, "--resolution")?,
+        format,
+        output,
+    }))
+}
+
+fn required<T>(value: Option<T>, flag: &str) -> std::result::Result<T, String> {
+    value.ok_or_else(|| format!("{flag} is required"))
+}
+
+fn run(args: &Args) -> Result<()> {
+    // Report any out-of-range resolution, negative ones included, the way the
+    // builder does
+    let resolution = u8::try_from(args.resolution).map_err(|_| h3o::error::InvalidResolution {
+        value: None,
+        reason: "out of range",
+    })?;
+    let smallest_enclosing_h3 = SmallestEnclosingH3Builder::new(
+        LatLng::new(args.lat, args.lng)?,
+        args.radius_meters,
+        Resolution::Zero,
+    )
+    .resolution(resolution)?
+    .radius_meters(args.radius_meters)?
+    .build()?;
+
+    let mut contents = match args.format {
+        Format::GeoJson => smallest_enclosing_h3.to_geojson_string()?,
+        Format::Csv => smallest_enclosing_h3.to_csv()?,
+        Format::Txt => smallest_enclosing_h3.hexagon_strings()?.join("\n"),
+    };
+    if !contents.ends_with('\n') {
+        contents.push('\n');
+    }
+
+    match &args.output {
+        Some(path) => File::create(path)?.write_all(contents.as_bytes())?,
+        None => io::stdout().lock().write_all(contents.as_bytes())?,
+    }
+    Ok(())
+}
@@ -0,0 +1,226 @@
+//! Exports for any set of cells, whether computed by [`SmallestEnclosingH3`]
+//! or obtained elsewhere (a polyfill, a database).
+
+use crate::{cell_polygon, error::SmallestEnclosingH3Error, lng_lat_bbox, Result};
+use crate::{SmallestEnclosingH3, CIRCLE_VERTICES};
+use geo::orient::{Direction, Orient};
+use geo::{BooleanOps, MultiPolygon, Polygon};
+use h3o::{CellIndex, LatLng, Resolution};
+
+/// A set of cells with the geometry they cover, if known, for export.
+///
+/// Build one from [`SmallestEnclosingH3::compute`], which keeps the circle as
+/// the reference geometry, or from [`CellCoverage::from_cells`] for cells of
+/// any origin. The cells are kept in the order given.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CellCoverage {
+    cells: Vec<CellIndex>,
+    reference: Option<Polygon<f64>>,
+}
+
+/// Summary of a [`CellCoverage`], see [`CellCoverage::stats`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CellCoverageStats {
+    pub cell_count: usize,
+    /// Total area of the cells in square meters
+    pub area_m2: f64,
+    /// The cells' resolution, `None` for an empty or mixed set
+    pub resolution: Option<Resolution>,
+}
+
+impl CellCoverage {
+    /// `cells` as they are, without a reference geometry
+    pub fn from_cells(cells: Vec<CellIndex>) -> Self {
+        Self {
+            cells,
+            reference: None,
+        }
+    }
+
+    /// Attach the geometry the cells cover, in lng/lat degrees, to export
+    /// alongside them
+    pub fn with_reference(mut self, reference: Polygon<f64>) -> Self {
+        self.reference = Some(reference);
+        self
+    }
+
+    pub fn cells(&self) -> &[CellIndex] {
+        &self.cells
+    }
+
+    pub fn into_cells(self) -> Vec<CellIndex> {
+        self.cells
+    }
+
+    pub fn reference(&self) -> Option<&Polygon<f64>> {
+        self.reference.as_ref()
+    }
+
+    pub fn stats(&self) -> CellCoverageStats {
+        let resolution = self.cells.first().map(|cell| cell.resolution());
+        CellCoverageStats {
+            cell_count: self.cells.len(),
+            area_m2: self.cells.iter().map(|cell| cell.area_m2()).sum(),
+            resolution: resolution
+                .filter(|resolution| self.cells.iter().all(|c| c.resolution() == *resolution)),
+        }
+    }
+
+    /// The cells as hex strings, in the same order
+    pub fn hex_strings(&self) -> Vec<String> {
+        self.cells.iter().map(|cell| cell.to_string()).collect()
+    }
+
+    /// The centroid of each cell, in the same order
+    pub fn centroids(&self) -> Vec<LatLng> {
+        self.cells.iter().copied().map(LatLng::from).collect()
+    }
+
+    /// The boundary of every cell as a closed, counterclockwise polygon in
+    /// lng/lat degrees, in the same order
+    pub fn polygons(&self) -> Vec<Polygon<f64>> {
+        self.cells
+            .iter()
+            .map(|cell| cell_polygon(*cell).orient(Direction::Default))
+            .collect()
+    }
+
+    /// [`Self::polygons`] as one MultiPolygon, shared edges kept
+    pub fn to_geo_multipolygon(&self) -> MultiPolygon<f64> {
+        MultiPolygon::new(self.polygons())
+    }
+
+    /// The union of the cells, oriented as RFC 7946 requires: one polygon
+    /// per connected piece, with holes where the cells enclose a gap
+    pub fn outline(&self) -> MultiPolygon<f64> {
+        self.cells
+            .iter()
+            .map(|cell| MultiPolygon::new(vec![cell_polygon(*cell)]))
+            .fold(MultiPolygon::new(vec![]), |union, cell| union.union(&cell))
+            .orient(Direction::Default)
+    }
+
+    /// [`Self::outline`] as a single polygon. Cells that don't join into one
+    /// piece are a [`SmallestEnclosingH3Error::UnsupportedGeometry`].
+    pub fn to_geo_polygon(&self) -> Result<Polygon<f64>> {
+        let mut outline = self.outline().0;
+        if outline.len() != 1 {
+            return Err(SmallestEnclosingH3Error::UnsupportedGeometry(format!(
+                "the cells form {} separate polygons",
+                outline.len()
+            )));
+        }
+        Ok(outline.remove(0))
+    }
+
+    /// `[min_lng, min_lat, max_lng, max_lat]` over the boundaries of every
+    /// cell. A set across the antimeridian follows the GeoJSON convention of
+    /// `min_lng > max_lng`.
+    pub fn bbox(&self) -> Result<[f64; 4]> {
+        let points = self.cells.iter().flat_map(|cell| {
+            cell.boundary()
+                .iter()
+                .map(|p| (p.lng(), p.lat()))
+                .collect::<Vec<_>>()
+        });
+        lng_lat_bbox(points).ok_or_else(|| {
+            SmallestEnclosingH3Error::UnsupportedGeometry("the coverage is empty".to_string())
+        })
+    }
+}
+
+impl From<Vec<CellIndex>> for CellCoverage {
+    fn from(cells: Vec<CellIndex>) -> Self {
+        Self::from_cells(cells)
+    }
+}
+
+impl SmallestEnclosingH3 {
+    /// [`Self::hexagons`] as a [`CellCoverage`], with the circle of
+    /// [`Self::circle_polygon`] as its reference geometry
+    pub fn compute(&self) -> Result<CellCoverage> {
+        Ok(CellCoverage::from_cells(self.hexagons()?)
+            .with_reference(self.circle_polygon(CIRCLE_VERTICES)?))
+    }
+
+    // `hexagons` as a `CellCoverage` without the circle, for exports that
+    // only need the cells
+    pub(crate) fn hexagon_coverage(&self) -> Result<CellCoverage> {
+        Ok(CellCoverage::from_cells(self.hexagons()?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SmallestEnclosingH3Builder;
+
+    #[test]
+    fn test_from_cells() {
+        // A hand-picked line of three neighbors and one far-off cell
+        let origin = LatLng::new(40.7128, -74.0060)
+            .unwrap()
+            .to_cell(Resolution::Nine);
+        let mut cells: Vec<CellIndex> = origin.grid_disk::<Vec<_>>(1)[..3].to_vec();
+        cells.push(
+            LatLng::new(51.5074, -0.1278)
+                .unwrap()
+                .to_cell(Resolution::Nine),
+        );
+        let coverage = CellCoverage::from_cells(cells.clone());
+
+        assert_eq!(coverage.cells(), cells);
+        assert!(coverage.reference().is_none());
+        let stats = coverage.stats();
+        assert_eq!(stats.cell_count, 4);
+        assert_eq!(stats.resolution, Some(Resolution::Nine));
+        let expected: f64 = cells.iter().map(|cell| cell.area_m2()).sum();
+        assert!((stats.area_m2 - expected).abs() < 1e-6);
+
+        // New York and London stay apart
+        assert_eq!(coverage.outline().0.len(), 2);
+        assert!(matches!(
+            coverage.to_geo_polygon(),
+            Err(SmallestEnclosingH3Error::UnsupportedGeometry(_))
+        ));
+        assert_eq!(coverage.to_geo_multipolygon().0.len(), 4);
+
+        let [min_lng, min_lat, max_lng, max_lat] = coverage.bbox().unwrap();
+        assert!(min_lng < -74.0 && max_lng > -0.13 && min_lat < 40.72 && max_lat > 51.5);
+
+        // Mixed resolutions have none
+        cells.push(origin.parent(Resolution::Eight).unwrap());
+        assert_eq!(CellCoverage::from(cells).stats().resolution, None);
+    }
+
+    #[test]
+    fn test_compute() {
+        let center = LatLng::new(33.4484, -112.0740).unwrap();
+        let smallest_enclosing_h3 = SmallestEnclosingH3Builder::new(center, 500.0, Resolution::Ten)
+            .build()
+            .unwrap();
+        let coverage = smallest_enclosing_h3.compute().unwrap();
+
+        assert_eq!(coverage.cells(), smallest_enclosing_h3.hexagons().unwrap());
+        assert_eq!(
+            coverage.reference(),
+            Some(
+                &smallest_enclosing_h3
+                    .circle_polygon(CIRCLE_VERTICES)
+                    .unwrap()
+            )
+        );
+        assert_eq!(
+            coverage.polygons(),
+            smallest_enclosing_h3.hexagon_polygons().unwrap()
+        );
+        assert_eq!(
+            coverage.bbox().unwrap(),
+            smallest_enclosing_h3.bbox().unwrap()
+        );
+        assert_eq!(
+            coverage.hex_strings(),
+            smallest_enclosing_h3.hexagon_strings().unwrap()
+        );
+    }
+}
@@ -0,0 +1,151 @@
+//! Cell sets tagged with whether their resolutions are uniform.
+//!
+//! Most cell algorithms (outlines, grid distances, set differences) only make
+//! sense for cells of one resolution, and silently produce nonsense for a
+//! mixed set. [`UniformCells`] can only hold cells of a single resolution,
+//! so APIs that require it take `&UniformCells`; [`MixedCells`] makes a
+//! mixed set explicit. Both dereference to `[CellIndex]`.
+
+use crate::{error::SmallestEnclosingH3Error, Result};
+use h3o::{CellIndex, Resolution};
+use std::ops::Deref;
+
+/// Cells that all have the same resolution
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UniformCells {
+    resolution: Resolution,
+    cells: Vec<CellIndex>,
+}
+
+impl UniformCells {
+    /// `cells`, checked to all be at `resolution`. Unlike
+    /// [`UniformCells::try_from`] this accepts an empty set.
+    pub fn new(resolution: Resolution, cells: Vec<CellIndex>) -> Result<Self> {
+        if let Some(cell) = cells.iter().find(|cell| cell.resolution() != resolution) {
+            return Err(SmallestEnclosingH3Error::NonUniformCells(format!(
+                "expected resolution {resolution}, {cell} is at {}",
+                cell.resolution()
+            )));
+        }
+        Ok(Self { resolution, cells })
+    }
+
+    pub fn resolution(&self) -> Resolution {
+        self.resolution
+    }
+
+    pub fn into_vec(self) -> Vec<CellIndex> {
+        self.cells
+    }
+}
+
+impl TryFrom<Vec<CellIndex>> for UniformCells {
+    type Error = SmallestEnclosingH3Error;
+
+    /// Take the resolution of the first cell and check the rest against it.
+    /// An empty set has no resolution to take, so it is rejected; use
+    /// [`UniformCells::new`] instead.
+    fn try_from(cells: Vec<CellIndex>) -> Result<Self> {
+        let resolution = cells.first().map(|cell| cell.resolution()).ok_or_else(|| {
+            SmallestEnclosingH3Error::NonUniformCells(
+                "an empty set of cells has no resolution".to_string(),
+            )
+        })?;
+        Self::new(resolution, cells)
+    }
+}
+
+impl TryFrom<&[CellIndex]> for UniformCells {
+    type Error = SmallestEnclosingH3Error;
+
+    fn try_from(cells: &[CellIndex]) -> Result<Self> {
+        Self::try_from(cells.to_vec())
+    }
+}
+
+impl Deref for UniformCells {
+    type Target = [CellIndex];
+
+    fn deref(&self) -> &[CellIndex] {
+        &self.cells
+    }
+}
+
+impl From<UniformCells> for Vec<CellIndex> {
+    fn from(cells: UniformCells) -> Self {
+        cells.cells
+    }
+}
+
+/// Cells that may have different resolutions
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MixedCells {
+    cells: Vec<CellIndex>,
+}
+
+impl MixedCells {
+    pub fn new(cells: Vec<CellIndex>) -> Self {
+        Self { cells }
+    }
+
+    pub fn into_vec(self) -> Vec<CellIndex> {
+        self.cells
+    }
+}
+
+impl Deref for MixedCells {
+    type Target = [CellIndex];
+
+    fn deref(&self) -> &[CellIndex] {
+        &self.cells
+    }
+}
+
+impl From<Vec<CellIndex>> for MixedCells {
+    fn from(cells: Vec<CellIndex>) -> Self {
+        Self { cells }
+    }
+}
+
+impl From<UniformCells> for MixedCells {
+    fn from(cells: UniformCells) -> Self {
+        Self { cells: cells.cells }
+    }
+}
+
+impl From<MixedCells> for Vec<CellIndex> {
+    fn from(cells: MixedCells) -> Self {
+        cells.cells
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use h3o::LatLng;
+
+    #[test]
+    fn test_uniform_cells_try_from() {
+        let center = LatLng::new(33.4484, -112.0740).unwrap();
+        let disk: Vec<CellIndex> = center.to_cell(Resolution::Nine).grid_disk(2);
+
+        let uniform = UniformCells::try_from(disk.clone()).unwrap();
+        assert_eq!(uniform.resolution(), Resolution::Nine);
+        assert_eq!(uniform.len(), disk.len());
+        assert_eq!(uniform.into_vec(), disk);
+
+        let mut mixed = disk;
+        mixed.push(center.to_cell(Resolution::Ten));
+        assert!(matches!(
+            UniformCells::try_from(mixed.as_slice()),
+            Err(SmallestEnclosingH3Error::NonUniformCells(_))
+        ));
+        let mixed = MixedCells::from(mixed);
+        assert_eq!(mixed.last(), Some(&center.to_cell(Resolution::Ten)));
+
+        assert!(UniformCells::try_from(Vec::new()).is_err());
+        assert!(UniformCells::new(Resolution::Nine, Vec::new())
+            .unwrap()
+            .is_empty());
+    }
+}
@@ -0,0 +1,279 @@
+//! Covering cells classified by how they meet the circle.
+
+use crate::{error::SmallestEnclosingH3Error, geodesy, MathMode, Result, SmallestEnclosingH3};
+use h3o::{CellIndex, LatLng};
+
+/// Where a covering cell lies relative to the circle, see
+/// [`SmallestEnclosingH3::classified_hexagons`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum CellClass {
+    /// Every vertex is within the radius, so the whole cell is inside
+    Interior,
+    /// The cell's boundary crosses the circle
+    Edge,
+    /// The cell doesn't reach the circle at all, covered only because the
+    /// disk of rings over-covers it
+    Outside,
+}
+
+impl CellClass {
+    /// Lowercase name, as exported in the GeoJSON `class` property
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Interior => "interior",
+            Self::Edge => "edge",
+            Self::Outside => "outside",
+        }
+    }
+}
+
+/// Where a point lies relative to the circle and the covering cells, see
+/// [`SmallestEnclosingH3::classify_point`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum PointRegion {
+    /// Within the radius of the center
+    InsideCircle,
+    /// Beyond the radius, but in one of the covering cells
+    InCoverageOnly,
+    /// Beyond the radius and outside every covering cell
+    Outside,
+}
+
+/// A point's [`PointRegion`] and how far it is from the circle
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PointClass {
+    pub region: PointRegion,
+    /// Distance to the circle in meters, negative inside it
+    pub distance_to_circle_m: f64,
+}
+
+impl SmallestEnclosingH3 {
+    /// Every cell of [`Self::disk`] with its [`CellClass`], in the disk's
+    /// order.
+    ///
+    /// Cells are classified geometrically against the circle of
+    /// `radius_meters`, ignoring any inner radius or bearing range: a cell is
+    /// [`CellClass::Interior`] when all its vertices are within the radius,
+    /// [`CellClass::Edge`] when its boundary comes within the radius (or it
+    /// holds the center), and [`CellClass::Outside`] otherwise. A bounding box
+    /// has no circle to classify against, and is a
+    /// [`SmallestEnclosingH3Error::UnsupportedGeometry`].
+    pub fn classified_hexagons(&self) -> Result<Vec<(CellIndex, CellClass)>> {
+        if self.rectangle.is_some() {
+            return Err(SmallestEnclosingH3Error::UnsupportedGeometry(
+                "a bounding box has no circle to classify cells against".to_string(),
+            ));
+        }
+        Ok(self
+            .disk()?
+            .into_iter()
+            .map(|cell| (cell, self.classify_cell(cell)))
+            .collect())
+    }
+
+    /// Whether `point` is inside the circle, only inside the covering cells
+    /// of [`Self::disk`], or outside both, with its distance to the circle.
+    ///
+    /// As for [`Self::classified_hexagons`], the circle is the one of
+    /// `radius_meters`, ignoring any inner radius or bearing range, and a
+    /// bounding box is a [`SmallestEnclosingH3Error::UnsupportedGeometry`].
+    pub fn classify_point(&self, point: LatLng) -> Result<PointClass> {
+        if self.rectangle.is_some() {
+            return Err(SmallestEnclosingH3Error::UnsupportedGeometry(
+                "a bounding box has no circle to classify points against".to_string(),
+            ));
+        }
+        let distance_to_circle_m = self.distance_from_center(point) - self.radius_meters;
+        let region = if distance_to_circle_m <= 0.0 {
+            PointRegion::InsideCircle
+        } else if self.disk()?.contains(&point.to_cell(self.resolution)) {
+            PointRegion::InCoverageOnly
+        } else {
+            PointRegion::Outside
+        };
+        Ok(PointClass {
+            region,
+            distance_to_circle_m,
+        })
+    }
+
+    pub(crate) fn classify_cell(&self, cell: CellIndex) -> CellClass {
+        let boundary = cell.boundary();
+        let inside = boundary
+            .iter()
+            .filter(|vertex| self.distance_from_center(**vertex) <= self.radius_meters)
+            .count();
+        if inside == boundary.len() {
+            return CellClass::Interior;
+        }
+        if inside > 0 || cell == self.center.to_cell(cell.resolution()) {
+            return CellClass::Edge;
+        }
+
+        // No vertex is inside, but the circle may still bulge across an edge
+        let crosses = (0..boundary.len()).any(|i| {
+            let (a, b) = (boundary[i], boundary[(i + 1) % boundary.len()]);
+            self.distance_to_edge(a, b) <= self.radius_meters
+        });
+        if crosses {
+            CellClass::Edge
+        } else {
+            CellClass::Outside
+        }
+    }
+
+    // Distance from the center to the nearest point of the edge from `a` to
+    // `b`, in meters, see `MathMode`
+    fn distance_to_edge(&self, a: LatLng, b: LatLng) -> f64 {
+        match self.math_mode {
+            MathMode::Spherical => self.sphere_meters(geodesy::distance_to_segment(
+                (self.center.lat(), self.center.lng()),
+                (a.lat(), a.lng()),
+                (b.lat(), b.lng()),
+            )),
+            MathMode::PlanarLocal => {
+                let (a, b) = (self.local_plane(a), self.local_plane(b));
+                let edge = (b.0 - a.0, b.1 - a.1);
+                let length2 = edge.0 * edge.0 + edge.1 * edge.1;
+                let t = if length2 == 0.0 {
+                    0.0
+                } else {
+                    (-(a.0 * edge.0 + a.1 * edge.1) / length2).clamp(0.0, 1.0)
+                };
+                (a.0 + t * edge.0).hypot(a.1 + t * edge.1)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{haversine_distance, SmallestEnclosingH3Builder};
+    use h3o::Resolution;
+
+    // Points along every edge of `cell`, vertices included
+    fn edge_samples(cell: CellIndex) -> Vec<LatLng> {
+        let boundary = cell.boundary();
+        (0..boundary.len())
+            .flat_map(|i| {
+                let (a, b) = (boundary[i], boundary[(i + 1) % boundary.len()]);
+                (0..20).map(move |step| {
+                    let t = step as f64 / 20.0;
+                    LatLng::new(
+                        a.lat() + t * (b.lat() - a.lat()),
+                        a.lng() + t * (b.lng() - a.lng()),
+                    )
+                    .unwrap()
+                })
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_classified_hexagons() {
+        let center = LatLng::new(33.4484, -112.0740).unwrap();
+        for math_mode in [MathMode::Spherical, MathMode::PlanarLocal] {
+            let coverage = SmallestEnclosingH3Builder::new(center, 1000.0, Resolution::Ten)
+                .math_mode(math_mode)
+                .build()
+                .unwrap();
+            let radius = coverage.radius_meters();
+            let within = |point: LatLng| haversine_distance(center, point) <= radius + 1e-3;
+
+            let classified = coverage.classified_hexagons().unwrap();
+            assert_eq!(classified.len(), coverage.disk().unwrap().len());
+            for &(cell, class) in &classified {
+                let boundary = cell.boundary();
+                match class {
+                    CellClass::Interior => {
+                        assert!(boundary.iter().all(|vertex| within(*vertex)), "{cell}");
+                    }
+                    CellClass::Edge => {
+                        assert!(!boundary.iter().all(|vertex| within(*vertex)), "{cell}");
+                        assert!(edge_samples(cell).into_iter().any(within), "{cell}");
+                    }
+                    CellClass::Outside => {
+                        assert!(!edge_samples(cell).into_iter().any(within), "{cell}");
+                    }
+                }
+            }
+
+            // The center cell is inside, and the disk's corners over-cover
+            let count = |class| classified.iter().filter(|(_, c)| *c == class).count();
+            let center_cell = center.to_cell(Resolution::Ten);
+            assert!(classified.contains(&(center_cell, CellClass::Interior)));
+            assert!(count(CellClass::Edge) > 0 && count(CellClass::Outside) > 0);
+        }
+    }
+
+    #[test]
+    fn test_classify_small_circle() {
+        // A circle well inside the center cell touches no vertex
+        let center = LatLng::new(33.4484, -112.0740).unwrap();
+        let coverage = SmallestEnclosingH3Builder::new(center, 1.0, Resolution::Eight)
+            .build()
+            .unwrap();
+        assert_eq!(
+            coverage.classified_hexagons().unwrap(),
+            vec![(center.to_cell(Resolution::Eight), CellClass::Edge)]
+        );
+
+        let bbox =
+            SmallestEnclosingH3Builder::from_bbox(33.44, -112.08, 33.45, -112.07, Resolution::Ten)
+                .unwrap()
+                .build()
+                .unwrap();
+        assert!(matches!(
+            bbox.classified_hexagons(),
+            Err(SmallestEnclosingH3Error::UnsupportedGeometry(_))
+        ));
+        assert!(matches!(
+            bbox.classify_point(LatLng::new(33.445, -112.075).unwrap()),
+            Err(SmallestEnclosingH3Error::UnsupportedGeometry(_))
+        ));
+    }
+
+    #[test]
+    fn test_classify_point() {
+        for center in [
+            LatLng::new(33.4484, -112.0740).unwrap(),
+            LatLng::new(60.1699, 24.9384).unwrap(),
+        ] {
+            let coverage = SmallestEnclosingH3Builder::new(center, 1000.0, Resolution::Nine)
+                .build()
+                .unwrap();
+            // Due north, just either side of the circle
+            let north =
+                |meters: f64| LatLng::new(center.lat() + meters / 111_195.0, center.lng()).unwrap();
+
+            let class = coverage.classify_point(center).unwrap();
+            assert_eq!(class.region, PointRegion::InsideCircle);
+            assert!((class.distance_to_circle_m + 1000.0).abs() < 1e-6);
+
+            let inside = coverage.classify_point(north(990.0)).unwrap();
+            assert_eq!(inside.region, PointRegion::InsideCircle);
+            assert!(inside.distance_to_circle_m < 0.0);
+            let beyond = coverage.classify_point(north(1010.0)).unwrap();
+            assert_eq!(beyond.region, PointRegion::InCoverageOnly);
+            assert!(beyond.distance_to_circle_m > 0.0);
+
+            // Either side of the outermost covering cell due north
+            let disk = coverage.disk().unwrap();
+            let mut last_covered = 1010.0;
+            while disk.contains(&north(last_covered + 10.0).to_cell(Resolution::Nine)) {
+                last_covered += 10.0;
+            }
+            let covered = coverage.classify_point(north(last_covered)).unwrap();
+            assert_eq!(covered.region, PointRegion::InCoverageOnly);
+            let outside = coverage.classify_point(north(last_covered + 10.0)).unwrap();
+            assert_eq!(outside.region, PointRegion::Outside);
+            assert!(outside.distance_to_circle_m > covered.distance_to_circle_m);
+        }
+    }
+}
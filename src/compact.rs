@@ -0,0 +1,268 @@
+//! Compacted cell sets, where complete groups of sibling cells are replaced
+//! by their parent.
+
+use crate::{Result, SmallestEnclosingH3};
+use h3o::{CellIndex, Resolution};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+impl SmallestEnclosingH3 {
+    /// [`Self::disk`] compacted: every complete set of seven siblings is
+    /// replaced by its parent, recursively, in index order. Uncompact with
+    /// [`CellIndex::uncompact`] at the configured resolution to recover the
+    /// disk.
+    pub fn compact(&self) -> Result<Vec<CellIndex>> {
+        Ok(CellIndex::compact(self.disk()?)?.collect())
+    }
+
+    /// The number of `cells` at each resolution, such as those of
+    /// [`Self::compact`] or [`Self::hexagons_adaptive`], to see how much
+    /// compaction saved. Resolutions without cells are left out.
+    pub fn hexagon_resolution_histogram(cells: &[CellIndex]) -> HashMap<Resolution, usize> {
+        let mut histogram = HashMap::new();
+        for cell in cells {
+            *histogram.entry(cell.resolution()).or_insert(0) += 1;
+        }
+        histogram
+    }
+
+    /// Cover the circle with `fine` cells, coarsened towards `coarse` where
+    /// possible: the interior gets coarse cells and only the rim is refined.
+    ///
+    /// Starting from [`Self::disk`] at `fine`, every complete set of siblings
+    /// is replaced by its parent, recursively up to `coarse`, as long as the
+    /// parent lies entirely within the circle (every vertex within the
+    /// radius). The result is a mixed-resolution set in index order where no
+    /// cell is an ancestor of another, and every `fine` cell of the disk has
+    /// itself or an ancestor in it.
+    ///
+    /// `coarse` finer than `fine` is a
+    /// [`crate::error::SmallestEnclosingH3Error::InvalidResolution`].
+    pub fn hexagons_adaptive(
+        &self,
+        coarse: Resolution,
+        fine: Resolution,
+    ) -> Result<Vec<CellIndex>> {
+        if coarse > fine {
+            return Err(h3o::error::InvalidResolution {
+                value: Some(u8::from(coarse)),
+                reason: "must not be finer than the fine resolution",
+            }
+            .into());
+        }
+
+        let mut cells: BTreeSet<CellIndex> = self.at_resolution(fine).disk()?.into_iter().collect();
+        for resolution in (u8::from(coarse)..u8::from(fine)).rev() {
+            let resolution = Resolution::try_from(resolution)?;
+            let child_resolution = resolution.succ().expect("finer than coarse");
+
+            // Present children of each parent one level up
+            let mut siblings: BTreeMap<CellIndex, u64> = BTreeMap::new();
+            for cell in cells
+                .iter()
+                .filter(|cell| cell.resolution() == child_resolution)
+            {
+                if let Some(parent) = cell.parent(resolution) {
+                    *siblings.entry(parent).or_insert(0) += 1;
+                }
+            }
+            for (parent, count) in siblings {
+                if count == parent.children_count(child_resolution) && self.within_radius(parent) {
+                    for child in parent.children(child_resolution) {
+                        cells.remove(&child);
+                    }
+                    cells.insert(parent);
+                }
+            }
+        }
+        Ok(cells.into_iter().collect())
+    }
+
+    // Whether every vertex of `cell` is within the radius
+    fn within_radius(&self, cell: CellIndex) -> bool {
+        cell.boundary()
+            .iter()
+            .all(|vertex| self.distance_from_center(*vertex) <= self.radius_meters)
+    }
+
+    /// [`Self::compact`] as a JSON array of hex strings, as taken by H3 REST
+    /// services and tile servers
+    #[cfg(any(feature = "czml", feature = "geojson_export"))]
+    pub fn to_h3_compact_json(&self) -> Result<String> {
+        let cells: Vec<String> = self
+            .compact()?
+            .iter()
+            .map(|cell| cell.to_string())
+            .collect();
+        Ok(serde_json::to_string(&cells)?)
+    }
+}
+
+/// Parse a JSON array of compacted hex strings, as written by
+/// [`SmallestEnclosingH3::to_h3_compact_json`], and uncompact it to
+/// `resolution`.
+///
+/// A cell finer than `resolution` is a
+/// [`crate::error::SmallestEnclosingH3Error::InvalidResolution`].
+#[cfg(any(feature = "czml", feature = "geojson_export"))]
+pub fn from_h3_compact_json(json: &str, resolution: h3o::Resolution) -> Result<Vec<CellIndex>> {
+    let cells = serde_json::from_str::<Vec<String>>(json)?
+        .iter()
+        .map(|cell| Ok(cell.parse::<CellIndex>()?))
+        .collect::<Result<Vec<_>>>()?;
+    if cells.iter().any(|cell| cell.resolution() > resolution) {
+        return Err(h3o::error::InvalidResolution {
+            value: Some(u8::from(resolution)),
+            reason: "coarser than a compacted cell",
+        }
+        .into());
+    }
+    Ok(CellIndex::uncompact(cells, resolution).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SmallestEnclosingH3Builder;
+    use h3o::{LatLng, Resolution};
+
+    fn smallest_enclosing_h3() -> SmallestEnclosingH3 {
+        let center = LatLng::new(33.4484, -112.0740).unwrap();
+        SmallestEnclosingH3Builder::new(center, 2000.0, Resolution::Ten)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_compact_round_trip() {
+        let smallest_enclosing_h3 = smallest_enclosing_h3();
+        let compacted = smallest_enclosing_h3.compact().unwrap();
+        let mut disk = smallest_enclosing_h3.disk().unwrap();
+        assert!(compacted.len() < disk.len());
+        assert!(compacted
+            .iter()
+            .any(|cell| cell.resolution() < Resolution::Ten));
+
+        let mut uncompacted: Vec<CellIndex> =
+            CellIndex::uncompact(compacted, Resolution::Ten).collect();
+        uncompacted.sort_unstable();
+        disk.sort_unstable();
+        assert_eq!(uncompacted, disk);
+    }
+
+    #[test]
+    fn test_hexagon_resolution_histogram() {
+        let smallest_enclosing_h3 = smallest_enclosing_h3();
+        let compacted = smallest_enclosing_h3.compact().unwrap();
+        let histogram = SmallestEnclosingH3::hexagon_resolution_histogram(&compacted);
+        assert_eq!(histogram.values().sum::<usize>(), compacted.len());
+        assert!(histogram.len() > 1);
+        assert!(histogram
+            .keys()
+            .all(|resolution| *resolution <= Resolution::Ten));
+
+        // Each cell stands for 7^(10 - r) cells of the disk
+        let uncompacted: u64 = histogram
+            .iter()
+            .map(|(resolution, count)| {
+                *count as u64 * 7u64.pow(u32::from(10 - u8::from(*resolution)))
+            })
+            .sum();
+        assert_eq!(
+            uncompacted,
+            smallest_enclosing_h3.disk().unwrap().len() as u64
+        );
+
+        assert!(SmallestEnclosingH3::hexagon_resolution_histogram(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_hexagons_adaptive() {
+        let smallest_enclosing_h3 = smallest_enclosing_h3();
+        let cells = smallest_enclosing_h3
+            .hexagons_adaptive(Resolution::Seven, Resolution::Ten)
+            .unwrap();
+        let fine = smallest_enclosing_h3.disk().unwrap();
+        assert!(cells.len() < fine.len());
+        assert!(cells.iter().any(|cell| cell.resolution() < Resolution::Ten));
+        assert!(cells
+            .iter()
+            .all(|cell| cell.resolution() >= Resolution::Seven));
+
+        // No cell is an ancestor of another
+        let set: BTreeSet<CellIndex> = cells.iter().copied().collect();
+        let covering = |cell: CellIndex| {
+            (u8::from(Resolution::Seven)..=u8::from(cell.resolution()))
+                .filter_map(|resolution| cell.parent(Resolution::try_from(resolution).unwrap()))
+                .find(|ancestor| set.contains(ancestor))
+        };
+        for cell in &cells {
+            assert_eq!(covering(*cell), Some(*cell), "{cell}");
+        }
+
+        // Every fine cell is covered, and so is every point sampled inside
+        // the circle (short of the rim, where the disk's own k decides)
+        assert!(fine.iter().all(|cell| covering(*cell).is_some()));
+        for distance in [0.0, 400.0, 800.0, 1200.0, 1600.0] {
+            for bearing in (0..16).map(|i| f64::from(i) * 22.5) {
+                let center = smallest_enclosing_h3.center;
+                let (lat, lng) = crate::geodesy::destination_point(
+                    (center.lat(), center.lng()),
+                    distance,
+                    bearing.to_radians(),
+                );
+                let point = LatLng::new(lat, lng).unwrap();
+                assert!(covering(point.to_cell(Resolution::Ten)).is_some());
+            }
+        }
+
+        // Merged parents lie entirely within the circle
+        assert!(cells
+            .iter()
+            .filter(|cell| cell.resolution() < Resolution::Ten)
+            .all(|cell| smallest_enclosing_h3.within_radius(*cell)));
+
+        assert_eq!(
+            smallest_enclosing_h3
+                .hexagons_adaptive(Resolution::Ten, Resolution::Ten)
+                .unwrap(),
+            fine.iter()
+                .copied()
+                .collect::<BTreeSet<_>>()
+                .into_iter()
+                .collect::<Vec<_>>()
+        );
+        assert!(matches!(
+            smallest_enclosing_h3.hexagons_adaptive(Resolution::Eleven, Resolution::Ten),
+            Err(crate::error::SmallestEnclosingH3Error::InvalidResolution(_))
+        ));
+    }
+
+    #[cfg(any(feature = "czml", feature = "geojson_export"))]
+    #[test]
+    fn test_h3_compact_json_round_trip() {
+        use crate::error::SmallestEnclosingH3Error;
+
+        let smallest_enclosing_h3 = smallest_enclosing_h3();
+        let json = smallest_enclosing_h3.to_h3_compact_json().unwrap();
+        assert!(json.starts_with("[\"8"));
+
+        let mut cells = from_h3_compact_json(&json, Resolution::Ten).unwrap();
+        let mut disk = smallest_enclosing_h3.disk().unwrap();
+        cells.sort_unstable();
+        disk.sort_unstable();
+        assert_eq!(cells, disk);
+
+        assert!(matches!(
+            from_h3_compact_json(&json, Resolution::Eight),
+            Err(SmallestEnclosingH3Error::InvalidResolution(_))
+        ));
+        assert!(matches!(
+            from_h3_compact_json("[\"not a cell\"]", Resolution::Ten),
+            Err(SmallestEnclosingH3Error::InvalidCellIndex(_))
+        ));
+        assert!(matches!(
+            from_h3_compact_json("{", Resolution::Ten),
+            Err(SmallestEnclosingH3Error::SerializationError(_))
+        ));
+    }
+}
@@ -0,0 +1,119 @@
+//! Frozen coverages for detecting behavioral drift across h3o versions.
+//!
+//! `fixtures/h3o_compat.golden` holds a dozen cases in the [`crate::golden`]
+//! format, recorded once and bundled with the crate. [`h3o_compat_check`]
+//! recomputes them with whatever h3o version the build resolved, so
+//! downstream crates can run it in their own CI after a dependency update.
+//!
+//! Policy: the fixtures are only regenerated together with a minor (before
+//! 1.0) or major version bump of this crate. Any h3o update that makes
+//! [`h3o_compat_check`] report a mismatch changes the cells users get for
+//! the same inputs, and must be released as such rather than as a patch.
+//!
+//! To regenerate after such a change:
+//!
+//! ```sh
+//! cargo test --lib compat_fixtures -- --ignored
+//! ```
+
+use crate::{error::SmallestEnclosingH3Error, golden::GoldenMismatch};
+
+const FIXTURES: &str = include_str!("../fixtures/h3o_compat.golden");
+
+/// A bundled fixture the current h3o no longer reproduces
+#[derive(Debug)]
+pub enum CompatMismatch {
+    /// The fixture's cells changed
+    Cells(GoldenMismatch),
+    /// The fixture can no longer be computed at all
+    Failed {
+        /// 1-based line of the fixture in `fixtures/h3o_compat.golden`
+        line: usize,
+        error: SmallestEnclosingH3Error,
+    },
+}
+
+/// Recompute every bundled fixture and return those that differ. An empty
+/// result means the resolved h3o behaves like the one they were recorded
+/// with.
+pub fn h3o_compat_check() -> Vec<CompatMismatch> {
+    FIXTURES
+        .lines()
+        .enumerate()
+        .filter_map(|(i, line)| match crate::golden::verify_line(i + 1, line) {
+            Ok(mismatch) => mismatch.map(CompatMismatch::Cells),
+            Err(error) => Some(CompatMismatch::Failed { line: i + 1, error }),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SmallestEnclosingH3Builder;
+    use h3o::{LatLng, Resolution};
+
+    // Center latitude and longitude, radius in meters and resolution of each
+    // fixture
+    const FIXTURE_CASES: [(f64, f64, f64, u8); 12] = [
+        (33.4484, -112.074, 50.0, 12),
+        (51.5074, -0.1278, 1000.0, 9),
+        (0.0, 0.0, 500.0, 10),
+        (0.0, 179.999, 2000.0, 8),
+        (89.9, 0.0, 5000.0, 7),
+        (-33.8688, 151.2093, 250.0, 11),
+        (23.7179, -67.1323, 20000.0, 6),
+        (35.6762, 139.6503, 10.0, 15),
+        (40.0, -100.0, 300000.0, 2),
+        (64.1466, -21.9426, 3000.0, 8),
+        (-34.6037, -58.3816, 120.0, 13),
+        (-33.9249, 18.4241, 800.0, 10),
+    ];
+
+    #[test]
+    fn test_h3o_compat_check() {
+        let mismatches = h3o_compat_check();
+        assert!(mismatches.is_empty(), "{mismatches:#?}");
+    }
+
+    #[test]
+    fn test_fixtures_cover_every_case() {
+        // Recorded through `LatLng`, so coordinates only round-trip closely
+        let recorded: Vec<Vec<f64>> = FIXTURES
+            .lines()
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(|line| {
+                line.split('\t')
+                    .take(4)
+                    .map(|field| field.parse().unwrap())
+                    .collect()
+            })
+            .collect();
+        assert_eq!(recorded.len(), FIXTURE_CASES.len());
+
+        for (fields, (lat, lng, radius_meters, resolution)) in recorded.iter().zip(FIXTURE_CASES) {
+            let expected = [lat, lng, radius_meters, f64::from(resolution)];
+            for (field, expected) in fields.iter().zip(expected) {
+                assert!((field - expected).abs() < 1e-9, "{fields:?}");
+            }
+        }
+    }
+
+    #[test]
+    #[ignore = "rewrites fixtures/h3o_compat.golden"]
+    fn regenerate_fixtures() {
+        let mut fixtures = b"# h3o compatibility fixtures, see src/compat_fixtures.rs\n".to_vec();
+        for (lat, lng, radius_meters, resolution) in FIXTURE_CASES {
+            let center = LatLng::new(lat, lng).unwrap();
+            let resolution = Resolution::try_from(resolution).unwrap();
+            let smallest_enclosing_h3 =
+                SmallestEnclosingH3Builder::new(center, radius_meters, resolution)
+                    .build()
+                    .unwrap();
+            crate::golden::record(&smallest_enclosing_h3, &mut fixtures).unwrap();
+        }
+
+        let path = concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures/h3o_compat.golden");
+        std::fs::write(path, fixtures).unwrap();
+    }
+}
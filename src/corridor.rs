@@ -0,0 +1,81 @@
+use crate::{
+    error::SmallestEnclosingH3Error, haversine_distance, Result, SmallestEnclosingH3Builder,
+};
+use h3o::{CellIndex, LatLng, Resolution};
+use std::collections::BTreeSet;
+
+/// Cells at `resolution` within `buffer_meters` of the line through `points`.
+///
+/// Segments are densified to about one cell edge between samples, and the
+/// disk around each sample (see [`crate::SmallestEnclosingH3::disk`]) is added
+/// to the corridor. Cells are returned sorted and without duplicates.
+pub fn corridor(
+    points: &[LatLng],
+    buffer_meters: f64,
+    resolution: Resolution,
+) -> Result<Vec<CellIndex>> {
+    if points.is_empty() {
+        return Err(SmallestEnclosingH3Error::UnsupportedGeometry(
+            "a corridor needs at least one point".to_string(),
+        ));
+    }
+
+    let mut cells = BTreeSet::new();
+    let edge_length_m = crate::resolution_info(resolution).avg_edge_length_km * 1000.0;
+    for point in densify(points, edge_length_m)? {
+        let disk = SmallestEnclosingH3Builder::new(point, buffer_meters, resolution)
+            .build()?
+            .disk()?;
+        cells.extend(disk);
+    }
+
+    Ok(cells.into_iter().collect())
+}
+
+// Points along the line no more than about `spacing` meters apart
+fn densify(points: &[LatLng], spacing: f64) -> Result<Vec<LatLng>> {
+    let mut dense = vec![points[0]];
+
+    for pair in points.windows(2) {
+        let (from, to) = (pair[0], pair[1]);
+        let steps = (haversine_distance(from, to) / spacing).ceil().max(1.0) as usize;
+        for step in 1..=steps {
+            let t = step as f64 / steps as f64;
+            dense.push(LatLng::new(
+                from.lat() + (to.lat() - from.lat()) * t,
+                from.lng() + (to.lng() - from.lng()) * t,
+            )?);
+        }
+    }
+
+    Ok(dense)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_corridor_covers_route() {
+        let from = LatLng::new(33.4484, -112.0740).unwrap();
+        let to = LatLng::new(33.4484, -112.0).unwrap();
+        let cells = corridor(&[from, to], 300.0, Resolution::Ten).unwrap();
+
+        let midpoint = LatLng::new(33.4484, -112.037).unwrap();
+        for point in [from, midpoint, to] {
+            assert!(cells.contains(&point.to_cell(Resolution::Ten)));
+        }
+        // Nothing far off the route
+        let offset = LatLng::new(33.47, -112.037).unwrap();
+        assert!(!cells.contains(&offset.to_cell(Resolution::Ten)));
+        assert!(cells.windows(2).all(|w| w[0] < w[1]));
+    }
+
+    #[test]
+    fn test_corridor_needs_points() {
+        assert!(matches!(
+            corridor(&[], 100.0, Resolution::Ten),
+            Err(SmallestEnclosingH3Error::UnsupportedGeometry(_))
+        ));
+    }
+}
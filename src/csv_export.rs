@@ -0,0 +1,185 @@
+use crate::{OutputCrs, Result, SmallestEnclosingH3};
+use h3o::LatLng;
+use std::fmt::Write;
+
+impl SmallestEnclosingH3 {
+    /// Export the hexagon IDs as CSV, one per line under an `h3_index` header
+    pub fn to_csv(&self) -> Result<String> {
+        self.to_csv_with_coordinates(false)
+    }
+
+    /// Like [`Self::to_csv`], optionally adding `lat` and `lng` columns with
+    /// each cell's centroid
+    pub fn to_csv_with_coordinates(&self, coordinates: bool) -> Result<String> {
+        let mut csv = String::from(if coordinates {
+            "h3_index,lat,lng\n"
+        } else {
+            "h3_index\n"
+        });
+
+        for cell in self.hexagons()? {
+            if coordinates {
+                let centroid = LatLng::from(cell);
+                // Writing to a String can't fail
+                let _ = writeln!(csv, "{cell},{},{}", centroid.lat(), centroid.lng());
+            } else {
+                let _ = writeln!(csv, "{cell}");
+            }
+        }
+
+        Ok(csv)
+    }
+
+    /// Like [`Self::to_csv_with_coordinates`], with each centroid in `crs`.
+    /// Web Mercator centroids go in `x` and `y` columns, in meters.
+    pub fn to_csv_with_crs(&self, crs: OutputCrs) -> Result<String> {
+        if crs == OutputCrs::Wgs84 {
+            return self.to_csv_with_coordinates(true);
+        }
+
+        let mut csv = String::from("h3_index,x,y\n");
+        for cell in self.hexagons()? {
+            let centroid = LatLng::from(cell);
+            let [x, y] = crs.project(centroid.lng(), centroid.lat());
+            let _ = writeln!(csv, "{cell},{x},{y}");
+        }
+        Ok(csv)
+    }
+
+    /// Stream the hexagons into `writer` as CSV, under a `hex_id, resolution,
+    /// center_lat, center_lng, distance_from_center_m` header. Centroids have
+    /// 7 decimals and great-circle distances from the center 3, so reruns
+    /// diff cleanly.
+    pub fn write_csv<W: std::io::Write>(&self, mut writer: W) -> Result<()> {
+        writeln!(
+            writer,
+            "hex_id,resolution,center_lat,center_lng,distance_from_center_m"
+        )?;
+        for cell in self.hexagons()? {
+            let centroid = LatLng::from(cell);
+            writeln!(
+                writer,
+                "{cell},{},{:.7},{:.7},{:.3}",
+                u8::from(cell.resolution()),
+                centroid.lat(),
+                centroid.lng(),
+                self.distance_from_center(centroid)
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{OutputCrs, SmallestEnclosingH3Builder};
+    use h3o::{CellIndex, LatLng, Resolution};
+
+    #[test]
+    fn test_csv_export() {
+        let center = LatLng::new(33.4484, -112.0740).unwrap();
+        let smallest_enclosing_h3 =
+            SmallestEnclosingH3Builder::new(center, 50.0, Resolution::Twelve)
+                .build()
+                .unwrap();
+
+        let csv = smallest_enclosing_h3.to_csv().unwrap();
+        let mut lines = csv.lines();
+        assert_eq!(lines.next(), Some("h3_index"));
+
+        let cells: Vec<CellIndex> = lines.map(|line| line.parse().unwrap()).collect();
+        assert_eq!(cells, smallest_enclosing_h3.hexagons().unwrap());
+    }
+
+    #[test]
+    fn test_csv_export_with_coordinates() {
+        let center = LatLng::new(33.4484, -112.0740).unwrap();
+        let smallest_enclosing_h3 =
+            SmallestEnclosingH3Builder::new(center, 50.0, Resolution::Twelve)
+                .build()
+                .unwrap();
+
+        let csv = smallest_enclosing_h3.to_csv_with_coordinates(true).unwrap();
+        let mut lines = csv.lines();
+        assert_eq!(lines.next(), Some("h3_index,lat,lng"));
+
+        let rows: Vec<Vec<&str>> = lines.map(|line| line.split(',').collect()).collect();
+        assert_eq!(rows.len(), smallest_enclosing_h3.hexagons().unwrap().len());
+        for row in rows {
+            let [cell, lat, lng] = row.as_slice() else {
+                panic!("Expected 3 columns, got {row:?}");
+            };
+            let cell: CellIndex = cell.parse().unwrap();
+            let centroid = LatLng::new(lat.parse().unwrap(), lng.parse().unwrap()).unwrap();
+            assert_eq!(centroid, LatLng::from(cell));
+            assert_eq!(centroid.to_cell(Resolution::Twelve), cell);
+        }
+    }
+
+    #[test]
+    fn test_write_csv() {
+        let center = LatLng::new(33.4484, -112.0740).unwrap();
+        let smallest_enclosing_h3 =
+            SmallestEnclosingH3Builder::new(center, 50.0, Resolution::Twelve)
+                .build()
+                .unwrap();
+
+        let mut csv = Vec::new();
+        smallest_enclosing_h3.write_csv(&mut csv).unwrap();
+        let csv = String::from_utf8(csv).unwrap();
+        let mut lines = csv.lines();
+        assert_eq!(
+            lines.next(),
+            Some("hex_id,resolution,center_lat,center_lng,distance_from_center_m")
+        );
+
+        let rows: Vec<Vec<&str>> = lines.map(|line| line.split(',').collect()).collect();
+        let hexagons = smallest_enclosing_h3.hexagons().unwrap();
+        assert_eq!(rows.len(), hexagons.len());
+        for (row, cell) in rows.iter().zip(&hexagons) {
+            let [hex_id, resolution, lat, lng, distance] = row.as_slice() else {
+                panic!("Expected 5 columns, got {row:?}");
+            };
+            assert_eq!(hex_id.parse::<CellIndex>().unwrap(), *cell);
+            assert_eq!(*resolution, "12");
+            assert_eq!(lat.split('.').nth(1).unwrap().len(), 7);
+            assert_eq!(lng.split('.').nth(1).unwrap().len(), 7);
+            assert_eq!(distance.split('.').nth(1).unwrap().len(), 3);
+            let distance: f64 = distance.parse().unwrap();
+            assert!(distance > 0.0 && distance < 100.0, "{distance}");
+        }
+
+        let row = rows.iter().find(|row| row[0] == "8c29b6d357acdff").unwrap();
+        assert_eq!(&row[2..], ["33.4478275", "-112.0742198", "66.847"]);
+    }
+
+    #[test]
+    fn test_csv_export_web_mercator() {
+        let center = LatLng::new(33.4484, -112.0740).unwrap();
+        let smallest_enclosing_h3 =
+            SmallestEnclosingH3Builder::new(center, 50.0, Resolution::Twelve)
+                .build()
+                .unwrap();
+
+        assert_eq!(
+            smallest_enclosing_h3
+                .to_csv_with_crs(OutputCrs::Wgs84)
+                .unwrap(),
+            smallest_enclosing_h3.to_csv_with_coordinates(true).unwrap()
+        );
+
+        let csv = smallest_enclosing_h3
+            .to_csv_with_crs(OutputCrs::WebMercator)
+            .unwrap();
+        let mut lines = csv.lines();
+        assert_eq!(lines.next(), Some("h3_index,x,y"));
+        for line in lines {
+            let row: Vec<&str> = line.split(',').collect();
+            let cell: CellIndex = row[0].parse().unwrap();
+            let centroid = LatLng::from(cell);
+            let (x, y) = crate::geodesy::web_mercator((centroid.lat(), centroid.lng()));
+            assert_eq!(row[1].parse::<f64>().unwrap(), x);
+            assert_eq!(row[2].parse::<f64>().unwrap(), y);
+        }
+    }
+}
@@ -0,0 +1,243 @@
+use crate::{error::SmallestEnclosingH3Error, grid_ring, Result, SmallestEnclosingH3};
+use geo::{LineString, Polygon};
+use h3o::geom::ToGeo;
+use serde_json::{json, Value};
+
+impl SmallestEnclosingH3 {
+    /// Export the coverage as a CZML document in which the rings around the
+    /// center cell appear one after another, `seconds_per_ring` apart, starting
+    /// at `start` (an ISO 8601 UTC timestamp such as `2024-01-01T00:00:00Z`).
+    ///
+    /// The document holds a `document` packet, one polygon packet per ring
+    /// from 0 (the center cell) to k, and a static `circle` polyline. Rings stay
+    /// visible once they have appeared. A ring whose outline splits into several
+    /// polygons gets one packet per polygon.
+    pub fn to_czml(&self, start: &str, seconds_per_ring: f64) -> Result<String> {
+        if !seconds_per_ring.is_finite() || seconds_per_ring <= 0.0 {
+            return Err(SmallestEnclosingH3Error::InvalidTimestamp(
+                "Seconds per ring must be positive".to_string(),
+            ));
+        }
+
+        let start_ms = parse_timestamp(start)?;
+        let (k, _) = self.enclosing_ring()?;
+        let ring_start =
+            |ring: u32| start_ms + (f64::from(ring) * seconds_per_ring * 1000.0) as i64;
+        let end = format_timestamp(ring_start(k + 1));
+
+        let mut packets = vec![json!({
+            "id": "document",
+            "name": "smallest-enclosing-h3",
+            "version": "1.0",
+            "clock": {
+                "interval": format!("{}/{}", format_timestamp(start_ms), end),
+                "currentTime": format_timestamp(start_ms),
+                "range": "CLAMPED",
+            },
+        })];
+
+        let center_cell = self.center.to_cell(self.resolution);
+        for ring in 0..=k {
+            let outline = grid_ring(center_cell, ring).to_geom(true)?;
+            let availability = format!("{}/{}", format_timestamp(ring_start(ring)), end);
+
+            for (part, polygon) in outline.into_iter().enumerate() {
+                let id = match part {
+                    0 => format!("ring-{ring}"),
+                    _ => format!("ring-{ring}-{part}"),
+                };
+                packets.push(ring_packet(id, ring, &availability, &polygon));
+            }
+        }
+
+        let circle: Vec<f64> = self
+            .generate_circle_coordinates()?
+            .into_iter()
+            .flat_map(|c| [c[0], c[1], 0.0])
+            .collect();
+        packets.push(json!({
+            "id": "circle",
+            "polyline": {
+                "positions": { "cartographicDegrees": circle },
+                "width": 2,
+                "material": { "solidColor": { "color": { "rgba": [255, 0, 0, 255] } } },
+            },
+        }));
+
+        Ok(Value::Array(packets).to_string())
+    }
+}
+
+fn ring_packet(id: String, ring: u32, availability: &str, polygon: &Polygon<f64>) -> Value {
+    let mut packet = json!({
+        "id": id,
+        "availability": availability,
+        "properties": { "ring": ring },
+        "polygon": {
+            "positions": { "cartographicDegrees": positions(polygon.exterior()) },
+            "material": { "solidColor": { "color": { "rgba": [0, 120, 255, 128] } } },
+            "outline": true,
+        },
+    });
+
+    if !polygon.interiors().is_empty() {
+        let holes: Vec<Vec<f64>> = polygon.interiors().iter().map(positions).collect();
+        packet["polygon"]["holes"] = json!({ "cartographicDegrees": holes });
+    }
+
+    packet
+}
+
+// Flattened lng/lat/height triples
+fn positions(ring: &LineString<f64>) -> Vec<f64> {
+    ring.coords().flat_map(|c| [c.x, c.y, 0.0]).collect()
+}
+
+const MS_PER_DAY: i64 = 86_400_000;
+
+// Milliseconds since the Unix epoch for `YYYY-MM-DDTHH:MM:SS[.fff]Z`
+fn parse_timestamp(timestamp: &str) -> Result<i64> {
+    let invalid = || SmallestEnclosingH3Error::InvalidTimestamp(timestamp.to_string());
+
+    let (date, time) = timestamp
+        .strip_suffix('Z')
+        .and_then(|t| t.split_once('T'))
+        .ok_or_else(invalid)?;
+
+    let date: Vec<i64> = date
+        .split('-')
+        .map(|part| part.parse().map_err(|_| invalid()))
+        .collect::<Result<_>>()?;
+    let time: Vec<&str> = time.split(':').collect();
+    let (&[year, month, day], &[hour, minute, second]) = (date.as_slice(), time.as_slice()) else {
+        return Err(invalid());
+    };
+
+    let hour: i64 = hour.parse().map_err(|_| invalid())?;
+    let minute: i64 = minute.parse().map_err(|_| invalid())?;
+    let second: f64 = second.parse().map_err(|_| invalid())?;
+    if !(1..=12).contains(&month)
+        || !(1..=31).contains(&day)
+        || !(0..24).contains(&hour)
+        || !(0..60).contains(&minute)
+        || !(0.0..61.0).contains(&second)
+    {
+        return Err(invalid());
+    }
+
+    Ok(days_from_civil(year, month, day) * MS_PER_DAY
+        + (hour * 3600 + minute * 60) * 1000
+        + (second * 1000.0).round() as i64)
+}
+
+fn format_timestamp(ms: i64) -> String {
+    let (year, month, day) = civil_from_days(ms.div_euclid(MS_PER_DAY));
+    let ms = ms.rem_euclid(MS_PER_DAY);
+    format!(
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}.{:03}Z",
+        ms / 3_600_000,
+        ms / 60_000 % 60,
+        ms / 1000 % 60,
+        ms % 1000
+    )
+}
+
+// Days since 1970-01-01 in the proleptic Gregorian calendar
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = (shifted_month + 2) % 12 + 1;
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SmallestEnclosingH3Builder;
+    use h3o::{LatLng, Resolution};
+
+    #[test]
+    fn test_czml_export() {
+        let center = LatLng::new(33.4484, -112.0740).unwrap();
+        let smallest_enclosing_h3 =
+            SmallestEnclosingH3Builder::new(center, 50.0, Resolution::Twelve)
+                .build()
+                .unwrap();
+        let (k, _) = smallest_enclosing_h3.enclosing_ring().unwrap();
+
+        let czml = smallest_enclosing_h3
+            .to_czml("2024-02-28T23:59:58Z", 1.5)
+            .unwrap();
+        let packets: Vec<Value> = serde_json::from_str(&czml).unwrap();
+
+        // document + rings 0..=k + circle
+        assert_eq!(packets.len(), k as usize + 3);
+        assert_eq!(packets[0]["id"], "document");
+        assert_eq!(packets.last().unwrap()["id"], "circle");
+
+        let end = "2024-02-29T00:00:04.000Z";
+        assert_eq!(
+            packets[0]["clock"]["interval"],
+            format!("2024-02-28T23:59:58.000Z/{end}")
+        );
+
+        // Each ring appears one step after the previous one and stays visible
+        let mut previous_start = None;
+        for packet in &packets[1..packets.len() - 1] {
+            let availability = packet["availability"].as_str().unwrap();
+            let (ring_start, ring_end) = availability.split_once('/').unwrap();
+            let ring_start = parse_timestamp(ring_start).unwrap();
+            if let Some(previous) = previous_start {
+                assert_eq!(ring_start - previous, 1500);
+            }
+            assert_eq!(ring_end, end);
+            previous_start = Some(ring_start);
+        }
+    }
+
+    #[test]
+    fn test_czml_rejects_invalid_start() {
+        let center = LatLng::new(33.4484, -112.0740).unwrap();
+        let smallest_enclosing_h3 =
+            SmallestEnclosingH3Builder::new(center, 50.0, Resolution::Twelve)
+                .build()
+                .unwrap();
+
+        for start in ["2024-02-28", "2024-13-01T00:00:00Z", "2024-01-01T00:00:00"] {
+            assert!(matches!(
+                smallest_enclosing_h3.to_czml(start, 1.0),
+                Err(SmallestEnclosingH3Error::InvalidTimestamp(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_timestamp_round_trip() {
+        for timestamp in [
+            "1970-01-01T00:00:00.000Z",
+            "2000-02-29T12:34:56.789Z",
+            "2100-12-31T23:59:59.999Z",
+        ] {
+            assert_eq!(
+                format_timestamp(parse_timestamp(timestamp).unwrap()),
+                timestamp
+            );
+        }
+    }
+}
@@ -0,0 +1,65 @@
+//! SQL queries selecting the rows of a DuckDB table within the coverage, with
+//! the functions of DuckDB's `h3` community extension.
+
+use crate::{quote_identifier, Result, SmallestEnclosingH3};
+
+impl SmallestEnclosingH3 {
+    /// `SELECT * FROM {table} WHERE h3_latlng_to_cell(ST_Y({geom_column}),
+    /// ST_X({geom_column}), r) = ANY([...])`, matching the rows whose point
+    /// geometry falls in a cell of [`Self::disk`] at the coverage's
+    /// resolution `r`. The point's coordinates are read with the `spatial`
+    /// extension.
+    ///
+    /// DuckDB's `h3_latlng_to_cell` takes latitude before longitude and
+    /// returns a `UBIGINT`, so the cells are listed as integer literals; the
+    /// extension doesn't bind H3 values as prepared statement parameters.
+    /// `table`, optionally schema-qualified, and `geom_column` are quoted as
+    /// identifiers, so give them as stored.
+    pub fn to_duckdb_h3_query(&self, table: &str, geom_column: &str) -> Result<String> {
+        let cells: Vec<String> = self
+            .disk()?
+            .into_iter()
+            .map(|cell| u64::from(cell).to_string())
+            .collect();
+        let geom_column = quote_identifier(geom_column);
+        Ok(format!(
+            "SELECT * FROM {} WHERE h3_latlng_to_cell(ST_Y({geom_column}), ST_X({geom_column}), {}) = ANY([{}])",
+            quote_identifier(table),
+            u8::from(self.resolution),
+            cells.join(", ")
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::SmallestEnclosingH3Builder;
+    use h3o::{LatLng, Resolution};
+
+    #[test]
+    fn test_to_duckdb_h3_query() {
+        let center = LatLng::new(33.4484, -112.0740).unwrap();
+        let smallest_enclosing_h3 =
+            SmallestEnclosingH3Builder::new(center, 1000.0, Resolution::Nine)
+                .build()
+                .unwrap();
+        let sql = smallest_enclosing_h3
+            .to_duckdb_h3_query("gis.places", "geom")
+            .unwrap();
+
+        let prefix = r#"SELECT * FROM "gis"."places" WHERE h3_latlng_to_cell(ST_Y("geom"), ST_X("geom"), 9) = ANY(["#;
+        assert!(sql.starts_with(prefix), "{sql}");
+        let list = sql[prefix.len()..].strip_suffix("])").unwrap();
+        assert!(!list.contains(['(', ')', '[', ']', '\'']));
+        let cells: Vec<u64> = list.split(", ").map(|cell| cell.parse().unwrap()).collect();
+        assert_eq!(
+            cells,
+            smallest_enclosing_h3
+                .disk()
+                .unwrap()
+                .into_iter()
+                .map(u64::from)
+                .collect::<Vec<_>>()
+        );
+    }
+}
@@ -0,0 +1,229 @@
+//! Elliptical coverage around the center, for satellite footprints, sensor
+//! cones and GPS uncertainty ellipses.
+
+use crate::{
+    error::SmallestEnclosingH3Error, estimate_ring_distance, Result, SmallestEnclosingH3,
+    CIRCLE_VERTICES,
+};
+use geo::{Intersects, LineString, Polygon};
+use h3o::{CellIndex, LatLng};
+
+impl SmallestEnclosingH3 {
+    /// `[lng, lat]` pairs for points on the ellipse around the center with the
+    /// given semi-axes, its major axis along `bearing_degrees` clockwise from
+    /// north, followed by the first point again to close the ring.
+    ///
+    /// The radius and any bearing range, inner radius or bounding box of the
+    /// coverage are ignored; the points are placed like the circle's, see
+    /// [`crate::MathMode`].
+    pub fn generate_ellipse_coordinates(
+        &self,
+        semi_major_meters: f64,
+        semi_minor_meters: f64,
+        bearing_degrees: f64,
+    ) -> Result<Vec<Vec<f64>>> {
+        self.generate_ellipse_coordinates_with(
+            semi_major_meters,
+            semi_minor_meters,
+            bearing_degrees,
+            CIRCLE_VERTICES,
+        )
+    }
+
+    /// [`Self::generate_ellipse_coordinates`] with `num_points` (at least 3)
+    /// points, evenly spaced in the ellipse's parametric angle
+    pub fn generate_ellipse_coordinates_with(
+        &self,
+        semi_major_meters: f64,
+        semi_minor_meters: f64,
+        bearing_degrees: f64,
+        num_points: usize,
+    ) -> Result<Vec<Vec<f64>>> {
+        check_axes(semi_major_meters, semi_minor_meters, bearing_degrees)?;
+        if num_points < 3 {
+            return Err(SmallestEnclosingH3Error::UnsupportedGeometry(format!(
+                "an ellipse needs at least 3 vertices, got {num_points}"
+            )));
+        }
+
+        let mut coordinates = Vec::with_capacity(num_points + 1);
+        for i in 0..num_points {
+            let t = i as f64 * std::f64::consts::TAU / num_points as f64;
+            // Offsets along the major axis and to its right
+            let (along, across) = (semi_major_meters * t.cos(), semi_minor_meters * t.sin());
+            let bearing = bearing_degrees.to_radians() + across.atan2(along);
+            let point = self.point_from_center(along.hypot(across), bearing);
+            coordinates.push(vec![point.x(), point.y()]);
+        }
+
+        // Close the polygon by repeating the first point
+        if let Some(first) = coordinates.first().cloned() {
+            coordinates.push(first);
+        }
+        Ok(coordinates)
+    }
+
+    /// The cells at the configured resolution meeting the ellipse of
+    /// [`Self::generate_ellipse_coordinates`], in grid disk order.
+    ///
+    /// k is the grid distance to the ends of the major axis, plus one ring for
+    /// the grid's anisotropy; the disk at k is then filtered to the cells whose
+    /// boundary meets the ellipse on the center's local plane. The count is
+    /// checked against [`crate::SmallestEnclosingH3Builder::max_cells`] before
+    /// the disk is generated.
+    pub fn ellipse_hexagons(
+        &self,
+        semi_major_meters: f64,
+        semi_minor_meters: f64,
+        bearing_degrees: f64,
+    ) -> Result<Vec<CellIndex>> {
+        check_axes(semi_major_meters, semi_minor_meters, bearing_degrees)?;
+        let center_cell = self.center.to_cell(self.resolution);
+
+        let mut k = estimate_ring_distance(center_cell, self.to_earth_meters(semi_major_meters));
+        for bearing in [bearing_degrees, bearing_degrees + 180.0] {
+            let end = self.point_from_center(semi_major_meters, bearing.to_radians());
+            let end_cell = LatLng::new(end.y(), end.x())?.to_cell(self.resolution);
+            if let Ok(distance) = center_cell.grid_distance(end_cell) {
+                k = k.max(distance as u32);
+            }
+        }
+        let k = k + 1;
+        self.check_cell_count(k)?;
+
+        let ellipse = self.local_ellipse(semi_major_meters, semi_minor_meters, bearing_degrees);
+        Ok(center_cell
+            .grid_disk::<Vec<_>>(k)
+            .into_iter()
+            .filter(|cell| {
+                let boundary = cell.boundary();
+                let outline: Vec<_> = boundary.iter().map(|v| self.local_plane(*v)).collect();
+                Polygon::new(LineString::from(outline), vec![]).intersects(&ellipse)
+            })
+            .collect())
+    }
+
+    // The ellipse as a polygon of `(east, north)` meters on the center's
+    // local plane
+    fn local_ellipse(&self, semi_major: f64, semi_minor: f64, bearing_degrees: f64) -> Polygon {
+        let (sin, cos) = bearing_degrees.to_radians().sin_cos();
+        let points: Vec<_> = (0..CIRCLE_VERTICES)
+            .map(|i| {
+                let t = i as f64 * std::f64::consts::TAU / CIRCLE_VERTICES as f64;
+                let (along, across) = (semi_major * t.cos(), semi_minor * t.sin());
+                (along * sin + across * cos, along * cos - across * sin)
+            })
+            .collect();
+        Polygon::new(LineString::from(points), vec![])
+    }
+}
+
+fn check_axes(semi_major: f64, semi_minor: f64, bearing_degrees: f64) -> Result<()> {
+    if !(semi_minor > 0.0 && semi_major >= semi_minor && semi_major.is_finite()) {
+        return Err(SmallestEnclosingH3Error::InvalidRadius(format!(
+            "Semi-axes must be positive with the major axis the longer, got {semi_major} and \
+             {semi_minor}"
+        )));
+    }
+    if !bearing_degrees.is_finite() {
+        return Err(SmallestEnclosingH3Error::InvalidBearing(format!(
+            "Bearing must be finite, got {bearing_degrees}"
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{haversine_distance, SmallestEnclosingH3Builder};
+    use h3o::Resolution;
+
+    fn phoenix() -> SmallestEnclosingH3 {
+        let center = LatLng::new(33.4484, -112.0740).unwrap();
+        SmallestEnclosingH3Builder::new(center, 100.0, Resolution::Ten)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_ellipse_coordinates() {
+        let coverage = phoenix();
+        let coordinates = coverage
+            .generate_ellipse_coordinates_with(2000.0, 500.0, 30.0, 4)
+            .unwrap();
+        assert_eq!(coordinates.len(), 5);
+        assert_eq!(coordinates[0], coordinates[4]);
+
+        // The vertices are the ends of the axes, the major one at 30°
+        let center = coverage.center;
+        let point = |c: &Vec<f64>| LatLng::new(c[1], c[0]).unwrap();
+        for (i, (distance, bearing)) in [(2000.0, 30.0), (500.0, 120.0), (2000.0, 210.0)]
+            .into_iter()
+            .enumerate()
+        {
+            let vertex = point(&coordinates[i]);
+            assert!((haversine_distance(center, vertex) - distance).abs() < 1e-6);
+            let actual = crate::geodesy::initial_bearing(
+                (center.lat(), center.lng()),
+                (vertex.lat(), vertex.lng()),
+            );
+            assert!((actual - bearing).abs() < 1e-6, "{actual} {bearing}");
+        }
+
+        // Equal axes are the circle
+        let circle = coverage.generate_circle_coordinates().unwrap();
+        let ellipse = coverage
+            .generate_ellipse_coordinates(100.0, 100.0, 0.0)
+            .unwrap();
+        for (a, b) in circle.iter().zip(&ellipse) {
+            assert!((a[0] - b[0]).abs() < 1e-12 && (a[1] - b[1]).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_ellipse_hexagons() {
+        let coverage = phoenix();
+        let center = coverage.center;
+        let cells = coverage.ellipse_hexagons(3000.0, 500.0, 90.0).unwrap();
+
+        // Every point of the ellipse is covered
+        for c in coverage
+            .generate_ellipse_coordinates_with(3000.0, 500.0, 90.0, 256)
+            .unwrap()
+        {
+            let cell = LatLng::new(c[1], c[0]).unwrap().to_cell(Resolution::Ten);
+            assert!(cells.contains(&cell));
+        }
+        assert!(cells.contains(&center.to_cell(Resolution::Ten)));
+
+        // Stretched east to west, and far smaller than the disk of the major
+        // axis
+        let east = coverage.point_from_center(2900.0, 90f64.to_radians());
+        let north = coverage.point_from_center(2900.0, 0.0);
+        let cell_at = |p: geo::Point| LatLng::new(p.y(), p.x()).unwrap().to_cell(Resolution::Ten);
+        assert!(cells.contains(&cell_at(east)));
+        assert!(!cells.contains(&cell_at(north)));
+        let disk = SmallestEnclosingH3Builder::new(center, 3000.0, Resolution::Ten)
+            .build()
+            .unwrap()
+            .disk()
+            .unwrap();
+        assert!(cells.len() * 3 < disk.len());
+    }
+
+    #[test]
+    fn test_invalid_ellipse() {
+        let coverage = phoenix();
+        for (major, minor) in [(100.0, 200.0), (100.0, 0.0), (f64::NAN, 1.0)] {
+            assert!(matches!(
+                coverage.ellipse_hexagons(major, minor, 0.0),
+                Err(SmallestEnclosingH3Error::InvalidRadius(_))
+            ));
+        }
+        assert!(matches!(
+            coverage.generate_ellipse_coordinates(100.0, 50.0, f64::INFINITY),
+            Err(SmallestEnclosingH3Error::InvalidBearing(_))
+        ));
+    }
+}
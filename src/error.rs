@@ -1,18 +1,495 @@
+#[cfg(feature = "h3")]
+use crate::adjustment::Adjustment;
 use std::result;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
+#[non_exhaustive]
 pub enum SmallestEnclosingH3Error {
+    #[cfg(feature = "h3")]
     #[error("Invalid lat lng: {0}")]
-    InvalidLatLng(String),
+    InvalidLatLng(#[from] h3o::error::InvalidLatLng),
+    #[cfg(feature = "h3")]
     #[error("Invalid resolution: {0}")]
-    InvalidResolution(String),
+    InvalidResolution(#[from] h3o::error::InvalidResolution),
     #[error("Invalid radius: {0}")]
     InvalidRadius(String),
+    #[error("Invalid bearing: {0}")]
+    InvalidBearing(String),
+    #[error("Invalid weight: {0}")]
+    InvalidWeight(String),
+    #[error("Invalid fraction: {0}")]
+    InvalidFraction(String),
+    #[cfg(feature = "h3")]
     #[error("Grid distance error: {0}")]
-    GridDistanceError(String),
+    GridDistanceError(#[from] h3o::error::LocalIjError),
     #[error("Grid Ring error: {0}")]
     GridRingError(String),
+    #[cfg(feature = "h3")]
+    #[error("Outline error: {0}")]
+    OutlineError(#[from] h3o::error::OutlinerError),
+    #[cfg(feature = "h3")]
+    #[error("Compaction error: {0}")]
+    CompactionError(#[from] h3o::error::CompactionError),
+    #[cfg(feature = "h3")]
+    #[error("Invalid cell index: {0}")]
+    InvalidCellIndex(#[from] h3o::error::InvalidCellIndex),
+    #[error("Invalid timestamp: {0}")]
+    InvalidTimestamp(String),
+    #[cfg(any(feature = "czml", feature = "geojson_export", feature = "json_config"))]
+    #[error("Serialization error: {0}")]
+    SerializationError(#[from] serde_json::Error),
+    #[error("No enclosing cell: {0}")]
+    NoEnclosingCell(String),
+    #[error("I/O error: {0}")]
+    IoError(#[from] std::io::Error),
+    #[error("Corrupt index file: {0}")]
+    CorruptIndexFile(String),
+    #[error("Invalid golden file: {0}")]
+    InvalidGoldenFile(String),
+    #[error("Cells of mixed resolutions: {0}")]
+    NonUniformCells(String),
+    #[cfg(feature = "h3")]
+    #[error("Strict mode violation: {0}")]
+    StrictModeViolation(Adjustment),
+    #[error("Too many cells: an estimated {estimated}, the limit is {limit}")]
+    TooManyCells { estimated: usize, limit: usize },
+    #[error("Unsupported geometry: {0}")]
+    UnsupportedGeometry(String),
+    #[error("Parse error at byte {offset}: {reason}")]
+    ParseError { offset: usize, reason: String },
+    #[error("Invalid GeoJSON: {0}")]
+    InvalidGeoJson(String),
+    #[cfg(feature = "h3")]
+    #[error("Invalid geometry: {0}")]
+    InvalidGeometry(#[from] h3o::error::InvalidGeometry),
+    #[error("step {index}: {step}: {source}")]
+    PipelineStep {
+        index: usize,
+        step: &'static str,
+        source: Box<SmallestEnclosingH3Error>,
+    },
 }
 
 pub type Result<T> = result::Result<T, SmallestEnclosingH3Error>;
+
+/// Describes a [`SmallestEnclosingH3Error`] variant, for generating tables
+/// that map errors to status codes or user-facing messages
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ErrorDescriptor {
+    /// The variant's name, e.g. `"InvalidRadius"`
+    pub variant: &'static str,
+    /// A stable snake case code, e.g. `"invalid_radius"`
+    pub code: &'static str,
+    /// The error's Display message; a sample one in [`SmallestEnclosingH3Error::catalog`]
+    pub message: String,
+    /// Whether the caller's input caused the error, rather than the library
+    /// or its environment
+    pub user_input: bool,
+    /// How to fix the input, for user input errors that have a clear fix
+    pub remediation: Option<&'static str>,
+}
+
+// Each variant's pattern, catalog data and a sample instance. `descriptor`
+// matches on every pattern without a wildcard, so a variant added without an
+// entry here fails to compile, and `catalog` has a sample for every entry.
+macro_rules! error_catalog {
+    ($(
+        $(#[$meta:meta])*
+        $variant:ident $fields:tt => {
+            code: $code:literal,
+            user_input: $user_input:literal,
+            remediation: $remediation:expr,
+            sample: $sample:expr $(,)?
+        }
+    ),* $(,)?) => {
+        impl SmallestEnclosingH3Error {
+            /// The catalog entry of this error's variant, with its own message
+            pub fn descriptor(&self) -> ErrorDescriptor {
+                match self {
+                    $(
+                        $(#[$meta])*
+                        SmallestEnclosingH3Error::$variant $fields => ErrorDescriptor {
+                            variant: stringify!($variant),
+                            code: $code,
+                            message: self.to_string(),
+                            user_input: $user_input,
+                            remediation: $remediation,
+                        },
+                    )*
+                }
+            }
+
+            /// An [`ErrorDescriptor`] with a sample message for every variant
+            /// enabled by the current features, in declaration order
+            pub fn catalog() -> Vec<ErrorDescriptor> {
+                vec![$(
+                    $(#[$meta])*
+                    SmallestEnclosingH3Error::descriptor(&$sample),
+                )*]
+            }
+        }
+    };
+}
+
+error_catalog! {
+    #[cfg(feature = "h3")]
+    InvalidLatLng(_) => {
+        code: "invalid_lat_lng",
+        user_input: true,
+        remediation: Some("Give a finite latitude within [-90, 90] and longitude within [-180, 180]"),
+        sample: SmallestEnclosingH3Error::InvalidLatLng(h3o::error::InvalidLatLng {
+            value: 91.0,
+            reason: "latitude must be within [-90, 90]",
+        }),
+    },
+    #[cfg(feature = "h3")]
+    InvalidResolution(_) => {
+        code: "invalid_resolution",
+        user_input: true,
+        remediation: Some("Give a resolution within [0, 15]"),
+        sample: SmallestEnclosingH3Error::InvalidResolution(h3o::error::InvalidResolution {
+            value: Some(16),
+            reason: "out of range",
+        }),
+    },
+    InvalidRadius(_) => {
+        code: "invalid_radius",
+        user_input: true,
+        remediation: Some("Give a positive radius of at most half the Earth's circumference"),
+        sample: SmallestEnclosingH3Error::InvalidRadius("Radius must be positive".to_string()),
+    },
+    InvalidBearing(_) => {
+        code: "invalid_bearing",
+        user_input: true,
+        remediation: Some("Give finite start and end bearings in degrees"),
+        sample: SmallestEnclosingH3Error::InvalidBearing("bearings must be finite".to_string()),
+    },
+    InvalidWeight(_) => {
+        code: "invalid_weight",
+        user_input: true,
+        remediation: Some("Give finite weights and a positive decay sigma"),
+        sample: SmallestEnclosingH3Error::InvalidWeight("Peak weight NaN must be finite".to_string()),
+    },
+    InvalidFraction(_) => {
+        code: "invalid_fraction",
+        user_input: true,
+        remediation: Some("Give a fraction greater than 0 and at most 1"),
+        sample: SmallestEnclosingH3Error::InvalidFraction("Coverage fraction 0 must be in (0, 1]".to_string()),
+    },
+    #[cfg(feature = "h3")]
+    GridDistanceError(_) => {
+        code: "grid_distance",
+        user_input: false,
+        remediation: None,
+        sample: SmallestEnclosingH3Error::GridDistanceError(h3o::error::LocalIjError::Pentagon),
+    },
+    GridRingError(_) => {
+        code: "grid_ring",
+        user_input: false,
+        remediation: None,
+        sample: SmallestEnclosingH3Error::GridRingError("pentagon distortion".to_string()),
+    },
+    #[cfg(feature = "h3")]
+    OutlineError(_) => {
+        code: "outline",
+        user_input: false,
+        remediation: None,
+        sample: SmallestEnclosingH3Error::OutlineError(
+            h3o::error::OutlinerError::HeterogeneousResolution,
+        ),
+    },
+    #[cfg(feature = "h3")]
+    CompactionError(_) => {
+        code: "compaction",
+        user_input: false,
+        remediation: None,
+        sample: SmallestEnclosingH3Error::CompactionError(
+            h3o::error::CompactionError::DuplicateInput,
+        ),
+    },
+    #[cfg(feature = "h3")]
+    InvalidCellIndex(_) => {
+        code: "invalid_cell_index",
+        user_input: true,
+        remediation: Some("Give valid H3 cell indexes as hex strings"),
+        sample: SmallestEnclosingH3Error::InvalidCellIndex(h3o::error::InvalidCellIndex {
+            value: None,
+            reason: "invalid hex string",
+        }),
+    },
+    InvalidTimestamp(_) => {
+        code: "invalid_timestamp",
+        user_input: true,
+        remediation: Some("Give timestamps in increasing order"),
+        sample: SmallestEnclosingH3Error::InvalidTimestamp("timestamps must increase".to_string()),
+    },
+    #[cfg(any(feature = "czml", feature = "geojson_export", feature = "json_config"))]
+    SerializationError(_) => {
+        code: "serialization",
+        user_input: false,
+        remediation: None,
+        sample: SmallestEnclosingH3Error::SerializationError(
+            serde_json::from_str::<serde_json::Value>("{").unwrap_err(),
+        ),
+    },
+    NoEnclosingCell(_) => {
+        code: "no_enclosing_cell",
+        user_input: false,
+        remediation: None,
+        sample: SmallestEnclosingH3Error::NoEnclosingCell("no cell contains the circle".to_string()),
+    },
+    IoError(_) => {
+        code: "io",
+        user_input: false,
+        remediation: None,
+        sample: SmallestEnclosingH3Error::IoError(std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            "coverage.idx not found",
+        )),
+    },
+    CorruptIndexFile(_) => {
+        code: "corrupt_index_file",
+        user_input: true,
+        remediation: Some("Rewrite the index file from its coverage"),
+        sample: SmallestEnclosingH3Error::CorruptIndexFile("bad magic number".to_string()),
+    },
+    InvalidGoldenFile(_) => {
+        code: "invalid_golden_file",
+        user_input: true,
+        remediation: Some("Regenerate the golden file"),
+        sample: SmallestEnclosingH3Error::InvalidGoldenFile("line 1: expected 5 fields".to_string()),
+    },
+    NonUniformCells(_) => {
+        code: "non_uniform_cells",
+        user_input: true,
+        remediation: Some("Give cells of a single resolution"),
+        sample: SmallestEnclosingH3Error::NonUniformCells(
+            "an empty set of cells has no resolution".to_string(),
+        ),
+    },
+    #[cfg(feature = "h3")]
+    StrictModeViolation(_) => {
+        code: "strict_mode_violation",
+        user_input: true,
+        remediation: Some("Give a center within range, or build without strict mode"),
+        sample: SmallestEnclosingH3Error::StrictModeViolation(Adjustment::NormalizeLatitude {
+            from: 91.0,
+            to: 89.0,
+        }),
+    },
+    TooManyCells { .. } => {
+        code: "too_many_cells",
+        user_input: true,
+        remediation: Some("Use a smaller radius or a coarser resolution"),
+        sample: SmallestEnclosingH3Error::TooManyCells {
+            estimated: 20_000_000,
+            limit: 10_000_000,
+        },
+    },
+    UnsupportedGeometry(_) => {
+        code: "unsupported_geometry",
+        user_input: true,
+        remediation: None,
+        sample: SmallestEnclosingH3Error::UnsupportedGeometry("the coverage is empty".to_string()),
+    },
+    ParseError { .. } => {
+        code: "parse",
+        user_input: true,
+        remediation: None,
+        sample: SmallestEnclosingH3Error::ParseError {
+            offset: 6,
+            reason: "expected '(' in WKT point \"POINT 0 0\"".to_string(),
+        },
+    },
+    InvalidGeoJson(_) => {
+        code: "invalid_geojson",
+        user_input: true,
+        remediation: Some("Give a FeatureCollection as written by to_geojson"),
+        sample: SmallestEnclosingH3Error::InvalidGeoJson("missing circle feature".to_string()),
+    },
+    #[cfg(feature = "h3")]
+    InvalidGeometry(_) => {
+        code: "invalid_geometry",
+        user_input: true,
+        remediation: Some("Give geometries with finite coordinates"),
+        sample: SmallestEnclosingH3Error::InvalidGeometry(
+            h3o::geom::Point::from_degrees(geo::Point::new(f64::NAN, 0.0)).unwrap_err(),
+        ),
+    },
+    PipelineStep { .. } => {
+        code: "pipeline_step",
+        user_input: true,
+        remediation: Some("Fix the failing step, see the error's source"),
+        sample: SmallestEnclosingH3Error::PipelineStep {
+            index: 3,
+            step: "mask",
+            source: Box::new(SmallestEnclosingH3Error::UnsupportedGeometry(
+                "the coverage is empty".to_string(),
+            )),
+        },
+    },
+}
+
+/// For IO pipelines. An [`SmallestEnclosingH3Error::IoError`] is unwrapped
+/// back into the original error; any other error is wrapped, of kind
+/// [`std::io::ErrorKind::InvalidInput`] when the caller's input caused it
+/// (see [`ErrorDescriptor::user_input`]) and [`std::io::ErrorKind::Other`]
+/// otherwise.
+///
+/// The error is `Send + Sync + 'static`, so `?` converts it into a
+/// `Box<dyn std::error::Error + Send + Sync>` through the standard library's
+/// blanket impl.
+impl From<SmallestEnclosingH3Error> for std::io::Error {
+    fn from(err: SmallestEnclosingH3Error) -> Self {
+        match err {
+            SmallestEnclosingH3Error::IoError(err) => err,
+            err => {
+                let kind = if err.descriptor().user_input {
+                    std::io::ErrorKind::InvalidInput
+                } else {
+                    std::io::ErrorKind::Other
+                };
+                std::io::Error::new(kind, err)
+            }
+        }
+    }
+}
+
+#[cfg(all(test, feature = "h3"))]
+mod tests {
+    use super::*;
+    use crate::SmallestEnclosingH3Builder;
+    use h3o::geom::ToGeo;
+    use h3o::{LatLng, Resolution};
+    use std::error::Error;
+
+    #[test]
+    fn test_source_is_preserved() {
+        let center = LatLng::new(33.4484, -112.0740).unwrap();
+
+        let err = SmallestEnclosingH3Builder::new(center, 50.0, Resolution::Twelve)
+            .resolution(16)
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            SmallestEnclosingH3Error::InvalidResolution(_)
+        ));
+        assert!(err.to_string().starts_with("Invalid resolution: "));
+        assert!(err
+            .source()
+            .unwrap()
+            .downcast_ref::<h3o::error::InvalidResolution>()
+            .is_some());
+
+        // Cells of different resolutions can't be outlined together
+        let mixed = [
+            center.to_cell(Resolution::Nine),
+            center.to_cell(Resolution::Ten),
+        ];
+        let err: SmallestEnclosingH3Error = mixed.into_iter().to_geom(true).unwrap_err().into();
+        assert!(matches!(err, SmallestEnclosingH3Error::OutlineError(_)));
+        assert!(err
+            .source()
+            .unwrap()
+            .downcast_ref::<h3o::error::OutlinerError>()
+            .is_some());
+
+        // Cells on opposite sides of the globe have no local IJ distance
+        let antipode = LatLng::new(-33.4484, 67.926).unwrap();
+        let err: SmallestEnclosingH3Error = center
+            .to_cell(Resolution::Five)
+            .grid_distance(antipode.to_cell(Resolution::Five))
+            .unwrap_err()
+            .into();
+        assert!(err.source().is_some());
+    }
+
+    #[test]
+    fn test_catalog_covers_every_variant() {
+        let catalog = SmallestEnclosingH3Error::catalog();
+        assert!(catalog
+            .iter()
+            .any(|entry| entry.variant == "InvalidGeometry"));
+
+        let mut variants: Vec<_> = catalog.iter().map(|entry| entry.variant).collect();
+        variants.sort_unstable();
+        variants.dedup();
+        assert_eq!(
+            variants.len(),
+            catalog.len(),
+            "a variant is catalogued twice"
+        );
+
+        for entry in &catalog {
+            assert!(!entry.message.is_empty(), "{}", entry.variant);
+            assert!(
+                entry.user_input || entry.remediation.is_none(),
+                "{}",
+                entry.variant
+            );
+        }
+
+        let err = SmallestEnclosingH3Error::InvalidRadius("Radius must be positive".to_string());
+        let descriptor = err.descriptor();
+        assert_eq!(descriptor.code, "invalid_radius");
+        assert_eq!(
+            descriptor.message,
+            "Invalid radius: Radius must be positive"
+        );
+    }
+
+    #[test]
+    fn test_errors_without_source() {
+        let err = SmallestEnclosingH3Error::TooManyCells {
+            estimated: 10,
+            limit: 5,
+        };
+        assert_eq!(
+            err.to_string(),
+            "Too many cells: an estimated 10, the limit is 5"
+        );
+        assert!(err.source().is_none());
+    }
+
+    #[test]
+    fn test_into_boxed_and_io_errors() {
+        fn assert_send_sync<T: Send + Sync + 'static>() {}
+        assert_send_sync::<SmallestEnclosingH3Error>();
+
+        fn boxed() -> result::Result<(), Box<dyn Error + Send + Sync>> {
+            SmallestEnclosingH3Builder::default()
+                .radius_meters(-1.0)?
+                .build()?;
+            Ok(())
+        }
+        let err = boxed().unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<SmallestEnclosingH3Error>(),
+            Some(SmallestEnclosingH3Error::InvalidRadius(_))
+        ));
+
+        let err = std::io::Error::from(SmallestEnclosingH3Error::InvalidRadius(
+            "Radius must be positive".to_string(),
+        ));
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+        assert_eq!(err.to_string(), "Invalid radius: Radius must be positive");
+        assert!(err
+            .get_ref()
+            .unwrap()
+            .downcast_ref::<SmallestEnclosingH3Error>()
+            .is_some());
+
+        let err = std::io::Error::from(SmallestEnclosingH3Error::NoEnclosingCell(
+            "the circle is larger than a resolution 0 cell".to_string(),
+        ));
+        assert_eq!(err.kind(), std::io::ErrorKind::Other);
+
+        // An IO error comes back out as it went in
+        let original = std::io::Error::new(std::io::ErrorKind::NotFound, "no index");
+        let err = std::io::Error::from(SmallestEnclosingH3Error::from(original));
+        assert_eq!(err.kind(), std::io::ErrorKind::NotFound);
+        assert_eq!(err.to_string(), "no index");
+    }
+}
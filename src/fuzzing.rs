@@ -0,0 +1,93 @@
+//! [`arbitrary::Arbitrary`] for the builder, so downstream crates can fuzz
+//! their own integrations with structured coverage requests.
+
+use crate::{MathMode, SmallestEnclosingH3Builder};
+use arbitrary::{Arbitrary, Unstructured};
+use h3o::{LatLng, Resolution};
+
+/// A builder from an arbitrary `(lat, lng, radius_meters, resolution)`, with
+/// the inner radius, padding and math mode drawn as well.
+///
+/// Any finite coordinate is accepted, out of range ones included, and the
+/// radii may be NaN, infinite or negative: building then fails with an error,
+/// never a panic. Non-finite coordinates can't make a [`LatLng`], and are an
+/// [`arbitrary::Error::IncorrectFormat`].
+impl<'a> Arbitrary<'a> for SmallestEnclosingH3Builder {
+    fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
+        let (lat, lng, radius_meters, resolution) = <(f64, f64, f64, u8)>::arbitrary(u)?;
+        let center = LatLng::new(lat, lng).map_err(|_| arbitrary::Error::IncorrectFormat)?;
+        let resolution =
+            Resolution::try_from(resolution % 16).expect("resolutions 0 to 15 are valid");
+
+        let mut builder = Self::new(center, radius_meters, resolution);
+        builder.inner_radius_meters = if bool::arbitrary(u)? {
+            f64::arbitrary(u)?
+        } else {
+            0.0
+        };
+        builder.padding_rings = u.int_in_range(0..=3)?;
+        builder.math_mode = MathMode::arbitrary(u)?;
+        Ok(builder)
+    }
+
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        <(f64, f64, f64, u8, bool, f64, u32, MathMode)>::size_hint(depth)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Bytes from SplitMix64, so every run sees the same inputs
+    fn seeded_bytes(mut state: u64, len: usize) -> Vec<u8> {
+        (0..len)
+            .map(|_| {
+                state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+                let mut z = state;
+                z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+                z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+                (z ^ (z >> 31)) as u8
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_arbitrary_builders_never_panic() {
+        let mut built = 0;
+        for seed in 0..2000 {
+            let bytes = seeded_bytes(seed, 64);
+            let Ok(builder) = SmallestEnclosingH3Builder::arbitrary(&mut Unstructured::new(&bytes))
+            else {
+                continue;
+            };
+            if let Ok(coverage) = builder.max_cells(10_000).build() {
+                built += 1;
+                let _ = coverage.hexagons();
+                let _ = coverage.disk();
+                #[cfg(feature = "geojson_export")]
+                let _ = coverage.to_geojson();
+            }
+        }
+        // Random bits are mostly huge or NaN radii, but not all of them
+        assert!(built > 0);
+    }
+
+    #[test]
+    fn test_arbitrary_builder_inputs() {
+        let mut bytes = Vec::new();
+        for value in [33.4484f64, -112.0740, 500.0] {
+            bytes.extend(value.to_le_bytes());
+        }
+        bytes.push(25);
+        let builder =
+            SmallestEnclosingH3Builder::arbitrary(&mut Unstructured::new(&bytes)).unwrap();
+        let coverage = builder.build().unwrap();
+        assert_eq!(coverage.radius_meters(), 500.0);
+        assert_eq!(coverage.resolution, Resolution::Nine);
+
+        let mut nan = f64::NAN.to_le_bytes().to_vec();
+        nan.extend(&bytes[8..]);
+        assert!(SmallestEnclosingH3Builder::arbitrary(&mut Unstructured::new(&nan)).is_err());
+    }
+}
@@ -0,0 +1,531 @@
+//! Cells covering any [`geo::Geometry`], see [`coverage_from_geo`].
+
+use crate::{
+    corridor::corridor, error::SmallestEnclosingH3Error, haversine_distance, Result,
+    SmallestEnclosingH3, SmallestEnclosingH3Builder,
+};
+use geo::{Coord, Geometry, LineString, MultiPolygon, Polygon};
+use h3o::geom::{ContainmentMode, PolyfillConfig, ToCells};
+use h3o::{CellIndex, LatLng, Resolution};
+use std::collections::BTreeSet;
+
+/// How [`coverage_from_geo`] treats points and lines
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GeoCoverageMode {
+    /// Only the cells the geometry itself touches
+    Exact,
+    /// The geometry grown by `buffer_meters`, which must be positive
+    Buffered { buffer_meters: f64 },
+}
+
+/// Cells at `resolution` covering `geom`, sorted and without duplicates.
+///
+/// Each variant is covered as follows:
+///
+/// - `Point`: in [`GeoCoverageMode::Exact`] the cell containing it, otherwise
+///   the disk (see [`crate::SmallestEnclosingH3::disk`]) of a circle of
+///   `buffer_meters` around it.
+/// - `MultiPoint`: in [`GeoCoverageMode::Exact`] the cell of every point,
+///   otherwise the disk of the minimal circle enclosing all the points, grown
+///   by `buffer_meters`.
+/// - `Line`, `LineString` and `MultiLineString`: in
+///   [`GeoCoverageMode::Exact`] the cells along each line, otherwise the
+///   [`corridor`] of `buffer_meters` around each line.
+/// - `Polygon`, `MultiPolygon`, `Rect` and `Triangle`: every cell overlapping
+///   the area, holes excluded, in both modes.
+/// - `GeometryCollection`: the union of its members' cells.
+pub fn coverage_from_geo(
+    geom: &Geometry<f64>,
+    resolution: Resolution,
+    mode: GeoCoverageMode,
+) -> Result<Vec<CellIndex>> {
+    let mut cells = BTreeSet::new();
+    cover(geom, resolution, mode, &mut cells)?;
+    Ok(cells.into_iter().collect())
+}
+
+/// Radius of the circle [`SmallestEnclosingH3Builder::from_points`] puts
+/// around a single point, or points that all coincide, in meters: radii must
+/// be positive, and this is far below the size of the finest cells
+pub const MIN_ENCLOSING_RADIUS_METERS: f64 = 0.01;
+
+impl SmallestEnclosingH3Builder {
+    /// A builder for the smallest circle enclosing every one of `points`,
+    /// such as a cluster of GPS fixes.
+    ///
+    /// The circle is found with Welzl's algorithm on an equirectangular
+    /// projection around the first point, which suits extents up to about a
+    /// hundred kilometers, and its radius is then measured on the sphere so
+    /// every point lies within it. A single point, or points that all
+    /// coincide, get a [`MIN_ENCLOSING_RADIUS_METERS`] circle around them, and
+    /// no points at all are a [`SmallestEnclosingH3Error::UnsupportedGeometry`].
+    pub fn from_points(points: &[LatLng], resolution: Resolution) -> Result<Self> {
+        if points.is_empty() {
+            return Err(SmallestEnclosingH3Error::UnsupportedGeometry(
+                "an empty set of points has no enclosing circle".to_string(),
+            ));
+        }
+        let (center, radius_meters) = enclosing_circle(points)?;
+        Ok(Self::new(
+            center,
+            radius_meters.max(MIN_ENCLOSING_RADIUS_METERS),
+            resolution,
+        ))
+    }
+}
+
+impl SmallestEnclosingH3 {
+    /// The cells covering each polygon of `polygons` by its own circle: the
+    /// union of the [`SmallestEnclosingH3::disk`] of every polygon's smallest
+    /// enclosing circle (see [`SmallestEnclosingH3Builder::from_points`]) at
+    /// `resolution`, sorted and without duplicates.
+    ///
+    /// Circles are fitted to the exterior rings, so an archipelago is covered
+    /// island by island rather than by one circle spanning the water between
+    /// them. Polygons without vertices are skipped, and any invalid
+    /// coordinate fails the whole call.
+    pub fn from_multi_polygon(
+        polygons: &MultiPolygon<f64>,
+        resolution: Resolution,
+    ) -> Result<Vec<CellIndex>> {
+        let mut cells = BTreeSet::new();
+        for polygon in polygons {
+            let points = polygon
+                .exterior()
+                .coords()
+                .map(|c| Ok(LatLng::new(c.y, c.x)?))
+                .collect::<Result<Vec<_>>>()?;
+            if points.is_empty() {
+                continue;
+            }
+            let disk = SmallestEnclosingH3Builder::from_points(&points, resolution)?
+                .build()?
+                .disk()?;
+            cells.extend(disk);
+        }
+        Ok(cells.into_iter().collect())
+    }
+}
+
+fn cover(
+    geom: &Geometry<f64>,
+    resolution: Resolution,
+    mode: GeoCoverageMode,
+    cells: &mut BTreeSet<CellIndex>,
+) -> Result<()> {
+    match geom {
+        Geometry::Point(point) => cover_points(&[point.0], resolution, mode, cells),
+        Geometry::MultiPoint(points) => {
+            let coords: Vec<Coord<f64>> = points.iter().map(|point| point.0).collect();
+            cover_points(&coords, resolution, mode, cells)
+        }
+        Geometry::Line(line) => cover_line(
+            &LineString::new(vec![line.start, line.end]),
+            resolution,
+            mode,
+            cells,
+        ),
+        Geometry::LineString(line) => cover_line(line, resolution, mode, cells),
+        Geometry::MultiLineString(lines) => lines
+            .iter()
+            .try_for_each(|line| cover_line(line, resolution, mode, cells)),
+        Geometry::Polygon(polygon) => cover_polygon(polygon, resolution, cells),
+        Geometry::MultiPolygon(polygons) => polygons
+            .iter()
+            .try_for_each(|polygon| cover_polygon(polygon, resolution, cells)),
+        Geometry::Rect(rect) => cover_polygon(&rect.to_polygon(), resolution, cells),
+        Geometry::Triangle(triangle) => cover_polygon(&triangle.to_polygon(), resolution, cells),
+        Geometry::GeometryCollection(collection) => collection
+            .iter()
+            .try_for_each(|member| cover(member, resolution, mode, cells)),
+    }
+}
+
+fn cover_points(
+    coords: &[Coord<f64>],
+    resolution: Resolution,
+    mode: GeoCoverageMode,
+    cells: &mut BTreeSet<CellIndex>,
+) -> Result<()> {
+    let points = coords
+        .iter()
+        .map(|c| Ok(LatLng::new(c.y, c.x)?))
+        .collect::<Result<Vec<_>>>()?;
+    if points.is_empty() {
+        return Ok(());
+    }
+
+    match mode {
+        GeoCoverageMode::Exact => {
+            cells.extend(points.iter().map(|point| point.to_cell(resolution)));
+        }
+        GeoCoverageMode::Buffered { buffer_meters } => {
+            let (center, radius_meters) = enclosing_circle(&points)?;
+            let disk =
+                SmallestEnclosingH3Builder::new(center, radius_meters + buffer_meters, resolution)
+                    .build()?
+                    .disk()?;
+            cells.extend(disk);
+        }
+    }
+    Ok(())
+}
+
+fn cover_line(
+    line: &LineString<f64>,
+    resolution: Resolution,
+    mode: GeoCoverageMode,
+    cells: &mut BTreeSet<CellIndex>,
+) -> Result<()> {
+    if line.0.is_empty() {
+        return Ok(());
+    }
+
+    match mode {
+        GeoCoverageMode::Exact => {
+            let line = h3o::geom::LineString::from_degrees(line.clone())?;
+            cells.extend(line.to_cells(PolyfillConfig::new(resolution)));
+        }
+        GeoCoverageMode::Buffered { buffer_meters } => {
+            let points = line
+                .coords()
+                .map(|c| Ok(LatLng::new(c.y, c.x)?))
+                .collect::<Result<Vec<_>>>()?;
+            cells.extend(corridor(&points, buffer_meters, resolution)?);
+        }
+    }
+    Ok(())
+}
+
+fn cover_polygon(
+    polygon: &Polygon<f64>,
+    resolution: Resolution,
+    cells: &mut BTreeSet<CellIndex>,
+) -> Result<()> {
+    if polygon.exterior().0.is_empty() {
+        return Ok(());
+    }
+
+    let polygon = h3o::geom::Polygon::from_degrees(polygon.clone())?;
+    let config = PolyfillConfig::new(resolution).containment_mode(ContainmentMode::Covers);
+    cells.extend(polygon.to_cells(config));
+    Ok(())
+}
+
+// Minimal circle enclosing `points` (Welzl's algorithm), found on an
+// equirectangular projection around the first point. The radius is then
+// measured on the sphere so every point is enclosed.
+fn enclosing_circle(points: &[LatLng]) -> Result<(LatLng, f64)> {
+    let origin = points[0];
+    let scale = origin.lat().to_radians().cos().max(1e-9);
+    let projected: Vec<(f64, f64)> = points
+        .iter()
+        .map(|p| {
+            // Unwrap longitudes around the origin
+            let dlng = (p.lng() - origin.lng() + 540.0).rem_euclid(360.0) - 180.0;
+            (dlng * scale, p.lat() - origin.lat())
+        })
+        .collect();
+
+    let ((x, y), _) = welzl(&projected);
+    let lng = (origin.lng() + x / scale + 540.0).rem_euclid(360.0) - 180.0;
+    let center = LatLng::new((origin.lat() + y).clamp(-90.0, 90.0), lng)?;
+    let radius_meters = points
+        .iter()
+        .map(|&p| haversine_distance(center, p))
+        .fold(0.0, f64::max);
+    Ok((center, radius_meters))
+}
+
+type Circle = ((f64, f64), f64);
+
+fn welzl(points: &[(f64, f64)]) -> Circle {
+    let contains = |(c, r): Circle, p: (f64, f64)| distance(c, p) <= r * (1.0 + 1e-12) + 1e-12;
+
+    let mut circle = (points[0], 0.0);
+    for i in 1..points.len() {
+        if contains(circle, points[i]) {
+            continue;
+        }
+        circle = (points[i], 0.0);
+        for j in 0..i {
+            if contains(circle, points[j]) {
+                continue;
+            }
+            circle = diameter_circle(points[i], points[j]);
+            for k in 0..j {
+                if !contains(circle, points[k]) {
+                    circle = circumcircle(points[i], points[j], points[k]);
+                }
+            }
+        }
+    }
+    circle
+}
+
+fn distance(a: (f64, f64), b: (f64, f64)) -> f64 {
+    (a.0 - b.0).hypot(a.1 - b.1)
+}
+
+fn diameter_circle(a: (f64, f64), b: (f64, f64)) -> Circle {
+    let center = ((a.0 + b.0) / 2.0, (a.1 + b.1) / 2.0);
+    (center, distance(center, a))
+}
+
+fn circumcircle(a: (f64, f64), b: (f64, f64), c: (f64, f64)) -> Circle {
+    let (bx, by) = (b.0 - a.0, b.1 - a.1);
+    let (cx, cy) = (c.0 - a.0, c.1 - a.1);
+    let d = 2.0 * (bx * cy - by * cx);
+    if d.abs() < f64::EPSILON {
+        // Collinear: the two farthest apart points span the circle
+        return [
+            diameter_circle(a, b),
+            diameter_circle(a, c),
+            diameter_circle(b, c),
+        ]
+        .into_iter()
+        .max_by(|x, y| x.1.total_cmp(&y.1))
+        .unwrap_or((a, 0.0));
+    }
+
+    let b2 = bx * bx + by * by;
+    let c2 = cx * cx + cy * cy;
+    let center = (a.0 + (cy * b2 - by * c2) / d, a.1 + (bx * c2 - cx * b2) / d);
+    (center, distance(center, a))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use geo::{coord, line_string, point, polygon, GeometryCollection, MultiPoint, Rect};
+
+    const RES: Resolution = Resolution::Ten;
+
+    fn cell(lat: f64, lng: f64) -> CellIndex {
+        LatLng::new(lat, lng).unwrap().to_cell(RES)
+    }
+
+    #[test]
+    fn test_point() {
+        let point = Geometry::Point(point!(x: -112.0740, y: 33.4484));
+        let exact = coverage_from_geo(&point, RES, GeoCoverageMode::Exact).unwrap();
+        assert_eq!(exact, vec![cell(33.4484, -112.0740)]);
+
+        let mode = GeoCoverageMode::Buffered {
+            buffer_meters: 500.0,
+        };
+        let buffered = coverage_from_geo(&point, RES, mode).unwrap();
+        let disk =
+            SmallestEnclosingH3Builder::new(LatLng::new(33.4484, -112.0740).unwrap(), 500.0, RES)
+                .build()
+                .unwrap()
+                .disk()
+                .unwrap();
+        assert_eq!(buffered.len(), disk.len());
+        assert!(buffered.contains(&exact[0]));
+    }
+
+    #[test]
+    fn test_multi_point() {
+        let points = [
+            (-112.0740, 33.4484),
+            (-112.0640, 33.4484),
+            (-112.0690, 33.4534),
+        ];
+        let multi_point = Geometry::MultiPoint(MultiPoint::from(points.to_vec()));
+
+        let exact = coverage_from_geo(&multi_point, RES, GeoCoverageMode::Exact).unwrap();
+        assert_eq!(exact.len(), 3);
+
+        let mode = GeoCoverageMode::Buffered {
+            buffer_meters: 100.0,
+        };
+        let buffered = coverage_from_geo(&multi_point, RES, mode).unwrap();
+        for (lng, lat) in points {
+            assert!(buffered.contains(&cell(lat, lng)));
+        }
+        // Between the points, too
+        assert!(buffered.contains(&cell(33.4500, -112.0690)));
+    }
+
+    #[test]
+    fn test_enclosing_circle() {
+        let points: Vec<LatLng> = [(0.0, 0.0), (0.0, 0.02), (0.01, 0.01), (0.005, 0.01)]
+            .iter()
+            .map(|&(lat, lng)| LatLng::new(lat, lng).unwrap())
+            .collect();
+        let (center, radius_meters) = enclosing_circle(&points).unwrap();
+
+        // The two farthest points span the circle
+        let expected = haversine_distance(points[0], points[1]) / 2.0;
+        assert!((radius_meters - expected).abs() < 1.0);
+        assert!((center.lng() - 0.01).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_from_points() {
+        // An obtuse triangle, so the longest side is the diameter: half of
+        // 0.02° of the equator, 6371 km × 0.01 × π / 180 = 1111.949 m
+        let points: Vec<LatLng> = [(0.0, 0.0), (0.0, 0.02), (0.004, 0.012)]
+            .iter()
+            .map(|&(lat, lng)| LatLng::new(lat, lng).unwrap())
+            .collect();
+        let coverage = SmallestEnclosingH3Builder::from_points(&points, RES)
+            .unwrap()
+            .build()
+            .unwrap();
+        assert!((coverage.radius_meters() - 1111.949).abs() < 1e-3);
+
+        let disk = coverage.disk().unwrap();
+        for point in &points {
+            assert!(disk.contains(&point.to_cell(RES)), "{point}");
+        }
+    }
+
+    #[test]
+    fn test_from_degenerate_points() {
+        assert!(matches!(
+            SmallestEnclosingH3Builder::from_points(&[], RES),
+            Err(SmallestEnclosingH3Error::UnsupportedGeometry(_))
+        ));
+
+        let point = LatLng::new(33.4484, -112.0740).unwrap();
+        for points in [vec![point], vec![point; 3]] {
+            let coverage = SmallestEnclosingH3Builder::from_points(&points, RES)
+                .unwrap()
+                .build()
+                .unwrap();
+            assert_eq!(coverage.radius_meters(), MIN_ENCLOSING_RADIUS_METERS);
+            assert_eq!(coverage.disk().unwrap(), vec![point.to_cell(RES)]);
+        }
+    }
+
+    #[test]
+    fn test_from_multi_polygon() {
+        // Two islands about 11 km apart
+        let west = polygon![
+            (x: -112.080, y: 33.440),
+            (x: -112.070, y: 33.440),
+            (x: -112.075, y: 33.450),
+        ];
+        let east = polygon![
+            (x: -111.960, y: 33.440),
+            (x: -111.950, y: 33.445),
+            (x: -111.955, y: 33.452),
+            (x: -111.962, y: 33.448),
+        ];
+        let islands = MultiPolygon::new(vec![
+            west.clone(),
+            east.clone(),
+            Polygon::new(LineString::new(vec![]), vec![]),
+        ]);
+        let cells = SmallestEnclosingH3::from_multi_polygon(&islands, RES).unwrap();
+        assert!(cells.windows(2).all(|pair| pair[0] < pair[1]));
+
+        let mut expected = BTreeSet::new();
+        for polygon in [&west, &east] {
+            let points: Vec<LatLng> = polygon
+                .exterior()
+                .coords()
+                .map(|c| LatLng::new(c.y, c.x).unwrap())
+                .collect();
+            let disk = SmallestEnclosingH3Builder::from_points(&points, RES)
+                .unwrap()
+                .build()
+                .unwrap()
+                .disk()
+                .unwrap();
+            expected.extend(disk);
+            assert!(cells.contains(&cell(points[0].lat(), points[0].lng())));
+        }
+        assert_eq!(cells, expected.into_iter().collect::<Vec<_>>());
+
+        // The water between the islands is left out
+        assert!(!cells.contains(&cell(33.445, -112.015)));
+        assert!(
+            SmallestEnclosingH3::from_multi_polygon(&MultiPolygon::new(vec![]), RES)
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_line_string() {
+        let line = Geometry::LineString(line_string![
+            (x: -112.0740, y: 33.4484),
+            (x: -112.0400, y: 33.4484),
+        ]);
+
+        let exact = coverage_from_geo(&line, RES, GeoCoverageMode::Exact).unwrap();
+        assert!(exact.contains(&cell(33.4484, -112.0740)));
+        assert!(exact.contains(&cell(33.4484, -112.0570)));
+        assert!(exact.contains(&cell(33.4484, -112.0400)));
+
+        let mode = GeoCoverageMode::Buffered {
+            buffer_meters: 300.0,
+        };
+        let buffered = coverage_from_geo(&line, RES, mode).unwrap();
+        assert!(exact.iter().all(|cell| buffered.contains(cell)));
+        assert!(buffered.len() > exact.len());
+    }
+
+    #[test]
+    fn test_polygon_and_rect() {
+        let rect = Rect::new(
+            coord! { x: -112.08, y: 33.44 },
+            coord! { x: -112.06, y: 33.46 },
+        );
+        let polygon = Geometry::Polygon(polygon![
+            (x: -112.08, y: 33.44),
+            (x: -112.06, y: 33.44),
+            (x: -112.06, y: 33.46),
+            (x: -112.08, y: 33.46),
+        ]);
+
+        let cells = coverage_from_geo(&polygon, RES, GeoCoverageMode::Exact).unwrap();
+        assert!(cells.contains(&cell(33.45, -112.07)));
+        // Corners are covered too
+        assert!(cells.contains(&cell(33.4401, -112.0799)));
+        assert_eq!(
+            coverage_from_geo(&Geometry::Rect(rect), RES, GeoCoverageMode::Exact).unwrap(),
+            cells
+        );
+    }
+
+    #[test]
+    fn test_geometry_collection() {
+        let point = Geometry::Point(point!(x: 2.3522, y: 48.8566));
+        let line = Geometry::LineString(line_string![
+            (x: -112.0740, y: 33.4484),
+            (x: -112.0400, y: 33.4484),
+        ]);
+        let collection = Geometry::GeometryCollection(GeometryCollection::new_from(vec![
+            point.clone(),
+            line.clone(),
+        ]));
+
+        let mut expected = coverage_from_geo(&point, RES, GeoCoverageMode::Exact).unwrap();
+        expected.extend(coverage_from_geo(&line, RES, GeoCoverageMode::Exact).unwrap());
+        expected.sort_unstable();
+        assert_eq!(
+            coverage_from_geo(&collection, RES, GeoCoverageMode::Exact).unwrap(),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_invalid_coordinates() {
+        let point = Geometry::Point(point!(x: f64::NAN, y: 0.0));
+        assert!(matches!(
+            coverage_from_geo(&point, RES, GeoCoverageMode::Exact),
+            Err(SmallestEnclosingH3Error::InvalidLatLng(_))
+        ));
+
+        let mode = GeoCoverageMode::Buffered { buffer_meters: 0.0 };
+        let point = Geometry::Point(point!(x: 0.0, y: 0.0));
+        assert!(matches!(
+            coverage_from_geo(&point, RES, mode),
+            Err(SmallestEnclosingH3Error::InvalidRadius(_))
+        ));
+    }
+}
@@ -0,0 +1,236 @@
+//! Spherical geodesy on plain `(lat, lng)` tuples in degrees.
+//!
+//! This module has no dependencies, so it can be built on its own with
+//! `default-features = false, features = ["geodesy"]`.
+
+/// Mean Earth radius, in meters
+pub const EARTH_RADIUS: f64 = 6371000.0;
+
+/// Great-circle distance between `a` and `b`, in meters
+pub fn haversine_distance(a: (f64, f64), b: (f64, f64)) -> f64 {
+    let (lat1, lat2) = (a.0.to_radians(), b.0.to_radians());
+    let dlat = lat2 - lat1;
+    let dlng = (b.1 - a.1).to_radians();
+    let h = (dlat / 2.0).sin().powi(2) + lat1.cos() * lat2.cos() * (dlng / 2.0).sin().powi(2);
+    2.0 * EARTH_RADIUS * h.sqrt().asin()
+}
+
+/// Initial bearing from `a` to `b`, in degrees clockwise from north in
+/// [0, 360)
+pub fn initial_bearing(a: (f64, f64), b: (f64, f64)) -> f64 {
+    let (lat1, lat2) = (a.0.to_radians(), b.0.to_radians());
+    let dlng = (b.1 - a.1).to_radians();
+    let y = dlng.sin() * lat2.cos();
+    let x = lat1.cos() * lat2.sin() - lat1.sin() * lat2.cos() * dlng.cos();
+    y.atan2(x).to_degrees().rem_euclid(360.0)
+}
+
+/// The point `distance` meters from `start` along `bearing_radians`, in
+/// radians (not degrees) clockwise from north. The longitude is wrapped into
+/// [-180, 180].
+pub fn destination_point(start: (f64, f64), distance: f64, bearing_radians: f64) -> (f64, f64) {
+    let lat1 = start.0.to_radians();
+    let lon1 = start.1.to_radians();
+    let angular_distance = distance / EARTH_RADIUS;
+
+    let lat2 = (lat1.sin() * angular_distance.cos()
+        + lat1.cos() * angular_distance.sin() * bearing_radians.cos())
+    .asin();
+
+    let lon2 = lon1
+        + (bearing_radians.sin() * angular_distance.sin() * lat1.cos())
+            .atan2(angular_distance.cos() - lat1.sin() * lat2.sin());
+
+    (lat2.to_degrees(), normalize_lng(lon2.to_degrees()))
+}
+
+/// Great-circle distance from `point` to the nearest point of the
+/// great-circle arc from `a` to `b`, in meters. The arc is the shorter one,
+/// so `a` and `b` must not be antipodal.
+pub fn distance_to_segment(point: (f64, f64), a: (f64, f64), b: (f64, f64)) -> f64 {
+    let to_point = haversine_distance(a, point);
+    let length = haversine_distance(a, b);
+    if length == 0.0 {
+        return to_point;
+    }
+
+    // Cross-track and along-track distances from `a`, as angles
+    let angular = to_point / EARTH_RADIUS;
+    let turn = (initial_bearing(a, point) - initial_bearing(a, b)).to_radians();
+    let cross_track = (angular.sin() * turn.sin()).asin();
+    let along_track = (angular.cos() / cross_track.cos()).clamp(-1.0, 1.0).acos();
+
+    if turn.cos() < 0.0 {
+        // Behind `a`
+        to_point
+    } else if along_track * EARTH_RADIUS > length {
+        haversine_distance(b, point)
+    } else {
+        cross_track.abs() * EARTH_RADIUS
+    }
+}
+
+/// `point` as `(east, north)` meters on a plane around `origin`, scaling
+/// longitude by the cosine of the two points' mean latitude.
+///
+/// Plain arithmetic besides one cosine, and within a millimeter of the
+/// great-circle offsets for points a couple of kilometers apart; the relative
+/// error grows with the square of the distance, staying under 0.01% at 50 km.
+/// Points must not straddle a pole.
+pub fn local_plane(origin: (f64, f64), point: (f64, f64)) -> (f64, f64) {
+    let mean_lat = ((origin.0 + point.0) / 2.0).to_radians();
+    let east = EARTH_RADIUS * normalize_lng(point.1 - origin.1).to_radians() * mean_lat.cos();
+    let north = EARTH_RADIUS * (point.0 - origin.0).to_radians();
+    (east, north)
+}
+
+/// The inverse of [`local_plane`]: the `(lat, lng)` `east` and `north` meters
+/// from `origin`. The longitude is wrapped into [-180, 180].
+pub fn from_local_plane(origin: (f64, f64), (east, north): (f64, f64)) -> (f64, f64) {
+    let lat = origin.0 + (north / EARTH_RADIUS).to_degrees();
+    let mean_lat = ((origin.0 + lat) / 2.0).to_radians();
+    let lng = origin.1 + (east / (EARTH_RADIUS * mean_lat.cos())).to_degrees();
+    (lat, normalize_lng(lng))
+}
+
+/// Sphere radius of Web Mercator (EPSG:3857), the WGS84 semi-major axis
+pub const WEB_MERCATOR_RADIUS: f64 = 6378137.0;
+
+/// Latitude beyond which Web Mercator is clipped, making the projected world
+/// square
+pub const WEB_MERCATOR_MAX_LAT: f64 = 85.05112877980659;
+
+/// Spherical (Web) Mercator projection of `(lat, lng)` to `(x, y)` meters.
+/// Latitudes are clamped to ±[`WEB_MERCATOR_MAX_LAT`].
+pub fn web_mercator(lat_lng: (f64, f64)) -> (f64, f64) {
+    let lat = lat_lng
+        .0
+        .clamp(-WEB_MERCATOR_MAX_LAT, WEB_MERCATOR_MAX_LAT)
+        .to_radians();
+    let x = WEB_MERCATOR_RADIUS * lat_lng.1.to_radians();
+    let y = WEB_MERCATOR_RADIUS * (std::f64::consts::FRAC_PI_4 + lat / 2.0).tan().ln();
+    (x, y)
+}
+
+/// Wrap a longitude into [-180, 180]. In-range longitudes are returned as is,
+/// rather than round-tripped through the modulo.
+pub fn normalize_lng(lng: f64) -> f64 {
+    if (-180.0..=180.0).contains(&lng) {
+        lng
+    } else {
+        (lng + 180.0).rem_euclid(360.0) - 180.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PHOENIX: (f64, f64) = (33.4484, -112.0740);
+
+    #[test]
+    fn test_destination_round_trip() {
+        for bearing in [0.0, 45.0, 90.0, 200.0, 315.0_f64] {
+            let end = destination_point(PHOENIX, 10_000.0, bearing.to_radians());
+            assert!((haversine_distance(PHOENIX, end) - 10_000.0).abs() < 1e-6);
+            assert!((initial_bearing(PHOENIX, end) - bearing).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_destination_distance_over_a_grid() {
+        for lat in [-89.0, -60.0, -33.4484, 0.0, 17.5, 45.0, 75.0, 89.0] {
+            for lng in [-179.5, -112.074, 0.0, 120.0] {
+                for bearing in (0..24).map(|i| (i as f64 * 15.0).to_radians()) {
+                    for distance in [1.0, 500.0, 50_000.0, 2_000_000.0] {
+                        let end = destination_point((lat, lng), distance, bearing);
+                        let error = (haversine_distance((lat, lng), end) - distance).abs();
+                        assert!(error < 1e-6 * distance.max(1.0), "{lat} {lng} {bearing}");
+                        assert!((-180.0..=180.0).contains(&end.1));
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_destination_bearing_is_in_radians() {
+        // A quarter turn heads due east along the equator
+        let east = destination_point((0.0, 0.0), 111_000.0, std::f64::consts::FRAC_PI_2);
+        assert!(east.0.abs() < 1e-9 && east.1 > 0.99);
+        let north = destination_point((0.0, 0.0), 111_000.0, 0.0);
+        assert!(north.0 > 0.99 && north.1.abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_destination_wraps_longitude() {
+        let end = destination_point((0.0, 179.9), 100_000.0, 90f64.to_radians());
+        assert!((-180.0..-179.0).contains(&end.1));
+        assert_eq!(normalize_lng(-112.074), -112.074);
+        assert_eq!(normalize_lng(190.0), -170.0);
+    }
+
+    #[test]
+    fn test_distance_to_segment() {
+        // Along the equator, from 0° to 1° of longitude
+        let (a, b) = ((0.0, 0.0), (0.0, 1.0));
+        let degree = EARTH_RADIUS.to_radians();
+
+        let above = distance_to_segment((0.5, 0.5), a, b);
+        assert!((above - 0.5 * degree).abs() < 1e-6);
+        let before = distance_to_segment((0.0, -0.5), a, b);
+        assert!((before - 0.5 * degree).abs() < 1e-6);
+        let beyond = distance_to_segment((0.0, 1.5), a, b);
+        assert!((beyond - 0.5 * degree).abs() < 1e-6);
+        assert!(distance_to_segment((0.0, 0.25), a, b) < 1e-6);
+        assert_eq!(
+            distance_to_segment(PHOENIX, a, a),
+            haversine_distance(PHOENIX, a)
+        );
+    }
+
+    #[test]
+    fn test_local_plane() {
+        for origin in [(0.0, 0.0), PHOENIX, (70.0, 179.99)] {
+            for bearing in (0..8).map(|i| (i as f64 * 45.0).to_radians()) {
+                for distance in [200.0, 2_000.0, 50_000.0] {
+                    let point = destination_point(origin, distance, bearing);
+                    let (east, north) = local_plane(origin, point);
+                    let planar = east.hypot(north);
+                    assert!(
+                        (planar - distance).abs() < 1e-4 * distance,
+                        "{origin:?} {distance}"
+                    );
+                    if distance <= 2_000.0 {
+                        assert!((planar - distance).abs() < 1e-3, "{origin:?} {distance}");
+                    }
+
+                    let back = from_local_plane(origin, (east, north));
+                    assert!(haversine_distance(back, point) < 1e-6);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_web_mercator() {
+        let (x, y) = web_mercator((0.0, 0.0));
+        assert!(x.abs() < 1e-3 && y.abs() < 1e-3);
+
+        let (_, y) = web_mercator((45.0, 0.0));
+        assert!((y - 5621521.486).abs() < 1e-3);
+
+        // The corners of the projected world, with latitudes clamped
+        let (x, y) = web_mercator((90.0, 180.0));
+        assert!((x - 20037508.343).abs() < 1e-3);
+        assert!((y - 20037508.343).abs() < 1e-3);
+        assert_eq!(web_mercator((-90.0, 0.0)), web_mercator((-86.0, 0.0)));
+    }
+
+    #[test]
+    fn test_haversine_distance() {
+        // One degree of latitude
+        let distance = haversine_distance((0.0, 0.0), (1.0, 0.0));
+        assert!((distance - EARTH_RADIUS.to_radians()).abs() < 1e-6);
+    }
+}
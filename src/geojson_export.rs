@@ -1,83 +1,1222 @@
-use crate::{Result, SmallestEnclosingH3};
-use serde_json::Map;
+//! GeoJSON export of the coverage, and the builders recovered from it.
+//!
+//! Enabled by the `geojson_export` feature, which adds the `geojson`, `serde`
+//! and `serde_json` dependencies; the whole module is left out without it.
 
-#[cfg(feature = "geojson_export")]
-pub mod geojson_export {
-    use super::*;
-    use geojson::{Feature, FeatureCollection, Geometry, Value};
-    use h3o::CellIndex;
-    use serde_json::json;
+use crate::adjustment::Adjustment;
+use crate::{cell_coverage::CellCoverage, CellOrder, OutputCrs, SmallestEnclosingH3Builder};
+use crate::{error::SmallestEnclosingH3Error, Result, SmallestEnclosingH3};
+use geo::{Centroid, LineString, Polygon};
+use geojson::{feature::Id, Feature, FeatureCollection, Geometry, Value};
+use h3o::{CellIndex, DirectedEdgeIndex, LatLng, Resolution};
+use serde::ser::{Error as _, SerializeMap, SerializeSeq};
+use serde::{Serialize, Serializer};
+use serde_json::{json, Map};
+use std::fmt;
+use std::io::Write;
+use std::sync::Arc;
 
-    impl SmallestEnclosingH3 {
-        pub fn to_geojson(&self) -> Result<FeatureCollection> {
-            let mut features = Vec::new();
+/// Output options for [`SmallestEnclosingH3::to_geojson_with_options`] and
+/// [`SmallestEnclosingH3::write_geojson`]
+#[derive(Debug, Clone, Default)]
+pub struct GeoJsonOptions {
+    /// Indent the output like [`SmallestEnclosingH3::to_geojson_string_pretty`]
+    pub pretty: bool,
+    /// Order of the hexagon features. `None` keeps the order of
+    /// [`SmallestEnclosingH3::to_geojson`].
+    pub order: Option<CellOrder>,
+    /// Add a Point feature at the circle's center, tagged
+    /// `"type": "center"`, with the `radius_meters`, `resolution` and
+    /// `cell_count` that produced the file
+    pub center_marker: bool,
+    /// Add a `generator` member to the collection naming this crate, its
+    /// version and the input parameters
+    pub generator: bool,
+    /// Coordinate reference system of the geometries and bounding boxes.
+    /// RFC 7946 mandates WGS84, so Web Mercator output keeps the GeoJSON
+    /// structure but is non-standard, meant for tile pipelines that take
+    /// projected coordinates. It is recorded in a `crs` member; properties
+    /// such as the circle's `center` stay in degrees.
+    pub crs: OutputCrs,
+    /// Combine the hexagons into one MultiPolygon feature tagged
+    /// `"type": "hexagons"`, one polygon per cell in the usual order,
+    /// instead of a feature per cell. Its `hex_ids` property lists the
+    /// cells in polygon order, as do the `class` array and the
+    /// `coverage_overlap` and `padded` arrays when those are enabled.
+    /// Large coverages come out at about half the size.
+    pub multipolygon: bool,
+    /// Style the features for geojson.io, Mapbox and other viewers of the
+    /// [simplestyle spec](https://github.com/mapbox/simplestyle-spec):
+    /// hexagons are filled along a color ramp by their distance from the
+    /// center, and the circle is drawn as an outline only. Combined
+    /// hexagons ([`Self::multipolygon`]) aren't filled.
+    pub simplestyle: Option<SimpleStyle>,
+    /// Add a LineString feature per edge of
+    /// [`SmallestEnclosingH3::boundary_edges`], tagged
+    /// `"type": "boundary_edge"`, with the `edge_id` and the `origin` and
+    /// `destination` cells
+    pub boundary_edges: bool,
+    /// Leave out the circle and inner circle features, exporting only
+    /// the cells. [`SmallestEnclosingH3::from_geojson`] can't rebuild a
+    /// coverage from such a file.
+    pub omit_circle: bool,
+    /// Precede every feature written by
+    /// [`SmallestEnclosingH3::write_geojson_seq`] with the RS character
+    /// (`0x1E`), as RFC 8142 requires. Without it the sequence is
+    /// newline-delimited GeoJSON. Other exports ignore this.
+    pub record_separator: bool,
+    /// Give each hexagon a height in meters for extruded 3D views such as
+    /// deck.gl and kepler.gl, as `elevation` and `height` properties
+    /// (arrays in polygon order with [`Self::multipolygon`]). The circle
+    /// and other features aren't extruded.
+    pub extrusion: Option<ExtrusionSpec>,
+    /// With [`Self::extrusion`], also write each hexagon's height as the
+    /// third coordinate of its ring positions
+    pub extrusion_z: bool,
+}
 
-            // Add hexagon features
-            for hex_id in self.hexagons()? {
-                features.push(create_hex_feature(hex_id)?);
-            }
+/// Hexagon heights for [`GeoJsonOptions::extrusion`]
+#[derive(Clone)]
+pub enum ExtrusionSpec {
+    /// The same height for every hexagon, in meters
+    Constant(f64),
+    /// The height of each hexagon in meters, such as a demand metric or
+    /// its distance from the center
+    PerCell(Arc<dyn Fn(CellIndex) -> f64 + Send + Sync>),
+}
 
-            // Add circle feature
-            features.push(create_circle_feature(
-                self.generate_circle_coordinates()?,
-                self.radius_meters,
-            )?);
+impl ExtrusionSpec {
+    /// Heights from `f`, see [`ExtrusionSpec::PerCell`]
+    pub fn per_cell(f: impl Fn(CellIndex) -> f64 + Send + Sync + 'static) -> Self {
+        Self::PerCell(Arc::new(f))
+    }
 
-            Ok(FeatureCollection {
-                features,
-                bbox: None,
-                foreign_members: None,
-            })
+    /// The height of `cell`, in meters
+    pub fn height(&self, cell: CellIndex) -> f64 {
+        match self {
+            Self::Constant(height) => *height,
+            Self::PerCell(f) => f(cell),
+        }
+    }
+}
+
+impl fmt::Debug for ExtrusionSpec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Constant(height) => f.debug_tuple("Constant").field(height).finish(),
+            Self::PerCell(_) => f.write_str("PerCell(..)"),
+        }
+    }
+}
+
+/// Colors and opacity for [`GeoJsonOptions::simplestyle`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct SimpleStyle {
+    /// `fill` of a hexagon whose centroid is at the center, as RGB
+    pub near_color: [u8; 3],
+    /// `fill` of a hexagon whose centroid is at the radius or beyond, as
+    /// RGB. Hexagons in between are interpolated by distance over radius.
+    pub far_color: [u8; 3],
+    /// `fill-opacity` of the hexagons, from 0 to 1
+    pub fill_opacity: f64,
+    /// `stroke` of the hexagons and the circle, as RGB
+    pub stroke: [u8; 3],
+    /// `stroke-width` of the hexagons and the circle, in pixels
+    pub stroke_width: f64,
+}
+
+/// A yellow to purple ramp, the ends of viridis, half opaque with dark
+/// gray outlines
+impl Default for SimpleStyle {
+    fn default() -> Self {
+        Self {
+            near_color: [0xfd, 0xe7, 0x25],
+            far_color: [0x44, 0x01, 0x54],
+            fill_opacity: 0.5,
+            stroke: [0x55, 0x55, 0x55],
+            stroke_width: 1.0,
+        }
+    }
+}
+
+impl SimpleStyle {
+    // The ramp color `t` of the way from near to far, as `#rrggbb`
+    fn fill(&self, t: f64) -> String {
+        let t = t.clamp(0.0, 1.0);
+        let channel = |i: usize| {
+            let (near, far) = (f64::from(self.near_color[i]), f64::from(self.far_color[i]));
+            (near + t * (far - near)).round() as u8
+        };
+        hex_color([channel(0), channel(1), channel(2)])
+    }
+}
+
+fn hex_color([r, g, b]: [u8; 3]) -> String {
+    format!("#{r:02x}{g:02x}{b:02x}")
+}
+
+impl SmallestEnclosingH3 {
+    pub fn to_geojson(&self) -> Result<FeatureCollection> {
+        FeatureCollection::try_from(self)
+    }
+
+    /// Like [`Self::to_geojson`], with the feature order, center marker and
+    /// generator metadata from `opts`
+    pub fn to_geojson_with_options(&self, opts: &GeoJsonOptions) -> Result<FeatureCollection> {
+        self.feature_collection(opts, |_| Map::new())
+    }
+
+    /// Like [`Self::to_geojson`], merging the properties returned by `f` for
+    /// each hexagon into its feature.
+    ///
+    /// The built-in `hex_id`, `type`, `class`, `coverage_overlap` and
+    /// `padded` properties are reserved and win on conflict. An `id` entry
+    /// holding a string or number becomes the feature's GeoJSON `id` instead
+    /// of a property.
+    pub fn to_geojson_with_properties<F>(&self, f: F) -> Result<FeatureCollection>
+    where
+        F: Fn(CellIndex) -> Map<String, serde_json::Value> + Sync,
+    {
+        self.feature_collection(&GeoJsonOptions::default(), f)
+    }
+
+    fn feature_collection<F>(&self, opts: &GeoJsonOptions, f: F) -> Result<FeatureCollection>
+    where
+        F: Fn(CellIndex) -> Map<String, serde_json::Value> + Sync,
+    {
+        // Add hexagon features
+        let hexagons = self.export_hexagons(opts)?;
+        let mut features = if opts.multipolygon {
+            vec![self.multipolygon_feature(&hexagons)?]
+        } else {
+            self.hex_features(&hexagons, f)?
+        };
+
+        if let (Some(style), false) = (&opts.simplestyle, opts.multipolygon) {
+            for (feature, cell) in features.iter_mut().zip(&hexagons) {
+                self.style_hex_feature(feature, *cell, style);
+            }
+        }
+        if opts.multipolygon {
+            extrude_feature(&mut features[0], &hexagons, opts);
+        } else {
+            for (feature, cell) in features.iter_mut().zip(&hexagons) {
+                extrude_feature(feature, std::slice::from_ref(cell), opts);
+            }
+        }
+
+        // Add circle feature
+        if !opts.omit_circle {
+            features.push(self.styled_circle_feature(opts)?);
+            features.extend(self.inner_circle_feature()?);
+        }
+        if opts.boundary_edges {
+            features.extend(self.boundary_edges()?.into_iter().map(edge_feature));
+        }
+        if opts.center_marker {
+            features.push(self.center_feature(hexagons.len()));
         }
+        for feature in &mut features {
+            project_feature(feature, opts.crs);
+        }
+
+        Ok(FeatureCollection {
+            features,
+            bbox: Some(self.collection_bbox(&hexagons, opts.crs)?),
+            foreign_members: Some(self.foreign_members(opts)),
+        })
+    }
+
+    // A feature per hexagon, with the properties returned by `f` merged in
+    fn hex_features<F>(&self, hexagons: &[CellIndex], f: F) -> Result<Vec<Feature>>
+    where
+        F: Fn(CellIndex) -> Map<String, serde_json::Value> + Sync,
+    {
+        crate::parallel::map(hexagons, |hex_id| {
+            let mut feature = self.hex_feature(*hex_id)?;
+            for (key, value) in f(*hex_id) {
+                match (key.as_str(), value) {
+                    ("id", serde_json::Value::String(id)) => feature.id = Some(Id::String(id)),
+                    ("id", serde_json::Value::Number(id)) => feature.id = Some(Id::Number(id)),
+                    (_, value) if !feature.contains_property(&key) => {
+                        feature.set_property(key, value)
+                    }
+                    _ => {}
+                }
+            }
+            Ok(feature)
+        })
+        .into_iter()
+        .collect()
     }
 
-    fn create_circle_feature(coordinates: Vec<Vec<f64>>, radius_meters: f64) -> Result<Feature> {
+    // Every hexagon as one polygon of a MultiPolygon feature, see
+    // `GeoJsonOptions::multipolygon`
+    fn multipolygon_feature(&self, hexagons: &[CellIndex]) -> Result<Feature> {
+        let polygons: Vec<Vec<Vec<Vec<f64>>>> =
+            hexagons.iter().map(|cell| vec![hex_ring(*cell)]).collect();
+
         let mut properties = Map::new();
-        properties.insert("type".to_string(), json!("circle"));
-        properties.insert("radius_meters".to_string(), json!(radius_meters));
+        properties.insert("type".to_string(), json!("hexagons"));
+        properties.insert(
+            "hex_ids".to_string(),
+            json!(hexagons
+                .iter()
+                .map(|cell| cell.to_string())
+                .collect::<Vec<_>>()),
+        );
+        if self.rectangle.is_none() {
+            let classes: Vec<&str> = hexagons
+                .iter()
+                .map(|cell| self.classify_cell(*cell).as_str())
+                .collect();
+            properties.insert("class".to_string(), json!(classes));
+        }
+        if self.coverage_overlap {
+            let overlaps = hexagons
+                .iter()
+                .map(|cell| self.coverage_overlap(*cell))
+                .collect::<Result<Vec<_>>>()?;
+            properties.insert("coverage_overlap".to_string(), json!(overlaps));
+        }
+        if self.padding_rings > 0 {
+            let padding = self.padding_cells()?;
+            let padded: Vec<bool> = hexagons
+                .iter()
+                .map(|cell| padding.binary_search(cell).is_ok())
+                .collect();
+            properties.insert("padded".to_string(), json!(padded));
+        }
 
         Ok(Feature {
-            bbox: None,
-            geometry: Some(Geometry::new(Value::Polygon(vec![coordinates]))),
+            bbox: crate::lng_lat_bbox(polygons.iter().flatten().flatten().map(|c| (c[0], c[1])))
+                .map(Vec::from),
+            geometry: Some(Geometry::new(Value::MultiPolygon(polygons))),
             id: None,
             properties: Some(properties),
             foreign_members: None,
         })
     }
 
-    fn create_hex_feature(hex_id: CellIndex) -> Result<Feature> {
-        let boundary: Vec<_> = hex_id
-            .boundary()
+    /// Like [`Self::to_geojson_with_options`], covering at `resolution`
+    /// instead of the configured one, see [`Self::hexagons_at`]
+    pub fn to_geojson_at(
+        &self,
+        resolution: Resolution,
+        opts: &GeoJsonOptions,
+    ) -> Result<FeatureCollection> {
+        self.at_resolution(resolution).to_geojson_with_options(opts)
+    }
+
+    /// Export [`Self::hexagons_adaptive`] with the circle, each hexagon
+    /// carrying its own `resolution` property
+    pub fn to_geojson_adaptive(
+        &self,
+        coarse: Resolution,
+        fine: Resolution,
+    ) -> Result<FeatureCollection> {
+        let hexagons = self.hexagons_adaptive(coarse, fine)?;
+        let mut features = crate::parallel::map(&hexagons, |hex_id| {
+            let mut feature = create_hex_feature(*hex_id)?;
+            feature.set_property("resolution", u8::from(hex_id.resolution()));
+            Ok(feature)
+        })
+        .into_iter()
+        .collect::<Result<Vec<_>>>()?;
+        features.push(self.circle_feature()?);
+
+        Ok(FeatureCollection {
+            features,
+            bbox: Some(self.collection_bbox(&hexagons, OutputCrs::Wgs84)?),
+            foreign_members: Some(self.foreign_members(&GeoJsonOptions::default())),
+        })
+    }
+
+    /// Serialise the same document as [`Self::to_geojson_with_options`]
+    /// straight into `writer`, building one feature at a time instead of
+    /// the whole collection. The output matches serialising
+    /// [`Self::to_geojson_with_options`] byte for byte.
+    pub fn write_geojson<W: Write>(&self, writer: W, opts: &GeoJsonOptions) -> Result<()> {
+        let hexagons = self.export_hexagons(opts)?;
+        let collection = StreamedCollection {
+            smallest_enclosing_h3: self,
+            opts,
+            bbox: self.collection_bbox(&hexagons, opts.crs)?,
+            hexagons,
+        };
+        if opts.pretty {
+            serde_json::to_writer_pretty(writer, &collection)?;
+        } else {
+            serde_json::to_writer(writer, &collection)?;
+        }
+        Ok(())
+    }
+
+    /// Write the features of [`Self::to_geojson_with_options`] as a GeoJSON
+    /// Text Sequence ([RFC 8142]): each feature a standalone GeoJSON
+    /// object on its own line, for tippecanoe and stream processors.
+    ///
+    /// See [`GeoJsonOptions::record_separator`] for the RS character RFC
+    /// 8142 puts before each feature. Features are always written compact,
+    /// whatever [`GeoJsonOptions::pretty`]. The collection's own members,
+    /// such as its `bbox` and `generator`, have no place in a sequence and
+    /// are left out.
+    ///
+    /// [RFC 8142]: https://www.rfc-editor.org/rfc/rfc8142
+    pub fn write_geojson_seq<W: Write>(&self, mut writer: W, opts: &GeoJsonOptions) -> Result<()> {
+        let hexagons = self.export_hexagons(opts)?;
+        for feature in self.exported_features(opts, &hexagons)?.1 {
+            if opts.record_separator {
+                writer.write_all(b"\x1e")?;
+            }
+            serde_json::to_writer(&mut writer, &feature?)?;
+            writer.write_all(b"\n")?;
+        }
+        Ok(())
+    }
+
+    // The count and the projected features of the collection, in order,
+    // generated one at a time
+    fn exported_features<'a>(
+        &'a self,
+        opts: &'a GeoJsonOptions,
+        hexagons: &'a [CellIndex],
+    ) -> Result<(usize, impl Iterator<Item = Result<Feature>> + 'a)> {
+        let circles = if opts.omit_circle {
+            Vec::new()
+        } else {
+            let circle = self.styled_circle_feature(opts)?;
+            [Some(circle), self.inner_circle_feature()?]
+                .into_iter()
+                .flatten()
+                .collect()
+        };
+        let boundary_edges = if opts.boundary_edges {
+            self.boundary_edges()?
+        } else {
+            Vec::new()
+        };
+        let hexagon_features = if opts.multipolygon { 1 } else { hexagons.len() };
+        let len = hexagon_features
+            + circles.len()
+            + boundary_edges.len()
+            + usize::from(opts.center_marker);
+
+        let hexagon_features: Box<dyn Iterator<Item = Result<Feature>>> = if opts.multipolygon {
+            Box::new(std::iter::once_with(|| {
+                let mut feature = self.multipolygon_feature(hexagons)?;
+                extrude_feature(&mut feature, hexagons, opts);
+                Ok(feature)
+            }))
+        } else {
+            Box::new(hexagons.iter().map(|hex_id| {
+                let mut feature = self.hex_feature(*hex_id)?;
+                if let Some(style) = &opts.simplestyle {
+                    self.style_hex_feature(&mut feature, *hex_id, style);
+                }
+                extrude_feature(&mut feature, std::slice::from_ref(hex_id), opts);
+                Ok(feature)
+            }))
+        };
+        let features = hexagon_features
+            .chain(circles.into_iter().map(Ok))
+            .chain(
+                boundary_edges
+                    .into_iter()
+                    .map(|edge| Ok(edge_feature(edge))),
+            )
+            .chain(
+                opts.center_marker
+                    .then(|| Ok(self.center_feature(hexagons.len()))),
+            )
+            .map(|feature| {
+                let mut feature = feature?;
+                project_feature(&mut feature, opts.crs);
+                Ok(feature)
+            });
+        Ok((len, features))
+    }
+
+    fn export_hexagons(&self, opts: &GeoJsonOptions) -> Result<Vec<CellIndex>> {
+        match opts.order {
+            Some(order) => self.hexagons_sorted(order),
+            None => self.hexagons(),
+        }
+    }
+
+    // Fill `feature` along the ramp by its centroid's distance over the
+    // radius, see `GeoJsonOptions::simplestyle`
+    fn style_hex_feature(&self, feature: &mut Feature, hex_id: CellIndex, style: &SimpleStyle) {
+        let t = self.distance_from_center(LatLng::from(hex_id)) / self.radius_meters;
+        feature.set_property("fill", style.fill(t));
+        feature.set_property("fill-opacity", style.fill_opacity);
+        feature.set_property("stroke", hex_color(style.stroke));
+        feature.set_property("stroke-width", style.stroke_width);
+    }
+
+    // The circle feature, drawn as an outline only with
+    // `GeoJsonOptions::simplestyle`
+    fn styled_circle_feature(&self, opts: &GeoJsonOptions) -> Result<Feature> {
+        let mut circle = self.circle_feature()?;
+        if let Some(style) = &opts.simplestyle {
+            circle.set_property("fill-opacity", 0.0);
+            circle.set_property("stroke", hex_color(style.stroke));
+            circle.set_property("stroke-width", style.stroke_width);
+        }
+        Ok(circle)
+    }
+
+    fn hex_feature(&self, hex_id: CellIndex) -> Result<Feature> {
+        let mut feature = create_hex_feature(hex_id)?;
+        if self.rectangle.is_none() {
+            feature.set_property("class", self.classify_cell(hex_id).as_str());
+        }
+        if self.coverage_overlap {
+            feature.set_property("coverage_overlap", self.coverage_overlap(hex_id)?);
+        }
+        if self.padding_rings > 0 {
+            let padded = self.padding_cells()?.binary_search(&hex_id).is_ok();
+            feature.set_property("padded", padded);
+        }
+        Ok(feature)
+    }
+
+    // The circle feature, with its exact center so the circle can be
+    // rebuilt by `from_geojson`. A bounding box is tagged `"type": "bbox"`
+    // instead, with its `bounds` as `[min_lat, min_lng, max_lat, max_lng]`.
+    fn circle_feature(&self) -> Result<Feature> {
+        let coordinates = self.generate_circle_coordinates()?;
+        let spherical =
+            self.is_large_cap() && self.bearing_range.is_none() && self.rectangle.is_none();
+        let mut feature = create_circle_feature(coordinates.clone(), self.radius_meters)?;
+        if spherical {
+            // Already wound around the cap; its planar area in degrees
+            // says nothing about which side is inside
+            feature.geometry = Some(Geometry::new(Value::Polygon(vec![coordinates])));
+        }
+        feature.set_property("center", json!([self.center.lng(), self.center.lat()]));
+        if let Some(rectangle) = self.rectangle {
+            feature.set_property("type", "bbox");
+            feature.set_property(
+                "bounds",
+                json!([
+                    rectangle.min_lat,
+                    rectangle.min_lng,
+                    rectangle.max_lat,
+                    rectangle.max_lng
+                ]),
+            );
+        }
+        Ok(feature)
+    }
+
+    // The inner circle of an annulus, tagged `"type": "inner_circle"`
+    fn inner_circle_feature(&self) -> Result<Option<Feature>> {
+        if self.inner_radius_meters <= 0.0 {
+            return Ok(None);
+        }
+        let coordinates =
+            self.circle_coordinates(self.inner_radius_meters, crate::CIRCLE_VERTICES)?;
+        let mut feature = create_circle_feature(coordinates, self.inner_radius_meters)?;
+        feature.set_property("type", "inner_circle");
+        Ok(Some(feature))
+    }
+
+    fn center_feature(&self, cell_count: usize) -> Feature {
+        let (lng, lat) = (self.center.lng(), self.center.lat());
+
+        let mut properties = Map::new();
+        properties.insert("type".to_string(), json!("center"));
+        properties.insert("radius_meters".to_string(), json!(self.radius_meters));
+        properties.insert("resolution".to_string(), json!(u8::from(self.resolution)));
+        properties.insert("cell_count".to_string(), json!(cell_count));
+
+        Feature {
+            bbox: Some(vec![lng, lat, lng, lat]),
+            geometry: Some(Geometry::new(Value::Point(vec![lng, lat]))),
+            id: None,
+            properties: Some(properties),
+            foreign_members: None,
+        }
+    }
+
+    // Bounding box of every hexagon and the circle, as exported
+    fn collection_bbox(&self, hexagons: &[CellIndex], crs: OutputCrs) -> Result<Vec<f64>> {
+        let circle = self.generate_circle_coordinates()?;
+        let points = hexagons
+            .iter()
+            .flat_map(|cell| {
+                cell.boundary()
+                    .iter()
+                    .map(|p| (p.lng(), p.lat()))
+                    .collect::<Vec<_>>()
+            })
+            .chain(circle.iter().map(|c| (c[0], c[1])));
+        let mut bbox = crate::lng_lat_bbox(points).map_or_else(Vec::new, Vec::from);
+        project_bbox(&mut bbox, crs);
+        Ok(bbox)
+    }
+
+    fn foreign_members(&self, opts: &GeoJsonOptions) -> Map<String, serde_json::Value> {
+        let mut foreign_members = Map::new();
+        foreign_members.insert(
+            "h3_resolution".to_string(),
+            json!(u8::from(self.resolution)),
+        );
+        foreign_members.insert("center_lat".to_string(), json!(self.center.lat()));
+        foreign_members.insert("center_lng".to_string(), json!(self.center.lng()));
+        foreign_members.insert("radius_meters".to_string(), json!(self.radius_meters));
+        if opts.crs != OutputCrs::Wgs84 {
+            foreign_members.insert("crs".to_string(), json!(opts.crs.code()));
+        }
+        // The hexagons are computed by now, so any adjustment made while
+        // covering is already recorded. A snapped center is exported on
+        // its own: nothing may tell where in its cell the requested one was.
+        let applied = self.policy.applied();
+        let snapped = applied
+            .iter()
+            .any(|adjustment| matches!(adjustment, Adjustment::SnapCenter { .. }));
+        let adjustments: Vec<serde_json::Value> = applied
             .iter()
-            .map(|p| vec![p.lng(), p.lat()])
+            .filter(|adjustment| {
+                !snapped
+                    || matches!(adjustment, Adjustment::SnapCenter { .. })
+                    || !adjustment.moves_center()
+            })
+            .map(adjustment_json)
             .collect();
+        if !adjustments.is_empty() {
+            let mut requested = json!({
+                "radius_meters": self.requested_radius_meters,
+                "resolution": u8::from(self.requested_resolution),
+            });
+            if !snapped {
+                let center = self.requested_center;
+                requested["center"] = json!([center.lng(), center.lat()]);
+            }
+            foreign_members.insert(
+                "effective_parameters".to_string(),
+                json!({
+                    "requested": requested,
+                    "effective": {
+                        "center": [self.center.lng(), self.center.lat()],
+                        "radius_meters": self.radius_meters,
+                        "resolution": u8::from(self.resolution),
+                    },
+                    "adjustments": adjustments,
+                }),
+            );
+        }
+        if opts.generator {
+            let mut generator = json!({
+                "name": env!("CARGO_PKG_NAME"),
+                "version": env!("CARGO_PKG_VERSION"),
+                "parameters": {
+                    "center": [self.center.lng(), self.center.lat()],
+                    "radius_meters": self.radius_meters,
+                    "resolution": u8::from(self.resolution),
+                    "coverage_overlap": self.coverage_overlap,
+                    "crs": opts.crs.code(),
+                },
+            });
+            if !adjustments.is_empty() {
+                generator["parameters"]["adjustments"] = json!(adjustments);
+            }
+            foreign_members.insert("generator".to_string(), generator);
+        }
+        foreign_members
+    }
 
-        // Close the polygon by repeating the first point
-        let mut coordinates = vec![boundary];
-        // Get the first point before mutating the vector
-        if let Some(first) = coordinates[0].first().cloned() {
-            coordinates[0].push(first);
+    /// Recover the builder for a circle exported by [`Self::to_geojson`].
+    ///
+    /// The radius comes from the `circle` feature's `radius_meters`, the
+    /// center from its `center` property or, for files written before that
+    /// existed, the centroid of its ring. The resolution comes from the
+    /// collection's `h3_resolution` member, or else from a hexagon feature.
+    /// An `inner_circle` feature's `radius_meters` sets the inner radius.
+    /// A `bbox` feature's `bounds` rebuild a bounding box coverage instead.
+    pub fn from_geojson(fc: &FeatureCollection) -> Result<SmallestEnclosingH3Builder> {
+        let invalid = |reason: &str| SmallestEnclosingH3Error::InvalidGeoJson(reason.to_string());
+
+        if let Some(rectangle) = fc
+            .features
+            .iter()
+            .find(|feature| feature_type(feature, "bbox"))
+        {
+            let bounds = rectangle
+                .property("bounds")
+                .and_then(|v| v.as_array())
+                .map(|b| b.iter().filter_map(|v| v.as_f64()).collect::<Vec<_>>())
+                .filter(|b| b.len() == 4)
+                .ok_or_else(|| {
+                    invalid("bbox bounds must be [min_lat, min_lng, max_lat, max_lng]")
+                })?;
+            return SmallestEnclosingH3Builder::from_bbox(
+                bounds[0],
+                bounds[1],
+                bounds[2],
+                bounds[3],
+                geojson_resolution(fc)?,
+            );
         }
 
+        let circle = fc
+            .features
+            .iter()
+            .find(|feature| feature_type(feature, "circle"))
+            .ok_or_else(|| invalid("no feature with type \"circle\""))?;
+        let radius_meters = circle
+            .property("radius_meters")
+            .and_then(|v| v.as_f64())
+            .ok_or_else(|| invalid("circle feature has no numeric radius_meters"))?;
+
+        let center = match circle.property("center") {
+            Some(center) => {
+                let lng_lat = center
+                    .as_array()
+                    .map(|c| c.iter().filter_map(|v| v.as_f64()).collect::<Vec<_>>())
+                    .filter(|c| c.len() == 2)
+                    .ok_or_else(|| invalid("circle center must be a [lng, lat] pair"))?;
+                LatLng::new(lng_lat[1], lng_lat[0])?
+            }
+            None => {
+                let Some(Geometry {
+                    value: Value::Polygon(rings),
+                    ..
+                }) = &circle.geometry
+                else {
+                    return Err(invalid("circle feature geometry must be a polygon"));
+                };
+                let ring: Vec<(f64, f64)> = rings
+                    .first()
+                    .map(|ring| {
+                        ring.iter()
+                            .filter(|c| c.len() >= 2)
+                            .map(|c| (c[0], c[1]))
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                let centroid = Polygon::new(LineString::from(ring), vec![])
+                    .centroid()
+                    .ok_or_else(|| invalid("circle ring is empty"))?;
+                LatLng::new(centroid.y(), centroid.x())?
+            }
+        };
+
+        let resolution = geojson_resolution(fc)?;
+
+        let inner_radius_meters = fc
+            .features
+            .iter()
+            .find(|feature| feature_type(feature, "inner_circle"))
+            .map(|feature| {
+                feature
+                    .property("radius_meters")
+                    .and_then(|v| v.as_f64())
+                    .ok_or_else(|| invalid("inner_circle feature has no numeric radius_meters"))
+            })
+            .transpose()?
+            .unwrap_or(0.0);
+
+        SmallestEnclosingH3Builder::new(center, radius_meters, resolution)
+            .radius_meters(radius_meters)?
+            .inner_radius_meters(inner_radius_meters)
+    }
+
+    /// Export the hexagons as a single feature holding their dissolved
+    /// outline, so filled maps show no borders between neighbouring cells.
+    /// The geometry is a `MultiPolygon`; a ring around the center yields one
+    /// polygon with a hole.
+    pub fn to_geojson_union(&self) -> Result<Feature> {
+        let hexagons = self.hexagons()?;
+        let union = self.hexagon_coverage()?.outline();
+
         let mut properties = Map::new();
-        properties.insert("hex_id".to_string(), json!(hex_id.to_string()));
-        properties.insert("type".to_string(), json!("hexagon"));
+        properties.insert("type".to_string(), json!("union"));
+        properties.insert("cell_count".to_string(), json!(hexagons.len()));
+        properties.insert(
+            "h3_resolution".to_string(),
+            json!(u8::from(self.resolution)),
+        );
 
         Ok(Feature {
             bbox: None,
-            geometry: Some(Geometry::new(Value::Polygon(coordinates))),
+            geometry: Some(Geometry::new(Value::from(&union))),
             id: None,
             properties: Some(properties),
             foreign_members: None,
         })
     }
+
+    /// Export [`Self::boundary_samples`] as point features for debugging,
+    /// coloured green when covered and red when not (via the simplestyle
+    /// `marker-color` property)
+    pub fn boundary_samples_geojson(&self, n: usize) -> Result<FeatureCollection> {
+        let features = self
+            .boundary_samples(n)?
+            .into_iter()
+            .map(|sample| {
+                let mut properties = Map::new();
+                properties.insert("type".to_string(), json!("boundary_sample"));
+                properties.insert("bearing".to_string(), json!(sample.bearing));
+                properties.insert("hex_id".to_string(), json!(sample.cell.to_string()));
+                properties.insert("covered".to_string(), json!(sample.covered));
+                properties.insert("grid_distance".to_string(), json!(sample.grid_distance));
+                properties.insert(
+                    "marker-color".to_string(),
+                    json!(if sample.covered { "#00aa00" } else { "#dd0000" }),
+                );
+
+                Feature {
+                    bbox: None,
+                    geometry: Some(Geometry::new(Value::Point(vec![
+                        sample.lat_lng.lng(),
+                        sample.lat_lng.lat(),
+                    ]))),
+                    id: None,
+                    properties: Some(properties),
+                    foreign_members: None,
+                }
+            })
+            .collect();
+
+        Ok(FeatureCollection {
+            features,
+            bbox: None,
+            foreign_members: None,
+        })
+    }
+
+    /// Serialise [`Self::to_geojson`] to a compact JSON string
+    pub fn to_geojson_string(&self) -> Result<String> {
+        serde_json::to_string(&self.to_geojson()?).map_err(SmallestEnclosingH3Error::from)
+    }
+
+    /// Serialise [`Self::to_geojson`] to a pretty-printed JSON string
+    pub fn to_geojson_string_pretty(&self) -> Result<String> {
+        serde_json::to_string_pretty(&self.to_geojson()?).map_err(SmallestEnclosingH3Error::from)
+    }
+}
+
+impl SmallestEnclosingH3Builder {
+    /// A builder from a GeoJSON Point feature with a `radius_meters`
+    /// property and an optional `resolution`, as map applications send
+    /// them; without a resolution it is that of
+    /// [`SmallestEnclosingH3Builder::default`]. Other properties are
+    /// ignored.
+    ///
+    /// A feature without a Point geometry is a
+    /// [`SmallestEnclosingH3Error::InvalidLatLng`], a missing or
+    /// non-numeric `radius_meters` a
+    /// [`SmallestEnclosingH3Error::InvalidRadius`], and a `resolution`
+    /// that isn't an integer from 0 to 15 a
+    /// [`SmallestEnclosingH3Error::InvalidResolution`].
+    pub fn from_geojson_feature(feature: &Feature) -> Result<Self> {
+        let Some(Geometry {
+            value: Value::Point(position),
+            ..
+        }) = &feature.geometry
+        else {
+            return Err(h3o::error::InvalidLatLng {
+                value: f64::NAN,
+                reason: "feature geometry must be a Point",
+            }
+            .into());
+        };
+        let &[lng, lat, ..] = position.as_slice() else {
+            return Err(h3o::error::InvalidLatLng {
+                value: f64::NAN,
+                reason: "Point must have a longitude and latitude",
+            }
+            .into());
+        };
+
+        let radius_meters = feature
+            .property("radius_meters")
+            .and_then(|v| v.as_f64())
+            .ok_or_else(|| {
+                SmallestEnclosingH3Error::InvalidRadius(
+                    "radius_meters must be a number".to_string(),
+                )
+            })?;
+
+        let builder = Self::default()
+            .center(LatLng::new(lat, lng)?)
+            .radius_meters(radius_meters)?;
+        match feature.property("resolution") {
+            Some(resolution) => {
+                let resolution = resolution
+                    .as_u64()
+                    .and_then(|r| u8::try_from(r).ok())
+                    .ok_or(h3o::error::InvalidResolution {
+                        value: None,
+                        reason: "resolution must be an integer",
+                    })?;
+                builder.resolution(resolution)
+            }
+            None => Ok(builder),
+        }
+    }
+}
+
+// Resolution from the `h3_resolution` member, or else from a hexagon feature
+fn geojson_resolution(fc: &FeatureCollection) -> Result<Resolution> {
+    let invalid = |reason: &str| SmallestEnclosingH3Error::InvalidGeoJson(reason.to_string());
+
+    Ok(
+        match fc
+            .foreign_members
+            .as_ref()
+            .and_then(|members| members.get("h3_resolution"))
+        {
+            Some(resolution) => resolution
+                .as_u64()
+                .and_then(|r| u8::try_from(r).ok())
+                .ok_or_else(|| invalid("h3_resolution must be an integer"))
+                .and_then(|r| Ok(Resolution::try_from(r)?))?,
+            None => fc
+                .features
+                .iter()
+                .filter(|feature| feature_type(feature, "hexagon"))
+                .find_map(|feature| feature.property("hex_id").and_then(|v| v.as_str()))
+                .ok_or_else(|| invalid("no h3_resolution member or hexagon feature"))?
+                .parse::<CellIndex>()
+                .map_err(|_| invalid("hexagon feature has an invalid hex_id"))?
+                .resolution(),
+        },
+    )
+}
+
+// An `Adjustment` as a JSON object tagged with its `kind`, with
+// coordinates as `[lng, lat]`. A snapped center only gives where it was
+// snapped to.
+fn adjustment_json(adjustment: &Adjustment) -> serde_json::Value {
+    match *adjustment {
+        Adjustment::NormalizeLatitude { from, to } => {
+            json!({ "kind": "normalize_latitude", "from": from, "to": to })
+        }
+        Adjustment::NormalizeLongitude { from, to } => {
+            json!({ "kind": "normalize_longitude", "from": from, "to": to })
+        }
+        Adjustment::ApproximateRingDistance { estimate } => {
+            json!({ "kind": "approximate_ring_distance", "estimate": estimate })
+        }
+        Adjustment::SnapCenter { to, .. } => {
+            json!({ "kind": "snap_center", "to": [to.1, to.0] })
+        }
+        Adjustment::CoarsenResolution { from, to } => {
+            json!({ "kind": "coarsen_resolution", "from": u8::from(from), "to": u8::from(to) })
+        }
+        Adjustment::ExpandRingDistance { from, to } => {
+            json!({ "kind": "expand_ring_distance", "from": from, "to": to })
+        }
+    }
+}
+
+fn feature_type(feature: &Feature, expected: &str) -> bool {
+    feature.property("type").and_then(|v| v.as_str()) == Some(expected)
+}
+
+// A FeatureCollection whose features are only built while serialising
+struct StreamedCollection<'a> {
+    smallest_enclosing_h3: &'a SmallestEnclosingH3,
+    opts: &'a GeoJsonOptions,
+    bbox: Vec<f64>,
+    hexagons: Vec<CellIndex>,
+}
+
+struct StreamedFeatures<'a>(&'a StreamedCollection<'a>);
+
+impl Serialize for StreamedCollection<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        // geojson serialises a collection through a serde_json::Map, so
+        // collect the members the same way to reproduce its key order
+        let mut members = Map::new();
+        members.insert("type".to_string(), json!("FeatureCollection"));
+        members.insert("features".to_string(), serde_json::Value::Null);
+        members.insert("bbox".to_string(), json!(self.bbox));
+        members.extend(self.smallest_enclosing_h3.foreign_members(self.opts));
+
+        let mut map = serializer.serialize_map(Some(members.len()))?;
+        for (key, value) in &members {
+            if key == "features" {
+                map.serialize_entry(key, &StreamedFeatures(self))?;
+            } else {
+                map.serialize_entry(key, value)?;
+            }
+        }
+        map.end()
+    }
+}
+
+impl Serialize for StreamedFeatures<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        let StreamedCollection {
+            smallest_enclosing_h3,
+            opts,
+            hexagons,
+            ..
+        } = self.0;
+
+        let (len, features) = smallest_enclosing_h3
+            .exported_features(opts, hexagons)
+            .map_err(S::Error::custom)?;
+        let mut seq = serializer.serialize_seq(Some(len))?;
+        for feature in features {
+            seq.serialize_element(&feature.map_err(S::Error::custom)?)?;
+        }
+        seq.end()
+    }
+}
+
+/// [`SmallestEnclosingH3::to_geojson`], for `FeatureCollection::try_from`
+/// and APIs taking `TryInto<FeatureCollection>`
+impl TryFrom<&SmallestEnclosingH3> for FeatureCollection {
+    type Error = SmallestEnclosingH3Error;
+
+    fn try_from(smallest_enclosing_h3: &SmallestEnclosingH3) -> Result<Self> {
+        smallest_enclosing_h3.to_geojson_with_options(&GeoJsonOptions::default())
+    }
+}
+
+impl CellCoverage {
+    /// A feature per cell, as [`create_hex_feature`], in order, followed
+    /// by the reference geometry, if any, tagged `"type": "reference"`.
+    /// The collection's bbox covers both.
+    pub fn to_geojson(&self) -> Result<FeatureCollection> {
+        let mut features = self
+            .cells()
+            .iter()
+            .map(|cell| create_hex_feature(*cell))
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut reference_points = Vec::new();
+        if let Some(reference) = self.reference() {
+            let ring = |line: &LineString, exterior| {
+                let coordinates = line.coords().map(|c| vec![c.x, c.y]).collect();
+                rfc7946_ring(coordinates, exterior)
+            };
+            let rings: Vec<_> = std::iter::once(ring(reference.exterior(), true))
+                .chain(reference.interiors().iter().map(|hole| ring(hole, false)))
+                .collect();
+            reference_points.extend(rings[0].iter().map(|c| (c[0], c[1])));
+
+            let mut properties = Map::new();
+            properties.insert("type".to_string(), json!("reference"));
+            features.push(Feature {
+                bbox: ring_bbox(&rings[0]),
+                geometry: Some(Geometry::new(Value::Polygon(rings))),
+                id: None,
+                properties: Some(properties),
+                foreign_members: None,
+            });
+        }
+
+        let points = self
+            .cells()
+            .iter()
+            .flat_map(|cell| {
+                cell.boundary()
+                    .iter()
+                    .map(|p| (p.lng(), p.lat()))
+                    .collect::<Vec<_>>()
+            })
+            .chain(reference_points);
+        Ok(FeatureCollection {
+            features,
+            bbox: crate::lng_lat_bbox(points).map(Vec::from),
+            foreign_members: None,
+        })
+    }
+}
+
+/// Polygon feature for a circle ring, tagged `"type": "circle"`, as added by
+/// [`SmallestEnclosingH3::to_geojson`]. `coordinates` are `[lng, lat]` pairs;
+/// the ring is closed and wound counterclockwise per RFC 7946.
+pub fn create_circle_feature(coordinates: Vec<Vec<f64>>, radius_meters: f64) -> Result<Feature> {
+    let mut properties = Map::new();
+    properties.insert("type".to_string(), json!("circle"));
+    properties.insert("radius_meters".to_string(), json!(radius_meters));
+
+    let ring = rfc7946_ring(coordinates, true);
+    Ok(Feature {
+        bbox: ring_bbox(&ring),
+        geometry: Some(Geometry::new(Value::Polygon(vec![ring]))),
+        id: None,
+        properties: Some(properties),
+        foreign_members: None,
+    })
+}
+
+// Add the heights of `cells`, the cells of a hexagon or combined
+// hexagons feature in polygon order, see `GeoJsonOptions::extrusion`
+fn extrude_feature(feature: &mut Feature, cells: &[CellIndex], opts: &GeoJsonOptions) {
+    let Some(spec) = &opts.extrusion else {
+        return;
+    };
+    let heights: Vec<f64> = cells.iter().map(|cell| spec.height(*cell)).collect();
+    let (polygons, value) = match feature.geometry.as_mut().map(|g| &mut g.value) {
+        Some(Value::Polygon(rings)) => (vec![rings], json!(heights[0])),
+        Some(Value::MultiPolygon(polygons)) => (polygons.iter_mut().collect(), json!(heights)),
+        _ => return,
+    };
+    if opts.extrusion_z {
+        for (rings, height) in polygons.into_iter().zip(&heights) {
+            for position in rings.iter_mut().flatten() {
+                position.truncate(2);
+                position.push(*height);
+            }
+        }
+    }
+    feature.set_property("elevation", value.clone());
+    feature.set_property("height", value);
+}
+
+// Project a feature's geometry and bbox, built in WGS84, into `crs`
+fn project_feature(feature: &mut Feature, crs: OutputCrs) {
+    if crs == OutputCrs::Wgs84 {
+        return;
+    }
+    if let Some(geometry) = &mut feature.geometry {
+        project_value(&mut geometry.value, crs);
+    }
+    if let Some(bbox) = &mut feature.bbox {
+        project_bbox(bbox, crs);
+    }
+}
+
+fn project_value(value: &mut Value, crs: OutputCrs) {
+    let project = |position: &mut Vec<f64>| {
+        let [x, y] = crs.project(position[0], position[1]);
+        position[0] = x;
+        position[1] = y;
+    };
+    match value {
+        Value::Point(position) => project(position),
+        Value::MultiPoint(positions) | Value::LineString(positions) => {
+            positions.iter_mut().for_each(project)
+        }
+        Value::MultiLineString(lines) | Value::Polygon(lines) => {
+            lines.iter_mut().flatten().for_each(project)
+        }
+        Value::MultiPolygon(polygons) => polygons.iter_mut().flatten().flatten().for_each(project),
+        Value::GeometryCollection(geometries) => {
+            for geometry in geometries {
+                project_value(&mut geometry.value, crs);
+            }
+        }
+    }
+}
+
+// Project a `[min_lng, min_lat, max_lng, max_lat]` bbox. Both projections
+// preserve the order of coordinates, so the corners stay the corners.
+fn project_bbox(bbox: &mut [f64], crs: OutputCrs) {
+    for corner in bbox.chunks_exact_mut(2) {
+        let [x, y] = crs.project(corner[0], corner[1]);
+        corner[0] = x;
+        corner[1] = y;
+    }
+}
+
+// LineString feature for a directed edge, see
+// `GeoJsonOptions::boundary_edges`
+fn edge_feature(edge: DirectedEdgeIndex) -> Feature {
+    let line: Vec<Vec<f64>> = edge
+        .boundary()
+        .iter()
+        .map(|p| vec![p.lng(), p.lat()])
+        .collect();
+
+    let mut properties = Map::new();
+    properties.insert("type".to_string(), json!("boundary_edge"));
+    properties.insert("edge_id".to_string(), json!(edge.to_string()));
+    properties.insert("origin".to_string(), json!(edge.origin().to_string()));
+    properties.insert(
+        "destination".to_string(),
+        json!(edge.destination().to_string()),
+    );
+
+    Feature {
+        bbox: ring_bbox(&line),
+        geometry: Some(Geometry::new(Value::LineString(line))),
+        id: None,
+        properties: Some(properties),
+        foreign_members: None,
+    }
+}
+
+fn ring_bbox(ring: &[Vec<f64>]) -> Option<Vec<f64>> {
+    crate::lng_lat_bbox(ring.iter().map(|c| (c[0], c[1]))).map(Vec::from)
+}
+
+// The closed, counterclockwise boundary of `cell`
+fn hex_ring(cell: CellIndex) -> Vec<Vec<f64>> {
+    let boundary: Vec<_> = cell
+        .boundary()
+        .iter()
+        .map(|p| vec![p.lng(), p.lat()])
+        .collect();
+    rfc7946_ring(boundary, true)
+}
+
+// Close `ring` exactly once and wind it as RFC 7946 requires:
+// counterclockwise for exterior rings, clockwise for holes
+fn rfc7946_ring(mut ring: Vec<Vec<f64>>, exterior: bool) -> Vec<Vec<f64>> {
+    while ring.len() > 1 && ring.first() == ring.last() {
+        ring.pop();
+    }
+    if (signed_area(&ring) > 0.0) != exterior {
+        ring.reverse();
+    }
+    if let Some(first) = ring.first().cloned() {
+        ring.push(first);
+    }
+    ring
+}
+
+// Shoelace area of a ring of [x, y] positions, positive when
+// counterclockwise
+pub(crate) fn signed_area(ring: &[Vec<f64>]) -> f64 {
+    let n = ring.len();
+    (0..n)
+        .map(|i| {
+            let (a, b) = (&ring[i], &ring[(i + 1) % n]);
+            a[0] * b[1] - b[0] * a[1]
+        })
+        .sum::<f64>()
+        / 2.0
+}
+
+/// Closed, counterclockwise polygon feature for a cell boundary, tagged
+/// `"type": "hexagon"` with its `hex_id`, as added by
+/// [`SmallestEnclosingH3::to_geojson`]
+pub fn create_hex_feature(hex_id: CellIndex) -> Result<Feature> {
+    let ring = hex_ring(hex_id);
+
+    let mut properties = Map::new();
+    properties.insert("hex_id".to_string(), json!(hex_id.to_string()));
+    properties.insert("type".to_string(), json!("hexagon"));
+
+    Ok(Feature {
+        bbox: ring_bbox(&ring),
+        geometry: Some(Geometry::new(Value::Polygon(vec![ring]))),
+        id: None,
+        properties: Some(properties),
+        foreign_members: None,
+    })
+}
+
+/// The former path of this module's items, kept for one release
+#[deprecated(note = "the items moved up to `smallest_enclosing_h3::geojson_export`")]
+#[allow(clippy::module_inception)]
+pub mod geojson_export {
+    pub use super::*;
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::error::SmallestEnclosingH3Error;
     use h3o::{LatLng, Resolution};
-    use std::fs::File;
-    use std::io::Write;
 
     #[test]
     fn test_geojson_export() {
@@ -95,6 +1234,12 @@ mod tests {
 
         let geojson = smallest_enclosing_h3.to_geojson().unwrap();
 
+        // The conversion needs no import of this module
+        assert_eq!(
+            geojson::FeatureCollection::try_from(&smallest_enclosing_h3).unwrap(),
+            geojson
+        );
+
         // Basic structure checks
         assert!(geojson.features.len() > 1, "Should have multiple features");
 
@@ -115,10 +1260,1411 @@ mod tests {
         assert_eq!(circle_count, 1, "Should have exactly one circle feature");
 
         // Optional: Write to file for visual inspection
-        let json_string = serde_json::to_string_pretty(&geojson).unwrap();
-        File::create("rust_map.geojson")
-            .unwrap()
-            .write_all(json_string.as_bytes())
+        let json_string = smallest_enclosing_h3.to_geojson_string_pretty().unwrap();
+        std::fs::write("rust_map.geojson", json_string).unwrap();
+    }
+
+    // Every polygon ring is closed exactly once, exteriors wind
+    // counterclockwise and holes clockwise (RFC 7946 section 3.1.6)
+    fn assert_rfc7946(geometry: &geojson::Geometry) {
+        use super::signed_area;
+
+        let polygons = match &geometry.value {
+            geojson::Value::Polygon(polygon) => vec![polygon.clone()],
+            geojson::Value::MultiPolygon(polygons) => polygons.clone(),
+            _ => return,
+        };
+        for polygon in polygons {
+            for (i, ring) in polygon.iter().enumerate() {
+                assert!(ring.len() >= 4);
+                assert_eq!(ring.first(), ring.last());
+                assert_ne!(ring[ring.len() - 2], ring[0]);
+                let area = signed_area(ring);
+                if i == 0 {
+                    assert!(area > 0.0, "exterior ring should be counterclockwise");
+                } else {
+                    assert!(area < 0.0, "holes should be clockwise");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_geojson_rfc7946_winding() {
+        for (lat, lng) in [(33.4484, -112.0740), (-33.8688, 151.2093)] {
+            let center = LatLng::new(lat, lng).unwrap();
+            let smallest_enclosing_h3 =
+                crate::SmallestEnclosingH3Builder::new(center, 500.0, Resolution::Ten)
+                    .build()
+                    .unwrap();
+
+            for feature in smallest_enclosing_h3.to_geojson().unwrap().features {
+                assert_rfc7946(feature.geometry.as_ref().unwrap());
+            }
+            let union = smallest_enclosing_h3.to_geojson_union().unwrap();
+            assert_rfc7946(union.geometry.as_ref().unwrap());
+        }
+    }
+
+    #[test]
+    fn test_geojson_foreign_members() {
+        let center = LatLng::new(33.4484, -112.0740).unwrap();
+        let smallest_enclosing_h3 =
+            crate::SmallestEnclosingH3Builder::new(center, 50.0, Resolution::Twelve)
+                .build()
+                .unwrap();
+
+        let geojson = smallest_enclosing_h3.to_geojson().unwrap();
+        let members = geojson
+            .foreign_members
+            .expect("Should have foreign members");
+
+        assert_eq!(members["h3_resolution"].as_u64(), Some(12));
+        assert_eq!(members["center_lat"].as_f64(), Some(33.4484));
+        assert_eq!(members["center_lng"].as_f64(), Some(-112.0740));
+        assert_eq!(members["radius_meters"].as_f64(), Some(50.0));
+    }
+
+    #[test]
+    fn test_geojson_center_marker_and_generator() {
+        use super::GeoJsonOptions;
+
+        let center = LatLng::new(33.4484, -112.0740).unwrap();
+        let smallest_enclosing_h3 =
+            crate::SmallestEnclosingH3Builder::new(center, 1234.5678, Resolution::Eleven)
+                .build()
+                .unwrap();
+        let opts = GeoJsonOptions {
+            center_marker: true,
+            generator: true,
+            ..Default::default()
+        };
+
+        let mut written = Vec::new();
+        smallest_enclosing_h3
+            .write_geojson(&mut written, &opts)
+            .unwrap();
+        let geojson = smallest_enclosing_h3
+            .to_geojson_with_options(&opts)
+            .unwrap();
+        assert_eq!(
+            String::from_utf8(written).unwrap(),
+            serde_json::to_string(&geojson).unwrap()
+        );
+
+        let parsed: geojson::FeatureCollection =
+            serde_json::to_string(&geojson).unwrap().parse().unwrap();
+        let generator = &parsed.foreign_members.unwrap()["generator"];
+        assert_eq!(generator["name"], "smallest-enclosing-h3");
+        assert_eq!(generator["version"], env!("CARGO_PKG_VERSION"));
+        let parameters = &generator["parameters"];
+        assert_eq!(parameters["radius_meters"].as_f64(), Some(1234.5678));
+        assert_eq!(parameters["resolution"].as_u64(), Some(11));
+        assert_eq!(
+            parameters["center"],
+            serde_json::json!([-112.0740, 33.4484])
+        );
+
+        let marker = parsed.features.last().unwrap();
+        assert_eq!(
+            marker.geometry.as_ref().unwrap().value,
+            geojson::Value::Point(vec![-112.0740, 33.4484])
+        );
+        assert_eq!(marker.property("type").unwrap(), "center");
+        assert_eq!(
+            marker.property("radius_meters").unwrap().as_f64(),
+            Some(1234.5678)
+        );
+        assert_eq!(marker.property("resolution").unwrap().as_u64(), Some(11));
+        assert_eq!(
+            marker.property("cell_count").unwrap().as_u64(),
+            Some(smallest_enclosing_h3.hexagons().unwrap().len() as u64)
+        );
+
+        // Off by default, for minimal files
+        let minimal = smallest_enclosing_h3.to_geojson().unwrap();
+        assert_eq!(minimal.features.len(), parsed.features.len() - 1);
+        assert!(!minimal.foreign_members.unwrap().contains_key("generator"));
+    }
+
+    #[test]
+    fn test_geojson_web_mercator() {
+        use crate::{geojson_export::GeoJsonOptions, OutputCrs};
+        use geojson::Value;
+
+        let center = LatLng::new(33.4484, -112.0740).unwrap();
+        let smallest_enclosing_h3 =
+            crate::SmallestEnclosingH3Builder::new(center, 500.0, Resolution::Ten)
+                .build()
+                .unwrap();
+        let opts = GeoJsonOptions {
+            center_marker: true,
+            generator: true,
+            crs: OutputCrs::WebMercator,
+            ..Default::default()
+        };
+
+        let wgs84 = smallest_enclosing_h3
+            .to_geojson_with_options(&GeoJsonOptions {
+                crs: OutputCrs::Wgs84,
+                ..opts.clone()
+            })
+            .unwrap();
+        let mercator = smallest_enclosing_h3
+            .to_geojson_with_options(&opts)
+            .unwrap();
+
+        let project = |position: &Vec<f64>| {
+            let (x, y) = crate::geodesy::web_mercator((position[1], position[0]));
+            vec![x, y]
+        };
+        for (degrees, meters) in wgs84.features.iter().zip(&mercator.features) {
+            match (
+                &degrees.geometry.as_ref().unwrap().value,
+                &meters.geometry.as_ref().unwrap().value,
+            ) {
+                (Value::Polygon(degrees), Value::Polygon(meters)) => {
+                    let degrees: Vec<Vec<f64>> = degrees[0].iter().map(project).collect();
+                    assert_eq!(&degrees, &meters[0]);
+                }
+                (Value::Point(degrees), Value::Point(meters)) => {
+                    assert_eq!(&project(degrees), meters)
+                }
+                other => panic!("Unexpected geometries {other:?}"),
+            }
+            assert_eq!(degrees.properties, meters.properties);
+        }
+
+        let bbox = wgs84.bbox.unwrap();
+        let projected = [project(&bbox[..2].to_vec()), project(&bbox[2..].to_vec())].concat();
+        assert_eq!(mercator.bbox.as_ref().unwrap(), &projected);
+
+        let members = mercator.foreign_members.as_ref().unwrap();
+        assert_eq!(members["crs"], "EPSG:3857");
+        assert_eq!(members["generator"]["parameters"]["crs"], "EPSG:3857");
+        assert!(!wgs84.foreign_members.unwrap().contains_key("crs"));
+
+        // Streaming writes the same document
+        let mut written = Vec::new();
+        smallest_enclosing_h3
+            .write_geojson(&mut written, &opts)
+            .unwrap();
+        assert_eq!(written, serde_json::to_vec(&mercator).unwrap());
+    }
+
+    #[test]
+    fn test_geojson_at() {
+        use crate::geojson_export::GeoJsonOptions;
+
+        let center = LatLng::new(33.4484, -112.0740).unwrap();
+        let build = |resolution| {
+            crate::SmallestEnclosingH3Builder::new(center, 500.0, resolution)
+                .build()
+                .unwrap()
+        };
+        let smallest_enclosing_h3 = build(Resolution::Nine);
+        let configured = smallest_enclosing_h3.to_geojson().unwrap();
+
+        let opts = GeoJsonOptions {
+            center_marker: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            smallest_enclosing_h3
+                .to_geojson_at(Resolution::Eleven, &opts)
+                .unwrap(),
+            build(Resolution::Eleven)
+                .to_geojson_with_options(&opts)
+                .unwrap()
+        );
+        assert_eq!(smallest_enclosing_h3.to_geojson().unwrap(), configured);
+    }
+
+    #[test]
+    fn test_geojson_large_cap() {
+        let center = LatLng::new(33.4484, -112.0740).unwrap();
+        let smallest_enclosing_h3 =
+            crate::SmallestEnclosingH3Builder::new(center, 4_000_000.0, Resolution::Two)
+                .build()
+                .unwrap();
+        let geojson = smallest_enclosing_h3.to_geojson().unwrap();
+        let circle = geojson
+            .features
+            .iter()
+            .find(|f| f.property("type").unwrap() == "circle")
+            .unwrap();
+        let Some(geojson::Value::Polygon(rings)) = circle.geometry.as_ref().map(|g| &g.value)
+        else {
+            panic!("Expected a polygon");
+        };
+
+        // Densified, closed and counterclockwise around the center
+        let ring = &rings[0];
+        assert_eq!(ring.len(), 361);
+        assert_eq!(ring.first(), ring.last());
+        let bearings: Vec<f64> = ring[..360]
+            .iter()
+            .map(|c| crate::bearing_degrees(center, LatLng::new(c[1], c[0]).unwrap()))
+            .collect();
+        let turning: f64 = (0..360)
+            .map(|i| (bearings[(i + 1) % 360] - bearings[i] + 540.0).rem_euclid(360.0) - 180.0)
+            .sum();
+        assert!((turning + 360.0).abs() < 1e-6, "{turning}");
+    }
+
+    #[test]
+    fn test_geojson_bbox() {
+        let smallest_enclosing_h3 = crate::SmallestEnclosingH3Builder::from_bbox(
+            33.40,
+            -112.12,
+            33.50,
+            -112.02,
+            Resolution::Eight,
+        )
+        .unwrap()
+        .build()
+        .unwrap();
+        let geojson = smallest_enclosing_h3.to_geojson().unwrap();
+
+        let rectangle = geojson
+            .features
+            .iter()
+            .find(|f| f.property("type").unwrap() == "bbox")
+            .unwrap();
+        let Some(geojson::Value::Polygon(rings)) = rectangle.geometry.as_ref().map(|g| &g.value)
+        else {
+            panic!("Expected a polygon");
+        };
+        assert_eq!(rings[0].len(), 5);
+        assert_eq!(rectangle.bbox, Some(vec![-112.12, 33.40, -112.02, 33.50]));
+
+        let rebuilt = crate::SmallestEnclosingH3::from_geojson(&geojson)
+            .unwrap()
+            .build()
+            .unwrap();
+        assert_eq!(rebuilt.rectangle(), smallest_enclosing_h3.rectangle());
+        assert_eq!(
+            rebuilt.disk().unwrap(),
+            smallest_enclosing_h3.disk().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_geojson_annulus() {
+        let center = LatLng::new(33.4484, -112.0740).unwrap();
+        let annulus = crate::SmallestEnclosingH3Builder::new(center, 1000.0, Resolution::Ten)
+            .inner_radius_meters(500.0)
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let geojson = annulus.to_geojson().unwrap();
+        let radii: Vec<(&str, f64)> = geojson
+            .features
+            .iter()
+            .filter_map(|feature| {
+                let kind = feature.property("type")?.as_str()?;
+                let radius = feature.property("radius_meters")?.as_f64()?;
+                Some((kind, radius))
+            })
+            .collect();
+        assert_eq!(radii, vec![("circle", 1000.0), ("inner_circle", 500.0)]);
+
+        let mut written = Vec::new();
+        annulus
+            .write_geojson(&mut written, &Default::default())
+            .unwrap();
+        assert_eq!(
+            String::from_utf8(written).unwrap(),
+            annulus.to_geojson_string().unwrap()
+        );
+
+        let rebuilt = crate::SmallestEnclosingH3::from_geojson(&geojson)
+            .unwrap()
+            .build()
+            .unwrap();
+        assert_eq!(rebuilt.inner_radius_meters(), 500.0);
+        assert_eq!(rebuilt.disk().unwrap(), annulus.disk().unwrap());
+
+        // The dissolved outline of the ring has a hole
+        let union = annulus.to_geojson_union().unwrap();
+        let geojson::Value::MultiPolygon(polygons) = union.geometry.unwrap().value else {
+            panic!("Expected a MultiPolygon");
+        };
+        assert!(polygons.iter().any(|polygon| polygon.len() > 1));
+    }
+
+    #[test]
+    fn test_geojson_padded() {
+        let center = LatLng::new(33.4484, -112.0740).unwrap();
+        let smallest_enclosing_h3 =
+            crate::SmallestEnclosingH3Builder::new(center, 500.0, Resolution::Nine)
+                .padding_rings(1)
+                .build()
+                .unwrap();
+        let geojson = smallest_enclosing_h3.to_geojson().unwrap();
+
+        let padded: Vec<bool> = geojson
+            .features
+            .iter()
+            .filter_map(|f| f.property("padded").and_then(|v| v.as_bool()))
+            .collect();
+        assert_eq!(
+            padded.len(),
+            smallest_enclosing_h3.hexagons().unwrap().len()
+        );
+        assert_eq!(
+            padded.iter().filter(|padded| **padded).count(),
+            smallest_enclosing_h3.padding_cells().unwrap().len()
+        );
+    }
+
+    #[test]
+    fn test_geojson_snapped_center_is_identical() {
+        let centroid = LatLng::from(
+            LatLng::new(33.4484, -112.0740)
+                .unwrap()
+                .to_cell(Resolution::Seven),
+        );
+        let export = |lng_offset: f64| {
+            let center = LatLng::new(centroid.lat(), centroid.lng() + lng_offset).unwrap();
+            crate::SmallestEnclosingH3Builder::new(center, 500.0, Resolution::Nine)
+                .snap_center_to_resolution(Resolution::Seven)
+                .build()
+                .unwrap()
+                .to_geojson_with_options(&super::GeoJsonOptions {
+                    order: Some(crate::CellOrder::Index),
+                    center_marker: true,
+                    generator: true,
+                    ..Default::default()
+                })
+                .unwrap()
+                .to_string()
+        };
+        assert_eq!(export(0.002), export(-0.002));
+    }
+
+    #[test]
+    fn test_geojson_effective_parameters() {
+        // Beyond the north pole, so both coordinates are normalized
+        let requested = LatLng::new(95.0, 190.0).unwrap();
+        let smallest_enclosing_h3 =
+            crate::SmallestEnclosingH3Builder::new(requested, 1000.0, Resolution::Nine)
+                .build()
+                .unwrap();
+        let geojson = smallest_enclosing_h3
+            .to_geojson_with_options(&super::GeoJsonOptions {
+                generator: true,
+                ..Default::default()
+            })
+            .unwrap();
+        let members = geojson.foreign_members.unwrap();
+
+        let effective = &members["effective_parameters"];
+        assert_eq!(
+            effective["requested"]["center"],
+            serde_json::json!([190.0, 95.0])
+        );
+        let center = &effective["effective"]["center"];
+        assert!((center[0].as_f64().unwrap() - 10.0).abs() < 1e-9);
+        assert!((center[1].as_f64().unwrap() - 85.0).abs() < 1e-9);
+        let adjustments = effective["adjustments"].as_array().unwrap();
+        assert_eq!(adjustments.len(), 2);
+        assert_eq!(adjustments[0]["kind"], "normalize_latitude");
+        assert_eq!(adjustments[0]["from"], 95.0);
+        assert_eq!(adjustments[0]["to"], 85.0);
+        assert_eq!(adjustments[1]["kind"], "normalize_longitude");
+        assert_eq!(adjustments[1]["from"], 190.0);
+        assert!((adjustments[1]["to"].as_f64().unwrap() - 10.0).abs() < 1e-9);
+        assert_eq!(
+            members["generator"]["parameters"]["adjustments"],
+            effective["adjustments"]
+        );
+
+        // The requested radius is the builder's, before scaling
+        let scaled = smallest_enclosing_h3.scale(2.0).unwrap();
+        let members = scaled.to_geojson().unwrap().foreign_members.unwrap();
+        let effective = &members["effective_parameters"];
+        assert_eq!(effective["requested"]["radius_meters"], 1000.0);
+        assert_eq!(effective["effective"]["radius_meters"], 2000.0);
+
+        // A snapped center doesn't give the requested one away
+        let snapped = crate::SmallestEnclosingH3Builder::new(
+            LatLng::new(10.0, 190.0).unwrap(),
+            1000.0,
+            Resolution::Nine,
+        )
+        .snap_center_to_resolution(Resolution::Seven)
+        .build()
+        .unwrap();
+        let members = snapped.to_geojson().unwrap().foreign_members.unwrap();
+        let effective = &members["effective_parameters"];
+        assert!(effective["requested"].get("center").is_none());
+        let center = snapped.effective_parameters().unwrap().center;
+        assert_eq!(
+            effective["adjustments"],
+            serde_json::json!([{ "kind": "snap_center", "to": [center.lng(), center.lat()] }])
+        );
+
+        // Nothing is added when nothing was adjusted
+        let center = LatLng::new(33.4484, -112.0740).unwrap();
+        let plain = crate::SmallestEnclosingH3Builder::new(center, 1000.0, Resolution::Nine)
+            .build()
+            .unwrap()
+            .to_geojson()
+            .unwrap();
+        assert!(!plain
+            .foreign_members
+            .unwrap()
+            .contains_key("effective_parameters"));
+    }
+
+    #[test]
+    fn test_geojson_class_property() {
+        let center = LatLng::new(33.4484, -112.0740).unwrap();
+        let smallest_enclosing_h3 =
+            crate::SmallestEnclosingH3Builder::new(center, 1000.0, Resolution::Ten)
+                .build()
+                .unwrap();
+        let classified = smallest_enclosing_h3.classified_hexagons().unwrap();
+
+        let geojson = smallest_enclosing_h3.to_geojson().unwrap();
+        for feature in geojson
+            .features
+            .iter()
+            .filter(|f| f.property("type").and_then(|v| v.as_str()) == Some("hexagon"))
+        {
+            let hex_id: h3o::CellIndex = feature
+                .property("hex_id")
+                .unwrap()
+                .as_str()
+                .unwrap()
+                .parse()
+                .unwrap();
+            let (_, class) = classified.iter().find(|(cell, _)| *cell == hex_id).unwrap();
+            assert_eq!(feature.property("class").unwrap(), class.as_str());
+        }
+
+        // A bounding box has no circle to classify against
+        let bbox = crate::SmallestEnclosingH3Builder::from_bbox(
+            33.44,
+            -112.08,
+            33.45,
+            -112.07,
+            Resolution::Ten,
+        )
+        .unwrap()
+        .build()
+        .unwrap();
+        let geojson = bbox.to_geojson().unwrap();
+        assert!(geojson
+            .features
+            .iter()
+            .all(|f| !f.contains_property("class")));
+    }
+
+    #[test]
+    fn test_geojson_boundary_edges() {
+        let center = LatLng::new(33.4484, -112.0740).unwrap();
+        let smallest_enclosing_h3 =
+            crate::SmallestEnclosingH3Builder::new(center, 1000.0, Resolution::Nine)
+                .build()
+                .unwrap();
+        let opts = super::GeoJsonOptions {
+            boundary_edges: true,
+            ..super::GeoJsonOptions::default()
+        };
+        let geojson = smallest_enclosing_h3
+            .to_geojson_with_options(&opts)
+            .unwrap();
+
+        let edges: Vec<_> = geojson
+            .features
+            .iter()
+            .filter(|f| f.property("type").and_then(|v| v.as_str()) == Some("boundary_edge"))
+            .collect();
+        let expected = smallest_enclosing_h3.boundary_edges().unwrap();
+        assert_eq!(edges.len(), expected.len());
+        for (feature, edge) in edges.iter().zip(&expected) {
+            let property = |key| feature.property(key).and_then(|v| v.as_str()).unwrap();
+            assert_eq!(property("edge_id"), edge.to_string());
+            assert_eq!(property("origin"), edge.origin().to_string());
+            assert_eq!(property("destination"), edge.destination().to_string());
+            match &feature.geometry.as_ref().unwrap().value {
+                geojson::Value::LineString(line) => assert_eq!(line.len(), 2),
+                other => panic!("Expected a LineString, got {other:?}"),
+            }
+        }
+
+        let mut written = Vec::new();
+        smallest_enclosing_h3
+            .write_geojson(&mut written, &opts)
+            .unwrap();
+        assert_eq!(written, serde_json::to_vec(&geojson).unwrap());
+    }
+
+    #[test]
+    fn test_geojson_simplestyle() {
+        // A circle within its center cell, padded so the rings beyond the
+        // radius are exported too. The center is the cell's centroid.
+        let center_cell = LatLng::new(33.4484, -112.0740)
+            .unwrap()
+            .to_cell(Resolution::Ten);
+        let smallest_enclosing_h3 = crate::SmallestEnclosingH3Builder::new(
+            LatLng::from(center_cell),
+            10.0,
+            Resolution::Ten,
+        )
+        .padding_rings(2)
+        .build()
+        .unwrap();
+        let style = super::SimpleStyle {
+            near_color: [0xff, 0x00, 0x00],
+            far_color: [0x00, 0x00, 0xff],
+            ..super::SimpleStyle::default()
+        };
+        let opts = super::GeoJsonOptions {
+            simplestyle: Some(style),
+            ..super::GeoJsonOptions::default()
+        };
+        let geojson = smallest_enclosing_h3
+            .to_geojson_with_options(&opts)
+            .unwrap();
+
+        // Streamed with the same styling
+        let mut written = Vec::new();
+        smallest_enclosing_h3
+            .write_geojson(&mut written, &opts)
+            .unwrap();
+        assert_eq!(written, serde_json::to_vec(&geojson).unwrap());
+
+        let fill = |hex_id: h3o::CellIndex| {
+            let feature = geojson
+                .features
+                .iter()
+                .find(|f| {
+                    f.property("hex_id").and_then(|v| v.as_str()) == Some(&hex_id.to_string())
+                })
+                .unwrap();
+            assert_eq!(feature.property("fill-opacity").unwrap(), 0.5);
+            assert_eq!(feature.property("stroke").unwrap(), "#555555");
+            feature
+                .property("fill")
+                .unwrap()
+                .as_str()
+                .unwrap()
+                .to_string()
+        };
+        assert_eq!(fill(center_cell), "#ff0000");
+        let farthest = smallest_enclosing_h3
+            .hexagons()
+            .unwrap()
+            .into_iter()
+            .max_by(|a, b| {
+                let distance = |cell: &h3o::CellIndex| {
+                    smallest_enclosing_h3.distance_from_center(LatLng::from(*cell))
+                };
+                distance(a).total_cmp(&distance(b))
+            })
+            .unwrap();
+        assert_eq!(fill(farthest), "#0000ff");
+
+        let circle = geojson
+            .features
+            .iter()
+            .find(|f| f.property("type").and_then(|v| v.as_str()) == Some("circle"))
+            .unwrap();
+        assert_eq!(circle.property("fill-opacity").unwrap(), 0.0);
+        assert_eq!(circle.property("stroke-width").unwrap(), 1.0);
+    }
+
+    #[test]
+    fn test_geojson_adaptive() {
+        let center = LatLng::new(33.4484, -112.0740).unwrap();
+        let smallest_enclosing_h3 =
+            crate::SmallestEnclosingH3Builder::new(center, 2000.0, Resolution::Ten)
+                .build()
+                .unwrap();
+        let geojson = smallest_enclosing_h3
+            .to_geojson_adaptive(Resolution::Seven, Resolution::Ten)
+            .unwrap();
+        let cells = smallest_enclosing_h3
+            .hexagons_adaptive(Resolution::Seven, Resolution::Ten)
+            .unwrap();
+
+        let hexagons: Vec<&geojson::Feature> = geojson
+            .features
+            .iter()
+            .filter(|f| f.property("type").and_then(|v| v.as_str()) == Some("hexagon"))
+            .collect();
+        assert_eq!(hexagons.len(), cells.len());
+        for (feature, cell) in hexagons.iter().zip(&cells) {
+            assert_eq!(
+                feature.property("resolution").and_then(|v| v.as_u64()),
+                Some(u64::from(u8::from(cell.resolution())))
+            );
+        }
+        assert!(geojson
+            .features
+            .iter()
+            .any(|f| f.property("type").and_then(|v| v.as_str()) == Some("circle")));
+    }
+
+    #[test]
+    fn test_geojson_multipolygon() {
+        use super::GeoJsonOptions;
+
+        let center = LatLng::new(33.4484, -112.0740).unwrap();
+        let smallest_enclosing_h3 =
+            crate::SmallestEnclosingH3Builder::new(center, 500.0, Resolution::Eleven)
+                .with_coverage_overlap(true)
+                .build()
+                .unwrap();
+        let hexagons = smallest_enclosing_h3.hexagons().unwrap();
+
+        let per_feature = smallest_enclosing_h3.to_geojson().unwrap();
+        let polygons = |fc: &geojson::FeatureCollection| -> usize {
+            fc.features
+                .iter()
+                .map(|f| match &f.geometry.as_ref().unwrap().value {
+                    geojson::Value::MultiPolygon(polygons) => polygons.len(),
+                    _ => 0,
+                })
+                .sum::<usize>()
+                + fc.features
+                    .iter()
+                    .filter(|f| f.property("type").and_then(|v| v.as_str()) == Some("hexagon"))
+                    .count()
+        };
+        assert_eq!(polygons(&per_feature), hexagons.len());
+
+        let opts = GeoJsonOptions {
+            multipolygon: true,
+            ..Default::default()
+        };
+        let combined = smallest_enclosing_h3
+            .to_geojson_with_options(&opts)
+            .unwrap();
+        assert_eq!(polygons(&combined), hexagons.len());
+        assert_eq!(combined.features.len(), 2);
+        assert_eq!(combined.bbox, per_feature.bbox);
+
+        let feature = &combined.features[0];
+        assert_eq!(
+            feature.property("type").and_then(|v| v.as_str()),
+            Some("hexagons")
+        );
+        let hex_ids: Vec<String> = hexagons.iter().map(|cell| cell.to_string()).collect();
+        assert_eq!(
+            feature.property("hex_ids"),
+            Some(&serde_json::json!(hex_ids))
+        );
+        let overlaps = feature
+            .property("coverage_overlap")
+            .unwrap()
+            .as_array()
+            .unwrap();
+        assert_eq!(overlaps.len(), hexagons.len());
+
+        // The combined file is smaller, and streams identically
+        let per_feature_len = per_feature.to_string().len();
+        assert!(combined.to_string().len() < per_feature_len * 3 / 4);
+        let mut streamed = Vec::new();
+        smallest_enclosing_h3
+            .write_geojson(&mut streamed, &opts)
+            .unwrap();
+        assert_eq!(String::from_utf8(streamed).unwrap(), combined.to_string());
+    }
+
+    #[test]
+    fn test_geojson_coverage_overlap() {
+        let center = LatLng::new(33.4484, -112.0740).unwrap();
+        let builder = crate::SmallestEnclosingH3Builder::new(center, 50.0, Resolution::Twelve);
+
+        let without = builder.build().unwrap().to_geojson().unwrap();
+        assert!(without
+            .features
+            .iter()
+            .all(|f| !f.contains_property("coverage_overlap")));
+
+        let builder = crate::SmallestEnclosingH3Builder::new(center, 50.0, Resolution::Twelve);
+        let with = builder
+            .with_coverage_overlap(true)
+            .build()
+            .unwrap()
+            .to_geojson()
+            .unwrap();
+        let overlaps: Vec<f64> = with
+            .features
+            .iter()
+            .filter(|f| f.property("type").and_then(|v| v.as_str()) == Some("hexagon"))
+            .map(|f| f.property("coverage_overlap").unwrap().as_f64().unwrap())
+            .collect();
+
+        assert_eq!(overlaps.len(), with.features.len() - 1);
+        assert!(overlaps.iter().all(|o| (0.0..=1.0).contains(o)));
+        // The ring straddles the circle's edge, so some cells are partly inside
+        assert!(overlaps.iter().any(|o| *o > 0.0 && *o < 1.0));
+    }
+
+    #[test]
+    fn test_geojson_string() {
+        let center = LatLng::new(33.4484, -112.0740).unwrap();
+        let smallest_enclosing_h3 =
+            crate::SmallestEnclosingH3Builder::new(center, 50.0, Resolution::Twelve)
+                .build()
+                .unwrap();
+
+        let compact = smallest_enclosing_h3.to_geojson_string().unwrap();
+        let pretty = smallest_enclosing_h3.to_geojson_string_pretty().unwrap();
+        assert!(!compact.contains('\n'));
+        assert!(pretty.contains('\n'));
+
+        let compact: geojson::FeatureCollection = compact.parse().unwrap();
+        let pretty: geojson::FeatureCollection = pretty.parse().unwrap();
+        assert_eq!(compact, pretty);
+        assert_eq!(
+            compact.features.len(),
+            smallest_enclosing_h3.to_geojson().unwrap().features.len()
+        );
+    }
+
+    #[test]
+    fn test_geojson_union() {
+        let center = LatLng::new(33.4484, -112.0740).unwrap();
+        let smallest_enclosing_h3 =
+            crate::SmallestEnclosingH3Builder::new(center, 50.0, Resolution::Twelve)
+                .build()
+                .unwrap();
+
+        let feature = smallest_enclosing_h3.to_geojson_union().unwrap();
+        assert_eq!(
+            feature.property("cell_count").and_then(|v| v.as_u64()),
+            Some(smallest_enclosing_h3.hexagons().unwrap().len() as u64)
+        );
+
+        // The ring dissolves into one polygon with the inner disk as its hole
+        let geojson::Value::MultiPolygon(polygons) = &feature.geometry.as_ref().unwrap().value
+        else {
+            panic!("Union feature should be a multipolygon");
+        };
+        assert_eq!(polygons.len(), 1);
+        assert_eq!(polygons[0].len(), 2);
+    }
+
+    #[test]
+    fn test_boundary_samples_geojson() {
+        let center = LatLng::new(33.4484, -112.0740).unwrap();
+        let smallest_enclosing_h3 =
+            crate::SmallestEnclosingH3Builder::new(center, 50.0, Resolution::Twelve)
+                .build()
+                .unwrap();
+
+        let samples = smallest_enclosing_h3.boundary_samples(24).unwrap();
+        let geojson = smallest_enclosing_h3.boundary_samples_geojson(24).unwrap();
+        assert_eq!(geojson.features.len(), 24);
+
+        for (feature, sample) in geojson.features.iter().zip(&samples) {
+            assert_eq!(
+                feature.property("covered").and_then(|v| v.as_bool()),
+                Some(sample.covered)
+            );
+            let color = feature.property("marker-color").and_then(|v| v.as_str());
+            assert_eq!(color == Some("#00aa00"), sample.covered);
+        }
+    }
+
+    #[test]
+    fn test_write_geojson_seq() {
+        use super::GeoJsonOptions;
+
+        let center = LatLng::new(33.4484, -112.0740).unwrap();
+        let smallest_enclosing_h3 =
+            crate::SmallestEnclosingH3Builder::new(center, 1000.0, Resolution::Twelve)
+                .build()
+                .unwrap();
+        let opts = GeoJsonOptions {
+            center_marker: true,
+            ..Default::default()
+        };
+        let collection = smallest_enclosing_h3
+            .to_geojson_with_options(&opts)
+            .unwrap();
+        assert!(collection.features.len() > 200);
+
+        // Every line a standalone feature, identical to the collection's
+        let mut written = Vec::new();
+        smallest_enclosing_h3
+            .write_geojson_seq(&mut written, &opts)
+            .unwrap();
+        let text = String::from_utf8(written).unwrap();
+        assert!(text.ends_with('\n'));
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines.len(), collection.features.len());
+        for (line, feature) in lines.iter().zip(&collection.features) {
+            assert!(line.parse::<geojson::Feature>().is_ok());
+            assert_eq!(*line, serde_json::to_string(feature).unwrap());
+        }
+
+        // RFC 8142 record separators, and the cells alone
+        let mut written = Vec::new();
+        smallest_enclosing_h3
+            .write_geojson_seq(
+                &mut written,
+                &GeoJsonOptions {
+                    record_separator: true,
+                    omit_circle: true,
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+        let text = String::from_utf8(written).unwrap();
+        let records: Vec<&str> = text.split_terminator('\n').collect();
+        assert_eq!(
+            records.len(),
+            smallest_enclosing_h3.hexagons().unwrap().len()
+        );
+        for record in records {
+            let feature: geojson::Feature = record.strip_prefix('\u{1e}').unwrap().parse().unwrap();
+            assert_eq!(feature.property("type").unwrap(), "hexagon");
+        }
+    }
+
+    #[test]
+    fn test_write_geojson_streams_same_document() {
+        use super::GeoJsonOptions;
+
+        let center = LatLng::new(33.4484, -112.0740).unwrap();
+        let smallest_enclosing_h3 =
+            crate::SmallestEnclosingH3Builder::new(center, 5000.0, Resolution::Thirteen)
+                .build()
+                .unwrap();
+        let hexagon_count = smallest_enclosing_h3.hexagons().unwrap().len();
+        assert!(hexagon_count > 1000);
+
+        let mut compact = Vec::new();
+        smallest_enclosing_h3
+            .write_geojson(&mut compact, &GeoJsonOptions::default())
+            .unwrap();
+        assert_eq!(
+            String::from_utf8(compact.clone()).unwrap(),
+            smallest_enclosing_h3.to_geojson_string().unwrap()
+        );
+
+        let mut pretty = Vec::new();
+        smallest_enclosing_h3
+            .write_geojson(
+                &mut pretty,
+                &GeoJsonOptions {
+                    pretty: true,
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+        assert_eq!(
+            String::from_utf8(pretty).unwrap(),
+            smallest_enclosing_h3.to_geojson_string_pretty().unwrap()
+        );
+
+        let parsed: geojson::FeatureCollection =
+            String::from_utf8(compact).unwrap().parse().unwrap();
+        assert_eq!(parsed.features.len(), hexagon_count + 1);
+        assert_eq!(
+            parsed.foreign_members.unwrap()["h3_resolution"].as_u64(),
+            Some(13)
+        );
+    }
+
+    #[test]
+    fn test_from_geojson_round_trip() {
+        let center = LatLng::new(-33.8688, 151.2093).unwrap();
+        let original = crate::SmallestEnclosingH3Builder::new(center, 750.0, Resolution::Ten)
+            .build()
+            .unwrap();
+        let geojson = original.to_geojson().unwrap();
+
+        let rebuilt = crate::SmallestEnclosingH3::from_geojson(&geojson)
+            .unwrap()
+            .build()
+            .unwrap();
+        assert_eq!(rebuilt.hexagons().unwrap(), original.hexagons().unwrap());
+
+        // Recompute the archived circle at another resolution
+        let finer = crate::SmallestEnclosingH3::from_geojson(&geojson)
+            .unwrap()
+            .resolution(11)
+            .unwrap()
+            .build()
+            .unwrap();
+        assert_eq!(finer.radius_meters(), 750.0);
+        assert!(finer
+            .hexagons()
+            .unwrap()
+            .iter()
+            .all(|cell| cell.resolution() == Resolution::Eleven));
+    }
+
+    #[test]
+    fn test_from_geojson_without_center_or_members() {
+        let center = LatLng::new(33.4484, -112.0740).unwrap();
+        let mut geojson = crate::SmallestEnclosingH3Builder::new(center, 50.0, Resolution::Twelve)
+            .build()
+            .unwrap()
+            .to_geojson()
+            .unwrap();
+        geojson.foreign_members = None;
+        let circle = geojson.features.last_mut().unwrap();
+        circle.properties.as_mut().unwrap().remove("center");
+
+        // Falls back to the ring's centroid and a hexagon's resolution
+        let rebuilt = crate::SmallestEnclosingH3::from_geojson(&geojson)
+            .unwrap()
+            .build()
+            .unwrap();
+        assert!(crate::haversine_distance(rebuilt.center, center) < 0.01);
+        assert_eq!(rebuilt.resolution, Resolution::Twelve);
+    }
+
+    #[test]
+    fn test_from_geojson_rejects_missing_circle() {
+        let center = LatLng::new(33.4484, -112.0740).unwrap();
+        let mut geojson = crate::SmallestEnclosingH3Builder::new(center, 50.0, Resolution::Twelve)
+            .build()
+            .unwrap()
+            .to_geojson()
+            .unwrap();
+
+        geojson
+            .features
+            .last_mut()
+            .unwrap()
+            .set_property("radius_meters", "far");
+        assert!(matches!(
+            crate::SmallestEnclosingH3::from_geojson(&geojson),
+            Err(SmallestEnclosingH3Error::InvalidGeoJson(_))
+        ));
+
+        geojson.features.pop();
+        assert!(matches!(
+            crate::SmallestEnclosingH3::from_geojson(&geojson),
+            Err(SmallestEnclosingH3Error::InvalidGeoJson(_))
+        ));
+    }
+
+    #[test]
+    fn test_from_geojson_feature() {
+        let feature = |json: serde_json::Value| geojson::Feature::from_json_value(json).unwrap();
+        let point = |properties: serde_json::Value| {
+            feature(serde_json::json!({
+                "type": "Feature",
+                "geometry": { "type": "Point", "coordinates": [-112.0740, 33.4484] },
+                "properties": properties,
+            }))
+        };
+
+        let smallest_enclosing_h3 = crate::SmallestEnclosingH3Builder::from_geojson_feature(
+            &point(serde_json::json!({ "radius_meters": 500, "resolution": 10, "name": "a" })),
+        )
+        .unwrap()
+        .build()
+        .unwrap();
+        assert_eq!(
+            smallest_enclosing_h3.center,
+            LatLng::new(33.4484, -112.0740).unwrap()
+        );
+        assert_eq!(smallest_enclosing_h3.radius_meters(), 500.0);
+        assert_eq!(smallest_enclosing_h3.resolution, Resolution::Ten);
+
+        // The default resolution
+        let default = crate::SmallestEnclosingH3Builder::from_geojson_feature(&point(
+            serde_json::json!({ "radius_meters": 500 }),
+        ))
+        .unwrap()
+        .build()
+        .unwrap();
+        assert_eq!(default.resolution, Resolution::Nine);
+
+        let error = |feature: &geojson::Feature| {
+            crate::SmallestEnclosingH3Builder::from_geojson_feature(feature).unwrap_err()
+        };
+        assert!(matches!(
+            error(&point(serde_json::json!({ "resolution": 10 }))),
+            SmallestEnclosingH3Error::InvalidRadius(_)
+        ));
+        assert!(matches!(
+            error(&point(serde_json::json!({ "radius_meters": "500" }))),
+            SmallestEnclosingH3Error::InvalidRadius(_)
+        ));
+        assert!(matches!(
+            error(&point(
+                serde_json::json!({ "radius_meters": 500, "resolution": 16 })
+            )),
+            SmallestEnclosingH3Error::InvalidResolution(_)
+        ));
+        assert!(matches!(
+            error(&point(
+                serde_json::json!({ "radius_meters": 500, "resolution": "9" })
+            )),
+            SmallestEnclosingH3Error::InvalidResolution(_)
+        ));
+        let line = feature(serde_json::json!({
+            "type": "Feature",
+            "geometry": { "type": "LineString", "coordinates": [[0.0, 0.0], [1.0, 1.0]] },
+            "properties": { "radius_meters": 500 },
+        }));
+        assert!(matches!(
+            error(&line),
+            SmallestEnclosingH3Error::InvalidLatLng(_)
+        ));
+        let no_geometry = feature(serde_json::json!({
+            "type": "Feature",
+            "geometry": null,
+            "properties": { "radius_meters": 500 },
+        }));
+        assert!(matches!(
+            error(&no_geometry),
+            SmallestEnclosingH3Error::InvalidLatLng(_)
+        ));
+    }
+
+    #[test]
+    fn test_geojson_with_properties() {
+        let center = LatLng::new(33.4484, -112.0740).unwrap();
+        let smallest_enclosing_h3 =
+            crate::SmallestEnclosingH3Builder::new(center, 50.0, Resolution::Twelve)
+                .build()
+                .unwrap();
+        let target = smallest_enclosing_h3.hexagons().unwrap()[3];
+
+        let geojson = smallest_enclosing_h3
+            .to_geojson_with_properties(|hex_id| {
+                let mut properties = serde_json::Map::new();
+                if hex_id == target {
+                    properties.insert("signal".to_string(), serde_json::json!(-67));
+                    properties.insert("type".to_string(), serde_json::json!("tower"));
+                    properties.insert("id".to_string(), serde_json::json!("tower-1"));
+                }
+                properties
+            })
+            .unwrap();
+
+        let tagged: Vec<_> = geojson
+            .features
+            .iter()
+            .filter(|f| f.contains_property("signal"))
+            .collect();
+        assert_eq!(tagged.len(), 1);
+
+        let feature = tagged[0];
+        let target_id = target.to_string();
+        assert_eq!(
+            feature.property("hex_id").and_then(|v| v.as_str()),
+            Some(target_id.as_str())
+        );
+        assert_eq!(
+            feature.property("signal").and_then(|v| v.as_i64()),
+            Some(-67)
+        );
+        // Built-in properties win, and `id` moves to the feature
+        assert_eq!(
+            feature.property("type").and_then(|v| v.as_str()),
+            Some("hexagon")
+        );
+        assert_eq!(
+            feature.id,
+            Some(geojson::feature::Id::String("tower-1".to_string()))
+        );
+        assert!(!feature.contains_property("id"));
+        assert_eq!(
+            geojson.features.iter().filter(|f| f.id.is_some()).count(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_write_geojson_in_ring_clock_order() {
+        use super::GeoJsonOptions;
+        use crate::CellOrder;
+
+        let center = LatLng::new(33.4484, -112.0740).unwrap();
+        let smallest_enclosing_h3 =
+            crate::SmallestEnclosingH3Builder::new(center, 500.0, Resolution::Eleven)
+                .build()
+                .unwrap();
+
+        let mut output = Vec::new();
+        smallest_enclosing_h3
+            .write_geojson(
+                &mut output,
+                &GeoJsonOptions {
+                    order: Some(CellOrder::RingClock),
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+        let parsed: geojson::FeatureCollection =
+            String::from_utf8(output).unwrap().parse().unwrap();
+
+        let hex_ids: Vec<h3o::CellIndex> = parsed
+            .features
+            .iter()
+            .filter_map(|f| f.property("hex_id").and_then(|v| v.as_str()))
+            .map(|id| id.parse().unwrap())
+            .collect();
+        assert_eq!(
+            hex_ids,
+            smallest_enclosing_h3
+                .hexagons_sorted(CellOrder::RingClock)
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_geojson_bbox_holds_every_coordinate() {
+        let center = LatLng::new(-33.8688, 151.2093).unwrap();
+        let geojson = crate::SmallestEnclosingH3Builder::new(center, 500.0, Resolution::Ten)
+            .build()
+            .unwrap()
+            .to_geojson()
+            .unwrap();
+
+        let within = |bbox: &[f64], c: &[f64]| {
+            (bbox[0]..=bbox[2]).contains(&c[0]) && (bbox[1]..=bbox[3]).contains(&c[1])
+        };
+        let collection_bbox = geojson.bbox.clone().unwrap();
+        for feature in &geojson.features {
+            let feature_bbox = feature.bbox.clone().unwrap();
+            let geojson::Value::Polygon(rings) = &feature.geometry.as_ref().unwrap().value else {
+                panic!("Expected polygon features");
+            };
+            for c in rings.iter().flatten() {
+                assert!(within(&collection_bbox, c));
+                assert!(within(&feature_bbox, c));
+            }
+        }
+    }
+
+    #[test]
+    fn test_public_feature_builders() {
+        use super::{create_circle_feature, create_hex_feature};
+
+        let center = LatLng::new(33.4484, -112.0740).unwrap();
+        let cell = center.to_cell(Resolution::Twelve);
+        let hex = create_hex_feature(cell).unwrap();
+        assert_eq!(
+            hex.property("hex_id").and_then(|v| v.as_str()),
+            Some(cell.to_string().as_str())
+        );
+        let geojson::Value::Polygon(rings) = &hex.geometry.as_ref().unwrap().value else {
+            panic!("Hexagon feature should be a polygon");
+        };
+        assert_eq!(rings[0].first(), rings[0].last());
+
+        let circle = create_circle_feature(vec![vec![0.0, 0.0], vec![1.0, 0.0]], 10.0).unwrap();
+        assert_eq!(
+            circle.property("type").and_then(|v| v.as_str()),
+            Some("circle")
+        );
+        assert_eq!(
+            circle.property("radius_meters").and_then(|v| v.as_f64()),
+            Some(10.0)
+        );
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_parallel_geojson_matches_serial() {
+        use super::{create_circle_feature, create_hex_feature};
+        use geojson::FeatureCollection;
+        use serde_json::json;
+
+        let center = LatLng::new(33.4484, -112.0740).unwrap();
+        let smallest_enclosing_h3 =
+            crate::SmallestEnclosingH3Builder::new(center, 2000.0, Resolution::Twelve)
+                .build()
+                .unwrap();
+        let hexagons: Vec<h3o::CellIndex> = smallest_enclosing_h3.hexagons().unwrap();
+        assert!(hexagons.len() > 64, "Should exercise the threaded path");
+
+        let mut features: Vec<_> = hexagons
+            .into_iter()
+            .map(|hex_id| {
+                let mut feature = create_hex_feature(hex_id).unwrap();
+                let class = smallest_enclosing_h3.classify_cell(hex_id);
+                feature.set_property("class", class.as_str());
+                feature
+            })
+            .collect();
+        let mut circle = create_circle_feature(
+            smallest_enclosing_h3.generate_circle_coordinates().unwrap(),
+            2000.0,
+        )
+        .unwrap();
+        circle.set_property("center", json!([center.lng(), center.lat()]));
+        features.push(circle);
+        let geojson = smallest_enclosing_h3.to_geojson().unwrap();
+        let serial = FeatureCollection {
+            features,
+            bbox: geojson.bbox.clone(),
+            foreign_members: geojson.foreign_members.clone(),
+        };
+
+        assert_eq!(
+            serde_json::to_string(&geojson).unwrap(),
+            serde_json::to_string(&serial).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_cell_coverage_geojson() {
+        use super::create_hex_feature;
+        use crate::{cell_coverage::CellCoverage, SmallestEnclosingH3Builder};
+        use geojson::{GeoJson, Value};
+        use h3o::CellIndex;
+        use serde_json::json;
+
+        // Cells from elsewhere: a hand-picked pair in New York and one in Tokyo
+        let cells: Vec<CellIndex> = ["8a2a1072b59ffff", "8a2a1072b597fff", "8a2f5aadb0b7fff"]
+            .iter()
+            .map(|hex_id| hex_id.parse().unwrap())
+            .collect();
+        let coverage = CellCoverage::from_cells(cells.clone());
+
+        let collection = coverage.to_geojson().unwrap();
+        let json = collection.to_string();
+        assert!(matches!(
+            json.parse::<GeoJson>(),
+            Ok(GeoJson::FeatureCollection(_))
+        ));
+        assert_eq!(collection.features.len(), cells.len());
+        for (feature, cell) in collection.features.iter().zip(&cells) {
+            assert_eq!(feature, &create_hex_feature(*cell).unwrap());
+            assert_eq!(
+                feature.property("hex_id").unwrap(),
+                &json!(cell.to_string())
+            );
+            let Some(Value::Polygon(rings)) = feature.geometry.as_ref().map(|g| &g.value) else {
+                panic!("{feature:?} isn't a polygon");
+            };
+            assert_eq!(rings[0].len(), 7);
+            assert_eq!(rings[0].first(), rings[0].last());
+        }
+        // The shortest span runs from Tokyo east across the antimeridian
+        let bbox = collection.bbox.unwrap();
+        assert!(
+            bbox[0] < 139.8 && bbox[2] > -74.05 && bbox[0] > bbox[2],
+            "{bbox:?}"
+        );
+
+        // A reference geometry follows the cells
+        let center = LatLng::new(33.4484, -112.0740).unwrap();
+        let computed = SmallestEnclosingH3Builder::new(center, 500.0, Resolution::Ten)
+            .build()
+            .unwrap()
+            .compute()
+            .unwrap();
+        let collection = computed.to_geojson().unwrap();
+        assert_eq!(collection.features.len(), computed.cells().len() + 1);
+        let reference = collection.features.last().unwrap();
+        assert_eq!(reference.property("type").unwrap(), "reference");
+        let Some(Value::Polygon(rings)) = reference.geometry.as_ref().map(|g| &g.value) else {
+            panic!("{reference:?} isn't a polygon");
+        };
+        assert!(super::signed_area(&rings[0]) > 0.0);
+    }
+
+    #[test]
+    fn test_extrusion() {
+        use super::{ExtrusionSpec, GeoJsonOptions};
+        use crate::{haversine_distance, SmallestEnclosingH3Builder};
+        use geojson::Value;
+
+        let center = LatLng::new(33.4484, -112.0740).unwrap();
+        let smallest_enclosing_h3 = SmallestEnclosingH3Builder::new(center, 500.0, Resolution::Ten)
+            .padding_rings(2)
+            .build()
+            .unwrap();
+
+        // Heights from each cell's distance from the center rank the cells
+        // the same way
+        let opts = GeoJsonOptions {
+            extrusion: Some(ExtrusionSpec::per_cell(move |cell| {
+                haversine_distance(center, LatLng::from(cell))
+            })),
+            ..Default::default()
+        };
+        let geojson = smallest_enclosing_h3
+            .to_geojson_with_options(&opts)
+            .unwrap();
+        let mut extruded: Vec<(f64, f64)> = geojson
+            .features
+            .iter()
+            .filter(|f| f.property("type").unwrap() == "hexagon")
+            .map(|feature| {
+                let elevation = feature.property("elevation").unwrap().as_f64().unwrap();
+                assert_eq!(feature.property("height").unwrap(), elevation);
+                let cell: h3o::CellIndex = feature
+                    .property("hex_id")
+                    .and_then(|id| id.as_str())
+                    .unwrap()
+                    .parse()
+                    .unwrap();
+                (
+                    elevation,
+                    smallest_enclosing_h3.distance_from_center(LatLng::from(cell)),
+                )
+            })
+            .collect();
+        assert_eq!(
+            extruded.len(),
+            smallest_enclosing_h3.hexagons().unwrap().len()
+        );
+        extruded.sort_by(|a, b| a.0.total_cmp(&b.0));
+        assert!(extruded.windows(2).all(|w| w[0].1 <= w[1].1));
+        assert!(extruded[0].0 < extruded[extruded.len() - 1].0);
+
+        // The circle isn't extruded
+        let circle = geojson
+            .features
+            .iter()
+            .find(|f| f.property("type").unwrap() == "circle")
+            .unwrap();
+        assert!(!circle.contains_property("elevation"));
+
+        // Constant heights as the third coordinate, also of combined hexagons
+        let opts = GeoJsonOptions {
+            extrusion: Some(ExtrusionSpec::Constant(30.0)),
+            extrusion_z: true,
+            multipolygon: true,
+            ..Default::default()
+        };
+        let geojson = smallest_enclosing_h3
+            .to_geojson_with_options(&opts)
+            .unwrap();
+        let hexagons = &geojson.features[0];
+        let heights = hexagons.property("elevation").unwrap().as_array().unwrap();
+        let Some(Value::MultiPolygon(polygons)) = hexagons.geometry.as_ref().map(|g| &g.value)
+        else {
+            panic!("{hexagons:?} isn't a MultiPolygon");
+        };
+        assert_eq!(heights.len(), polygons.len());
+        assert!(heights.iter().all(|height| height == 30.0));
+        assert!(polygons
+            .iter()
+            .flatten()
+            .flatten()
+            .all(|p| p.len() == 3 && p[2] == 30.0));
+        let Some(Value::Polygon(circle)) = geojson.features[1].geometry.as_ref().map(|g| &g.value)
+        else {
+            panic!("the circle isn't a Polygon");
+        };
+        assert!(circle[0].iter().all(|p| p.len() == 2));
+
+        // Streamed output matches
+        let mut written = Vec::new();
+        smallest_enclosing_h3
+            .write_geojson(&mut written, &opts)
             .unwrap();
+        assert_eq!(String::from_utf8(written).unwrap(), geojson.to_string());
     }
 }
@@ -0,0 +1,210 @@
+//! Golden record/verify files for gating dependency upgrades.
+//!
+//! [`record`] writes one line per case: the center latitude and longitude,
+//! the radius in meters and the resolution, followed by the sorted cells of
+//! [`SmallestEnclosingH3::hexagons`], all separated by tabs. Floats use
+//! Rust's shortest round-trip formatting, so recorded inputs are reproduced
+//! exactly. Lines starting with `#` and blank lines are ignored.
+//!
+//! [`verify`] recomputes every recorded case with the current code and
+//! reports the cases whose cells changed.
+
+use crate::{
+    error::SmallestEnclosingH3Error, haversine_distance, Result, SmallestEnclosingH3,
+    SmallestEnclosingH3Builder,
+};
+use h3o::{CellIndex, LatLng, Resolution};
+use std::collections::BTreeSet;
+use std::io::{BufRead, Write};
+
+/// A cell present on only one side of a [`GoldenMismatch`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct CellDifference {
+    pub cell: CellIndex,
+    /// Distance from the circle's center to the cell's centroid
+    pub distance_meters: f64,
+}
+
+/// A recorded case whose cells no longer match
+#[derive(Debug, Clone, PartialEq)]
+pub struct GoldenMismatch {
+    /// 1-based line of the case in the golden file
+    pub line: usize,
+    pub center: LatLng,
+    pub radius_meters: f64,
+    pub resolution: Resolution,
+    pub expected_count: usize,
+    pub actual_count: usize,
+    /// Recorded cells the current code no longer produces
+    pub missing: Vec<CellDifference>,
+    /// Cells the current code produces that weren't recorded
+    pub extra: Vec<CellDifference>,
+}
+
+/// Append `smallest_enclosing_h3`'s inputs and sorted cells to `writer` as
+/// one golden case
+pub fn record<W: Write>(smallest_enclosing_h3: &SmallestEnclosingH3, mut writer: W) -> Result<()> {
+    let cells: BTreeSet<CellIndex> = smallest_enclosing_h3.hexagons()?.into_iter().collect();
+    let cells: Vec<String> = cells.iter().map(ToString::to_string).collect();
+
+    writeln!(
+        writer,
+        "{}\t{}\t{}\t{}\t{}",
+        smallest_enclosing_h3.center.lat(),
+        smallest_enclosing_h3.center.lng(),
+        smallest_enclosing_h3.radius_meters,
+        u8::from(smallest_enclosing_h3.resolution),
+        cells.join(",")
+    )?;
+    Ok(())
+}
+
+/// Recompute every case in a golden file and return those that differ
+pub fn verify<R: BufRead>(reader: R) -> Result<Vec<GoldenMismatch>> {
+    let mut mismatches = Vec::new();
+    for (i, line) in reader.lines().enumerate() {
+        if let Some(mismatch) = verify_line(i + 1, &line?)? {
+            mismatches.push(mismatch);
+        }
+    }
+    Ok(mismatches)
+}
+
+// Recompute the case on one line of a golden file, if it holds one and its
+// cells changed
+pub(crate) fn verify_line(line_number: usize, line: &str) -> Result<Option<GoldenMismatch>> {
+    if line.trim().is_empty() || line.starts_with('#') {
+        return Ok(None);
+    }
+
+    let case = parse_case(line_number, line)?;
+    let smallest_enclosing_h3 =
+        SmallestEnclosingH3Builder::new(case.center, case.radius_meters, case.resolution)
+            .build()?;
+    let actual: BTreeSet<CellIndex> = smallest_enclosing_h3.hexagons()?.into_iter().collect();
+    if actual == case.cells {
+        return Ok(None);
+    }
+
+    let difference = |cells: &mut dyn Iterator<Item = &CellIndex>| {
+        cells
+            .map(|cell| CellDifference {
+                cell: *cell,
+                distance_meters: haversine_distance(case.center, LatLng::from(*cell)),
+            })
+            .collect()
+    };
+    Ok(Some(GoldenMismatch {
+        line: line_number,
+        center: case.center,
+        radius_meters: case.radius_meters,
+        resolution: case.resolution,
+        expected_count: case.cells.len(),
+        actual_count: actual.len(),
+        missing: difference(&mut case.cells.difference(&actual)),
+        extra: difference(&mut actual.difference(&case.cells)),
+    }))
+}
+
+struct GoldenCase {
+    center: LatLng,
+    radius_meters: f64,
+    resolution: Resolution,
+    cells: BTreeSet<CellIndex>,
+}
+
+fn parse_case(line_number: usize, line: &str) -> Result<GoldenCase> {
+    let invalid = |reason: &str| {
+        SmallestEnclosingH3Error::InvalidGoldenFile(format!("line {line_number}: {reason}"))
+    };
+
+    let fields: Vec<&str> = line.split('\t').collect();
+    let &[lat, lng, radius_meters, resolution, cells] = fields.as_slice() else {
+        return Err(invalid("expected 5 tab-separated fields"));
+    };
+
+    let lat: f64 = lat.parse().map_err(|_| invalid("invalid latitude"))?;
+    let lng: f64 = lng.parse().map_err(|_| invalid("invalid longitude"))?;
+    let radius_meters = radius_meters
+        .parse()
+        .map_err(|_| invalid("invalid radius"))?;
+    let resolution = resolution
+        .parse::<u8>()
+        .ok()
+        .and_then(|r| Resolution::try_from(r).ok())
+        .ok_or_else(|| invalid("invalid resolution"))?;
+    let cells = cells
+        .split(',')
+        .filter(|cell| !cell.is_empty())
+        .map(|cell| cell.parse().map_err(|_| invalid("invalid cell index")))
+        .collect::<Result<_>>()?;
+
+    Ok(GoldenCase {
+        center: LatLng::new(lat, lng)?,
+        radius_meters,
+        resolution,
+        cells,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record_cases() -> String {
+        let mut golden = b"# smallest-enclosing-h3 golden cases\n".to_vec();
+        for (lat, lng, radius_meters, resolution) in [
+            (33.4484, -112.0740, 50.0, Resolution::Twelve),
+            (51.5074, -0.1278, 1000.0, Resolution::Nine),
+        ] {
+            let center = LatLng::new(lat, lng).unwrap();
+            let smallest_enclosing_h3 =
+                SmallestEnclosingH3Builder::new(center, radius_meters, resolution)
+                    .build()
+                    .unwrap();
+            record(&smallest_enclosing_h3, &mut golden).unwrap();
+        }
+        String::from_utf8(golden).unwrap()
+    }
+
+    #[test]
+    fn test_verify_clean_record() {
+        assert!(verify(record_cases().as_bytes()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_verify_reports_tampered_cell() {
+        let golden = record_cases();
+        let lines: Vec<&str> = golden.lines().collect();
+
+        // Swap the first recorded cell of the second case for its center cell
+        let fields: Vec<&str> = lines[2].split('\t').collect();
+        let original: CellIndex = fields[4].split(',').next().unwrap().parse().unwrap();
+        let center = LatLng::new(51.5074, -0.1278).unwrap();
+        let replacement = center.to_cell(Resolution::Nine);
+        let tampered = lines[2].replacen(&original.to_string(), &replacement.to_string(), 1);
+        let golden = [lines[0], lines[1], &tampered].join("\n");
+
+        let mismatches = verify(golden.as_bytes()).unwrap();
+        assert_eq!(mismatches.len(), 1);
+
+        let mismatch = &mismatches[0];
+        assert_eq!(mismatch.line, 3);
+        assert_eq!(mismatch.resolution, Resolution::Nine);
+        assert_eq!(mismatch.expected_count, mismatch.actual_count);
+        assert_eq!(mismatch.missing.len(), 1);
+        assert_eq!(mismatch.missing[0].cell, replacement);
+        assert!(mismatch.missing[0].distance_meters < 500.0);
+        assert_eq!(mismatch.extra.len(), 1);
+        assert_eq!(mismatch.extra[0].cell, original);
+        assert!(mismatch.extra[0].distance_meters > 500.0);
+    }
+
+    #[test]
+    fn test_verify_rejects_malformed_line() {
+        assert!(matches!(
+            verify("33.4\t-112.0\t50".as_bytes()),
+            Err(SmallestEnclosingH3Error::InvalidGoldenFile(_))
+        ));
+    }
+}
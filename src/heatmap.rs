@@ -0,0 +1,207 @@
+//! Weighted unions of many circles, for heatmaps of overlapping coverage.
+
+use crate::{
+    error::SmallestEnclosingH3Error, haversine_distance, parallel, Result,
+    SmallestEnclosingH3Builder,
+};
+use h3o::{CellIndex, LatLng, Resolution};
+use std::collections::HashMap;
+
+/// How a circle's weight falls off with the distance from its center to a
+/// cell's centroid
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Decay {
+    /// The peak weight in every covered cell
+    #[default]
+    None,
+    /// From the peak weight at the center down to zero at the radius
+    Linear,
+    /// A gaussian of the distance with standard deviation `sigma_m` meters
+    Gaussian { sigma_m: f64 },
+}
+
+impl Decay {
+    /// The fraction of the peak weight at `distance` meters from the center
+    /// of a circle of `radius_meters`
+    pub fn factor(self, distance: f64, radius_meters: f64) -> f64 {
+        match self {
+            Decay::None => 1.0,
+            Decay::Linear => (1.0 - distance / radius_meters).max(0.0),
+            Decay::Gaussian { sigma_m } => {
+                (-(distance * distance) / (2.0 * sigma_m * sigma_m)).exp()
+            }
+        }
+    }
+}
+
+/// Sum the weight every `(center, radius in meters, peak weight)` circle in
+/// `circles` contributes to each cell it covers at `resolution`.
+///
+/// A circle covers the cells of [`crate::SmallestEnclosingH3::disk`], and
+/// contributes its peak weight scaled by `decay` at the distance to the cell's
+/// centroid. With the `parallel` feature enabled the circles are covered
+/// across threads. An invalid circle, a non-finite peak weight or a
+/// non-positive `sigma_m` fails the whole union.
+pub fn weighted_union(
+    circles: &[(LatLng, f64, f64)],
+    resolution: Resolution,
+    decay: Decay,
+) -> Result<HashMap<CellIndex, f64>> {
+    if let Decay::Gaussian { sigma_m } = decay {
+        if !(sigma_m.is_finite() && sigma_m > 0.0) {
+            return Err(SmallestEnclosingH3Error::InvalidWeight(format!(
+                "Gaussian sigma {sigma_m} must be positive"
+            )));
+        }
+    }
+
+    let contributions = parallel::map(circles, |&(center, radius_meters, peak_weight)| {
+        if !peak_weight.is_finite() {
+            return Err(SmallestEnclosingH3Error::InvalidWeight(format!(
+                "Peak weight {peak_weight} must be finite"
+            )));
+        }
+        let disk = SmallestEnclosingH3Builder::new(center, radius_meters, resolution)
+            .build()?
+            .disk()?;
+        Ok(disk
+            .into_iter()
+            .map(|cell| {
+                let distance = haversine_distance(center, LatLng::from(cell));
+                (cell, peak_weight * decay.factor(distance, radius_meters))
+            })
+            .collect::<Vec<_>>())
+    });
+
+    let mut weights = HashMap::new();
+    for contribution in contributions {
+        for (cell, weight) in contribution? {
+            *weights.entry(cell).or_insert(0.0) += weight;
+        }
+    }
+    Ok(weights)
+}
+
+/// A FeatureCollection of the cells of `weights`, each a hexagon feature as
+/// built by [`crate::geojson_export::create_hex_feature`] with its `weight`,
+/// in index order
+#[cfg(feature = "geojson_export")]
+pub fn weighted_geojson(weights: &HashMap<CellIndex, f64>) -> Result<geojson::FeatureCollection> {
+    let mut cells: Vec<(&CellIndex, &f64)> = weights.iter().collect();
+    cells.sort_unstable_by_key(|(cell, _)| **cell);
+    let features = cells
+        .into_iter()
+        .map(|(cell, weight)| {
+            let mut feature = crate::geojson_export::create_hex_feature(*cell)?;
+            feature.set_property("weight", *weight);
+            Ok(feature)
+        })
+        .collect::<Result<Vec<_>>>()?;
+    Ok(geojson::FeatureCollection {
+        bbox: None,
+        features,
+        foreign_members: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RESOLUTION: Resolution = Resolution::Ten;
+
+    fn circles() -> [(LatLng, f64, f64); 2] {
+        [
+            (LatLng::new(33.4484, -112.0740).unwrap(), 500.0, 2.0),
+            (LatLng::new(33.4500, -112.0700).unwrap(), 400.0, 3.0),
+        ]
+    }
+
+    #[test]
+    fn test_weighted_union_is_additive() {
+        let circles = circles();
+        let union = weighted_union(&circles, RESOLUTION, Decay::Linear).unwrap();
+        let first = weighted_union(&circles[..1], RESOLUTION, Decay::Linear).unwrap();
+        let second = weighted_union(&circles[1..], RESOLUTION, Decay::Linear).unwrap();
+
+        assert_eq!(union.len(), {
+            let mut cells: Vec<_> = first.keys().chain(second.keys()).collect();
+            cells.sort_unstable();
+            cells.dedup();
+            cells.len()
+        });
+        for (cell, weight) in &union {
+            let expected = first.get(cell).unwrap_or(&0.0) + second.get(cell).unwrap_or(&0.0);
+            assert!((weight - expected).abs() < 1e-12, "{cell}");
+        }
+
+        // Without decay, the overlap holds both peak weights
+        let flat = weighted_union(&circles, RESOLUTION, Decay::None).unwrap();
+        let overlap = circles[0].0.to_cell(RESOLUTION);
+        assert_eq!(flat[&overlap], 5.0);
+    }
+
+    #[test]
+    fn test_decay_falls_toward_the_edge() {
+        let (center, radius_meters, peak_weight) = circles()[0];
+        for decay in [Decay::Linear, Decay::Gaussian { sigma_m: 200.0 }] {
+            let weights =
+                weighted_union(&[(center, radius_meters, peak_weight)], RESOLUTION, decay).unwrap();
+            let center_weight = weights[&center.to_cell(RESOLUTION)];
+            assert!(center_weight > 0.9 * peak_weight && center_weight <= peak_weight);
+
+            let mut by_distance: Vec<(f64, f64)> = weights
+                .iter()
+                .map(|(cell, weight)| (haversine_distance(center, LatLng::from(*cell)), *weight))
+                .collect();
+            by_distance.sort_by(|a, b| a.0.total_cmp(&b.0));
+            assert!(by_distance.windows(2).all(|pair| pair[1].1 <= pair[0].1));
+            assert!(
+                by_distance.last().unwrap().1 < 0.5 * peak_weight,
+                "{decay:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_weighted_union_validation() {
+        let center = LatLng::new(33.4484, -112.0740).unwrap();
+        assert!(matches!(
+            weighted_union(
+                &[(center, 100.0, 1.0)],
+                RESOLUTION,
+                Decay::Gaussian { sigma_m: 0.0 }
+            ),
+            Err(SmallestEnclosingH3Error::InvalidWeight(_))
+        ));
+        assert!(matches!(
+            weighted_union(&[(center, 100.0, f64::NAN)], RESOLUTION, Decay::None),
+            Err(SmallestEnclosingH3Error::InvalidWeight(_))
+        ));
+        assert!(matches!(
+            weighted_union(&[(center, -1.0, 1.0)], RESOLUTION, Decay::None),
+            Err(SmallestEnclosingH3Error::InvalidRadius(_))
+        ));
+    }
+
+    #[cfg(feature = "geojson_export")]
+    #[test]
+    fn test_weighted_geojson() {
+        let weights = weighted_union(&circles(), RESOLUTION, Decay::Linear).unwrap();
+        let geojson = weighted_geojson(&weights).unwrap();
+        assert_eq!(geojson.features.len(), weights.len());
+        for feature in &geojson.features {
+            let cell: CellIndex = feature
+                .property("hex_id")
+                .unwrap()
+                .as_str()
+                .unwrap()
+                .parse()
+                .unwrap();
+            assert_eq!(
+                feature.property("weight").unwrap().as_f64(),
+                Some(weights[&cell])
+            );
+        }
+    }
+}
@@ -0,0 +1,200 @@
+//! A coverage that follows a changing radius, ring by ring.
+
+use crate::{
+    error::SmallestEnclosingH3Error, grid_ring, Result, SmallestEnclosingH3,
+    SmallestEnclosingH3Builder,
+};
+use h3o::{CellIndex, LatLng, Resolution};
+
+/// The cells a [`ComputedCoverage::set_radius`] added to and removed from the
+/// disk
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RadiusDelta {
+    /// Grid distance k before the update
+    pub previous_k: u32,
+    /// Grid distance k after the update
+    pub k: u32,
+    /// Cells of the rings beyond the previous k, ring by ring outwards
+    pub added: Vec<CellIndex>,
+    /// Cells of the rings beyond the new k, ring by ring outwards
+    pub removed: Vec<CellIndex>,
+}
+
+impl RadiusDelta {
+    /// Whether the update left the disk unchanged
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty()
+    }
+}
+
+/// The [`SmallestEnclosingH3::disk`] of a full circle whose radius changes
+/// often, as with a slider.
+///
+/// The disk is kept as its rings around the center cell. When a new radius
+/// moves k, only the rings between the old and new k are generated or
+/// dropped, and the change is returned as a [`RadiusDelta`] to patch a layer
+/// with. After any sequence of updates the cells are those of a coverage
+/// built fresh at the current radius.
+#[derive(Debug, Clone)]
+pub struct ComputedCoverage {
+    center: LatLng,
+    resolution: Resolution,
+    radius_meters: f64,
+    // `rings[i]` holds the cells at grid distance `i` from the center cell
+    rings: Vec<Vec<CellIndex>>,
+}
+
+impl ComputedCoverage {
+    /// The disk of the circle of `radius_meters` around `center`, validated
+    /// as by [`SmallestEnclosingH3Builder::build`]
+    pub fn new(center: LatLng, radius_meters: f64, resolution: Resolution) -> Result<Self> {
+        let coverage = coverage(center, radius_meters, resolution)?;
+        let mut computed = Self {
+            center: coverage.center,
+            resolution,
+            radius_meters,
+            rings: Vec::new(),
+        };
+        computed.resize(coverage.k_value()?);
+        Ok(computed)
+    }
+
+    /// Move the circle's edge to `radius_meters`, generating or dropping only
+    /// the rings between the old and new k.
+    ///
+    /// An invalid radius, or one too large for [`crate::DEFAULT_MAX_CELLS`]
+    /// or past [`crate::spherical_cap::LARGE_CAP_DEGREES`], is an error and
+    /// leaves the coverage unchanged.
+    pub fn set_radius(&mut self, radius_meters: f64) -> Result<RadiusDelta> {
+        let k = coverage(self.center, radius_meters, self.resolution)?.k_value()?;
+        let previous_k = self.k();
+        let removed = self
+            .rings
+            .iter()
+            .skip(k as usize + 1)
+            .flatten()
+            .copied()
+            .collect();
+        self.resize(k);
+        let added = self
+            .rings
+            .iter()
+            .skip(previous_k as usize + 1)
+            .flatten()
+            .copied()
+            .collect();
+        self.radius_meters = radius_meters;
+        Ok(RadiusDelta {
+            previous_k,
+            k,
+            added,
+            removed,
+        })
+    }
+
+    pub fn radius_meters(&self) -> f64 {
+        self.radius_meters
+    }
+
+    /// Grid distance from the center cell to the outermost ring
+    pub fn k(&self) -> u32 {
+        self.rings.len() as u32 - 1
+    }
+
+    /// Every cell of the disk, ring by ring outwards
+    pub fn cells(&self) -> Vec<CellIndex> {
+        self.rings.iter().flatten().copied().collect()
+    }
+
+    /// The outermost ring, as [`SmallestEnclosingH3::hexagons`]
+    pub fn hexagons(&self) -> &[CellIndex] {
+        self.rings.last().map_or(&[], Vec::as_slice)
+    }
+
+    fn resize(&mut self, k: u32) {
+        let center_cell = self.center.to_cell(self.resolution);
+        self.rings.truncate(k as usize + 1);
+        for distance in self.rings.len() as u32..=k {
+            self.rings.push(grid_ring(center_cell, distance));
+        }
+    }
+}
+
+// A fresh coverage at `radius_meters`, checked to be a disk of rings within
+// the default cell limit
+fn coverage(
+    center: LatLng,
+    radius_meters: f64,
+    resolution: Resolution,
+) -> Result<SmallestEnclosingH3> {
+    let coverage = SmallestEnclosingH3Builder::new(center, radius_meters, resolution).build()?;
+    if coverage.is_large_cap() {
+        return Err(SmallestEnclosingH3Error::UnsupportedGeometry(
+            "a large spherical cap isn't a disk of rings".to_string(),
+        ));
+    }
+    coverage.check_cell_count(coverage.k_value()?)?;
+    Ok(coverage)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_radius_sweep_matches_fresh_coverage() {
+        let center = LatLng::new(33.4484, -112.0740).unwrap();
+        let mut computed = ComputedCoverage::new(center, 900.0, Resolution::Ten).unwrap();
+        let mut cells: HashSet<CellIndex> = computed.cells().into_iter().collect();
+
+        // Up by small and large steps, down across several rings, and back
+        for radius in [950.0, 1000.0, 2500.0, 2600.0, 400.0, 10.0, 3000.0, 900.0] {
+            let delta = computed.set_radius(radius).unwrap();
+            for cell in &delta.removed {
+                assert!(cells.remove(cell), "{cell} removed twice");
+            }
+            for cell in &delta.added {
+                assert!(cells.insert(*cell), "{cell} added twice");
+            }
+
+            let fresh = SmallestEnclosingH3Builder::new(center, radius, Resolution::Ten)
+                .build()
+                .unwrap();
+            let expected: HashSet<CellIndex> = fresh.disk().unwrap().into_iter().collect();
+            assert_eq!(cells, expected, "{radius}");
+            assert_eq!(computed.cells().len(), expected.len());
+            assert_eq!(computed.k(), fresh.k_value().unwrap());
+
+            let mut ring = computed.hexagons().to_vec();
+            let mut fresh_ring = fresh.hexagons().unwrap();
+            ring.sort_unstable();
+            fresh_ring.sort_unstable();
+            assert_eq!(ring, fresh_ring, "{radius}");
+        }
+        assert_eq!(computed.radius_meters(), 900.0);
+
+        // Within the same ring nothing changes
+        let delta = computed.set_radius(901.0).unwrap();
+        assert!(delta.is_empty());
+        assert_eq!(delta.k, delta.previous_k);
+    }
+
+    #[test]
+    fn test_invalid_radius_leaves_coverage() {
+        let center = LatLng::new(33.4484, -112.0740).unwrap();
+        let mut computed = ComputedCoverage::new(center, 900.0, Resolution::Ten).unwrap();
+        let cells = computed.cells();
+
+        assert!(matches!(
+            computed.set_radius(-1.0),
+            Err(SmallestEnclosingH3Error::InvalidRadius(_))
+        ));
+        assert!(matches!(
+            computed.set_radius(5_000_000.0),
+            Err(SmallestEnclosingH3Error::UnsupportedGeometry(_))
+        ));
+        assert_eq!(computed.cells(), cells);
+        assert_eq!(computed.radius_meters(), 900.0);
+    }
+}
@@ -0,0 +1,141 @@
+//! Builders from JSON request payloads, for services that receive coverage
+//! requests over the wire.
+
+use crate::{error::SmallestEnclosingH3Error, Result, SmallestEnclosingH3Builder};
+use h3o::error::{InvalidLatLng, InvalidResolution};
+use h3o::LatLng;
+use serde_json::{Map, Value};
+
+impl SmallestEnclosingH3Builder {
+    /// A builder from a JSON object such as
+    /// `{"lat": 33.4, "lng": -112.0, "radius_meters": 500, "resolution": 9}`.
+    /// Other fields are ignored; set the remaining options on the builder.
+    ///
+    /// JSON that isn't an object is a
+    /// [`SmallestEnclosingH3Error::SerializationError`]. A missing or
+    /// mistyped field is reported like an invalid value of it: a
+    /// [`SmallestEnclosingH3Error::InvalidLatLng`] for `lat` and `lng`,
+    /// [`SmallestEnclosingH3Error::InvalidRadius`] for `radius_meters`, and
+    /// [`SmallestEnclosingH3Error::InvalidResolution`] for `resolution`.
+    pub fn from_json_config(json: &str) -> Result<Self> {
+        let config: Map<String, Value> = serde_json::from_str(json)?;
+
+        let coordinate = |name, reason| {
+            config
+                .get(name)
+                .and_then(Value::as_f64)
+                .ok_or(InvalidLatLng {
+                    value: f64::NAN,
+                    reason,
+                })
+        };
+        let center = LatLng::new(
+            coordinate("lat", "lat must be a number")?,
+            coordinate("lng", "lng must be a number")?,
+        )?;
+
+        let radius_meters = config
+            .get("radius_meters")
+            .and_then(Value::as_f64)
+            .ok_or_else(|| {
+                SmallestEnclosingH3Error::InvalidRadius(
+                    "radius_meters must be a number".to_string(),
+                )
+            })?;
+
+        let resolution =
+            config
+                .get("resolution")
+                .and_then(Value::as_u64)
+                .ok_or(InvalidResolution {
+                    value: None,
+                    reason: "resolution must be an integer",
+                })?;
+        let resolution = u8::try_from(resolution).map_err(|_| InvalidResolution {
+            value: None,
+            reason: "out of range",
+        })?;
+
+        Self::default()
+            .center(center)
+            .radius_meters(radius_meters)?
+            .resolution(resolution)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use h3o::Resolution;
+
+    #[test]
+    fn test_from_json_config() {
+        let smallest_enclosing_h3 = SmallestEnclosingH3Builder::from_json_config(
+            r#"{"lat": 33.4484, "lng": -112.0740, "radius_meters": 500, "resolution": 9, "id": "a"}"#,
+        )
+        .unwrap()
+        .build()
+        .unwrap();
+        let parameters = smallest_enclosing_h3.effective_parameters().unwrap();
+        assert_eq!(parameters.center, LatLng::new(33.4484, -112.0740).unwrap());
+        assert_eq!(parameters.radius_meters, 500.0);
+        assert_eq!(parameters.resolution, Resolution::Nine);
+    }
+
+    #[test]
+    fn test_from_json_config_errors() {
+        let error = |json: &str| SmallestEnclosingH3Builder::from_json_config(json).unwrap_err();
+
+        // Not an object
+        assert!(matches!(
+            error(r#"[33.4, -112.0, 500, 9]"#),
+            SmallestEnclosingH3Error::SerializationError(_)
+        ));
+        assert!(matches!(
+            error(r#"{"lat": 33.4"#),
+            SmallestEnclosingH3Error::SerializationError(_)
+        ));
+
+        // Missing fields
+        assert!(matches!(
+            error(r#"{"lng": -112.0, "radius_meters": 500, "resolution": 9}"#),
+            SmallestEnclosingH3Error::InvalidLatLng(_)
+        ));
+        assert!(matches!(
+            error(r#"{"lat": 33.4, "lng": -112.0, "resolution": 9}"#),
+            SmallestEnclosingH3Error::InvalidRadius(_)
+        ));
+        assert!(matches!(
+            error(r#"{"lat": 33.4, "lng": -112.0, "radius_meters": 500}"#),
+            SmallestEnclosingH3Error::InvalidResolution(_)
+        ));
+
+        // Mistyped or invalid values
+        assert!(matches!(
+            error(r#"{"lat": "33.4", "lng": -112.0, "radius_meters": 500, "resolution": 9}"#),
+            SmallestEnclosingH3Error::InvalidLatLng(_)
+        ));
+        assert!(matches!(
+            error(r#"{"lat": 33.4, "lng": null, "radius_meters": 500, "resolution": 9}"#),
+            SmallestEnclosingH3Error::InvalidLatLng(_)
+        ));
+        // An out of range latitude is left to the builder to normalize
+        assert!(SmallestEnclosingH3Builder::from_json_config(
+            r#"{"lat": 91.0, "lng": -112.0, "radius_meters": 500, "resolution": 9}"#
+        )
+        .is_ok());
+        assert!(matches!(
+            error(r#"{"lat": 33.4, "lng": -112.0, "radius_meters": -5, "resolution": 9}"#),
+            SmallestEnclosingH3Error::InvalidRadius(_)
+        ));
+        for resolution in ["9.5", "-1", "16", "300", "\"9\""] {
+            let json = format!(
+                r#"{{"lat": 33.4, "lng": -112.0, "radius_meters": 500, "resolution": {resolution}}}"#
+            );
+            assert!(
+                matches!(error(&json), SmallestEnclosingH3Error::InvalidResolution(_)),
+                "{resolution}"
+            );
+        }
+    }
+}
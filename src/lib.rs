@@ -1,164 +1,4490 @@
+#[cfg(feature = "h3")]
+use adjustment::{Adjustment, AdjustmentPolicy};
+#[cfg(feature = "h3")]
 use error::{Result, SmallestEnclosingH3Error};
-use geo::{point, Point};
+#[cfg(feature = "h3")]
+use geo::{
+    point, Area, BooleanOps, BoundingRect, Contains, Intersects, LineString, MultiPolygon, Point,
+    Polygon,
+};
+#[cfg(feature = "h3")]
 use h3o::{CellIndex, LatLng, Resolution};
+#[cfg(feature = "h3")]
+use std::collections::HashSet;
+#[cfg(feature = "h3")]
+use std::ops::RangeInclusive;
+#[cfg(feature = "h3")]
+use std::sync::OnceLock;
 
 pub mod error;
+#[cfg(feature = "geodesy")]
+pub mod geodesy;
 
+#[cfg(feature = "h3")]
+pub mod adjustment;
+#[cfg(feature = "h3")]
+pub mod alert;
+#[cfg(feature = "h3")]
+pub mod batch;
+#[cfg(feature = "h3")]
+pub mod bbox;
+#[cfg(feature = "h3")]
+pub mod cell_coverage;
+#[cfg(feature = "h3")]
+pub mod cells;
+#[cfg(feature = "h3")]
+pub mod classify;
+#[cfg(feature = "h3")]
+pub mod compact;
+#[cfg(feature = "h3")]
+pub mod compat_fixtures;
+#[cfg(feature = "h3")]
+pub mod corridor;
+#[cfg(feature = "h3")]
+pub mod csv_export;
+#[cfg(feature = "h3")]
+pub mod ellipse;
+#[cfg(feature = "h3")]
+pub mod geo_coverage;
+#[cfg(feature = "h3")]
+pub mod golden;
+#[cfg(feature = "h3")]
+pub mod heatmap;
+#[cfg(feature = "h3")]
+pub mod incremental;
+#[cfg(feature = "h3")]
+mod min_coverage;
+#[cfg(feature = "h3")]
+pub mod nearest;
+#[cfg(feature = "h3")]
+mod parallel;
+#[cfg(feature = "h3")]
+pub mod pipeline;
+#[cfg(feature = "h3")]
+mod polyfill;
+#[cfg(feature = "h3")]
+pub mod set_ops;
+#[cfg(feature = "h3")]
+pub mod spherical_cap;
+#[cfg(feature = "h3")]
+pub mod topology;
+#[cfg(feature = "h3")]
+pub mod util;
+
+#[cfg(feature = "czml")]
+pub mod czml_export;
+#[cfg(feature = "duckdb")]
+pub mod duckdb;
+#[cfg(feature = "arbitrary")]
+mod fuzzing;
 #[cfg(feature = "geojson_export")]
 pub mod geojson_export;
+#[cfg(feature = "json_config")]
+pub mod json_config;
+#[cfg(all(feature = "mmap", unix))]
+pub mod mmap;
+#[cfg(feature = "polyline")]
+pub mod polyline;
+#[cfg(feature = "postcard")]
+pub mod postcard;
+#[cfg(feature = "postgis")]
+pub mod postgis;
+#[cfg(feature = "preview")]
+pub mod preview;
+#[cfg(feature = "sampling")]
+pub mod sampling;
+#[cfg(feature = "svg_export")]
+pub mod svg_export;
+#[cfg(feature = "wkb_export")]
+pub mod wkb;
+#[cfg(feature = "wkt")]
+pub mod wkt;
+
+/// Coordinate reference system of exported coordinates
+#[cfg(feature = "h3")]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum OutputCrs {
+    /// Longitude and latitude in degrees (EPSG:4326)
+    #[default]
+    Wgs84,
+    /// Web Mercator meters (EPSG:3857), see [`geodesy::web_mercator`]
+    WebMercator,
+}
+
+#[cfg(feature = "h3")]
+impl OutputCrs {
+    /// The EPSG code, such as `"EPSG:4326"`
+    pub fn code(self) -> &'static str {
+        match self {
+            OutputCrs::Wgs84 => "EPSG:4326",
+            OutputCrs::WebMercator => "EPSG:3857",
+        }
+    }
+
+    /// Project a longitude and latitude in degrees to `[x, y]` in this CRS
+    pub fn project(self, lng: f64, lat: f64) -> [f64; 2] {
+        match self {
+            OutputCrs::Wgs84 => [lng, lat],
+            OutputCrs::WebMercator => {
+                let (x, y) = geodesy::web_mercator((lat, lng));
+                [x, y]
+            }
+        }
+    }
+}
+
+// Vertices of the circle polygon used for exports and area computations
+#[cfg(feature = "h3")]
+const CIRCLE_VERTICES: usize = 64;
+
+/// Unit of a distance passed to [`SmallestEnclosingH3Builder::radius`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum DistanceUnit {
+    Meters,
+    Kilometers,
+    Miles,
+    Feet,
+    NauticalMiles,
+}
+
+impl DistanceUnit {
+    /// Convert `value` in this unit to meters
+    pub fn to_meters(self, value: f64) -> f64 {
+        match self {
+            DistanceUnit::Meters => value,
+            DistanceUnit::Kilometers => value * 1000.0,
+            DistanceUnit::Miles => value * 1609.344,
+            DistanceUnit::Feet => value * 0.3048,
+            DistanceUnit::NauticalMiles => value * 1852.0,
+        }
+    }
+}
+
+/// Builder for creating a SmallestEnclosingH3 instance.
+///
+/// Every setter takes the builder by value and returns the updated one (or a
+/// `Result` of it), so discarding the return value drops the change; the
+/// compiler warns about it:
+///
+/// ```compile_fail
+/// #![deny(unused_must_use)]
+/// use h3o::{LatLng, Resolution};
+/// use smallest_enclosing_h3::SmallestEnclosingH3Builder;
+///
+/// let builder = SmallestEnclosingH3Builder::new(
+///     LatLng::new(33.4484, -112.0740).unwrap(),
+///     500.0,
+///     Resolution::Ten,
+/// );
+/// builder.padding_rings(1);
+/// ```
+#[cfg(feature = "h3")]
+#[derive(Debug)]
+#[must_use = "a builder does nothing until it is built"]
+pub struct SmallestEnclosingH3Builder {
+    resolution: Resolution,
+    center: LatLng,
+    radius_meters: f64,
+    inner_radius_meters: f64,
+    bearing_range: Option<(f64, f64)>,
+    rectangle: Option<bbox::GeoBBox>,
+    policy: AdjustmentPolicy,
+    coverage_overlap: bool,
+    padding_rings: u32,
+    snap_resolution: Option<Resolution>,
+    math_mode: MathMode,
+    max_cells: usize,
+    resolution_fallback: bool,
+    sphere_radius_meters: f64,
+    ensure_full_coverage: bool,
+    algorithm: Algorithm,
+    min_coverage_fraction: Option<f64>,
+}
+
+/// A 1 km circle on the equator at the prime meridian, `(0, 0)`, covered at
+/// resolution 9. Override the fields with [`SmallestEnclosingH3Builder::center`],
+/// [`SmallestEnclosingH3Builder::radius_meters`] and
+/// [`SmallestEnclosingH3Builder::resolution`].
+#[cfg(feature = "h3")]
+impl Default for SmallestEnclosingH3Builder {
+    fn default() -> Self {
+        let center = LatLng::new(0.0, 0.0).expect("(0, 0) is a valid coordinate");
+        Self::new(center, 1000.0, Resolution::Nine)
+    }
+}
+
+#[cfg(feature = "h3")]
+impl SmallestEnclosingH3Builder {
+    pub fn new(center: LatLng, radius_meters: f64, resolution: Resolution) -> Self {
+        Self {
+            resolution,
+            center,
+            radius_meters,
+            inner_radius_meters: 0.0,
+            bearing_range: None,
+            rectangle: None,
+            policy: AdjustmentPolicy::default(),
+            coverage_overlap: false,
+            padding_rings: 0,
+            snap_resolution: None,
+            math_mode: MathMode::Spherical,
+            max_cells: DEFAULT_MAX_CELLS,
+            resolution_fallback: false,
+            sphere_radius_meters: geodesy::EARTH_RADIUS,
+            ensure_full_coverage: false,
+            algorithm: Algorithm::KRing,
+            min_coverage_fraction: None,
+        }
+    }
+
+    /// Start from an existing cell: the circle is centered on the cell's
+    /// centroid, with half the average edge length at the cell's resolution as
+    /// its radius, and covered at the finer `resolution`
+    pub fn from_h3_cell(cell: CellIndex, resolution: Resolution) -> Result<Self> {
+        if resolution <= cell.resolution() {
+            return Err(h3o::error::InvalidResolution {
+                value: Some(u8::from(resolution)),
+                reason: "must be finer than the cell's resolution",
+            }
+            .into());
+        }
+
+        Ok(Self::new(
+            LatLng::from(cell),
+            resolution_info(cell.resolution()).avg_edge_length_km * 1000.0 / 2.0,
+            resolution,
+        ))
+    }
+
+    pub fn resolution(mut self, resolution: u8) -> Result<Self> {
+        self.resolution = Resolution::try_from(resolution)?;
+        Ok(self)
+    }
+
+    pub fn center(mut self, center: LatLng) -> Self {
+        self.center = center;
+        self
+    }
+
+    pub fn radius_meters(mut self, radius: f64) -> Result<Self> {
+        if radius.is_nan() || radius <= 0.0 {
+            return Err(SmallestEnclosingH3Error::InvalidRadius(
+                "Radius must be positive".to_string(),
+            ));
+        }
+        self.radius_meters = radius;
+        Ok(self)
+    }
+
+    /// Set the radius in the given unit. It is stored, and reported, in meters.
+    pub fn radius(self, radius: f64, unit: DistanceUnit) -> Result<Self> {
+        if radius.is_nan() || radius <= 0.0 {
+            return Err(SmallestEnclosingH3Error::InvalidRadius(
+                "Radius must be positive".to_string(),
+            ));
+        }
+        self.radius_meters(unit.to_meters(radius))
+    }
+
+    /// Exclude the core within `radius` meters of the center, turning the
+    /// circle into an annulus. `0`, the default, keeps the whole circle; see
+    /// [`SmallestEnclosingH3::disk`] for the cells excluded.
+    pub fn inner_radius_meters(mut self, radius: f64) -> Result<Self> {
+        if radius.is_nan() || radius < 0.0 {
+            return Err(SmallestEnclosingH3Error::InvalidRadius(
+                "Inner radius must not be negative".to_string(),
+            ));
+        }
+        self.inner_radius_meters = radius;
+        Ok(self)
+    }
+
+    /// Restrict the coverage to the sector between the bearings `start_deg`
+    /// and `end_deg`, in degrees clockwise from north. The sector runs
+    /// clockwise from start to end, so `(350.0, 20.0)` wraps through north.
+    ///
+    /// Bearings are normalized into [0, 360). A range spanning a whole turn,
+    /// such as `(0.0, 360.0)`, keeps the full circle; an empty one, where
+    /// start and end are equal, is rejected.
+    pub fn bearing_range(mut self, start_deg: f64, end_deg: f64) -> Result<Self> {
+        if !start_deg.is_finite() || !end_deg.is_finite() {
+            return Err(SmallestEnclosingH3Error::InvalidBearing(format!(
+                "bearings must be finite, got {start_deg} to {end_deg}"
+            )));
+        }
+
+        let (start, end) = (start_deg.rem_euclid(360.0), end_deg.rem_euclid(360.0));
+        self.bearing_range = if start != end {
+            Some((start, end))
+        } else if start_deg != end_deg {
+            None
+        } else {
+            return Err(SmallestEnclosingH3Error::InvalidBearing(format!(
+                "the range from {start_deg} to {end_deg} is empty"
+            )));
+        };
+        Ok(self)
+    }
+
+    pub fn radius_kilometers(self, radius: f64) -> Result<Self> {
+        self.radius(radius, DistanceUnit::Kilometers)
+    }
+
+    pub fn radius_miles(self, radius: f64) -> Result<Self> {
+        self.radius(radius, DistanceUnit::Miles)
+    }
+
+    pub fn radius_feet(self, radius: f64) -> Result<Self> {
+        self.radius(radius, DistanceUnit::Feet)
+    }
+
+    pub fn radius_nautical_miles(self, radius: f64) -> Result<Self> {
+        self.radius(radius, DistanceUnit::NauticalMiles)
+    }
+
+    /// In strict mode every [`Adjustment`] to the inputs, such as wrapping an
+    /// out-of-range longitude, is reported as
+    /// [`SmallestEnclosingH3Error::StrictModeViolation`] instead of applied.
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.policy.strict = strict;
+        self
+    }
+
+    /// Add a `coverage_overlap` property to exported hexagon features with the
+    /// fraction of each cell's area inside the circle
+    pub fn with_coverage_overlap(mut self, enabled: bool) -> Self {
+        self.coverage_overlap = enabled;
+        self
+    }
+
+    /// Expand the coverage by `rings` extra rings of cells around it, as a
+    /// safety margin. `0`, the default, adds none.
+    ///
+    /// The padding is every cell within grid distance `rings` of
+    /// [`SmallestEnclosingH3::disk`] that isn't already in it, see
+    /// [`SmallestEnclosingH3::padding_cells`]. It is added to both
+    /// [`SmallestEnclosingH3::hexagons`] and [`SmallestEnclosingH3::disk`], so
+    /// for a plain circle with ring distance k the ring gains the rings at k + 1
+    /// to k + `rings`, and the disk becomes the disk at k + `rings`. An annulus
+    /// is padded into its hole as well.
+    pub fn padding_rings(mut self, rings: u32) -> Self {
+        self.padding_rings = rings;
+        self
+    }
+
+    /// Replace the center with the centroid of its cell at `resolution`,
+    /// quantizing the location for public releases.
+    ///
+    /// The snap is applied in [`Self::build`] after the center is normalized,
+    /// and everything is computed and exported from the snapped center only,
+    /// so every center within one cell yields the same output. Snapping is
+    /// explicit, so strict mode allows it; it is listed as an
+    /// [`Adjustment::SnapCenter`]. It doesn't apply to a bounding box,
+    /// whose bounds are exported as given.
+    pub fn snap_center_to_resolution(mut self, resolution: Resolution) -> Self {
+        self.snap_resolution = Some(resolution);
+        self
+    }
+
+    /// Compute distances and bearings from the center with `math_mode`,
+    /// [`MathMode::Spherical`] by default.
+    ///
+    /// [`MathMode::PlanarLocal`] is rejected by [`Self::build`] with
+    /// [`SmallestEnclosingH3Error::InvalidRadius`] for radii over
+    /// [`PLANAR_MAX_RADIUS_METERS`] or circles reaching a pole, rather than
+    /// silently falling back, and doesn't apply to a bounding box.
+    pub fn math_mode(mut self, math_mode: MathMode) -> Self {
+        self.math_mode = math_mode;
+        self
+    }
+
+    /// Refuse to generate more than `limit` cells, [`DEFAULT_MAX_CELLS`] by
+    /// default.
+    ///
+    /// The count is estimated before any cells are allocated, from the grid
+    /// distance k as `1 + 3k(k + 1)` cells (padding included) for a circle,
+    /// and from the area for a bounding box or a large spherical cap. The
+    /// estimate never falls short of the cells generated, and going over the
+    /// limit is a [`SmallestEnclosingH3Error::TooManyCells`] carrying both.
+    pub fn max_cells(mut self, limit: usize) -> Self {
+        self.max_cells = limit;
+        self
+    }
+
+    /// Measure distances on a sphere of `radius` meters instead of
+    /// [`geodesy::EARTH_RADIUS`], e.g. `3_389_500.0` for Mars or the WGS84
+    /// authalic radius `6_371_007.2` for Earth.
+    ///
+    /// The circle's radius, and every distance and destination computed from
+    /// it, is scaled to this sphere; the H3 grid itself stays the Earth's, so
+    /// a smaller sphere makes the same radius span more cells.
+    pub fn sphere_radius_meters(mut self, radius: f64) -> Result<Self> {
+        if !(radius > 0.0 && radius.is_finite()) {
+            return Err(SmallestEnclosingH3Error::InvalidRadius(format!(
+                "Sphere radius must be positive, got {radius}"
+            )));
+        }
+        self.sphere_radius_meters = radius;
+        Ok(self)
+    }
+
+    /// Instead of failing with [`SmallestEnclosingH3Error::TooManyCells`],
+    /// retry at ever coarser resolutions, one at a time, until the cells fit
+    /// within [`Self::max_cells`]. Only resolution 0 failing too is an error.
+    ///
+    /// The cells are then computed by [`Self::build`], which settles the
+    /// resolution; it is listed as an [`Adjustment::CoarsenResolution`],
+    /// allowed in strict mode, and the resolution used is
+    /// [`SmallestEnclosingH3::last_resolution_used`].
+    pub fn resolution_downgrade_fallback(mut self, enabled: bool) -> Self {
+        self.resolution_fallback = enabled;
+        self
+    }
+
+    /// Move the ring of [`SmallestEnclosingH3::hexagons`] out, one grid step
+    /// at a time, until the disk within it holds every point
+    /// [`SmallestEnclosingH3::verify_coverage`] samples on the circle's
+    /// boundary.
+    ///
+    /// The grid distance to the circle's edge is taken due north, east, south
+    /// and west only, so a circle whose edge bulges into a farther cell in
+    /// between, as around a cell's vertex, is otherwise left partly
+    /// uncovered. A wider ring is listed as an
+    /// [`Adjustment::ExpandRingDistance`], allowed in strict mode. A bounding
+    /// box or a large spherical cap is covered in full already. With a bearing
+    /// range, cells are still kept by their centroid, so the sector's sides
+    /// may stay partly uncovered.
+    pub fn ensure_full_coverage(mut self, enabled: bool) -> Self {
+        self.ensure_full_coverage = enabled;
+        self
+    }
+
+    /// Choose the cells with `algorithm`, [`Algorithm::KRing`] by default.
+    ///
+    /// [`Algorithm::Polyfill`] applies to circles only: a bounding box is
+    /// always polyfilled and a large spherical cap flood-filled, and both are
+    /// rejected by [`Self::build`] with
+    /// [`SmallestEnclosingH3Error::UnsupportedGeometry`], as is a polygon of
+    /// fewer than 3 vertices.
+    pub fn algorithm(mut self, algorithm: Algorithm) -> Self {
+        self.algorithm = algorithm;
+        self
+    }
+
+    /// Keep a cell only if at least `fraction` of its area lies within the
+    /// circle, `0.3` for 30%. The fraction must be in (0, 1]; anything else
+    /// is a [`SmallestEnclosingH3Error::InvalidFraction`].
+    ///
+    /// The threshold trims the cells [`Self::algorithm`] selects, so a
+    /// fraction near 0 keeps those intersecting the circle. Only the rim
+    /// cells, reaching past the circle, are measured, with their overlap
+    /// taken on a plane around the center. A bounding box or a large
+    /// spherical cap is rejected by [`Self::build`] with
+    /// [`SmallestEnclosingH3Error::UnsupportedGeometry`].
+    pub fn min_coverage_fraction(mut self, fraction: f64) -> Result<Self> {
+        if fraction.is_nan() || fraction <= 0.0 || fraction > 1.0 {
+            return Err(SmallestEnclosingH3Error::InvalidFraction(format!(
+                "Coverage fraction {fraction} must be in (0, 1]"
+            )));
+        }
+        self.min_coverage_fraction = Some(fraction);
+        Ok(self)
+    }
+
+    /// Run every check of [`Self::build`] on the inputs, returning the first
+    /// error, without consuming the builder or computing any cells.
+    ///
+    /// Only the cell limit is left to [`Self::build`] and the computations
+    /// after it, as it depends on the cells themselves.
+    pub fn validate(&self) -> Result<()> {
+        self.validated_center().map(|_| ())
+    }
+
+    pub fn build(self) -> Result<SmallestEnclosingH3> {
+        let center = self.validated_center()?;
+        let coverage = SmallestEnclosingH3 {
+            resolution: self.resolution,
+            requested_resolution: self.resolution,
+            requested_center: self.center,
+            center,
+            requested_radius_meters: self.radius_meters,
+            radius_meters: self.radius_meters,
+            inner_radius_meters: self.inner_radius_meters,
+            bearing_range: self.bearing_range,
+            rectangle: self.rectangle,
+            policy: self.policy,
+            coverage_overlap: self.coverage_overlap,
+            padding_rings: self.padding_rings,
+            math_mode: self.math_mode,
+            max_cells: self.max_cells,
+            sphere_radius_meters: self.sphere_radius_meters,
+            ensure_full_coverage: self.ensure_full_coverage,
+            algorithm: self.algorithm,
+            min_coverage_fraction: self.min_coverage_fraction,
+            ring: OnceLock::new(),
+            disk: OnceLock::new(),
+            padding: OnceLock::new(),
+        };
+        if self.resolution_fallback {
+            return coverage.coarsen_to_fit();
+        }
+        Ok(coverage)
+    }
+
+    // The center to build around, normalized and snapped, once the inputs
+    // pass every check
+    fn validated_center(&self) -> Result<LatLng> {
+        if self.radius_meters.is_nan() || self.radius_meters <= 0.0 {
+            return Err(SmallestEnclosingH3Error::InvalidRadius(
+                "Radius must be positive".to_string(),
+            ));
+        }
+        // Beyond the antipode a circle covers the whole globe
+        if self.radius_meters > std::f64::consts::PI * self.sphere_radius_meters {
+            return Err(SmallestEnclosingH3Error::InvalidRadius(format!(
+                "Radius {} exceeds half the sphere's circumference",
+                self.radius_meters
+            )));
+        }
+        if self.inner_radius_meters >= self.radius_meters {
+            return Err(SmallestEnclosingH3Error::InvalidRadius(format!(
+                "Inner radius {} must be less than the radius {}",
+                self.inner_radius_meters, self.radius_meters
+            )));
+        }
+        if self.rectangle.is_some()
+            && (self.inner_radius_meters > 0.0 || self.bearing_range.is_some())
+        {
+            return Err(SmallestEnclosingH3Error::UnsupportedGeometry(
+                "an inner radius or bearing range doesn't apply to a bounding box".to_string(),
+            ));
+        }
+        if self.rectangle.is_some() && self.snap_resolution.is_some() {
+            return Err(SmallestEnclosingH3Error::UnsupportedGeometry(
+                "center snapping doesn't apply to a bounding box".to_string(),
+            ));
+        }
+        if let Algorithm::Polyfill { vertices, .. } = self.algorithm {
+            if vertices < 3 {
+                return Err(SmallestEnclosingH3Error::UnsupportedGeometry(format!(
+                    "a circle needs at least 3 vertices, got {vertices}"
+                )));
+            }
+            if self.rectangle.is_some() {
+                return Err(SmallestEnclosingH3Error::UnsupportedGeometry(
+                    "a bounding box is always polyfilled".to_string(),
+                ));
+            }
+            if (self.radius_meters / self.sphere_radius_meters).to_degrees()
+                > spherical_cap::LARGE_CAP_DEGREES
+            {
+                return Err(SmallestEnclosingH3Error::UnsupportedGeometry(
+                    "a large spherical cap can't be polyfilled".to_string(),
+                ));
+            }
+        }
+        if self.min_coverage_fraction.is_some() {
+            if self.rectangle.is_some() {
+                return Err(SmallestEnclosingH3Error::UnsupportedGeometry(
+                    "a coverage fraction doesn't apply to a bounding box".to_string(),
+                ));
+            }
+            if (self.radius_meters / self.sphere_radius_meters).to_degrees()
+                > spherical_cap::LARGE_CAP_DEGREES
+            {
+                return Err(SmallestEnclosingH3Error::UnsupportedGeometry(
+                    "a large spherical cap is too curved for a planar coverage fraction"
+                        .to_string(),
+                ));
+            }
+        }
+        if self.rectangle.is_some() && self.math_mode == MathMode::PlanarLocal {
+            return Err(SmallestEnclosingH3Error::UnsupportedGeometry(
+                "planar math doesn't apply to a bounding box".to_string(),
+            ));
+        }
+
+        let mut center = normalize_center(self.center, &self.policy)?;
+        if let Some(snap_resolution) = self.snap_resolution {
+            let snapped = LatLng::from(center.to_cell(snap_resolution));
+            if snapped != center {
+                self.policy.allow(Adjustment::SnapCenter {
+                    from: (center.lat(), center.lng()),
+                    to: (snapped.lat(), snapped.lng()),
+                })?;
+            }
+            center = snapped;
+        }
+        if self.math_mode == MathMode::PlanarLocal {
+            if self.radius_meters > PLANAR_MAX_RADIUS_METERS {
+                return Err(SmallestEnclosingH3Error::InvalidRadius(format!(
+                    "Radius {} exceeds the {PLANAR_MAX_RADIUS_METERS} m planar math is accurate for",
+                    self.radius_meters
+                )));
+            }
+            let to_pole = (90.0 - center.lat().abs()).to_radians() * self.sphere_radius_meters;
+            if self.radius_meters >= to_pole {
+                return Err(SmallestEnclosingH3Error::InvalidRadius(format!(
+                    "Radius {} reaches a pole, where planar math breaks down",
+                    self.radius_meters
+                )));
+            }
+        }
+        Ok(center)
+    }
+}
+
+/// Build from `(lat, lng, radius_meters, resolution)`, as parsed from plain
+/// numbers in configuration files or environment variables
+#[cfg(feature = "h3")]
+impl TryFrom<(f64, f64, f64, u8)> for SmallestEnclosingH3 {
+    type Error = SmallestEnclosingH3Error;
+
+    fn try_from((lat, lng, radius_meters, resolution): (f64, f64, f64, u8)) -> Result<Self> {
+        SmallestEnclosingH3Builder::new(
+            LatLng::new(lat, lng)?,
+            radius_meters,
+            Resolution::try_from(resolution)?,
+        )
+        .build()
+    }
+}
+
+/// Build from `[lat, lng, radius_meters, resolution]`. The resolution must be
+/// a whole number within [0, 15].
+#[cfg(feature = "h3")]
+impl TryFrom<[f64; 4]> for SmallestEnclosingH3 {
+    type Error = SmallestEnclosingH3Error;
+
+    fn try_from([lat, lng, radius_meters, resolution]: [f64; 4]) -> Result<Self> {
+        if resolution.fract() != 0.0 || !(0.0..=15.0).contains(&resolution) {
+            return Err(h3o::error::InvalidResolution {
+                value: None,
+                reason: "must be a whole number within [0, 15]",
+            }
+            .into());
+        }
+        Self::try_from((lat, lng, radius_meters, resolution as u8))
+    }
+}
+
+// Bring a center given beyond the poles or outside [-180, 180] back into range
+#[cfg(feature = "h3")]
+fn normalize_center(center: LatLng, policy: &AdjustmentPolicy) -> Result<LatLng> {
+    let (mut lat, mut lng) = (center.lat(), center.lng());
+
+    if !(-90.0..=90.0).contains(&lat) {
+        let wrapped = (lat + 180.0).rem_euclid(360.0) - 180.0;
+        let folded = if wrapped > 90.0 {
+            180.0 - wrapped
+        } else if wrapped < -90.0 {
+            -180.0 - wrapped
+        } else {
+            wrapped
+        };
+        policy.allow(Adjustment::NormalizeLatitude {
+            from: lat,
+            to: folded,
+        })?;
+        // Crossing a pole puts the point on the opposite meridian
+        if folded != wrapped {
+            lng += 180.0;
+        }
+        lat = folded;
+    }
+
+    if !(-180.0..=180.0).contains(&lng) {
+        let wrapped = (lng + 180.0).rem_euclid(360.0) - 180.0;
+        policy.allow(Adjustment::NormalizeLongitude {
+            from: center.lng(),
+            to: wrapped,
+        })?;
+        lng = wrapped;
+    }
+
+    validate_center(lat, lng)?;
+    if lat == center.lat() && lng == center.lng() {
+        return Ok(center);
+    }
+    Ok(LatLng::new(lat, lng)?)
+}
+
+// `LatLng` only guarantees finite values, so check the normalized ranges
+// explicitly. NaN fails every range check.
+#[cfg(feature = "h3")]
+fn validate_center(lat: f64, lng: f64) -> Result<()> {
+    if !(-90.0..=90.0).contains(&lat) {
+        return Err(h3o::error::InvalidLatLng {
+            value: lat,
+            reason: "latitude must be within [-90, 90]",
+        }
+        .into());
+    }
+    if !(-180.0..=180.0).contains(&lng) {
+        return Err(h3o::error::InvalidLatLng {
+            value: lng,
+            reason: "longitude must be within [-180, 180]",
+        }
+        .into());
+    }
+    Ok(())
+}
+
+/// Order of cells returned by [`SmallestEnclosingH3::hexagons_sorted`]
+#[cfg(feature = "h3")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum CellOrder {
+    /// Ascending `u64` cell index: a single sort, and stable across h3o
+    /// versions unlike the ring traversal order of [`SmallestEnclosingH3::hexagons`]
+    #[default]
+    Index,
+    /// Ring by ring outwards from the center cell, each ring clockwise from
+    /// north, so a listing reads like the map. Cells at the same bearing are
+    /// ordered by index.
+    RingClock,
+    /// Along a Hilbert curve over the cell centroids, so cells adjacent in
+    /// the list are adjacent on the map, for tight scans such as rasterizing
+    /// or columnar storage. Cells at the same curve position are ordered by
+    /// index.
+    Hilbert,
+}
+
+/// How distances and bearings from the center are computed, see
+/// [`SmallestEnclosingH3Builder::math_mode`]
+#[cfg(feature = "h3")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum MathMode {
+    /// Great-circle trigonometry on the sphere
+    #[default]
+    Spherical,
+    /// Euclidean math in meters on a plane around the center, see
+    /// [`geodesy::local_plane`]. Cheaper, and within a millimeter of
+    /// [`MathMode::Spherical`] for radii of a couple of kilometers, but only
+    /// allowed up to [`PLANAR_MAX_RADIUS_METERS`].
+    PlanarLocal,
+}
+
+/// How the cells covering a circle are chosen, see
+/// [`SmallestEnclosingH3Builder::algorithm`]. Either way the cells come out
+/// through the same methods, [`SmallestEnclosingH3::hexagons`] as the outer
+/// cells and [`SmallestEnclosingH3::disk`] as all of them, but the two may
+/// select different cells for the same circle.
+#[cfg(feature = "h3")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Algorithm {
+    /// The ring of cells at the grid distance of the circle's edge, and the
+    /// disk within it: a hexagonal "circle" that over-covers the geometric
+    /// one, as the Python implementation does
+    #[default]
+    KRing,
+    /// The cells h3o's polygon-to-cells selects under `containment` for
+    /// the circle as a polygon of `vertices` points, see
+    /// [`SmallestEnclosingH3::generate_circle_coordinates_with`]. Tighter
+    /// than [`Algorithm::KRing`];
+    /// [`ContainmentMode::Covers`](h3o::geom::ContainmentMode::Covers) still
+    /// covers the whole circle, while the other modes may leave its rim
+    /// uncovered. The ring is the cells with a neighbour outside the cover.
+    Polyfill {
+        containment: h3o::geom::ContainmentMode,
+        vertices: usize,
+    },
+}
+
+/// The default of [`SmallestEnclosingH3Builder::max_cells`], enough for a
+/// 10 km radius at resolution 12 while stopping a typo from exhausting memory
+pub const DEFAULT_MAX_CELLS: usize = 10_000_000;
+
+/// The largest radius [`MathMode::PlanarLocal`] is allowed for, where the
+/// flat-Earth error is still well under 0.1%
+pub const PLANAR_MAX_RADIUS_METERS: f64 = 50_000.0;
+
+/// Shape of the cells generated around the center cell
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum CoverageMode {
+    /// The cells at exactly grid distance k, as [`SmallestEnclosingH3::hexagons`]
+    Ring,
+    /// Every cell within grid distance k, as [`SmallestEnclosingH3::disk`]
+    Disk,
+}
+
+/// Number of cells at grid distance `k` ([`CoverageMode::Ring`]) or within it
+/// ([`CoverageMode::Disk`]) of a cell: `6k` and `1 + 3k(k + 1)`.
+///
+/// These are exact away from pentagons. Where a pentagon lies within `k`
+/// rings, pass `near_pentagon` to get the count around the pentagon itself,
+/// `5k` and `1 + 5k(k + 1) / 2`, which is the fewest cells possible: actual
+/// counts near a pentagon lie between the two.
+pub fn expected_cell_count(k: u32, mode: CoverageMode, near_pentagon: bool) -> u64 {
+    let k = u64::from(k);
+    let sides = if near_pentagon { 5 } else { 6 };
+    match mode {
+        CoverageMode::Ring if k == 0 => 1,
+        CoverageMode::Ring => sides * k,
+        CoverageMode::Disk => 1 + sides * k * (k + 1) / 2,
+    }
+}
+
+/// Typical cell dimensions at a resolution, from [`resolution_info`]
+#[cfg(feature = "h3")]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ResolutionStats {
+    pub resolution: Resolution,
+    /// Average hexagon area, in square kilometers
+    pub avg_area_km2: f64,
+    /// Average hexagon edge length, in kilometers
+    pub avg_edge_length_km: f64,
+    /// Average distance between the centers of neighbouring hexagons, √3 times
+    /// the edge length, in kilometers
+    pub avg_cell_spacing_km: f64,
+}
+
+#[cfg(feature = "h3")]
+static RESOLUTION_STATS: OnceLock<[ResolutionStats; 16]> = OnceLock::new();
+
+/// [`ResolutionStats`] for every resolution, indexed by resolution. The table
+/// is computed on first use and shared by every thread.
+#[cfg(feature = "h3")]
+pub fn resolution_stats() -> &'static [ResolutionStats; 16] {
+    RESOLUTION_STATS.get_or_init(|| {
+        std::array::from_fn(|i| {
+            let resolution = Resolution::try_from(i as u8).expect("resolutions are 0 to 15");
+            let avg_edge_length_km = resolution.edge_length_km();
+            ResolutionStats {
+                resolution,
+                avg_area_km2: resolution.area_km2(),
+                avg_edge_length_km,
+                avg_cell_spacing_km: 3f64.sqrt() * avg_edge_length_km,
+            }
+        })
+    })
+}
+
+/// The average hexagon area and edge length at `resolution`, as tabulated in
+/// the H3 documentation, from [`resolution_stats`]
+#[cfg(feature = "h3")]
+pub fn resolution_info(resolution: Resolution) -> ResolutionStats {
+    resolution_stats()[usize::from(u8::from(resolution))]
+}
+
+/// What covering the circle at one resolution would take, as estimated by
+/// [`SmallestEnclosingH3::resolution_report`]
+#[cfg(feature = "h3")]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ResolutionEstimate {
+    pub resolution: Resolution,
+    /// Estimated grid distance from the center cell to the circle's edge
+    pub k: u32,
+    /// Estimated number of [`SmallestEnclosingH3::hexagons`], padding included
+    pub cell_count: u64,
+    /// Estimated area of [`SmallestEnclosingH3::disk`] over the circle's area,
+    /// how many times over the circle is covered
+    pub overshoot_ratio: f64,
+}
+
+/// The cells kept by [`SmallestEnclosingH3::truncate_closest`]
+#[cfg(feature = "h3")]
+#[derive(Debug, Clone)]
+pub struct TruncatedCoverage {
+    /// Kept cells, ordered by centroid distance to the center
+    pub cells: Vec<CellIndex>,
+    /// Whether any cells were dropped to honour the limit
+    pub truncated: bool,
+    /// Fraction (0.0–1.0) of the circle's area covered by the kept cells
+    pub circle_area_fraction: f64,
+}
+
+/// A point on the circle's boundary, as returned by
+/// [`SmallestEnclosingH3::boundary_samples`]
+#[cfg(feature = "h3")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct BoundarySample {
+    /// Bearing from the center, in degrees clockwise from north
+    pub bearing: f64,
+    pub lat_lng: LatLng,
+    /// Cell holding the sample at the circle's resolution
+    pub cell: CellIndex,
+    /// Whether `cell` is one of [`SmallestEnclosingH3::disk`]
+    pub covered: bool,
+    /// Grid distance from the center cell to `cell`, if it can be computed
+    pub grid_distance: Option<u32>,
+}
+
+/// The parameters a coverage was requested with and those it was computed
+/// with, as returned by [`SmallestEnclosingH3::effective_parameters`]
+#[cfg(feature = "h3")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct EffectiveParameters {
+    /// The center as given to the builder
+    pub requested_center: LatLng,
+    /// The radius as given to the builder, before any
+    /// [`SmallestEnclosingH3::scale`]
+    pub requested_radius_meters: f64,
+    pub requested_resolution: Resolution,
+    /// The center after normalization and snapping
+    pub center: LatLng,
+    pub radius_meters: f64,
+    pub resolution: Resolution,
+    /// Every change made, in the order applied, each explaining a difference
+    /// above or how the coverage was computed
+    pub adjustments: Vec<Adjustment>,
+}
+
+/// Represents a collection of H3 hexagons that enclose a circle.
+///
+/// The cells are computed on first use and cached, so repeated calls to
+/// [`Self::hexagons`], [`Self::disk`] and the methods built on them share one
+/// computation. The cache is thread-safe: the struct is `Send + Sync` and can
+/// be shared behind an `Arc`.
+#[cfg(feature = "h3")]
+#[derive(Debug)]
+pub struct SmallestEnclosingH3 {
+    resolution: Resolution,
+    // As given to the builder, before any `Adjustment::CoarsenResolution`
+    requested_resolution: Resolution,
+    // As given to the builder, before any `Adjustment`
+    requested_center: LatLng,
+    center: LatLng,
+    // As given to the builder, before any `Self::scale`
+    requested_radius_meters: f64,
+    radius_meters: f64,
+    inner_radius_meters: f64,
+    bearing_range: Option<(f64, f64)>,
+    rectangle: Option<bbox::GeoBBox>,
+    policy: AdjustmentPolicy,
+    #[cfg_attr(not(feature = "geojson_export"), allow(dead_code))]
+    coverage_overlap: bool,
+    padding_rings: u32,
+    math_mode: MathMode,
+    max_cells: usize,
+    sphere_radius_meters: f64,
+    ensure_full_coverage: bool,
+    algorithm: Algorithm,
+    min_coverage_fraction: Option<f64>,
+    // The grid distance and ring of `enclosing_ring`, the `disk`, and the
+    // `padding_cells`
+    ring: OnceLock<(u32, Vec<CellIndex>)>,
+    disk: OnceLock<Vec<CellIndex>>,
+    padding: OnceLock<Vec<CellIndex>>,
+}
+
+#[cfg(feature = "h3")]
+impl SmallestEnclosingH3 {
+    /// The circle's radius, in meters regardless of the unit it was given in
+    pub fn radius_meters(&self) -> f64 {
+        self.radius_meters
+    }
+
+    /// The radius of the sphere distances are measured on, see
+    /// [`SmallestEnclosingH3Builder::sphere_radius_meters`]
+    pub fn sphere_radius_meters(&self) -> f64 {
+        self.sphere_radius_meters
+    }
+
+    /// What was asked for and what was used, with every [`Adjustment`] made
+    /// to get from one to the other. The ring is computed first, so an
+    /// adjustment made while covering is listed too.
+    pub fn effective_parameters(&self) -> Result<EffectiveParameters> {
+        self.cached_ring()?;
+        Ok(EffectiveParameters {
+            requested_center: self.requested_center,
+            requested_radius_meters: self.requested_radius_meters,
+            requested_resolution: self.requested_resolution,
+            center: self.center,
+            radius_meters: self.radius_meters,
+            resolution: self.resolution,
+            adjustments: self.policy.applied(),
+        })
+    }
+
+    /// The radius of the excluded core, `0` unless set with
+    /// [`SmallestEnclosingH3Builder::inner_radius_meters`]
+    pub fn inner_radius_meters(&self) -> f64 {
+        self.inner_radius_meters
+    }
+
+    /// The least fraction of a cell's area within the circle, if set with
+    /// [`SmallestEnclosingH3Builder::min_coverage_fraction`]
+    pub fn min_coverage_fraction(&self) -> Option<f64> {
+        self.min_coverage_fraction
+    }
+
+    /// The sector's start and end bearings, in degrees within [0, 360), unless
+    /// the coverage is a full circle; see
+    /// [`SmallestEnclosingH3Builder::bearing_range`]
+    pub fn bearing_range(&self) -> Option<(f64, f64)> {
+        self.bearing_range
+    }
+
+    pub fn hexagons(&self) -> Result<Vec<CellIndex>> {
+        Ok(self.hexagons_cached()?.to_vec())
+    }
+
+    /// [`Self::hexagons`] borrowed from the cache, without copying
+    pub fn hexagons_cached(&self) -> Result<&[CellIndex]> {
+        Ok(&self.cached_ring()?.1)
+    }
+
+    /// Typical cell dimensions at the configured resolution, see
+    /// [`resolution_info`]
+    pub fn resolution_statistics(&self) -> ResolutionStats {
+        resolution_info(self.resolution)
+    }
+
+    /// [`Self::hexagons`] as [`cells::UniformCells`] at the configured
+    /// resolution, for APIs that require a single resolution
+    pub fn hexagons_uniform(&self) -> Result<cells::UniformCells> {
+        cells::UniformCells::new(self.resolution, self.hexagons()?)
+    }
+
+    /// [`Self::hexagons`] at `resolution` instead of the configured one, for a
+    /// single call. The grid distance is recomputed for the cell size at
+    /// `resolution`; `self` is left untouched.
+    pub fn hexagons_at(&self, resolution: Resolution) -> Result<Vec<CellIndex>> {
+        self.at_resolution(resolution).hexagons()
+    }
+
+    /// Estimate the coverage at each resolution of `range`, to choose one
+    /// without computing any cells.
+    ///
+    /// Each grid distance is taken to the circle's edge due north, east, south
+    /// and west as by [`Self::k_value`], without generating any ring, and the
+    /// counts follow from [`expected_cell_count`]; they are exact for a full
+    /// circle away from pentagons. The whole circle is assumed, ignoring any
+    /// inner radius or bearing range. A bounding box is a
+    /// [`SmallestEnclosingH3Error::UnsupportedGeometry`].
+    pub fn resolution_report(
+        &self,
+        range: RangeInclusive<Resolution>,
+    ) -> Result<Vec<ResolutionEstimate>> {
+        if self.rectangle.is_some() {
+            return Err(SmallestEnclosingH3Error::UnsupportedGeometry(
+                "a bounding box has no circle to estimate".to_string(),
+            ));
+        }
+        let radius = self.to_earth_meters(self.radius_meters);
+        let circle_area = 2.0
+            * std::f64::consts::PI
+            * geodesy::EARTH_RADIUS.powi(2)
+            * (1.0 - (radius / geodesy::EARTH_RADIUS).cos());
+
+        Resolution::range(*range.start(), *range.end())
+            .map(|resolution| {
+                let coverage = self.at_resolution(resolution);
+                let center_cell = self.center.to_cell(resolution);
+                let estimate = coverage.estimate_k(center_cell);
+                let k = if self.is_large_cap() {
+                    estimate
+                } else {
+                    // As `k_value`, but with the estimate standing in for
+                    // distances across pentagon distortion
+                    coverage
+                        .edge_cells()?
+                        .into_iter()
+                        .try_fold(0, |k, edge_cell| {
+                            center_cell
+                                .grid_distance(edge_cell)
+                                .map(|distance| k.max(distance as u32))
+                        })
+                        .unwrap_or(estimate)
+                };
+                let padded = k + self.padding_rings;
+                let cell_count = (k..=padded)
+                    .map(|ring| expected_cell_count(ring, CoverageMode::Ring, false))
+                    .sum();
+                let disk_area = expected_cell_count(padded, CoverageMode::Disk, false) as f64
+                    * center_cell.area_m2();
+                Ok(ResolutionEstimate {
+                    resolution,
+                    k,
+                    cell_count,
+                    overshoot_ratio: disk_area / circle_area,
+                })
+            })
+            .collect()
+    }
+
+    /// A copy with the radius, and any inner radius, multiplied by `factor`,
+    /// around the same center at the same resolution, such as `1.5` for a
+    /// safety margin. Its cells are computed anew, and
+    /// [`Self::effective_parameters`] still reports the builder's radius as
+    /// the one requested.
+    ///
+    /// A factor that isn't positive, or a radius that overflows or exceeds
+    /// half the sphere's circumference, is a
+    /// [`SmallestEnclosingH3Error::InvalidRadius`]. A bounding box has no
+    /// radius to scale, and like a large spherical cap with
+    /// [`Algorithm::Polyfill`] or
+    /// [`SmallestEnclosingH3Builder::min_coverage_fraction`] is a
+    /// [`SmallestEnclosingH3Error::UnsupportedGeometry`].
+    pub fn scale(&self, factor: f64) -> Result<SmallestEnclosingH3> {
+        if factor.is_nan() || factor <= 0.0 {
+            return Err(SmallestEnclosingH3Error::InvalidRadius(format!(
+                "Scale factor {factor} must be positive"
+            )));
+        }
+        if self.rectangle.is_some() {
+            return Err(SmallestEnclosingH3Error::UnsupportedGeometry(
+                "a bounding box has no radius to scale".to_string(),
+            ));
+        }
+        let radius_meters = self.radius_meters * factor;
+        if !radius_meters.is_finite() {
+            return Err(SmallestEnclosingH3Error::InvalidRadius(format!(
+                "Radius {} scaled by {factor} overflows",
+                self.radius_meters
+            )));
+        }
+        if radius_meters > std::f64::consts::PI * self.sphere_radius_meters {
+            return Err(SmallestEnclosingH3Error::InvalidRadius(format!(
+                "Radius {radius_meters} exceeds half the sphere's circumference"
+            )));
+        }
+
+        let mut scaled = self.at_resolution(self.resolution);
+        scaled.radius_meters = radius_meters;
+        scaled.inner_radius_meters = self.inner_radius_meters * factor;
+        if scaled.is_large_cap()
+            && (scaled.algorithm != Algorithm::KRing || scaled.min_coverage_fraction.is_some())
+        {
+            return Err(SmallestEnclosingH3Error::UnsupportedGeometry(
+                "a large spherical cap can't be polyfilled or trimmed to a coverage fraction"
+                    .to_string(),
+            ));
+        }
+        Ok(scaled)
+    }
+
+    /// A copy around `new_center` with the same radius, resolution and
+    /// options, for coverage of nearby locations or animations. Its cells are
+    /// computed anew.
+    ///
+    /// A bounding box moves as the circle of [`Self::radius_meters`] that
+    /// [`SmallestEnclosingH3Builder::from_bbox`] derives from it, reaching
+    /// the box's farthest corner. Adjustments made to the old center aren't
+    /// carried over; `new_center` is used as given.
+    pub fn move_center(&self, new_center: LatLng) -> SmallestEnclosingH3 {
+        let mut moved = self.at_resolution(self.resolution);
+        moved.requested_center = new_center;
+        moved.center = new_center;
+        moved.rectangle = None;
+        moved.policy = self.policy.for_new_inputs();
+        moved
+    }
+
+    // A copy of `self` covered at `resolution`, with its own cache
+    pub(crate) fn at_resolution(&self, resolution: Resolution) -> SmallestEnclosingH3 {
+        SmallestEnclosingH3 {
+            resolution,
+            requested_resolution: resolution,
+            requested_center: self.requested_center,
+            center: self.center,
+            requested_radius_meters: self.requested_radius_meters,
+            radius_meters: self.radius_meters,
+            inner_radius_meters: self.inner_radius_meters,
+            bearing_range: self.bearing_range,
+            rectangle: self.rectangle,
+            policy: self.policy.for_new_coverage(),
+            coverage_overlap: self.coverage_overlap,
+            padding_rings: self.padding_rings,
+            math_mode: self.math_mode,
+            max_cells: self.max_cells,
+            sphere_radius_meters: self.sphere_radius_meters,
+            ensure_full_coverage: self.ensure_full_coverage,
+            algorithm: self.algorithm,
+            min_coverage_fraction: self.min_coverage_fraction,
+            ring: OnceLock::new(),
+            disk: OnceLock::new(),
+            padding: OnceLock::new(),
+        }
+    }
+
+    // `self`, or failing that for too many cells, a copy at the first coarser
+    // resolution the cells fit at, see
+    // `SmallestEnclosingH3Builder::resolution_downgrade_fallback`
+    fn coarsen_to_fit(self) -> Result<SmallestEnclosingH3> {
+        let mut coverage = self;
+        loop {
+            let coarser = match coverage.cached_ring() {
+                Ok(_) => return Ok(coverage),
+                Err(err @ SmallestEnclosingH3Error::TooManyCells { .. }) => {
+                    match coverage.resolution.pred() {
+                        Some(coarser) => coarser,
+                        None => return Err(err),
+                    }
+                }
+                Err(err) => return Err(err),
+            };
+            let mut next = coverage.at_resolution(coarser);
+            next.requested_resolution = coverage.requested_resolution;
+            next.policy.allow(Adjustment::CoarsenResolution {
+                from: coverage.requested_resolution,
+                to: coarser,
+            })?;
+            coverage = next;
+        }
+    }
+
+    /// The resolution the cells were computed at, once they have been: the
+    /// builder's, or a coarser one after
+    /// [`SmallestEnclosingH3Builder::resolution_downgrade_fallback`]
+    pub fn last_resolution_used(&self) -> Option<Resolution> {
+        self.ring.get().map(|_| self.resolution)
+    }
+
+    /// [`Self::hexagons`] as hex strings, in the same order
+    pub fn hexagon_strings(&self) -> Result<Vec<String>> {
+        self.map_hexagons(|cell| cell.to_string())
+    }
+
+    /// [`Self::hexagons`] as raw `u64` indexes, in the same order, as the H3
+    /// C library and other bindings take them; read them back with
+    /// [`util::from_h3_index_slice`]
+    #[doc(alias = "to_h3_index_vec")]
+    pub fn hexagon_u64s(&self) -> Result<Vec<u64>> {
+        self.map_hexagons(u64::from)
+    }
+
+    /// The centroid of `cell`, see [`util::hexagon_centroid`]
+    pub fn hexagon_centroid(&self, cell: CellIndex) -> Result<LatLng> {
+        util::hexagon_centroid(cell)
+    }
+
+    /// The centroid of each cell of [`Self::hexagons`], in the same order
+    pub fn hexagon_centroids(&self) -> Result<Vec<LatLng>> {
+        self.map_hexagons(LatLng::from)
+    }
+
+    // Compute the coverage once and map each cell
+    fn map_hexagons<T>(&self, f: impl FnMut(CellIndex) -> T) -> Result<Vec<T>> {
+        Ok(self.hexagons_cached()?.iter().copied().map(f).collect())
+    }
+
+    /// Every cell within the grid distance of [`Self::hexagons`] from the
+    /// center cell: the ring and everything inside it.
+    ///
+    /// With an inner radius, cells lying entirely within the inner circle
+    /// (every vertex within the inner radius) are left out. Cells straddling
+    /// the inner circle stay in the annulus, so it is never understated.
+    /// [`Self::hexagons`], the outer ring, always lies in the annulus.
+    ///
+    /// With a bearing range, only the center cell and the cells whose centroid
+    /// lies within the sector are kept.
+    ///
+    /// For a bounding box, see [`SmallestEnclosingH3Builder::from_bbox`], this
+    /// is every cell covering the box, and for a large spherical cap, see
+    /// [`Self::is_large_cap`], or a circle across pentagon distortion, every
+    /// cell that may overlap the circle.
+    pub fn disk(&self) -> Result<Vec<CellIndex>> {
+        if let Some(disk) = self.disk.get() {
+            return Ok(disk.clone());
+        }
+        let k = self.cached_ring()?.0;
+        let mut disk = self.compute_disk(k)?;
+        disk.extend_from_slice(self.cached_padding(k)?);
+        Ok(self.disk.get_or_init(|| disk).clone())
+    }
+
+    /// The rings of [`Self::disk`] from the center outward, one grid distance
+    /// at a time up to [`Self::k_value`], each computed only when reached:
+    /// the center cell, then the cells 1 step away, and so on. Padding rings
+    /// aren't included.
+    ///
+    /// Each ring is trimmed like the disk, to the bearing range, past the
+    /// inner radius and to
+    /// [`SmallestEnclosingH3Builder::min_coverage_fraction`]. A bounding
+    /// box, a large spherical cap, a circle across pentagon distortion and
+    /// [`Algorithm::Polyfill`] aren't covered ring by ring, and yield a
+    /// [`SmallestEnclosingH3Error::UnsupportedGeometry`]. The iterator stops
+    /// after an error.
+    pub fn iter_rings(&self) -> RingIterator<'_> {
+        RingIterator {
+            smallest_enclosing_h3: self,
+            next: 0,
+            k: None,
+        }
+    }
+
+    // The cells of the disk at grid distance `distance` from the center cell,
+    // trimmed like `compute_disk`
+    fn disk_ring(&self, distance: u32) -> Result<Vec<CellIndex>> {
+        let center_cell = self.center.to_cell(self.resolution);
+        let mut ring = grid_ring(center_cell, distance);
+        if let Some(fraction) = self.min_coverage_fraction {
+            self.retain_min_coverage(&mut ring, fraction)?;
+        }
+        if self.inner_radius_meters > 0.0 {
+            ring.retain(|cell| !self.within_inner_circle(*cell));
+        }
+        if self.bearing_range.is_some() {
+            ring.retain(|cell| *cell == center_cell || self.within_sector(*cell));
+        }
+        Ok(ring)
+    }
+
+    /// The pentagons among the cells of [`Self::disk`], at most one unless
+    /// the coverage spans an icosahedron face.
+    ///
+    /// The grid is distorted around a pentagon, so the ring of
+    /// [`Self::hexagons`] has fewer cells than `6k`; the cells are still
+    /// complete, as they are traversed pentagon-safely there. Where the grid
+    /// distance to the circle's edge can't be computed at all, the circle is
+    /// flood-filled instead (listed as an
+    /// [`Adjustment::ApproximateRingDistance`]).
+    pub fn pentagons(&self) -> Result<Vec<CellIndex>> {
+        let mut pentagons = self.disk()?;
+        pentagons.retain(|cell| cell.is_pentagon());
+        Ok(pentagons)
+    }
+
+    /// The cells added by [`SmallestEnclosingH3Builder::padding_rings`], in
+    /// index order; empty without padding
+    pub fn padding_cells(&self) -> Result<&[CellIndex]> {
+        self.cached_padding(self.cached_ring()?.0)
+    }
+
+    /// [`Self::disk`] trimmed to the circle: cells whose centroid is farther
+    /// from the center than the radius are dropped. With `strict`, cells with
+    /// every vertex beyond the radius are dropped too, even when the circle
+    /// is small enough to hold their centroid.
+    ///
+    /// Without `strict` the circle may be slightly under-covered at its rim;
+    /// [`Self::disk`] itself never is. A bounding box has no circle to shrink
+    /// to, and is a [`SmallestEnclosingH3Error::UnsupportedGeometry`].
+    pub fn shrink_to_circle(&self, strict: bool) -> Result<Vec<CellIndex>> {
+        if self.rectangle.is_some() {
+            return Err(SmallestEnclosingH3Error::UnsupportedGeometry(
+                "a bounding box has no circle to shrink to".to_string(),
+            ));
+        }
+        let within = |point: LatLng| self.distance_from_center(point) <= self.radius_meters;
+
+        let mut cells = self.disk()?;
+        cells.retain(|cell| {
+            let centroid_within = within(LatLng::from(*cell));
+            let boundary_outside = strict && cell.boundary().iter().all(|vertex| !within(*vertex));
+            centroid_within && !boundary_outside
+        });
+        Ok(cells)
+    }
+
+    // `padding_cells`, computed once from the unpadded disk at grid distance k
+    fn cached_padding(&self, k: u32) -> Result<&[CellIndex]> {
+        if self.padding_rings == 0 {
+            return Ok(&[]);
+        }
+        if let Some(padding) = self.padding.get() {
+            return Ok(padding);
+        }
+        let padding = pad_cells(&self.compute_disk(k)?, self.padding_rings);
+        Ok(self.padding.get_or_init(|| padding))
+    }
+
+    // The unpadded disk, for the ring at grid distance k
+    fn compute_disk(&self, k: u32) -> Result<Vec<CellIndex>> {
+        if let Some(rectangle) = self.rectangle {
+            return self.rectangle_cells(rectangle, CoverageMode::Disk);
+        }
+        let center_cell = self.center.to_cell(self.resolution);
+        let mut disk = self.covering_cells(k)?;
+        if self.inner_radius_meters > 0.0 {
+            disk.retain(|cell| !self.within_inner_circle(*cell));
+        }
+        if self.bearing_range.is_some() {
+            disk.retain(|cell| *cell == center_cell || self.within_sector(*cell));
+        }
+        Ok(disk)
+    }
+
+    // The cells covering the whole circle, for the ring at grid distance k,
+    // trimmed to `min_coverage_fraction`
+    fn covering_cells(&self, k: u32) -> Result<Vec<CellIndex>> {
+        let mut cells = if let Algorithm::Polyfill {
+            containment,
+            vertices,
+        } = self.algorithm
+        {
+            self.polyfill_cells(containment, vertices, CoverageMode::Disk)?
+        } else if self.is_flood_filled()? {
+            self.spherical_cap_cells(CoverageMode::Disk)?
+        } else {
+            grid_disk(self.center.to_cell(self.resolution), k)
+        };
+        if let Some(fraction) = self.min_coverage_fraction {
+            self.retain_min_coverage(&mut cells, fraction)?;
+        }
+        Ok(cells)
+    }
+
+    /// The cells of a shortest grid path from `from` to `to`, both included,
+    /// at the configured resolution.
+    ///
+    /// Paths can't be computed across pentagon distortion, which is a
+    /// [`SmallestEnclosingH3Error::GridDistanceError`].
+    ///
+    /// ```
+    /// use h3o::{LatLng, Resolution};
+    /// use smallest_enclosing_h3::SmallestEnclosingH3Builder;
+    ///
+    /// let downtown = LatLng::new(33.4484, -112.0740).unwrap();
+    /// let airport = LatLng::new(33.4352, -112.0101).unwrap();
+    /// let smallest_enclosing_h3 = SmallestEnclosingH3Builder::new(downtown, 500.0, Resolution::Nine)
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// let path = smallest_enclosing_h3.grid_path(downtown, airport).unwrap();
+    /// assert_eq!(path.first(), Some(&downtown.to_cell(Resolution::Nine)));
+    /// assert_eq!(path.last(), Some(&airport.to_cell(Resolution::Nine)));
+    /// ```
+    pub fn grid_path(&self, from: LatLng, to: LatLng) -> Result<Vec<CellIndex>> {
+        let (from, to) = (from.to_cell(self.resolution), to.to_cell(self.resolution));
+        Ok(from
+            .grid_path_cells(to)?
+            .collect::<std::result::Result<_, _>>()?)
+    }
+
+    /// The neighbors of `cell` that are also covered, for adjacency within the
+    /// coverage area (flood fill, shortest paths).
+    ///
+    /// The covered area is [`Self::disk`]: [`Self::hexagons`] is only its
+    /// outermost ring.
+    pub fn neighbors(&self, cell: CellIndex) -> Result<Vec<CellIndex>> {
+        let disk: HashSet<CellIndex> = self.disk()?.into_iter().collect();
+        Ok(cell
+            .grid_disk::<Vec<_>>(1)
+            .into_iter()
+            .filter(|neighbor| *neighbor != cell && disk.contains(neighbor))
+            .collect())
+    }
+
+    /// Whether `cell` is at the coverage's resolution, as grid distances
+    /// require. A `CellIndex` is always a well-formed index; see
+    /// [`Self::intersects_cell`] for membership in the coverage.
+    pub fn is_at_resolution(&self, cell: CellIndex) -> bool {
+        cell.resolution() == self.resolution
+    }
+
+    /// Grid distance from the center cell to `cell`.
+    ///
+    /// A cell at another resolution is a
+    /// [`SmallestEnclosingH3Error::InvalidCellIndex`], see
+    /// [`Self::is_at_resolution`], leaving
+    /// [`SmallestEnclosingH3Error::GridDistanceError`] to distances H3 can't
+    /// compute (pentagon distortion, cells too far apart).
+    pub fn grid_distance_from_center(&self, cell: CellIndex) -> Result<u32> {
+        if !self.is_at_resolution(cell) {
+            return Err(h3o::error::InvalidCellIndex {
+                value: Some(u64::from(cell)),
+                reason: "not at the coverage's resolution",
+            }
+            .into());
+        }
+        let center_cell = self.center.to_cell(self.resolution);
+        Ok(center_cell.grid_distance(cell)? as u32)
+    }
+
+    /// Whether `cell`, from any resolution, overlaps [`Self::disk`] in the H3
+    /// hierarchy.
+    ///
+    /// A cell at the coverage's resolution must be in the disk. A finer cell
+    /// overlaps when its ancestor at the coverage's resolution is in the disk,
+    /// and a coarser one when it is the ancestor of any disk cell, which is
+    /// checked without enumerating its descendants. H3 children don't tile
+    /// their parent exactly, so across resolutions this follows the index
+    /// hierarchy rather than the geometry.
+    pub fn intersects_cell(&self, cell: CellIndex) -> Result<bool> {
+        let disk = self.disk()?;
+        Ok(match cell.resolution().cmp(&self.resolution) {
+            std::cmp::Ordering::Equal => disk.contains(&cell),
+            std::cmp::Ordering::Greater => cell
+                .parent(self.resolution)
+                .is_some_and(|parent| disk.contains(&parent)),
+            std::cmp::Ordering::Less => disk
+                .iter()
+                .any(|covered| covered.parent(cell.resolution()) == Some(cell)),
+        })
+    }
+
+    // Distance from the center to `point`, in meters, see `MathMode`
+    pub(crate) fn distance_from_center(&self, point: LatLng) -> f64 {
+        match self.math_mode {
+            MathMode::Spherical => self.sphere_meters(haversine_distance(self.center, point)),
+            MathMode::PlanarLocal => {
+                let (east, north) = self.local_plane(point);
+                east.hypot(north)
+            }
+        }
+    }
+
+    // Meters on the configured sphere as the same angle on the Earth, whose
+    // radius the geodesy functions and H3 cell sizes assume
+    fn to_earth_meters(&self, meters: f64) -> f64 {
+        meters * (geodesy::EARTH_RADIUS / self.sphere_radius_meters)
+    }
+
+    // Meters on the Earth as the same angle on the configured sphere, the inverse
+    // of `to_earth_meters`
+    fn sphere_meters(&self, meters: f64) -> f64 {
+        meters * (self.sphere_radius_meters / geodesy::EARTH_RADIUS)
+    }
+
+    // Bearing from the center to `point`, in degrees clockwise from north in
+    // [0, 360), see `MathMode`
+    fn bearing_from_center(&self, point: LatLng) -> f64 {
+        match self.math_mode {
+            MathMode::Spherical => bearing_degrees(self.center, point),
+            MathMode::PlanarLocal => {
+                let (east, north) = self.local_plane(point);
+                east.atan2(north).to_degrees().rem_euclid(360.0)
+            }
+        }
+    }
+
+    // The `x: lng, y: lat` point `distance` meters from the center along
+    // `bearing_radians`, see `MathMode`
+    fn point_from_center(&self, distance: f64, bearing_radians: f64) -> Point<f64> {
+        let center = (self.center.lat(), self.center.lng());
+        let distance = self.to_earth_meters(distance);
+        match self.math_mode {
+            MathMode::Spherical => {
+                destination(&point!(x: center.1, y: center.0), distance, bearing_radians)
+            }
+            MathMode::PlanarLocal => {
+                let offset = (
+                    distance * bearing_radians.sin(),
+                    distance * bearing_radians.cos(),
+                );
+                let (lat, lng) = geodesy::from_local_plane(center, offset);
+                point!(x: lng, y: lat)
+            }
+        }
+    }
+
+    fn local_plane(&self, point: LatLng) -> (f64, f64) {
+        let (east, north) = geodesy::local_plane(
+            (self.center.lat(), self.center.lng()),
+            (point.lat(), point.lng()),
+        );
+        (self.sphere_meters(east), self.sphere_meters(north))
+    }
+
+    // Whether the bearing to `cell`'s centroid falls within the bearing range
+    fn within_sector(&self, cell: CellIndex) -> bool {
+        self.bearing_range.is_none_or(|(start, end)| {
+            let bearing = self.bearing_from_center(LatLng::from(cell));
+            (bearing - start).rem_euclid(360.0) <= (end - start).rem_euclid(360.0)
+        })
+    }
+
+    // Whether all of `cell` lies within the inner circle
+    fn within_inner_circle(&self, cell: CellIndex) -> bool {
+        cell.boundary()
+            .iter()
+            .all(|vertex| self.distance_from_center(*vertex) <= self.inner_radius_meters)
+    }
+
+    // The grid distance k from the center cell to the circle's edge, along with
+    // the ring of cells at that distance within the bearing range. For a
+    // bounding box, a large spherical cap or a polyfilled circle, the outline
+    // of its covering cells and the estimated k.
+    pub(crate) fn enclosing_ring(&self) -> Result<(u32, Vec<CellIndex>)> {
+        self.cached_ring().cloned()
+    }
+
+    // `enclosing_ring`, computed once. Errors aren't cached, so a failed
+    // computation is retried on the next call.
+    fn cached_ring(&self) -> Result<&(u32, Vec<CellIndex>)> {
+        if let Some(ring) = self.ring.get() {
+            return Ok(ring);
+        }
+        let (k, mut ring) = self.compute_enclosing_ring()?;
+        if self.min_coverage_fraction.is_some() {
+            // The trimmed rim is no longer a ring at grid distance k
+            ring = self.sector_cells(outer_cells(self.covering_cells(k)?));
+        }
+        ring.extend_from_slice(self.cached_padding(k)?);
+        Ok(self.ring.get_or_init(|| (k, ring)))
+    }
+
+    /// The grid distance k from the center cell to the ring of
+    /// [`Self::hexagons`], the coverage's radius in H3 grid units.
+    ///
+    /// Unless the cells are already computed, this only takes the grid
+    /// distance to the circle's edge, without generating the ring. Where that
+    /// distance can't be computed (pentagon distortion), or the ring may be
+    /// expanded by [`SmallestEnclosingH3Builder::ensure_full_coverage`], it
+    /// falls back to the ring. For a bounding box, a large spherical cap or
+    /// [`Algorithm::Polyfill`], whose cells aren't a ring, it is the estimate
+    /// from the local cell size.
+    pub fn k_value(&self) -> Result<u32> {
+        if let Some((k, _)) = self.ring.get() {
+            return Ok(*k);
+        }
+        let center_cell = self.center.to_cell(self.resolution);
+        if self.rectangle.is_some() || self.is_large_cap() || self.algorithm != Algorithm::KRing {
+            return Ok(self.estimate_k(center_cell));
+        }
+        if self.ensure_full_coverage {
+            return Ok(self.cached_ring()?.0);
+        }
+        let mut k = 0;
+        for edge_cell in self.edge_cells()? {
+            match center_cell.grid_distance(edge_cell) {
+                Ok(distance) => k = k.max(distance as u32),
+                Err(_) => return Ok(self.cached_ring()?.0),
+            }
+        }
+        Ok(k)
+    }
+
+    // The cells of the circle's edge due north, east, south and west. Cells
+    // stretch differently in each direction, notably across the antimeridian.
+    fn edge_cells(&self) -> Result<Vec<CellIndex>> {
+        [0.0, 90.0, 180.0, 270.0_f64]
+            .into_iter()
+            .map(|bearing| {
+                let edge = self.point_from_center(self.radius_meters, bearing.to_radians());
+                Ok(LatLng::new(edge.y(), edge.x())?.to_cell(self.resolution))
+            })
+            .collect()
+    }
+
+    fn compute_enclosing_ring(&self) -> Result<(u32, Vec<CellIndex>)> {
+        let center_cell = self.center.to_cell(self.resolution);
+        if let Some(rectangle) = self.rectangle {
+            let outline = self.rectangle_cells(rectangle, CoverageMode::Ring)?;
+            return Ok((self.estimate_k(center_cell), outline));
+        }
+        if let Algorithm::Polyfill {
+            containment,
+            vertices,
+        } = self.algorithm
+        {
+            let outline = self.polyfill_cells(containment, vertices, CoverageMode::Ring)?;
+            return Ok((self.estimate_k(center_cell), outline));
+        }
+        if self.is_flood_filled()? {
+            let estimate = self.estimate_k(center_cell);
+            if !self.is_large_cap() {
+                self.policy
+                    .allow(Adjustment::ApproximateRingDistance { estimate })?;
+            }
+            let outline = self.spherical_cap_cells(CoverageMode::Ring)?;
+            return Ok((estimate, self.sector_cells(outline)));
+        }
+        let edge_cells = self.edge_cells()?;
+
+        // Estimate k from the local cell size and keep the ring if every edge
+        // cell is on it, which is the common case.
+        let estimate = self.estimate_k(center_cell);
+        self.check_cell_count(estimate)?;
+        let ring = grid_ring(center_cell, estimate);
+        if edge_cells.iter().all(|cell| ring.contains(cell)) {
+            return self.expand_to_cover(center_cell, estimate, ring);
+        }
+
+        // Otherwise take the largest exact grid distance to the edge, so the
+        // ring is never understated
+        let mut k = 0;
+        for edge_cell in edge_cells {
+            k = k.max(center_cell.grid_distance(edge_cell)? as u32);
+        }
+        self.check_cell_count(k)?;
+        self.expand_to_cover(center_cell, k, grid_ring(center_cell, k))
+    }
+
+    // `ring` at grid distance k within the bearing range, or with
+    // `ensure_full_coverage` the ring at the first grid distance whose disk
+    // holds every boundary sample
+    fn expand_to_cover(
+        &self,
+        center_cell: CellIndex,
+        k: u32,
+        ring: Vec<CellIndex>,
+    ) -> Result<(u32, Vec<CellIndex>)> {
+        if !self.ensure_full_coverage {
+            return Ok((k, self.sector_cells(ring)));
+        }
+        // The bearing range is left out: sector cells are kept by their
+        // centroid, which a wider ring doesn't change
+        let mut uncovered: Vec<CellIndex> = self
+            .boundary_points()?
+            .into_iter()
+            .map(|point| point.to_cell(self.resolution))
+            .collect();
+        let mut expanded = k;
+        loop {
+            let disk: HashSet<CellIndex> = grid_disk(center_cell, expanded).into_iter().collect();
+            uncovered.retain(|cell| !disk.contains(cell));
+            if uncovered.is_empty() {
+                break;
+            }
+            expanded += 1;
+            self.check_cell_count(expanded)?;
+        }
+        if expanded == k {
+            return Ok((k, self.sector_cells(ring)));
+        }
+        self.policy.allow(Adjustment::ExpandRingDistance {
+            from: k,
+            to: expanded,
+        })?;
+        Ok((
+            expanded,
+            self.sector_cells(grid_ring(center_cell, expanded)),
+        ))
+    }
+
+    // Points on the circle's edge within the bearing range, spaced a quarter
+    // of the average cell edge apart and at least one degree of bearing
+    fn boundary_points(&self) -> Result<Vec<LatLng>> {
+        let (start, sweep) = match self.bearing_range {
+            Some((start, end)) => (start, (end - start).rem_euclid(360.0)),
+            None => (0.0, 360.0),
+        };
+        let spacing = resolution_info(self.resolution).avg_edge_length_km * 1000.0 / 4.0;
+        let length = self.to_earth_meters(self.radius_meters) * sweep.to_radians();
+        let n = ((length / spacing).ceil() as usize)
+            .max(sweep.ceil() as usize)
+            .max(1);
+
+        (0..=n)
+            .map(|i| {
+                let bearing = start + sweep * i as f64 / n as f64;
+                let point = self.point_from_center(self.radius_meters, bearing.to_radians());
+                Ok(LatLng::new(point.y(), point.x())?)
+            })
+            .collect()
+    }
+
+    // Whether the circle is covered by flood fill, see `spherical_cap_cells`:
+    // a large cap, or a circle whose grid distance to the edge can't be
+    // computed across pentagon distortion, where a ring would be misshapen
+    pub(crate) fn is_flood_filled(&self) -> Result<bool> {
+        if self.is_large_cap() {
+            return Ok(true);
+        }
+        let center_cell = self.center.to_cell(self.resolution);
+        Ok(self
+            .edge_cells()?
+            .into_iter()
+            .any(|edge_cell| center_cell.grid_distance(edge_cell).is_err()))
+    }
+
+    // Fail before allocating when the disk at grid distance `k`, padding
+    // included, may exceed `max_cells`. Away from pentagons the count is
+    // exact for a full circle, and an upper bound otherwise.
+    fn check_cell_count(&self, k: u32) -> Result<()> {
+        let k = u64::from(k) + u64::from(self.padding_rings);
+        let estimated = k.saturating_mul(k + 1).saturating_mul(3).saturating_add(1);
+        let estimated = usize::try_from(estimated).unwrap_or(usize::MAX);
+        if estimated > self.max_cells {
+            return Err(SmallestEnclosingH3Error::TooManyCells {
+                estimated,
+                limit: self.max_cells,
+            });
+        }
+        Ok(())
+    }
+
+    fn sector_cells(&self, mut cells: Vec<CellIndex>) -> Vec<CellIndex> {
+        if self.bearing_range.is_some() {
+            cells.retain(|cell| self.within_sector(*cell));
+        }
+        cells
+    }
+
+    // Grid distance from the center cell to the circle's edge
+    fn estimate_k(&self, center_cell: CellIndex) -> u32 {
+        estimate_ring_distance(center_cell, self.to_earth_meters(self.radius_meters))
+    }
+
+    /// `[min_lng, min_lat, max_lng, max_lat]` over the boundaries of every
+    /// covering cell, which extend past the circle. A coverage across the
+    /// antimeridian follows the GeoJSON convention of `min_lng > max_lng`.
+    pub fn bbox(&self) -> Result<[f64; 4]> {
+        self.hexagon_coverage()?.bbox()
+    }
+
+    /// Every cell of [`Self::disk`] with the distance in meters from the
+    /// center to its centroid, nearest first, computed as every other
+    /// distance from the center is (see [`MathMode`] and
+    /// [`SmallestEnclosingH3Builder::sphere_radius_meters`]). Cells at the
+    /// same distance are ordered by index.
+    pub fn hexagons_with_distance(&self) -> Result<Vec<(CellIndex, f64)>> {
+        let mut cells: Vec<(CellIndex, f64)> = self
+            .disk()?
+            .into_iter()
+            .map(|cell| (cell, self.distance_from_center(LatLng::from(cell))))
+            .collect();
+        cells.sort_by(|a, b| a.1.total_cmp(&b.1).then(a.0.cmp(&b.0)));
+        Ok(cells)
+    }
+
+    /// Every cell of [`Self::hexagons`] with its grid distance from the
+    /// center cell, nearest first and by index within a ring, to shade cells
+    /// by ring or weight them by it. Padding cells lie past k.
+    ///
+    /// Distances spanning pentagon distortion can't be computed, and are a
+    /// [`SmallestEnclosingH3Error::GridDistanceError`]. See
+    /// [`Self::hexagons_with_distance`] for distances in meters.
+    pub fn hexagons_with_ring_distance(&self) -> Result<Vec<(CellIndex, u32)>> {
+        self.with_ring_distance(self.hexagons()?)
+    }
+
+    /// [`Self::hexagons_with_ring_distance`] for every cell of [`Self::disk`]
+    pub fn disk_with_ring_distance(&self) -> Result<Vec<(CellIndex, u32)>> {
+        self.with_ring_distance(self.disk()?)
+    }
+
+    fn with_ring_distance(&self, cells: Vec<CellIndex>) -> Result<Vec<(CellIndex, u32)>> {
+        let center_cell = self.center.to_cell(self.resolution);
+        let mut cells = cells
+            .into_iter()
+            .map(|cell| Ok((cell, center_cell.grid_distance(cell)? as u32)))
+            .collect::<Result<Vec<_>>>()?;
+        cells.sort_unstable_by_key(|&(cell, distance)| (distance, cell));
+        Ok(cells)
+    }
+
+    /// [`Self::hexagons`] in a deterministic [`CellOrder`]. Use
+    /// [`CellOrder::Index`] for a canonical order in tests, caches and binary
+    /// protocols.
+    pub fn hexagons_sorted(&self, order: CellOrder) -> Result<Vec<CellIndex>> {
+        let (k, mut cells) = self.enclosing_ring()?;
+        self.sort_cells(&mut cells, order, k);
+        Ok(cells)
+    }
+
+    // Sort cells around the center. `fallback_ring` stands in for the grid
+    // distance of cells it can't be computed for (pentagon distortion).
+    pub(crate) fn sort_cells(&self, cells: &mut [CellIndex], order: CellOrder, fallback_ring: u32) {
+        match order {
+            CellOrder::Index => cells.sort_unstable(),
+            CellOrder::RingClock => {
+                let center_cell = self.center.to_cell(self.resolution);
+                let mut keyed: Vec<(u32, f64, CellIndex)> = cells
+                    .iter()
+                    .map(|cell| {
+                        let ring = center_cell
+                            .grid_distance(*cell)
+                            .map_or(fallback_ring, |d| d as u32);
+                        // The center cell has no bearing of its own
+                        let bearing = if *cell == center_cell {
+                            0.0
+                        } else {
+                            bearing_degrees(self.center, LatLng::from(*cell))
+                        };
+                        (ring, bearing, *cell)
+                    })
+                    .collect();
+                keyed.sort_by(|a, b| a.0.cmp(&b.0).then(a.1.total_cmp(&b.1)).then(a.2.cmp(&b.2)));
+                for (cell, (_, _, sorted)) in cells.iter_mut().zip(keyed) {
+                    *cell = sorted;
+                }
+            }
+            CellOrder::Hilbert => {
+                // Centroids on the local plane, scaled to the curve's grid
+                let points: Vec<(f64, f64)> = cells
+                    .iter()
+                    .map(|cell| self.local_plane(LatLng::from(*cell)))
+                    .collect();
+                let (min_x, max_x, min_y, max_y) = points.iter().fold(
+                    (f64::MAX, f64::MIN, f64::MAX, f64::MIN),
+                    |(min_x, max_x, min_y, max_y), &(x, y)| {
+                        (min_x.min(x), max_x.max(x), min_y.min(y), max_y.max(y))
+                    },
+                );
+                let extent = (max_x - min_x).max(max_y - min_y).max(f64::MIN_POSITIVE);
+                let scale = f64::from(HILBERT_SIDE - 1) / extent;
+
+                let mut keyed: Vec<(u64, CellIndex)> = points
+                    .iter()
+                    .zip(cells.iter())
+                    .map(|(&(x, y), cell)| {
+                        let x = ((x - min_x) * scale).round() as u32;
+                        let y = ((y - min_y) * scale).round() as u32;
+                        (hilbert_distance(x, y), *cell)
+                    })
+                    .collect();
+                keyed.sort_unstable();
+                for (cell, (_, sorted)) in cells.iter_mut().zip(keyed) {
+                    *cell = sorted;
+                }
+            }
+        }
+    }
+
+    /// [`Self::hexagons`] in [`CellOrder::Hilbert`] order
+    pub fn hexagons_hilbert_ordered(&self) -> Result<Vec<CellIndex>> {
+        self.hexagons_sorted(CellOrder::Hilbert)
+    }
+
+    /// Keep at most `n` cells of [`Self::disk`], preferring those whose
+    /// centroid is closest to the center. Ties are broken by cell index so
+    /// the result is deterministic.
+    pub fn truncate_closest(&self, n: usize) -> Result<TruncatedCoverage> {
+        let mut cells: Vec<(f64, CellIndex)> = self
+            .disk()?
+            .into_iter()
+            .map(|cell| (self.distance_from_center(LatLng::from(cell)), cell))
+            .collect();
+        cells.sort_by(|a, b| a.0.total_cmp(&b.0).then_with(|| a.1.cmp(&b.1)));
+
+        let truncated = cells.len() > n;
+        cells.truncate(n);
+        let cells: Vec<CellIndex> = cells.into_iter().map(|(_, cell)| cell).collect();
+
+        let circle = self.circle_polygon(CIRCLE_VERTICES)?;
+        let circle_area = circle.unsigned_area();
+        // Folded from 0.0, as the `sum` of no cells is -0.0
+        let covered_area = cells
+            .iter()
+            .map(|cell| cell_polygon(*cell).intersection(&circle).unsigned_area())
+            .fold(0.0, |total, area| total + area);
+
+        Ok(TruncatedCoverage {
+            cells,
+            truncated,
+            circle_area_fraction: (covered_area / circle_area).min(1.0),
+        })
+    }
+
+    /// Sample `n` evenly spaced points on the circle's boundary, starting due
+    /// north, with the cell each falls in and whether that cell is covered.
+    /// Useful to see where and why the boundary escapes the coverage.
+    pub fn boundary_samples(&self, n: usize) -> Result<Vec<BoundarySample>> {
+        let disk: HashSet<CellIndex> = self.disk()?.into_iter().collect();
+        let center_cell = self.center.to_cell(self.resolution);
+
+        (0..n)
+            .map(|i| {
+                let bearing = i as f64 * 360.0 / n as f64;
+                let point = self.point_from_center(self.radius_meters, bearing.to_radians());
+                let lat_lng = LatLng::new(point.y(), point.x())?;
+                let cell = lat_lng.to_cell(self.resolution);
+
+                Ok(BoundarySample {
+                    bearing,
+                    lat_lng,
+                    cell,
+                    covered: disk.contains(&cell),
+                    grid_distance: center_cell.grid_distance(cell).ok().map(|d| d as u32),
+                })
+            })
+            .collect()
+    }
+
+    /// The points sampled on the circle's boundary, within any bearing range,
+    /// that fall outside every cell of [`Self::disk`]; empty when the circle
+    /// is fully covered.
+    ///
+    /// The points are spaced a quarter of the average cell edge apart, so a
+    /// cell the boundary crosses is all but certain to be sampled. See
+    /// [`SmallestEnclosingH3Builder::ensure_full_coverage`] to expand the
+    /// coverage until none are left. A bounding box has no circle to verify,
+    /// and is a [`SmallestEnclosingH3Error::UnsupportedGeometry`].
+    pub fn verify_coverage(&self) -> Result<Vec<LatLng>> {
+        if self.rectangle.is_some() {
+            return Err(SmallestEnclosingH3Error::UnsupportedGeometry(
+                "a bounding box has no circle to verify".to_string(),
+            ));
+        }
+        let disk: HashSet<CellIndex> = self.disk()?.into_iter().collect();
+        let mut points = self.boundary_points()?;
+        points.retain(|point| !disk.contains(&point.to_cell(self.resolution)));
+        Ok(points)
+    }
+
+    /// Whether [`Self::verify_coverage`] finds the whole boundary covered
+    pub fn is_fully_covered(&self) -> Result<bool> {
+        Ok(self.verify_coverage()?.is_empty())
+    }
+
+    /// Find the finest single cell, at any resolution, whose boundary contains
+    /// the whole circle.
+    ///
+    /// Only the cell holding the center can contain the circle, but because H3
+    /// children don't nest exactly inside their parents that cell isn't always
+    /// the ancestor of the finer ones, so each resolution is tested
+    /// geometrically.
+    pub fn enclosing_cell(&self) -> Result<(CellIndex, Resolution)> {
+        let circle = self.circle_polygon(CIRCLE_VERTICES)?;
+
+        Resolution::range(Resolution::Zero, Resolution::Fifteen)
+            .rev()
+            .map(|resolution| self.center.to_cell(resolution))
+            .find(|cell| cell_polygon(*cell).contains(&circle))
+            .map(|cell| (cell, cell.resolution()))
+            .ok_or_else(|| {
+                SmallestEnclosingH3Error::NoEnclosingCell(format!(
+                    "no cell contains a circle of radius {} m",
+                    self.radius_meters
+                ))
+            })
+    }
+
+    /// Fraction (0.0–1.0) of `cell`'s area that lies inside the circle
+    pub fn coverage_overlap(&self, cell: CellIndex) -> Result<f64> {
+        let polygon = cell_polygon(cell);
+        let overlap = polygon
+            .intersection(&self.circle_polygon(CIRCLE_VERTICES)?)
+            .unsigned_area();
+        Ok((overlap / polygon.unsigned_area()).min(1.0))
+    }
+
+    /// Fraction (0.0–1.0) of `polygon`'s area, in lng/lat degrees, covered by
+    /// the cells of [`Self::disk`]: `0.0` when they don't meet, `1.0` when the
+    /// polygon lies entirely within the cells.
+    ///
+    /// The cells don't overlap, so their union's intersection with `polygon`
+    /// is summed cell by cell, skipping cells outside its bounding box.
+    /// Areas are planar in degrees, like [`Self::coverage_overlap`]. A
+    /// polygon without area is a [`SmallestEnclosingH3Error::UnsupportedGeometry`].
+    pub fn overlap_with_polygon(&self, polygon: &Polygon<f64>) -> Result<f64> {
+        let area = polygon.unsigned_area();
+        let Some(bounds) = polygon.bounding_rect().filter(|_| area > 0.0) else {
+            return Err(SmallestEnclosingH3Error::UnsupportedGeometry(
+                "the polygon has no area".to_string(),
+            ));
+        };
+
+        let overlap: f64 = self
+            .disk()?
+            .into_iter()
+            .map(cell_polygon)
+            .filter(|cell| cell.intersects(&bounds))
+            .map(|cell| cell.intersection(polygon).unsigned_area())
+            .sum();
+        Ok((overlap / area).min(1.0))
+    }
+
+    /// The boundary of every cell of [`Self::hexagons`] as a closed,
+    /// counterclockwise polygon in lng/lat degrees, ready for geo algorithms.
+    /// See also the `MultiPolygon` conversion.
+    pub fn hexagon_polygons(&self) -> Result<Vec<Polygon<f64>>> {
+        Ok(self.hexagon_coverage()?.polygons())
+    }
+
+    /// [`Self::hexagon_polygons`] as one MultiPolygon, one polygon per cell
+    /// in the order of [`Self::hexagons`]. Far cheaper than
+    /// [`Self::to_geo_polygon`], but shared edges are kept.
+    pub fn to_geo_multipolygon(&self) -> Result<MultiPolygon<f64>> {
+        Ok(self.hexagon_coverage()?.to_geo_multipolygon())
+    }
+
+    /// The union of [`Self::hexagons`] as a single counterclockwise polygon
+    /// in lng/lat degrees, for area, containment or simplification with geo
+    /// algorithms. A ring around the center has the inner disk as its hole.
+    ///
+    /// Cells that don't join into one piece are a
+    /// [`SmallestEnclosingH3Error::UnsupportedGeometry`]; use
+    /// [`Self::to_geo_multipolygon`] for those.
+    pub fn to_geo_polygon(&self) -> Result<Polygon<f64>> {
+        self.hexagon_coverage()?.to_geo_polygon()
+    }
+
+    /// The circle as a closed polygon with `num_points` vertices, in lng/lat
+    /// degrees, ready for geo algorithms
+    pub fn circle_polygon(&self, num_points: usize) -> Result<Polygon<f64>> {
+        let coordinates = self.generate_circle_coordinates_with(num_points)?;
+        Ok(Polygon::new(
+            LineString::from(
+                coordinates
+                    .into_iter()
+                    .map(|c| (c[0], c[1]))
+                    .collect::<Vec<_>>(),
+            ),
+            vec![],
+        ))
+    }
+
+    pub fn generate_circle_coordinates(&self) -> Result<Vec<Vec<f64>>> {
+        self.generate_circle_coordinates_with(CIRCLE_VERTICES)
+    }
+
+    /// `[lng, lat]` pairs for `num_points` (at least 3) evenly spaced points on
+    /// the circle, followed by the first point again to close the ring.
+    ///
+    /// With a bearing range this is the pie slice instead: the center, then
+    /// `num_points` points along the arc from the start to the end bearing,
+    /// and the center again. For a bounding box it is the box's closed ring of
+    /// corners, whatever `num_points`.
+    ///
+    /// A large spherical cap, see [`Self::is_large_cap`], gets at least one
+    /// vertex per degree of bearing, and its points run counterclockwise
+    /// (decreasing bearing) so the cap is on the left of the ring, as RFC 7946
+    /// requires, however the ring projects onto longitude and latitude.
+    pub fn generate_circle_coordinates_with(&self, num_points: usize) -> Result<Vec<Vec<f64>>> {
+        if let Some(rectangle) = self.rectangle {
+            return Ok(rectangle.ring());
+        }
+        match self.bearing_range {
+            Some((start, end)) => self.sector_coordinates(start, end, num_points),
+            None if self.is_large_cap() => {
+                let num_points = num_points.max(spherical_cap::LARGE_CAP_VERTICES);
+                let mut coordinates = self.circle_coordinates(self.radius_meters, num_points)?;
+                coordinates.reverse();
+                Ok(coordinates)
+            }
+            None => self.circle_coordinates(self.radius_meters, num_points),
+        }
+    }
+
+    fn sector_coordinates(&self, start: f64, end: f64, num_points: usize) -> Result<Vec<Vec<f64>>> {
+        if num_points < 3 {
+            return Err(SmallestEnclosingH3Error::UnsupportedGeometry(format!(
+                "a sector needs at least 3 arc vertices, got {num_points}"
+            )));
+        }
+
+        let center_point = point!(x: self.center.lng(), y: self.center.lat());
+        let sweep = (end - start).rem_euclid(360.0);
+        let mut coordinates = Vec::with_capacity(num_points + 2);
+        coordinates.push(vec![center_point.x(), center_point.y()]);
+
+        for i in 0..num_points {
+            let bearing = start + i as f64 * sweep / (num_points - 1) as f64;
+            let point = self.point_from_center(self.radius_meters, bearing.to_radians());
+            coordinates.push(vec![point.x(), point.y()]);
+        }
+
+        coordinates.push(vec![center_point.x(), center_point.y()]);
+        Ok(coordinates)
+    }
+
+    // Closed ring of `num_points` points `radius_meters` from the center
+    pub(crate) fn circle_coordinates(
+        &self,
+        radius_meters: f64,
+        num_points: usize,
+    ) -> Result<Vec<Vec<f64>>> {
+        if num_points < 3 {
+            return Err(SmallestEnclosingH3Error::UnsupportedGeometry(format!(
+                "a circle needs at least 3 vertices, got {num_points}"
+            )));
+        }
+
+        let mut coordinates = Vec::with_capacity(num_points + 1);
+
+        for i in 0..num_points {
+            let bearing = (i as f64 * 360.0 / num_points as f64).to_radians();
+            let point = self.point_from_center(radius_meters, bearing);
+            coordinates.push(vec![point.x(), point.y()]);
+        }
+
+        // Close the polygon by repeating the first point
+        if let Some(first) = coordinates.first().cloned() {
+            coordinates.push(first);
+        }
+
+        Ok(coordinates)
+    }
+
+    /// The point `distance` meters from `start` along `bearing_radians`, in
+    /// radians clockwise from north; see [`geodesy::destination_point`]
+    #[deprecated(note = "use the free function `geodesy::destination_point`")]
+    pub fn destination_point(
+        &self,
+        start: &Point<f64>,
+        distance: f64,
+        bearing_radians: f64,
+    ) -> Result<Point<f64>> {
+        Ok(destination(start, distance, bearing_radians))
+    }
+}
+
+/// Iterator over [`SmallestEnclosingH3::hexagons`], created by iterating
+/// over `&SmallestEnclosingH3`.
+///
+/// The cells are generated on the first call to `next`. If generation fails
+/// the iterator yields nothing and [`HexIterator::error`] holds the error.
+#[cfg(feature = "h3")]
+#[derive(Debug)]
+pub struct HexIterator<'a> {
+    smallest_enclosing_h3: &'a SmallestEnclosingH3,
+    cells: Option<std::vec::IntoIter<CellIndex>>,
+    error: Option<SmallestEnclosingH3Error>,
+}
+
+#[cfg(feature = "h3")]
+impl HexIterator<'_> {
+    /// The error hit while generating the cells, if any
+    pub fn error(&self) -> Option<&SmallestEnclosingH3Error> {
+        self.error.as_ref()
+    }
+}
+
+#[cfg(feature = "h3")]
+impl Iterator for HexIterator<'_> {
+    type Item = CellIndex;
+
+    fn next(&mut self) -> Option<CellIndex> {
+        if self.cells.is_none() {
+            let cells = self
+                .smallest_enclosing_h3
+                .hexagons()
+                .unwrap_or_else(|error| {
+                    self.error = Some(error);
+                    Vec::new()
+                });
+            self.cells = Some(cells.into_iter());
+        }
+        self.cells.as_mut()?.next()
+    }
+}
+
+/// Iterator over the rings of [`SmallestEnclosingH3::disk`], created by
+/// [`SmallestEnclosingH3::iter_rings`].
+///
+/// The grid distance to stop at is computed on the first call to `next`,
+/// and each ring when it is reached.
+#[cfg(feature = "h3")]
+#[derive(Debug)]
+pub struct RingIterator<'a> {
+    smallest_enclosing_h3: &'a SmallestEnclosingH3,
+    next: u32,
+    // The last grid distance to yield, `None` until computed
+    k: Option<u32>,
+}
+
+#[cfg(feature = "h3")]
+impl RingIterator<'_> {
+    fn k(&self) -> Result<u32> {
+        let coverage = self.smallest_enclosing_h3;
+        if coverage.rectangle.is_some()
+            || coverage.algorithm != Algorithm::KRing
+            || coverage.is_flood_filled()?
+        {
+            return Err(SmallestEnclosingH3Error::UnsupportedGeometry(
+                "the coverage isn't made of rings around the center".to_string(),
+            ));
+        }
+        coverage.k_value()
+    }
+}
+
+#[cfg(feature = "h3")]
+impl Iterator for RingIterator<'_> {
+    type Item = Result<Vec<CellIndex>>;
+
+    fn next(&mut self) -> Option<Result<Vec<CellIndex>>> {
+        let k = match self.k {
+            Some(k) => k,
+            None => match self.k() {
+                Ok(k) => *self.k.insert(k),
+                Err(error) => {
+                    // Stop after the error
+                    self.k = Some(0);
+                    self.next = 1;
+                    return Some(Err(error));
+                }
+            },
+        };
+        if self.next > k {
+            return None;
+        }
+        let ring = self.smallest_enclosing_h3.disk_ring(self.next);
+        self.next = if ring.is_ok() { self.next + 1 } else { k + 1 };
+        Some(ring)
+    }
+}
+
+#[cfg(feature = "h3")]
+impl std::iter::FusedIterator for RingIterator<'_> {}
+
+/// Every cell of [`SmallestEnclosingH3::hexagons`] as a polygon of one
+/// MultiPolygon, see [`SmallestEnclosingH3::hexagon_polygons`]
+#[cfg(feature = "h3")]
+impl TryFrom<&SmallestEnclosingH3> for MultiPolygon<f64> {
+    type Error = SmallestEnclosingH3Error;
+
+    fn try_from(smallest_enclosing_h3: &SmallestEnclosingH3) -> Result<Self> {
+        smallest_enclosing_h3.to_geo_multipolygon()
+    }
+}
+
+#[cfg(feature = "h3")]
+impl<'a> IntoIterator for &'a SmallestEnclosingH3 {
+    type Item = CellIndex;
+    type IntoIter = HexIterator<'a>;
+
+    fn into_iter(self) -> HexIterator<'a> {
+        HexIterator {
+            smallest_enclosing_h3: self,
+            cells: None,
+            error: None,
+        }
+    }
+}
+
+// `grid_ring_fast` produces distorted rings when a pentagon lies within the
+// ring, so near pentagons the ring is taken from the slower pentagon-safe
+// traversal instead.
+#[cfg(feature = "h3")]
+pub(crate) fn grid_ring(center_cell: CellIndex, k: u32) -> Vec<CellIndex> {
+    let size = expected_cell_count(k, CoverageMode::Ring, false) as usize;
+    if !near_pentagon(center_cell, k) {
+        if let Some(ring) = collect_fast(center_cell.grid_ring_fast(k).take(size), size) {
+            debug_assert_eq!(ring.len(), size);
+            return ring;
+        }
+    }
+
+    let mut ring = Vec::with_capacity(size);
+    ring.extend(
+        center_cell
+            .grid_disk_distances_safe(k)
+            .filter_map(|(cell, distance)| (distance == k).then_some(cell)),
+    );
+    debug_assert!(ring.len() as u64 >= expected_cell_count(k, CoverageMode::Ring, true));
+    debug_assert!(ring.len() <= size);
+    ring
+}
+
+// Like `CellIndex::grid_disk`, allocating the exact number of cells up front
+#[cfg(feature = "h3")]
+pub(crate) fn grid_disk(center_cell: CellIndex, k: u32) -> Vec<CellIndex> {
+    let size = expected_cell_count(k, CoverageMode::Disk, false) as usize;
+    if let Some(disk) = collect_fast(center_cell.grid_disk_fast(k), size) {
+        debug_assert_eq!(disk.len(), size);
+        return disk;
+    }
+
+    let mut disk = Vec::with_capacity(size);
+    disk.extend(center_cell.grid_disk_safe(k));
+    debug_assert!(disk.len() as u64 >= expected_cell_count(k, CoverageMode::Disk, true));
+    debug_assert!(disk.len() <= size);
+    disk
+}
+
+// Collect a `*_fast` traversal, or `None` once it runs into a pentagon
+#[cfg(feature = "h3")]
+fn collect_fast(
+    cells: impl Iterator<Item = Option<CellIndex>>,
+    capacity: usize,
+) -> Option<Vec<CellIndex>> {
+    let mut collected = Vec::with_capacity(capacity);
+    for cell in cells {
+        collected.push(cell?);
+    }
+    Some(collected)
+}
+
+// Whether a pentagon may lie within `k` rings of `cell`. Cells shrink towards
+// pentagons, so twice the average spacing keeps this conservative.
+#[cfg(feature = "h3")]
+fn near_pentagon(cell: CellIndex, k: u32) -> bool {
+    let resolution = cell.resolution();
+    let spacing = resolution_info(resolution).avg_cell_spacing_km * 1000.0;
+    let reach = 2.0 * f64::from(k + 1) * spacing;
+    resolution
+        .pentagons()
+        .any(|pentagon| haversine_distance(LatLng::from(cell), LatLng::from(pentagon)) <= reach)
+}
+
+// `[west, south, east, north]` of lng/lat points. Longitudes are bounded by
+// the shortest arc holding them all, so a box across the antimeridian has
+// west > east as in GeoJSON (RFC 7946 section 5.2).
+#[cfg(feature = "h3")]
+pub(crate) fn lng_lat_bbox(points: impl IntoIterator<Item = (f64, f64)>) -> Option<[f64; 4]> {
+    let (mut south, mut north) = (f64::INFINITY, f64::NEG_INFINITY);
+    let mut lngs = Vec::new();
+    for (lng, lat) in points {
+        south = south.min(lat);
+        north = north.max(lat);
+        lngs.push(geodesy::normalize_lng(lng));
+    }
+    lngs.sort_by(f64::total_cmp);
+    let (&first, &last) = (lngs.first()?, lngs.last()?);
+
+    // The widest gap between neighbouring longitudes is the part of the globe
+    // outside the box
+    let widest = lngs
+        .windows(2)
+        .enumerate()
+        .map(|(i, w)| (w[1] - w[0], i))
+        .max_by(|a, b| a.0.total_cmp(&b.0));
+    match widest {
+        Some((gap, i)) if gap > first + 360.0 - last => Some([lngs[i + 1], south, lngs[i], north]),
+        _ => Some([first, south, last, north]),
+    }
+}
+
+// Initial bearing from `a` to `b`, in degrees clockwise from north in [0, 360)
+#[cfg(feature = "h3")]
+pub(crate) fn bearing_degrees(a: LatLng, b: LatLng) -> f64 {
+    geodesy::initial_bearing((a.lat(), a.lng()), (b.lat(), b.lng()))
+}
+
+// Cells per side of the grid `CellOrder::Hilbert` snaps centroids to
+#[cfg(feature = "h3")]
+const HILBERT_SIDE: u32 = 1 << 16;
+
+// Position of `(x, y)` along the Hilbert curve filling the
+// `HILBERT_SIDE` × `HILBERT_SIDE` grid
+#[cfg(feature = "h3")]
+fn hilbert_distance(mut x: u32, mut y: u32) -> u64 {
+    let mut distance = 0u64;
+    let mut side = HILBERT_SIDE / 2;
+    while side > 0 {
+        let rx = u32::from(x & side > 0);
+        let ry = u32::from(y & side > 0);
+        distance += u64::from(side) * u64::from(side) * u64::from((3 * rx) ^ ry);
+        // Rotate the quadrant so the curve stays continuous
+        if ry == 0 {
+            if rx == 1 {
+                x = HILBERT_SIDE - 1 - x;
+                y = HILBERT_SIDE - 1 - y;
+            }
+            std::mem::swap(&mut x, &mut y);
+        }
+        side /= 2;
+    }
+    distance
+}
+
+// The cells within grid distance `rings` of `cells` but not in them, in index
+// order. Each ring is found from the previous one's neighbours.
+#[cfg(feature = "h3")]
+fn pad_cells(cells: &[CellIndex], rings: u32) -> Vec<CellIndex> {
+    let mut covered: HashSet<CellIndex> = cells.iter().copied().collect();
+    let mut frontier = cells.to_vec();
+    let mut padding = Vec::new();
+    for _ in 0..rings {
+        let next: Vec<CellIndex> = frontier
+            .iter()
+            .flat_map(|cell| cell.grid_disk::<Vec<_>>(1))
+            .filter(|neighbor| covered.insert(*neighbor))
+            .collect();
+        padding.extend_from_slice(&next);
+        frontier = next;
+    }
+    padding.sort_unstable();
+    padding
+}
+
+// `geodesy::destination_point` on an `x: lng, y: lat` point
+#[cfg(feature = "h3")]
+fn destination(start: &Point<f64>, distance: f64, bearing_radians: f64) -> Point<f64> {
+    let (lat, lng) = geodesy::destination_point((start.y(), start.x()), distance, bearing_radians);
+    point!(x: lng, y: lat)
+}
+
+// Grid distance from `center_cell` to `distance_meters` away, derived from the
+// spacing between neighbouring cell centers around the center cell
+#[cfg(feature = "h3")]
+pub(crate) fn estimate_ring_distance(center_cell: CellIndex, distance_meters: f64) -> u32 {
+    // Edge length of a regular hexagon with the center cell's area
+    let edge_length = (2.0 * center_cell.area_m2() / (3.0 * 3f64.sqrt())).sqrt();
+    let spacing = 3f64.sqrt() * edge_length;
+    (distance_meters / spacing).round() as u32
+}
+
+// Great-circle distance in meters
+#[cfg(feature = "h3")]
+pub(crate) fn haversine_distance(a: LatLng, b: LatLng) -> f64 {
+    geodesy::haversine_distance((a.lat(), a.lng()), (b.lat(), b.lng()))
+}
+
+// `name` as a quoted SQL identifier, each dot-separated part in double
+// quotes with embedded quotes doubled
+#[cfg(any(feature = "postgis", feature = "duckdb"))]
+pub(crate) fn quote_identifier(name: &str) -> String {
+    name.split('.')
+        .map(|part| format!("\"{}\"", part.replace('"', "\"\"")))
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+// The cells of `cells` with a neighbour outside them
+#[cfg(feature = "h3")]
+pub(crate) fn outer_cells(mut cells: Vec<CellIndex>) -> Vec<CellIndex> {
+    let cover: HashSet<CellIndex> = cells.iter().copied().collect();
+    cells.retain(|cell| {
+        cell.grid_disk::<Vec<_>>(1)
+            .iter()
+            .any(|neighbor| !cover.contains(neighbor))
+    });
+    cells
+}
+
+#[cfg(feature = "h3")]
+pub(crate) fn cell_polygon(cell: CellIndex) -> Polygon<f64> {
+    let mut ring: Vec<(f64, f64)> = cell.boundary().iter().map(|p| (p.lng(), p.lat())).collect();
+    if let Some(first) = ring.first().cloned() {
+        ring.push(first);
+    }
+    Polygon::new(LineString::from(ring), vec![])
+}
+
+#[cfg(all(test, feature = "h3"))]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_builder_with_valid_inputs() {
+        let center = LatLng::new(0.0, 0.0).unwrap();
+        let result = SmallestEnclosingH3Builder::new(center, 1000.0, Resolution::Nine).build();
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_builder_with_invalid_radius() {
+        let center = LatLng::new(0.0, 0.0).unwrap();
+        let result = SmallestEnclosingH3Builder::new(center, -1.0, Resolution::Nine).build();
+        assert!(matches!(
+            result,
+            Err(SmallestEnclosingH3Error::InvalidRadius(_))
+        ));
+    }
+
+    #[test]
+    fn test_validate() {
+        let center = LatLng::new(33.4484, -112.0740).unwrap();
+        let builder = SmallestEnclosingH3Builder::new(center, 500.0, Resolution::Ten);
+        builder.validate().unwrap();
+        assert!(builder.build().is_ok());
+
+        // Same errors as build, and the builder is still there to build
+        let builder = SmallestEnclosingH3Builder::new(center, 500.0, Resolution::Ten)
+            .inner_radius_meters(600.0)
+            .unwrap();
+        assert!(matches!(
+            builder.validate(),
+            Err(SmallestEnclosingH3Error::InvalidRadius(_))
+        ));
+        assert!(matches!(
+            builder.build(),
+            Err(SmallestEnclosingH3Error::InvalidRadius(_))
+        ));
+
+        let strict = SmallestEnclosingH3Builder::new(
+            LatLng::new(10.0, 190.0).unwrap(),
+            1000.0,
+            Resolution::Four,
+        )
+        .strict(true);
+        assert!(matches!(
+            strict.validate(),
+            Err(SmallestEnclosingH3Error::StrictModeViolation(
+                Adjustment::NormalizeLongitude { .. }
+            ))
+        ));
+        assert!(strict.strict(false).validate().is_ok());
+    }
+
+    #[test]
+    fn test_builder_with_nan_radius() {
+        // NaN used to pass every comparison and fail later as an invalid edge
+        let center = LatLng::new(0.0, 0.0).unwrap();
+        assert!(matches!(
+            SmallestEnclosingH3Builder::new(center, f64::NAN, Resolution::Nine).build(),
+            Err(SmallestEnclosingH3Error::InvalidRadius(_))
+        ));
+        assert!(matches!(
+            SmallestEnclosingH3Builder::new(center, 1.0, Resolution::Nine).radius_meters(f64::NAN),
+            Err(SmallestEnclosingH3Error::InvalidRadius(_))
+        ));
+        assert!(matches!(
+            SmallestEnclosingH3Builder::new(center, 1.0, Resolution::Nine)
+                .radius(f64::NAN, DistanceUnit::Miles),
+            Err(SmallestEnclosingH3Error::InvalidRadius(_))
+        ));
+    }
+
+    #[test]
+    fn test_degenerate_inputs_error_without_panicking() {
+        let coordinates = [
+            0.0,
+            -0.0,
+            1e-300,
+            89.999999,
+            90.0,
+            -90.0,
+            180.0,
+            -180.0,
+            181.0,
+            f64::MAX,
+            f64::NAN,
+            f64::INFINITY,
+        ];
+        let radii = [f64::NAN, f64::INFINITY, -1.0, 0.0, 1e-300, 1.0, 50.0];
+        for lat in coordinates {
+            for lng in coordinates {
+                let Ok(center) = LatLng::new(lat, lng) else {
+                    continue;
+                };
+                for radius_meters in radii {
+                    for resolution in [Resolution::Zero, Resolution::Nine, Resolution::Fifteen] {
+                        let Ok(smallest_enclosing_h3) =
+                            SmallestEnclosingH3Builder::new(center, radius_meters, resolution)
+                                .build()
+                        else {
+                            continue;
+                        };
+                        let _ = smallest_enclosing_h3.hexagons();
+                        let _ = smallest_enclosing_h3.disk();
+                        let _ = smallest_enclosing_h3.generate_circle_coordinates();
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_builder_from_h3_cell() {
+        let cell = LatLng::new(33.4484, -112.0740)
+            .unwrap()
+            .to_cell(Resolution::Seven);
+
+        let smallest_enclosing_h3 = SmallestEnclosingH3Builder::from_h3_cell(cell, Resolution::Ten)
+            .unwrap()
+            .build()
+            .unwrap();
+        assert_eq!(smallest_enclosing_h3.center, LatLng::from(cell));
+        assert_eq!(
+            smallest_enclosing_h3.radius_meters(),
+            Resolution::Seven.edge_length_km() * 1000.0 / 2.0
+        );
+
+        // The circle sits well inside the cell, and so does the ring around it
+        let boundary = cell_polygon(cell);
+        for hexagon in smallest_enclosing_h3.hexagons().unwrap() {
+            assert_eq!(hexagon.resolution(), Resolution::Ten);
+            let centroid = LatLng::from(hexagon);
+            assert!(boundary.contains(&point!(x: centroid.lng(), y: centroid.lat())));
+        }
+
+        for resolution in [Resolution::Seven, Resolution::Three] {
+            assert!(matches!(
+                SmallestEnclosingH3Builder::from_h3_cell(cell, resolution),
+                Err(SmallestEnclosingH3Error::InvalidResolution(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_validate_center() {
+        assert!(validate_center(90.0, -180.0).is_ok());
+        for (lat, lng) in [
+            (f64::NAN, 0.0),
+            (0.0, f64::NAN),
+            (f64::INFINITY, 0.0),
+            (0.0, f64::NEG_INFINITY),
+            (90.5, 0.0),
+            (0.0, 180.5),
+        ] {
+            assert!(matches!(
+                validate_center(lat, lng),
+                Err(SmallestEnclosingH3Error::InvalidLatLng(_))
+            ));
+        }
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_anti_meridian_crossing() {
+        // Heading north over the pole from 100°E ends up at 280°, i.e. 80°W
+        let center = LatLng::new(89.9, 100.0).unwrap();
+        let smallest_enclosing_h3 =
+            SmallestEnclosingH3Builder::new(center, 50_000.0, Resolution::Five)
+                .build()
+                .unwrap();
+        let edge = smallest_enclosing_h3
+            .destination_point(&point!(x: 100.0, y: 89.9), 50_000.0, 0.0)
+            .unwrap();
+        assert!((edge.x() + 80.0).abs() < 1e-6);
+        assert!(smallest_enclosing_h3.hexagons().is_ok());
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_circle_across_anti_meridian() {
+        let center = LatLng::new(0.0, 179.9).unwrap();
+        let smallest_enclosing_h3 =
+            SmallestEnclosingH3Builder::new(center, 100_000.0, Resolution::Six)
+                .build()
+                .unwrap();
+
+        // Due east lands past 180°, wrapped to the western hemisphere
+        let east = smallest_enclosing_h3
+            .destination_point(&point!(x: 179.9, y: 0.0), 100_000.0, 90f64.to_radians())
+            .unwrap();
+        assert!((-180.0..-179.0).contains(&east.x()));
+
+        // The ring reaches at least as far as the edge in every direction
+        let (k, _) = smallest_enclosing_h3.enclosing_ring().unwrap();
+        let center_cell = center.to_cell(Resolution::Six);
+        for sample in smallest_enclosing_h3.boundary_samples(4).unwrap() {
+            assert!(sample.grid_distance.unwrap() <= k);
+        }
+        let disk = smallest_enclosing_h3.disk().unwrap();
+        assert!(disk.contains(
+            &LatLng::new(east.y(), east.x())
+                .unwrap()
+                .to_cell(Resolution::Six)
+        ));
+        assert!(disk.contains(&center_cell));
+    }
+
+    #[test]
+    fn test_radius_units() {
+        let center = LatLng::new(33.4484, -112.0740).unwrap();
+        let builder = || SmallestEnclosingH3Builder::new(center, 1.0, Resolution::Nine);
+
+        let meters = builder().radius_meters(1000.0).unwrap().build().unwrap();
+        let kilometers = builder().radius_kilometers(1.0).unwrap().build().unwrap();
+        assert_eq!(kilometers.radius_meters(), 1000.0);
+        assert_eq!(kilometers.hexagons().unwrap(), meters.hexagons().unwrap());
+
+        let miles = builder().radius_miles(1.0).unwrap().build().unwrap();
+        assert_eq!(miles.radius_meters(), 1609.344);
+        let feet = builder().radius(1000.0, DistanceUnit::Feet).unwrap();
+        assert_eq!(feet.build().unwrap().radius_meters(), 304.8);
+
+        for radius in [0.0, -1.0] {
+            assert!(builder().radius_meters(radius).is_err());
+            assert!(builder().radius_kilometers(radius).is_err());
+            assert!(builder().radius_miles(radius).is_err());
+            assert!(builder().radius_feet(radius).is_err());
+            assert!(builder().radius_nautical_miles(radius).is_err());
+        }
+    }
+
+    #[test]
+    fn test_strict_mode() {
+        let builder = |lat, lng, radius| {
+            SmallestEnclosingH3Builder::new(
+                LatLng::new(lat, lng).unwrap(),
+                radius,
+                Resolution::Four,
+            )
+        };
+
+        // Longitude outside [-180, 180]
+        let lenient = builder(10.0, 190.0, 1000.0).build().unwrap();
+        assert!((lenient.center.lng() + 170.0).abs() < 1e-9);
+        assert!(matches!(
+            builder(10.0, 190.0, 1000.0).strict(true).build(),
+            Err(SmallestEnclosingH3Error::StrictModeViolation(
+                Adjustment::NormalizeLongitude { .. }
+            ))
+        ));
+
+        // Latitude beyond the north pole
+        let lenient = builder(95.0, 10.0, 1000.0).build().unwrap();
+        assert!((lenient.center.lat() - 85.0).abs() < 1e-9);
+        assert!((lenient.center.lng() + 170.0).abs() < 1e-9);
+        assert!(matches!(
+            builder(95.0, 10.0, 1000.0).strict(true).build(),
+            Err(SmallestEnclosingH3Error::StrictModeViolation(
+                Adjustment::NormalizeLatitude { .. }
+            ))
+        ));
+
+        // Ring distance that can only be estimated across pentagon distortion
+        let lenient = builder(-67.0, -168.0, 500_000.0).build().unwrap();
+        assert!(lenient.hexagons().is_ok());
+        let strict = builder(-67.0, -168.0, 500_000.0)
+            .strict(true)
+            .build()
+            .unwrap();
+        assert!(matches!(
+            strict.hexagons(),
+            Err(SmallestEnclosingH3Error::StrictModeViolation(
+                Adjustment::ApproximateRingDistance { .. }
+            ))
+        ));
+
+        // In-range inputs are untouched either way
+        let center = LatLng::new(33.4484, -112.0740).unwrap();
+        let strict = SmallestEnclosingH3Builder::new(center, 50.0, Resolution::Twelve)
+            .strict(true)
+            .build()
+            .unwrap();
+        assert_eq!(strict.center, center);
+        assert!(strict.hexagons().is_ok());
+    }
+
+    #[test]
+    fn test_enclosing_cell() {
+        let center = LatLng::new(33.0, -112.0).unwrap();
+        let enclosing = SmallestEnclosingH3Builder::new(center, 30.0, Resolution::Nine)
+            .build()
+            .unwrap();
+        let circle = enclosing.circle_polygon(CIRCLE_VERTICES).unwrap();
+
+        let (cell, resolution) = enclosing.enclosing_cell().unwrap();
+        assert_eq!(cell.resolution(), resolution);
+        assert!(cell_polygon(cell).contains(&circle));
+
+        // The circle straddles the edges of every finer cell holding the center
+        for finer in Resolution::range(resolution, Resolution::Fifteen).skip(1) {
+            assert!(!cell_polygon(center.to_cell(finer)).contains(&circle));
+        }
+
+        // ...and the enclosing cell isn't the ancestor of the finest one
+        let ancestor = center.to_cell(Resolution::Fifteen).parent(resolution);
+        assert_ne!(ancestor, Some(cell));
+
+        let too_large = SmallestEnclosingH3Builder::new(center, 5_000_000.0, Resolution::Nine)
+            .build()
+            .unwrap();
+        assert!(matches!(
+            too_large.enclosing_cell(),
+            Err(SmallestEnclosingH3Error::NoEnclosingCell(_))
+        ));
+    }
+
+    #[test]
+    fn test_circle_vertex_count() {
+        let center = LatLng::new(33.4484, -112.0740).unwrap();
+        let smallest_enclosing_h3 =
+            SmallestEnclosingH3Builder::new(center, 50.0, Resolution::Twelve)
+                .build()
+                .unwrap();
+
+        // Exactly one closing duplicate
+        let coordinates = smallest_enclosing_h3.generate_circle_coordinates().unwrap();
+        assert_eq!(coordinates.len(), 65);
+        assert_eq!(coordinates.first(), coordinates.last());
+        assert_ne!(coordinates[0], coordinates[63]);
+
+        for num_points in [3, 7, 64, 360] {
+            let coordinates = smallest_enclosing_h3
+                .generate_circle_coordinates_with(num_points)
+                .unwrap();
+            assert_eq!(coordinates.len(), num_points + 1);
+            assert_eq!(coordinates.first(), coordinates.last());
+            let first = &coordinates[0];
+            assert_eq!(coordinates.iter().filter(|c| *c == first).count(), 2);
+        }
+
+        let polygon = smallest_enclosing_h3.circle_polygon(256).unwrap();
+        assert_eq!(polygon.exterior().0.len(), 257);
+        assert!(polygon.exterior().is_closed());
+
+        for num_points in [0, 2] {
+            assert!(matches!(
+                smallest_enclosing_h3.circle_polygon(num_points),
+                Err(SmallestEnclosingH3Error::UnsupportedGeometry(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_hexagon_polygons() {
+        use geo::GeodesicArea;
+
+        let center = LatLng::new(33.4484, -112.0740).unwrap();
+        let smallest_enclosing_h3 = SmallestEnclosingH3Builder::new(center, 500.0, Resolution::Ten)
+            .build()
+            .unwrap();
+        let hexagons = smallest_enclosing_h3.hexagons().unwrap();
+
+        let polygons = smallest_enclosing_h3.hexagon_polygons().unwrap();
+        assert_eq!(polygons.len(), hexagons.len());
+        for (polygon, cell) in polygons.iter().zip(&hexagons) {
+            assert!(polygon.exterior().is_closed());
+            assert!(polygon.signed_area() > 0.0);
+            // lng/lat, like GeoJSON
+            let centroid = LatLng::from(*cell);
+            assert!(polygon.contains(&point!(x: centroid.lng(), y: centroid.lat())));
+        }
+
+        let multi_polygon = MultiPolygon::try_from(&smallest_enclosing_h3).unwrap();
+        assert_eq!(multi_polygon.0, polygons);
+        // Cells here are larger than the global average at the resolution,
+        // so compare against h3o's own areas of these cells
+        let expected: f64 = hexagons.iter().map(|cell| cell.area_m2()).sum();
+        let area = multi_polygon.geodesic_area_unsigned();
+        assert!((area / expected - 1.0).abs() < 0.01, "{area} {expected}");
+        assert_eq!(
+            smallest_enclosing_h3.to_geo_multipolygon().unwrap(),
+            multi_polygon
+        );
+    }
+
+    #[test]
+    fn test_to_geo_polygon() {
+        use geo::GeodesicArea;
+
+        let center = LatLng::new(33.4484, -112.0740).unwrap();
+        let smallest_enclosing_h3 = SmallestEnclosingH3Builder::new(center, 500.0, Resolution::Ten)
+            .build()
+            .unwrap();
+        let hexagons = smallest_enclosing_h3.hexagons().unwrap();
+
+        // The ring dissolves into one polygon with the inner disk as its hole
+        let polygon = smallest_enclosing_h3.to_geo_polygon().unwrap();
+        assert_eq!(polygon.interiors().len(), 1);
+        assert!(polygon.signed_area() > 0.0);
+        let expected: f64 = hexagons.iter().map(|cell| cell.area_m2()).sum();
+        let area = polygon.geodesic_area_unsigned();
+        assert!((area / expected - 1.0).abs() < 0.01, "{area} {expected}");
+        let centroid = LatLng::from(center.to_cell(Resolution::Ten));
+        assert!(!polygon.contains(&point!(x: centroid.lng(), y: centroid.lat())));
+    }
+
+    #[test]
+    fn test_pentagons() {
+        let pentagon = Resolution::Nine.pentagons().next().unwrap();
+        let near =
+            SmallestEnclosingH3Builder::new(LatLng::from(pentagon), 2000.0, Resolution::Nine)
+                .build()
+                .unwrap();
+        assert_eq!(near.pentagons().unwrap(), vec![pentagon]);
+
+        // The ring around it is complete, if short of 6k cells
+        let k = near.k_value().unwrap();
+        let ring = near.hexagons().unwrap();
+        let expected: HashSet<CellIndex> = pentagon
+            .grid_disk_distances_safe(k)
+            .filter_map(|(cell, distance)| (distance == k).then_some(cell))
+            .collect();
+        assert_eq!(ring.iter().copied().collect::<HashSet<_>>(), expected);
+        assert!(ring.len() < 6 * k as usize);
+
+        let center = LatLng::new(33.4484, -112.0740).unwrap();
+        let away = SmallestEnclosingH3Builder::new(center, 2000.0, Resolution::Nine)
+            .build()
+            .unwrap();
+        assert!(away.pentagons().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_overlap_with_polygon() {
+        use geo::{polygon, Rect};
+
+        let center = LatLng::new(33.4484, -112.0740).unwrap();
+        let smallest_enclosing_h3 =
+            SmallestEnclosingH3Builder::new(center, 1000.0, Resolution::Nine)
+                .build()
+                .unwrap();
+        let disk = smallest_enclosing_h3.disk().unwrap();
+        let cells_area: f64 = disk
+            .iter()
+            .map(|cell| cell_polygon(*cell).unsigned_area())
+            .sum();
+        let rect = |min: (f64, f64), max: (f64, f64)| Rect::new(min, max).to_polygon();
+
+        // Within the center cell, and far away
+        let (lat, lng) = (center.lat(), center.lng());
+        let inside = rect((lng - 1e-4, lat - 1e-4), (lng + 1e-4, lat + 1e-4));
+        assert!((smallest_enclosing_h3.overlap_with_polygon(&inside).unwrap() - 1.0).abs() < 1e-9);
+        let tokyo = rect((139.6, 35.6), (139.8, 35.8));
+        assert_eq!(
+            smallest_enclosing_h3.overlap_with_polygon(&tokyo).unwrap(),
+            0.0
+        );
+
+        // Around every cell, the cells' share of the polygon
+        let [min_lng, min_lat, max_lng, max_lat] = cell_coverage::CellCoverage::from_cells(disk)
+            .bbox()
+            .unwrap();
+        let around = rect(
+            (min_lng - 0.01, min_lat - 0.01),
+            (max_lng + 0.01, max_lat + 0.01),
+        );
+        let expected = cells_area / around.unsigned_area();
+        let overlap = smallest_enclosing_h3.overlap_with_polygon(&around).unwrap();
+        assert!((overlap - expected).abs() < 1e-9, "{overlap} {expected}");
+
+        // Half the cells, east of the center
+        let east = rect((lng, min_lat - 0.01), (max_lng + 0.01, max_lat + 0.01));
+        let overlap = smallest_enclosing_h3.overlap_with_polygon(&east).unwrap();
+        assert!(overlap > 0.0 && overlap < 1.0, "{overlap}");
+        assert!(overlap > expected);
+
+        let line = polygon![(x: lng, y: lat), (x: lng + 0.01, y: lat), (x: lng, y: lat)];
+        assert!(matches!(
+            smallest_enclosing_h3.overlap_with_polygon(&line),
+            Err(SmallestEnclosingH3Error::UnsupportedGeometry(_))
+        ));
+    }
+
+    #[test]
+    fn test_boundary_samples() {
+        let center = LatLng::new(33.4484, -112.0740).unwrap();
+        let smallest_enclosing_h3 =
+            SmallestEnclosingH3Builder::new(center, 50.0, Resolution::Twelve)
+                .build()
+                .unwrap();
+        let samples = smallest_enclosing_h3.boundary_samples(36).unwrap();
+        assert_eq!(samples.len(), 36);
+        assert_eq!(samples[9].bearing, 90.0);
+
+        for sample in &samples {
+            let distance = haversine_distance(center, sample.lat_lng);
+            assert!((distance - 50.0).abs() < 0.01);
+            assert!(sample.covered, "{}", sample.bearing);
+        }
+        assert!(smallest_enclosing_h3.verify_coverage().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_scale() {
+        let center = LatLng::new(33.4484, -112.0740).unwrap();
+        let coverage = SmallestEnclosingH3Builder::new(center, 1000.0, Resolution::Nine)
+            .build()
+            .unwrap();
+
+        let same = coverage.scale(1.0).unwrap();
+        assert_eq!(same.hexagons().unwrap(), coverage.hexagons().unwrap());
+
+        let doubled = coverage.scale(2.0).unwrap();
+        assert_eq!(doubled.radius_meters(), 2000.0);
+        assert_eq!(doubled.center, coverage.center);
+        assert_eq!(doubled.resolution, coverage.resolution);
+        let disk: HashSet<CellIndex> = doubled.disk().unwrap().into_iter().collect();
+        let original = coverage.disk().unwrap();
+        assert!(disk.len() > original.len());
+        assert!(original.iter().all(|cell| disk.contains(cell)));
+
+        // An annulus keeps its proportions
+        let annulus = SmallestEnclosingH3Builder::new(center, 1000.0, Resolution::Nine)
+            .inner_radius_meters(400.0)
+            .unwrap()
+            .build()
+            .unwrap();
+        assert_eq!(annulus.scale(0.5).unwrap().inner_radius_meters(), 200.0);
+
+        for factor in [0.0, -1.0, f64::NAN, f64::INFINITY, f64::MAX, 1e5] {
+            assert!(
+                matches!(
+                    coverage.scale(factor),
+                    Err(SmallestEnclosingH3Error::InvalidRadius(_))
+                ),
+                "{factor}"
+            );
+        }
+        let rectangle =
+            SmallestEnclosingH3Builder::from_bbox(33.4, -112.1, 33.5, -112.0, Resolution::Eight)
+                .unwrap()
+                .build()
+                .unwrap();
+        assert!(matches!(
+            rectangle.scale(2.0),
+            Err(SmallestEnclosingH3Error::UnsupportedGeometry(_))
+        ));
+    }
+
+    #[test]
+    fn test_move_center() {
+        let center = LatLng::new(33.4484, -112.0740).unwrap();
+        let coverage = SmallestEnclosingH3Builder::new(center, 1000.0, Resolution::Nine)
+            .build()
+            .unwrap();
+        let hexagons = coverage.hexagons().unwrap();
+
+        // About 2 km east, several cells away
+        let moved_center = LatLng::new(33.4484, -112.0525).unwrap();
+        let moved = coverage.move_center(moved_center);
+        assert_eq!(moved.center, moved_center);
+        assert_eq!(moved.radius_meters(), coverage.radius_meters());
+        assert_eq!(moved.resolution, coverage.resolution);
+
+        let moved_hexagons = moved.hexagons().unwrap();
+        assert_eq!(moved_hexagons.len(), hexagons.len());
+        let original: HashSet<CellIndex> = hexagons.into_iter().collect();
+        let moved_hexagons: HashSet<CellIndex> = moved_hexagons.into_iter().collect();
+        assert!(original
+            .symmetric_difference(&moved_hexagons)
+            .next()
+            .is_some());
+
+        // Moving back gives the original cells
+        let back = moved.move_center(center).hexagons().unwrap();
+        assert_eq!(back.into_iter().collect::<HashSet<_>>(), original);
+
+        // A bounding box moves as its circle
+        let rectangle =
+            SmallestEnclosingH3Builder::from_bbox(33.4, -112.1, 33.5, -112.0, Resolution::Eight)
+                .unwrap()
+                .build()
+                .unwrap();
+        let moved = rectangle.move_center(moved_center);
+        assert!(moved.k_value().unwrap() > 0);
+        assert!(moved.is_fully_covered().unwrap());
+    }
+
+    #[test]
+    fn test_iter_rings() {
+        let center = LatLng::new(33.4484, -112.0740).unwrap();
+        let builder = || SmallestEnclosingH3Builder::new(center, 1000.0, Resolution::Nine);
+        let coverage = builder().padding_rings(1).build().unwrap();
+        let k = coverage.k_value().unwrap();
+
+        let rings: Vec<Vec<CellIndex>> = coverage.iter_rings().collect::<Result<_>>().unwrap();
+        assert_eq!(rings.len(), k as usize + 1);
+        assert_eq!(rings[0], vec![center.to_cell(Resolution::Nine)]);
+        for (distance, ring) in rings.iter().enumerate().skip(1) {
+            assert_eq!(ring.len(), 6 * distance);
+        }
+        // Every cell of the disk, the padding aside
+        let mut cells: Vec<CellIndex> = rings.concat();
+        let mut disk = builder().build().unwrap().disk().unwrap();
+        cells.sort_unstable();
+        disk.sort_unstable();
+        assert_eq!(cells, disk);
+
+        // Callers can stop early
+        let first: Vec<usize> = coverage
+            .iter_rings()
+            .take(2)
+            .map(|ring| ring.unwrap().len())
+            .collect();
+        assert_eq!(first, [1, 6]);
+
+        // Trimmed like the disk
+        let annulus = builder()
+            .inner_radius_meters(500.0)
+            .unwrap()
+            .bearing_range(0.0, 90.0)
+            .unwrap()
+            .build()
+            .unwrap();
+        let mut cells: Vec<CellIndex> = annulus
+            .iter_rings()
+            .collect::<Result<Vec<_>>>()
+            .unwrap()
+            .concat();
+        let mut disk = annulus.disk().unwrap();
+        cells.sort_unstable();
+        disk.sort_unstable();
+        assert_eq!(cells, disk);
+
+        // A bounding box isn't made of rings
+        let rectangle =
+            SmallestEnclosingH3Builder::from_bbox(33.4, -112.1, 33.5, -112.0, Resolution::Eight)
+                .unwrap()
+                .build()
+                .unwrap();
+        let mut rings = rectangle.iter_rings();
+        assert!(matches!(
+            rings.next(),
+            Some(Err(SmallestEnclosingH3Error::UnsupportedGeometry(_)))
+        ));
+        assert!(rings.next().is_none());
+    }
+
+    #[test]
+    fn test_ensure_full_coverage() {
+        // By a vertex of its cell, the circle's edge reaches cells past the
+        // grid distance due north, east, south and west
+        let center = LatLng::new(33.44696195878492, -112.0717139865501).unwrap();
+        let builder = || SmallestEnclosingH3Builder::new(center, 300.0, Resolution::Nine);
+        let plain = builder().build().unwrap();
+        let uncovered = plain.verify_coverage().unwrap();
+        assert!(!uncovered.is_empty());
+        assert!(!plain.is_fully_covered().unwrap());
+        for point in &uncovered {
+            assert!((plain.distance_from_center(*point) - 300.0).abs() < 0.01);
+        }
+
+        let expanded = builder()
+            .strict(true)
+            .ensure_full_coverage(true)
+            .build()
+            .unwrap();
+        assert!(expanded.is_fully_covered().unwrap());
+        let (from, to) = (plain.k_value().unwrap(), expanded.k_value().unwrap());
+        assert!(to > from);
+        assert_eq!(
+            expanded.effective_parameters().unwrap().adjustments,
+            vec![Adjustment::ExpandRingDistance { from, to }]
+        );
+        assert_eq!(expanded.hexagons().unwrap().len(), 6 * to as usize);
+
+        // A circle covered already is left as it is
+        let covered = || {
+            SmallestEnclosingH3Builder::new(
+                LatLng::new(33.4484, -112.0740).unwrap(),
+                500.0,
+                Resolution::Ten,
+            )
+        };
+        let plain = covered().build().unwrap();
+        assert!(plain.is_fully_covered().unwrap());
+        let full = covered().ensure_full_coverage(true).build().unwrap();
+        assert_eq!(full.hexagons().unwrap(), plain.hexagons().unwrap());
+        assert!(full.effective_parameters().unwrap().adjustments.is_empty());
+
+        assert!(matches!(
+            SmallestEnclosingH3Builder::from_bbox(33.4, -112.1, 33.5, -112.0, Resolution::Eight)
+                .unwrap()
+                .build()
+                .unwrap()
+                .verify_coverage(),
+            Err(SmallestEnclosingH3Error::UnsupportedGeometry(_))
+        ));
+    }
+
+    #[test]
+    fn test_hexagon_representations() {
+        let center = LatLng::new(33.4484, -112.0740).unwrap();
+        let smallest_enclosing_h3 =
+            SmallestEnclosingH3Builder::new(center, 200.0, Resolution::Eleven)
+                .build()
+                .unwrap();
+
+        let cells = smallest_enclosing_h3.hexagons().unwrap();
+        let strings = smallest_enclosing_h3.hexagon_strings().unwrap();
+        let u64s = smallest_enclosing_h3.hexagon_u64s().unwrap();
+        let centroids = smallest_enclosing_h3.hexagon_centroids().unwrap();
+        assert_eq!(strings.len(), cells.len());
+        assert_eq!(u64s.len(), cells.len());
+        assert_eq!(centroids.len(), cells.len());
+
+        for (i, cell) in cells.iter().enumerate() {
+            assert_eq!(u64::from_str_radix(&strings[i], 16).unwrap(), u64s[i]);
+            assert_eq!(CellIndex::try_from(u64s[i]).unwrap(), *cell);
+            assert_eq!(centroids[i].to_cell(Resolution::Eleven), *cell);
+            assert_eq!(
+                smallest_enclosing_h3.hexagon_centroid(*cell).unwrap(),
+                centroids[i]
+            );
+        }
+    }
+
+    #[test]
+    fn test_annulus() {
+        let center = LatLng::new(33.4484, -112.0740).unwrap();
+        let builder = || SmallestEnclosingH3Builder::new(center, 1000.0, Resolution::Ten);
+        let full = builder().build().unwrap();
+        let annulus = builder()
+            .inner_radius_meters(500.0)
+            .unwrap()
+            .build()
+            .unwrap();
+        assert_eq!(annulus.inner_radius_meters(), 500.0);
+
+        // The outer ring is unchanged and lies in the annulus
+        assert_eq!(annulus.hexagons().unwrap(), full.hexagons().unwrap());
+
+        // Exactly the cells entirely within the inner circle are left out
+        let disk: HashSet<CellIndex> = annulus.disk().unwrap().into_iter().collect();
+        let full_disk = full.disk().unwrap();
+        let inside = |cell: &CellIndex| {
+            cell.boundary()
+                .iter()
+                .all(|vertex| haversine_distance(center, *vertex) <= 500.0)
+        };
+        assert!(full_disk.iter().any(inside));
+        for cell in &full_disk {
+            assert_eq!(disk.contains(cell), !inside(cell));
+        }
+
+        // Cells straddling the inner circle stay in the annulus
+        let inner_circle = SmallestEnclosingH3Builder::new(center, 500.0, Resolution::Ten)
+            .build()
+            .unwrap();
+        for sample in inner_circle.boundary_samples(16).unwrap() {
+            assert!(disk.contains(&sample.cell));
+        }
+    }
+
+    #[test]
+    fn test_resolution_stats_across_threads() {
+        let threads = 16;
+        let barrier = std::sync::Barrier::new(threads);
+        let tables: Vec<usize> = std::thread::scope(|scope| {
+            let handles: Vec<_> = (0..threads)
+                .map(|_| {
+                    scope.spawn(|| {
+                        barrier.wait();
+                        resolution_stats() as *const _ as usize
+                    })
+                })
+                .collect();
+            handles
+                .into_iter()
+                .map(|handle| handle.join().unwrap())
+                .collect()
+        });
+        assert!(tables.iter().all(|table| *table == tables[0]));
+        for (i, stats) in resolution_stats().iter().enumerate() {
+            assert_eq!(usize::from(u8::from(stats.resolution)), i);
+        }
+    }
+
+    #[test]
+    fn test_resolution_info() {
+        // Average hexagon area (km²) and edge length (km) from the H3 tables
+        for (resolution, area_km2, edge_length_km) in [
+            (Resolution::Zero, 4_357_449.416078381, 1281.256011),
+            (Resolution::Five, 252.903858182, 9.854090990),
+            (Resolution::Nine, 0.105332513, 0.200786148),
+            (Resolution::Fifteen, 0.000000895, 0.000584169),
+        ] {
+            let stats = resolution_info(resolution);
+            assert_eq!(stats.resolution, resolution);
+            assert!(
+                (stats.avg_area_km2 / area_km2 - 1.0).abs() < 1e-3,
+                "{stats:?}"
+            );
+            assert!(
+                (stats.avg_edge_length_km / edge_length_km - 1.0).abs() < 1e-6,
+                "{stats:?}"
+            );
+        }
+
+        // Derived values agree with h3o's primitives
+        for stats in resolution_stats() {
+            let edge_length_m = stats.resolution.edge_length_m();
+            let spacing_m = 3f64.sqrt() * edge_length_m;
+            assert!((stats.avg_edge_length_km * 1000.0 / edge_length_m - 1.0).abs() < 1e-12);
+            assert!((stats.avg_area_km2 * 1e6 / stats.resolution.area_m2() - 1.0).abs() < 1e-12);
+            assert!((stats.avg_cell_spacing_km * 1000.0 / spacing_m - 1.0).abs() < 1e-12);
+        }
+
+        let center = LatLng::new(33.4484, -112.0740).unwrap();
+        let smallest_enclosing_h3 =
+            SmallestEnclosingH3Builder::new(center, 500.0, Resolution::Nine)
+                .build()
+                .unwrap();
+        assert_eq!(
+            smallest_enclosing_h3.resolution_statistics(),
+            resolution_info(Resolution::Nine)
+        );
+    }
+
+    #[test]
+    fn test_hexagons_at() {
+        let center = LatLng::new(33.4484, -112.0740).unwrap();
+        let smallest_enclosing_h3 =
+            SmallestEnclosingH3Builder::new(center, 500.0, Resolution::Nine)
+                .build()
+                .unwrap();
+        let configured = smallest_enclosing_h3.hexagons().unwrap();
+
+        for resolution in [Resolution::Seven, Resolution::Eleven] {
+            let fresh = SmallestEnclosingH3Builder::new(center, 500.0, resolution)
+                .build()
+                .unwrap();
+            assert_eq!(
+                smallest_enclosing_h3.hexagons_at(resolution).unwrap(),
+                fresh.hexagons().unwrap()
+            );
+        }
+
+        assert_eq!(smallest_enclosing_h3.resolution, Resolution::Nine);
+        assert_eq!(smallest_enclosing_h3.hexagons().unwrap(), configured);
+    }
+
+    #[test]
+    fn test_resolution_report() {
+        let center = LatLng::new(33.4484, -112.0740).unwrap();
+        let smallest_enclosing_h3 =
+            SmallestEnclosingH3Builder::new(center, 2000.0, Resolution::Nine)
+                .build()
+                .unwrap();
+        let report = smallest_enclosing_h3
+            .resolution_report(Resolution::Six..=Resolution::Twelve)
+            .unwrap();
+        assert_eq!(report.len(), 7);
+        assert_eq!(report[0].resolution, Resolution::Six);
+        assert_eq!(report[6].resolution, Resolution::Twelve);
+        for pair in report.windows(2) {
+            assert!(pair[0].k <= pair[1].k);
+            assert!(pair[0].cell_count <= pair[1].cell_count);
+        }
+        // Finer cells hug the circle more tightly
+        assert!(report[6].overshoot_ratio < report[3].overshoot_ratio);
+        assert!(report.iter().all(|estimate| estimate.overshoot_ratio > 1.0));
+        assert!(report[6].overshoot_ratio < 1.2);
+
+        // Within one ring of the cells actually computed
+        for estimate in &report[2..5] {
+            let exact = smallest_enclosing_h3
+                .hexagons_at(estimate.resolution)
+                .unwrap()
+                .len() as u64;
+            let ring = expected_cell_count(estimate.k + 1, CoverageMode::Ring, false);
+            assert!(
+                estimate.cell_count.abs_diff(exact) <= ring,
+                "{estimate:?} {exact}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_builder_default() {
+        let builder = SmallestEnclosingH3Builder::default();
+        assert_eq!(builder.center, LatLng::new(0.0, 0.0).unwrap());
+        assert_eq!(builder.radius_meters, 1000.0);
+        assert_eq!(builder.resolution, Resolution::Nine);
+
+        let center = LatLng::new(33.4484, -112.0740).unwrap();
+        let overridden = SmallestEnclosingH3Builder::default()
+            .center(center)
+            .build()
+            .unwrap();
+        let expected = SmallestEnclosingH3Builder::new(center, 1000.0, Resolution::Nine)
+            .build()
+            .unwrap();
+        assert_eq!(overridden.hexagons().unwrap(), expected.hexagons().unwrap());
+    }
+
+    #[test]
+    fn test_effective_parameters() {
+        let requested = LatLng::new(10.0, 190.0).unwrap();
+        let wrapped = LatLng::new(10.0, -170.0).unwrap();
+        let snapped = LatLng::from(wrapped.to_cell(Resolution::Seven));
+        let builder = || {
+            SmallestEnclosingH3Builder::new(requested, 1000.0, Resolution::Nine)
+                .snap_center_to_resolution(Resolution::Seven)
+        };
+
+        let parameters = builder().build().unwrap().effective_parameters().unwrap();
+        assert_eq!(parameters.requested_center, requested);
+        assert_eq!(parameters.center, snapped);
+        assert_eq!(parameters.requested_radius_meters, 1000.0);
+        assert_eq!(parameters.radius_meters, 1000.0);
+        assert_eq!(parameters.requested_resolution, Resolution::Nine);
+
+        // Scaling changes the radius used, not the one asked for
+        let scaled = builder().build().unwrap().scale(2.5).unwrap();
+        let parameters = scaled.effective_parameters().unwrap();
+        assert_eq!(parameters.requested_radius_meters, 1000.0);
+        assert_eq!(parameters.radius_meters, 2500.0);
+        assert_eq!(
+            parameters.adjustments,
+            [
+                Adjustment::NormalizeLongitude {
+                    from: 190.0,
+                    to: -170.0
+                },
+                Adjustment::SnapCenter {
+                    from: (10.0, -170.0),
+                    to: (snapped.lat(), snapped.lng()),
+                },
+            ]
+        );
+
+        // Snapping is asked for, so strict mode allows it
+        let center = LatLng::new(33.4484, -112.0740).unwrap();
+        let strict = SmallestEnclosingH3Builder::new(center, 1000.0, Resolution::Nine)
+            .snap_center_to_resolution(Resolution::Seven)
+            .strict(true)
+            .build()
+            .unwrap();
+        assert!(matches!(
+            strict.effective_parameters().unwrap().adjustments[..],
+            [Adjustment::SnapCenter { .. }]
+        ));
+
+        // An adjustment made while covering is listed, but not carried over
+        // to another resolution
+        let pentagon = SmallestEnclosingH3Builder::new(
+            LatLng::new(-67.0, -168.0).unwrap(),
+            500_000.0,
+            Resolution::Four,
+        )
+        .build()
+        .unwrap();
+        assert!(matches!(
+            pentagon.effective_parameters().unwrap().adjustments[..],
+            [Adjustment::ApproximateRingDistance { .. }]
+        ));
+        let untouched = SmallestEnclosingH3Builder::new(center, 50.0, Resolution::Twelve)
+            .build()
+            .unwrap();
+        assert!(untouched
+            .effective_parameters()
+            .unwrap()
+            .adjustments
+            .is_empty());
+        assert!(pentagon
+            .at_resolution(Resolution::Two)
+            .policy
+            .applied()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_sphere_radius() {
+        let center = LatLng::new(33.4484, -112.0740).unwrap();
+        let builder = || SmallestEnclosingH3Builder::new(center, 2000.0, Resolution::Ten);
+        let earth = builder().build().unwrap();
+        let half = builder()
+            .sphere_radius_meters(geodesy::EARTH_RADIUS / 2.0)
+            .unwrap()
+            .build()
+            .unwrap();
+        assert_eq!(earth.sphere_radius_meters(), geodesy::EARTH_RADIUS);
+
+        // The circle spans twice the angle, on the Earth's grid
+        let edge = |coverage: &SmallestEnclosingH3| {
+            let first = &coverage.generate_circle_coordinates().unwrap()[0];
+            haversine_distance(center, LatLng::new(first[1], first[0]).unwrap())
+        };
+        assert!((edge(&half) - 2.0 * edge(&earth)).abs() < 1e-6);
+        let (k_earth, k_half) = (earth.k_value().unwrap(), half.k_value().unwrap());
+        assert!(
+            k_half > k_earth && k_half.abs_diff(2 * k_earth) <= 1,
+            "{k_earth} {k_half}"
+        );
+        assert!(half.hexagons().unwrap().len() > earth.hexagons().unwrap().len());
+
+        // Distances are reported on the configured sphere
+        let cell = half.hexagons().unwrap()[0];
+        let earth_distance = haversine_distance(center, LatLng::from(cell));
+        let distance = half.distance_from_center(LatLng::from(cell));
+        assert!((distance - earth_distance / 2.0).abs() < 1e-6);
+
+        for radius in [0.0, -1.0, f64::NAN, f64::INFINITY] {
+            assert!(matches!(
+                builder().sphere_radius_meters(radius),
+                Err(SmallestEnclosingH3Error::InvalidRadius(_))
+            ));
+        }
+        // Half of Mars' circumference is well short of the Earth's
+        let mars = SmallestEnclosingH3Builder::new(center, 15_000_000.0, Resolution::Two)
+            .sphere_radius_meters(3_389_500.0)
+            .unwrap();
+        assert!(mars.build().is_err());
+    }
+
+    #[test]
+    fn test_max_cells() {
+        let center = LatLng::new(33.4484, -112.0740).unwrap();
+        let builder = || SmallestEnclosingH3Builder::new(center, 2000.0, Resolution::Twelve);
+
+        // The default limit passes, and the estimate covers the actual disk
+        let coverage = builder().build().unwrap();
+        let disk = coverage.disk().unwrap();
+        let k = coverage.k_value().unwrap();
+        let estimated = expected_cell_count(k, CoverageMode::Disk, false) as usize;
+        assert!(estimated >= disk.len());
+
+        let limited = builder().max_cells(1000).build().unwrap();
+        match limited.hexagons() {
+            Err(SmallestEnclosingH3Error::TooManyCells {
+                estimated: reported,
+                limit,
+            }) => {
+                assert_eq!(limit, 1000);
+                assert!(reported > limit && reported <= estimated, "{reported}");
+            }
+            other => panic!("Expected too many cells, got {other:?}"),
+        }
+        assert!(matches!(
+            limited.disk(),
+            Err(SmallestEnclosingH3Error::TooManyCells { .. })
+        ));
+        let padded = builder()
+            .padding_rings(1)
+            .max_cells(disk.len())
+            .build()
+            .unwrap();
+        assert!(padded.hexagons().is_err());
+        assert!(builder()
+            .max_cells(disk.len())
+            .build()
+            .unwrap()
+            .hexagons()
+            .is_ok());
+
+        // A typo'd radius is refused up front rather than generated
+        let typo = SmallestEnclosingH3Builder::new(center, 300_000.0, Resolution::Fourteen)
+            .build()
+            .unwrap();
+        assert!(matches!(
+            typo.hexagons(),
+            Err(SmallestEnclosingH3Error::TooManyCells {
+                limit: DEFAULT_MAX_CELLS,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_resolution_downgrade_fallback() {
+        let center = LatLng::new(33.4484, -112.0740).unwrap();
+        let builder =
+            || SmallestEnclosingH3Builder::new(center, 2000.0, Resolution::Twelve).max_cells(1000);
+
+        let plain = builder().build().unwrap();
+        assert_eq!(plain.last_resolution_used(), None);
+        assert!(plain.hexagons().is_err());
+
+        let coverage = builder()
+            .strict(true)
+            .resolution_downgrade_fallback(true)
+            .build()
+            .unwrap();
+        let resolution = coverage.last_resolution_used().unwrap();
+        assert!(resolution < Resolution::Twelve);
+        assert!(coverage.disk().unwrap().len() <= 1000);
+        assert!(coverage
+            .hexagons()
+            .unwrap()
+            .iter()
+            .all(|cell| cell.resolution() == resolution));
+
+        // One resolution finer would not have fitted
+        let finer = resolution.succ().unwrap();
+        let finer = SmallestEnclosingH3Builder::new(center, 2000.0, finer)
+            .max_cells(1000)
+            .build()
+            .unwrap();
+        assert!(finer.hexagons().is_err());
+
+        let effective = coverage.effective_parameters().unwrap();
+        assert_eq!(effective.requested_resolution, Resolution::Twelve);
+        assert_eq!(effective.resolution, resolution);
+        assert_eq!(
+            effective.adjustments,
+            vec![Adjustment::CoarsenResolution {
+                from: Resolution::Twelve,
+                to: resolution,
+            }]
+        );
+
+        // Even resolution 0 has a cell
+        assert!(matches!(
+            builder()
+                .max_cells(0)
+                .resolution_downgrade_fallback(true)
+                .build(),
+            Err(SmallestEnclosingH3Error::TooManyCells { .. })
+        ));
+    }
+
+    #[test]
+    fn test_planar_math_mode() {
+        for (lat, lng) in [(0.0, 0.0), (33.4484, -112.0740), (65.0, 25.0)] {
+            let center = LatLng::new(lat, lng).unwrap();
+            let build = |math_mode| {
+                SmallestEnclosingH3Builder::new(center, 200.0, Resolution::Twelve)
+                    .math_mode(math_mode)
+                    .build()
+                    .unwrap()
+            };
+            let (spherical, planar) = (build(MathMode::Spherical), build(MathMode::PlanarLocal));
+            assert_eq!(planar.hexagons().unwrap(), spherical.hexagons().unwrap());
+            assert_eq!(planar.disk().unwrap(), spherical.disk().unwrap());
+            assert_eq!(
+                planar.shrink_to_circle(true).unwrap(),
+                spherical.shrink_to_circle(true).unwrap()
+            );
+
+            let build = |math_mode| {
+                SmallestEnclosingH3Builder::new(center, 200.0, Resolution::Twelve)
+                    .inner_radius_meters(80.0)
+                    .unwrap()
+                    .bearing_range(30.0, 200.0)
+                    .unwrap()
+                    .math_mode(math_mode)
+                    .build()
+                    .unwrap()
+            };
+            let (spherical, planar) = (build(MathMode::Spherical), build(MathMode::PlanarLocal));
+            assert_eq!(planar.disk().unwrap(), spherical.disk().unwrap());
+        }
+
+        let center = LatLng::new(33.4484, -112.0740).unwrap();
+        let planar = |center, radius_meters| {
+            SmallestEnclosingH3Builder::new(center, radius_meters, Resolution::Six)
+                .math_mode(MathMode::PlanarLocal)
+                .build()
+        };
+        assert!(planar(center, PLANAR_MAX_RADIUS_METERS).is_ok());
+        assert!(matches!(
+            planar(center, 60_000.0),
+            Err(SmallestEnclosingH3Error::InvalidRadius(_))
+        ));
+        assert!(matches!(
+            planar(LatLng::new(89.9, 0.0).unwrap(), 20_000.0),
+            Err(SmallestEnclosingH3Error::InvalidRadius(_))
+        ));
+    }
+
+    #[test]
+    fn test_shrink_to_circle() {
+        let center = LatLng::new(33.4484, -112.0740).unwrap();
+        let smallest_enclosing_h3 =
+            SmallestEnclosingH3Builder::new(center, 1000.0, Resolution::Nine)
+                .build()
+                .unwrap();
+        let disk = smallest_enclosing_h3.disk().unwrap();
+        let shrunk = smallest_enclosing_h3.shrink_to_circle(false).unwrap();
+        let strict = smallest_enclosing_h3.shrink_to_circle(true).unwrap();
+
+        assert!(shrunk.len() < disk.len());
+        assert!(shrunk.iter().all(|cell| disk.contains(cell)));
+        assert!(strict.iter().all(|cell| shrunk.contains(cell)));
+        for cell in &shrunk {
+            assert!(haversine_distance(center, LatLng::from(*cell)) <= 1000.0);
+        }
+        for cell in disk.iter().filter(|cell| !shrunk.contains(cell)) {
+            assert!(haversine_distance(center, LatLng::from(*cell)) > 1000.0);
+        }
+
+        // A circle well inside its cell holds the centroid but no vertex
+        let centroid = LatLng::from(center.to_cell(Resolution::Nine));
+        let tiny = SmallestEnclosingH3Builder::new(centroid, 10.0, Resolution::Nine)
+            .build()
+            .unwrap();
+        assert_eq!(
+            tiny.shrink_to_circle(false).unwrap(),
+            vec![center.to_cell(Resolution::Nine)]
+        );
+        assert!(tiny.shrink_to_circle(true).unwrap().is_empty());
+
+        let bbox =
+            SmallestEnclosingH3Builder::from_bbox(33.4, -112.1, 33.5, -112.0, Resolution::Eight)
+                .unwrap()
+                .build()
+                .unwrap();
+        assert!(matches!(
+            bbox.shrink_to_circle(false),
+            Err(SmallestEnclosingH3Error::UnsupportedGeometry(_))
+        ));
+    }
 
-// in meters
-const EARTH_RADIUS: f64 = 6371000.0;
+    #[test]
+    fn test_padding_rings() {
+        let center = LatLng::new(33.4484, -112.0740).unwrap();
+        let builder = || SmallestEnclosingH3Builder::new(center, 500.0, Resolution::Nine);
+        let plain = builder().build().unwrap();
+        let padded = builder().padding_rings(2).build().unwrap();
+        let k = plain.enclosing_ring().unwrap().0 as usize;
+        assert!(plain.padding_cells().unwrap().is_empty());
 
-/// Builder for creating a SmallestEnclosingH3 instance
-#[derive(Debug)]
-pub struct SmallestEnclosingH3Builder {
-    resolution: Resolution,
-    center: LatLng,
-    radius_meters: f64,
-}
+        // The disk grows to the disk at k + 2, the ring by the rings at k + 1
+        // and k + 2
+        let ring_size = |k: usize| 6 * k;
+        let disk_size = |k: usize| 3 * k * (k + 1) + 1;
+        assert_eq!(plain.disk().unwrap().len(), disk_size(k));
+        assert_eq!(padded.disk().unwrap().len(), disk_size(k + 2));
+        assert_eq!(
+            padded.hexagons().unwrap().len(),
+            ring_size(k) + ring_size(k + 1) + ring_size(k + 2)
+        );
 
-impl SmallestEnclosingH3Builder {
-    pub fn new(center: LatLng, radius_meters: f64, resolution: Resolution) -> Self {
-        Self {
-            resolution,
-            center,
-            radius_meters,
-        }
+        let padding = padded.padding_cells().unwrap();
+        assert_eq!(padding.len(), ring_size(k + 1) + ring_size(k + 2));
+        let unpadded: HashSet<CellIndex> = plain.disk().unwrap().into_iter().collect();
+        assert!(padding.iter().all(|cell| !unpadded.contains(cell)));
+        let padded_disk: HashSet<CellIndex> = padded.disk().unwrap().into_iter().collect();
+        assert_eq!(padded_disk.len(), disk_size(k + 2));
     }
 
-    pub fn resolution(mut self, resolution: u8) -> Result<Self> {
-        self.resolution = Resolution::try_from(resolution)
-            .map_err(|e| SmallestEnclosingH3Error::InvalidResolution(e.to_string()))?;
-        Ok(self)
-    }
+    #[test]
+    fn test_snap_center_to_resolution() {
+        let cell = LatLng::new(33.4484, -112.0740)
+            .unwrap()
+            .to_cell(Resolution::Seven);
+        let centroid = LatLng::from(cell);
+        let snapped = |lat_offset: f64| {
+            let center = LatLng::new(centroid.lat() + lat_offset, centroid.lng()).unwrap();
+            assert_eq!(center.to_cell(Resolution::Seven), cell);
+            SmallestEnclosingH3Builder::new(center, 500.0, Resolution::Nine)
+                .snap_center_to_resolution(Resolution::Seven)
+                .strict(true)
+                .build()
+                .unwrap()
+        };
+        let (north, south) = (snapped(0.002), snapped(-0.002));
 
-    pub fn center(mut self, center: LatLng) -> Self {
-        self.center = center;
-        self
+        assert_eq!(north.center, centroid);
+        assert_eq!(north.hexagons().unwrap(), south.hexagons().unwrap());
+        assert_eq!(
+            north.generate_circle_coordinates().unwrap(),
+            south.generate_circle_coordinates().unwrap()
+        );
+
+        let bbox =
+            SmallestEnclosingH3Builder::from_bbox(33.4, -112.1, 33.5, -112.0, Resolution::Nine)
+                .unwrap()
+                .snap_center_to_resolution(Resolution::Seven)
+                .build();
+        assert!(matches!(
+            bbox,
+            Err(SmallestEnclosingH3Error::UnsupportedGeometry(_))
+        ));
     }
 
-    pub fn radius_meters(mut self, radius: f64) -> Result<Self> {
-        if radius <= 0.0 {
-            return Err(SmallestEnclosingH3Error::InvalidRadius(
-                "Radius must be positive".to_string(),
+    #[test]
+    fn test_try_from_tuple_and_array() {
+        let center = LatLng::new(33.4484, -112.0740).unwrap();
+        let expected = SmallestEnclosingH3Builder::new(center, 500.0, Resolution::Nine)
+            .build()
+            .unwrap()
+            .hexagons()
+            .unwrap();
+
+        let from_tuple = SmallestEnclosingH3::try_from((33.4484, -112.0740, 500.0, 9)).unwrap();
+        assert_eq!(from_tuple.hexagons().unwrap(), expected);
+        let from_array = SmallestEnclosingH3::try_from([33.4484, -112.0740, 500.0, 9.0]).unwrap();
+        assert_eq!(from_array.hexagons().unwrap(), expected);
+
+        assert!(matches!(
+            SmallestEnclosingH3::try_from((33.4484, -112.0740, 500.0, 16)),
+            Err(SmallestEnclosingH3Error::InvalidResolution(_))
+        ));
+        assert!(matches!(
+            SmallestEnclosingH3::try_from((f64::NAN, -112.0740, 500.0, 9)),
+            Err(SmallestEnclosingH3Error::InvalidLatLng(_))
+        ));
+        assert!(matches!(
+            SmallestEnclosingH3::try_from((33.4484, -112.0740, -1.0, 9)),
+            Err(SmallestEnclosingH3Error::InvalidRadius(_))
+        ));
+        for resolution in [9.5, -1.0, 16.0, f64::NAN] {
+            assert!(matches!(
+                SmallestEnclosingH3::try_from([33.4484, -112.0740, 500.0, resolution]),
+                Err(SmallestEnclosingH3Error::InvalidResolution(_))
             ));
         }
-        self.radius_meters = radius;
-        Ok(self)
     }
 
-    pub fn build(self) -> Result<SmallestEnclosingH3> {
-        if self.radius_meters <= 0.0 {
-            return Err(SmallestEnclosingH3Error::InvalidRadius(
-                "Radius must be positive".to_string(),
-            ));
-        }
+    #[test]
+    fn test_cells_are_cached() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<SmallestEnclosingH3>();
 
-        Ok(SmallestEnclosingH3 {
-            resolution: self.resolution,
-            center: self.center,
-            radius_meters: self.radius_meters,
-        })
+        let center = LatLng::new(33.4484, -112.0740).unwrap();
+        let smallest_enclosing_h3 =
+            SmallestEnclosingH3Builder::new(center, 500.0, Resolution::Nine)
+                .build()
+                .unwrap();
+        assert!(smallest_enclosing_h3.ring.get().is_none());
+
+        let first = smallest_enclosing_h3.hexagons_cached().unwrap();
+        let second = smallest_enclosing_h3.hexagons_cached().unwrap();
+        assert!(std::ptr::eq(first, second));
+        assert_eq!(smallest_enclosing_h3.hexagons().unwrap(), first);
+
+        let disk = smallest_enclosing_h3.disk().unwrap();
+        assert_eq!(smallest_enclosing_h3.disk.get(), Some(&disk));
+
+        // Another resolution starts with an empty cache of its own
+        let coarser = smallest_enclosing_h3.at_resolution(Resolution::Seven);
+        assert!(coarser.ring.get().is_none() && coarser.disk.get().is_none());
+        assert_ne!(coarser.hexagons_cached().unwrap(), first);
     }
-}
 
-/// Represents a collection of H3 hexagons that enclose a circle
-#[derive(Debug)]
-pub struct SmallestEnclosingH3 {
-    resolution: Resolution,
-    center: LatLng,
-    radius_meters: f64,
-}
+    #[test]
+    fn test_grid_path() {
+        let center = LatLng::new(33.4484, -112.0740).unwrap();
+        let smallest_enclosing_h3 = SmallestEnclosingH3Builder::new(center, 500.0, Resolution::Ten)
+            .build()
+            .unwrap();
 
-impl SmallestEnclosingH3 {
-    pub fn hexagons(&self) -> Result<Vec<CellIndex>> {
-        let center_cell = self.center.to_cell(self.resolution);
+        let to = LatLng::new(33.4352, -112.0101).unwrap();
+        let path = smallest_enclosing_h3.grid_path(center, to).unwrap();
+        let (from_cell, to_cell) = (center.to_cell(Resolution::Ten), to.to_cell(Resolution::Ten));
+        assert_eq!(
+            path.len() as i32,
+            from_cell.grid_distance(to_cell).unwrap() + 1
+        );
+        assert!(path
+            .windows(2)
+            .all(|step| step[0].is_neighbor_with(step[1]).unwrap()));
 
-        // Calculate the distance to the edge of the circle
-        let edge_lat = self.destination_point(
-            &point!(x: self.center.lng(), y: self.center.lat()),
-            self.radius_meters,
-            0.0, // bearing of 0 degrees (north)
-        )?;
+        // From a pentagon into a distorted direction
+        let pentagon = Resolution::Ten.pentagons().next().unwrap();
+        let beyond = pentagon.grid_disk::<Vec<_>>(3)[10];
+        assert!(matches!(
+            smallest_enclosing_h3.grid_path(LatLng::from(pentagon), LatLng::from(beyond)),
+            Err(SmallestEnclosingH3Error::GridDistanceError(_))
+        ));
+    }
 
-        let edge_cell = LatLng::new(edge_lat.y(), edge_lat.x())
-            .map_err(|e| SmallestEnclosingH3Error::InvalidLatLng(e.to_string()))?
-            .to_cell(self.resolution);
+    #[test]
+    fn test_neighbors() {
+        let center = LatLng::new(33.4484, -112.0740).unwrap();
+        let smallest_enclosing_h3 = SmallestEnclosingH3Builder::new(center, 500.0, Resolution::Ten)
+            .build()
+            .unwrap();
 
-        // Calculate the grid distance between center and edge
-        let k = center_cell
-            .grid_distance(edge_cell)
-            .map_err(|e| SmallestEnclosingH3Error::GridDistanceError(e.to_string()))?;
+        let center_cell = center.to_cell(Resolution::Ten);
+        let neighbors = smallest_enclosing_h3.neighbors(center_cell).unwrap();
+        assert_eq!(neighbors.len(), 6);
+        assert!(!neighbors.contains(&center_cell));
 
-        // Get only the ring at distance k (not the entire disk)
-        Ok(center_cell.grid_ring_fast(k as u32).flatten().collect())
+        // Ring cells have neighbors inside the disk, but none beyond it
+        let disk = smallest_enclosing_h3.disk().unwrap();
+        let ring_cell = smallest_enclosing_h3.hexagons().unwrap()[0];
+        let neighbors = smallest_enclosing_h3.neighbors(ring_cell).unwrap();
+        assert!(!neighbors.is_empty() && neighbors.len() < 6);
+        assert!(neighbors.iter().all(|cell| disk.contains(cell)));
     }
 
-    pub fn generate_circle_coordinates(&self) -> Result<Vec<Vec<f64>>> {
-        let num_points = 64;
-        let center_point = point!(x: self.center.lng(), y: self.center.lat());
-        let mut coordinates = Vec::with_capacity(num_points + 1);
+    #[test]
+    fn test_grid_distance_from_center() {
+        let center = LatLng::new(33.4484, -112.0740).unwrap();
+        let smallest_enclosing_h3 = SmallestEnclosingH3Builder::new(center, 500.0, Resolution::Ten)
+            .build()
+            .unwrap();
 
-        for i in 0..=num_points {
-            let bearing = (i as f64 * 360.0 / num_points as f64).to_radians();
-            let point = self.destination_point(&center_point, self.radius_meters, bearing)?;
-            coordinates.push(vec![point.x(), point.y()]);
+        let k = smallest_enclosing_h3.k_value().unwrap();
+        for cell in smallest_enclosing_h3.hexagons().unwrap() {
+            assert!(smallest_enclosing_h3.is_at_resolution(cell));
+            assert_eq!(
+                smallest_enclosing_h3
+                    .grid_distance_from_center(cell)
+                    .unwrap(),
+                k
+            );
         }
 
-        // Close the polygon by repeating the first point
-        if let Some(first) = coordinates.first().cloned() {
-            coordinates.push(first);
+        // A cell at another resolution is reported as such, not as a grid
+        // distance failure
+        let coarse = center.to_cell(Resolution::Nine);
+        assert!(!smallest_enclosing_h3.is_at_resolution(coarse));
+        assert!(matches!(
+            smallest_enclosing_h3.grid_distance_from_center(coarse),
+            Err(SmallestEnclosingH3Error::InvalidCellIndex(_))
+        ));
+    }
+
+    #[test]
+    fn test_hexagons_with_distance() {
+        let center_cell = LatLng::new(33.4484, -112.0740)
+            .unwrap()
+            .to_cell(Resolution::Ten);
+        let center = LatLng::from(center_cell);
+        let radius_meters = 1000.0;
+        let smallest_enclosing_h3 =
+            SmallestEnclosingH3Builder::new(center, radius_meters, Resolution::Ten)
+                .build()
+                .unwrap();
+
+        let cells = smallest_enclosing_h3.hexagons_with_distance().unwrap();
+        assert_eq!(cells.len(), smallest_enclosing_h3.disk().unwrap().len());
+        assert_eq!(cells[0].0, center_cell);
+        assert!(cells[0].1 < 1e-6);
+
+        // Cells meeting the circle are within a circumradius of it; the
+        // disk's corners reach further
+        for &(cell, distance) in &cells {
+            let centroid = LatLng::from(cell);
+            assert_eq!(distance, haversine_distance(center, centroid));
+            let circumradius = cell
+                .boundary()
+                .iter()
+                .map(|vertex| haversine_distance(centroid, *vertex))
+                .fold(0.0, f64::max);
+            if smallest_enclosing_h3.classify_cell(cell) != classify::CellClass::Outside {
+                assert!(
+                    distance <= radius_meters + circumradius,
+                    "{cell} {distance}"
+                );
+            }
         }
+        assert!(cells
+            .windows(2)
+            .all(|w| w[0].1 < w[1].1 || (w[0].1 == w[1].1 && w[0].0 < w[1].0)));
+    }
 
-        Ok(coordinates)
+    #[test]
+    fn test_ring_distances() {
+        let center = LatLng::new(33.4484, -112.0740).unwrap();
+        let smallest_enclosing_h3 =
+            SmallestEnclosingH3Builder::new(center, 1000.0, Resolution::Nine)
+                .padding_rings(1)
+                .build()
+                .unwrap();
+        let k = smallest_enclosing_h3.k_value().unwrap();
+
+        // The ring at k, then the padding at k + 1
+        let hexagons = smallest_enclosing_h3.hexagons_with_ring_distance().unwrap();
+        assert_eq!(
+            hexagons.len(),
+            smallest_enclosing_h3.hexagons().unwrap().len()
+        );
+        let at = |distance| hexagons.iter().filter(|(_, d)| *d == distance).count();
+        assert_eq!(at(k), 6 * k as usize);
+        assert_eq!(at(k + 1), 6 * (k as usize + 1));
+        assert!(hexagons
+            .windows(2)
+            .all(|w| (w[0].1, w[0].0) < (w[1].1, w[1].0)));
+
+        let disk = smallest_enclosing_h3.disk_with_ring_distance().unwrap();
+        assert_eq!(disk.len(), smallest_enclosing_h3.disk().unwrap().len());
+        assert_eq!(disk[0], (center.to_cell(Resolution::Nine), 0));
+        for distance in 1..=k + 1 {
+            let ring = disk.iter().filter(|(_, d)| *d == distance).count();
+            assert_eq!(ring, 6 * distance as usize);
+        }
     }
 
-    pub fn destination_point(
-        &self,
-        start: &Point<f64>,
-        distance: f64,
-        bearing: f64,
-    ) -> Result<Point<f64>> {
-        let lat1 = start.y().to_radians();
-        let lon1 = start.x().to_radians();
-        let angular_distance = distance / EARTH_RADIUS;
+    #[test]
+    fn test_intersects_cell() {
+        let center = LatLng::new(33.4484, -112.0740).unwrap();
+        let smallest_enclosing_h3 = SmallestEnclosingH3Builder::new(center, 500.0, Resolution::Ten)
+            .build()
+            .unwrap();
+        let intersects = |cell| smallest_enclosing_h3.intersects_cell(cell).unwrap();
+        let far = LatLng::new(33.5, -112.0740).unwrap();
 
-        let lat2 = (lat1.sin() * angular_distance.cos()
-            + lat1.cos() * angular_distance.sin() * bearing.cos())
-        .asin();
+        // At the coverage's resolution
+        assert!(intersects(center.to_cell(Resolution::Ten)));
+        assert!(!intersects(far.to_cell(Resolution::Ten)));
 
-        let lon2 = lon1
-            + (bearing.sin() * angular_distance.sin() * lat1.cos())
-                .atan2(angular_distance.cos() - lat1.sin() * lat2.sin());
+        // Finer cells by their ancestor, including those of a rim cell
+        assert!(intersects(center.to_cell(Resolution::Thirteen)));
+        assert!(!intersects(far.to_cell(Resolution::Thirteen)));
+        let rim = smallest_enclosing_h3.hexagons().unwrap()[0];
+        assert!(rim.children(Resolution::Twelve).all(&intersects));
 
-        Ok(point!(
-            x: lon2.to_degrees(),
-            y: lat2.to_degrees()
-        ))
+        // Coarser cells by any descendant in the disk
+        assert!(intersects(center.to_cell(Resolution::Five)));
+        assert!(intersects(rim.parent(Resolution::Nine).unwrap()));
+        assert!(!intersects(far.to_cell(Resolution::Eight)));
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use std::collections::HashSet;
+    #[test]
+    fn test_sector_across_north() {
+        let center = LatLng::new(33.4484, -112.0740).unwrap();
+        let builder = || SmallestEnclosingH3Builder::new(center, 2000.0, Resolution::Ten);
+        let circle = builder().build().unwrap();
+        let sector = builder()
+            .bearing_range(350.0, 20.0)
+            .unwrap()
+            .build()
+            .unwrap();
+        assert_eq!(sector.bearing_range(), Some((350.0, 20.0)));
+
+        let hexagons = sector.hexagons().unwrap();
+        let ring = circle.hexagons().unwrap();
+        assert!(!hexagons.is_empty());
+        assert!(hexagons.len() < ring.len() / 6);
+        for cell in &ring {
+            let bearing = bearing_degrees(center, LatLng::from(*cell));
+            let in_sector = !(20.0..350.0).contains(&bearing);
+            assert_eq!(hexagons.contains(cell), in_sector, "{bearing}");
+        }
+
+        let disk = sector.disk().unwrap();
+        assert!(disk.contains(&center.to_cell(Resolution::Ten)));
+        assert!(hexagons.iter().all(|cell| disk.contains(cell)));
+
+        // Center, 64 points along the arc, center again
+        let coordinates = sector.generate_circle_coordinates().unwrap();
+        assert_eq!(coordinates.len(), 66);
+        assert_eq!(coordinates[0], coordinates[65]);
+        assert_eq!(coordinates[0], vec![center.lng(), center.lat()]);
+        let first = LatLng::new(coordinates[1][1], coordinates[1][0]).unwrap();
+        let last = LatLng::new(coordinates[64][1], coordinates[64][0]).unwrap();
+        assert!((bearing_degrees(center, first) - 350.0).abs() < 1e-6);
+        assert!((bearing_degrees(center, last) - 20.0).abs() < 1e-6);
+    }
 
     #[test]
-    fn test_builder_with_valid_inputs() {
-        let center = LatLng::new(0.0, 0.0).unwrap();
-        let result = SmallestEnclosingH3Builder::new(center, 1000.0, Resolution::Nine).build();
-        assert!(result.is_ok());
+    fn test_full_turn_sector_is_circle() {
+        let center = LatLng::new(33.4484, -112.0740).unwrap();
+        let builder = || SmallestEnclosingH3Builder::new(center, 2000.0, Resolution::Ten);
+        let circle = builder().build().unwrap();
+
+        for (start, end) in [(0.0, 360.0), (-90.0, 270.0), (45.0, 405.0)] {
+            let sector = builder()
+                .bearing_range(start, end)
+                .unwrap()
+                .build()
+                .unwrap();
+            assert_eq!(sector.bearing_range(), None);
+            assert_eq!(sector.hexagons().unwrap(), circle.hexagons().unwrap());
+            assert_eq!(sector.disk().unwrap(), circle.disk().unwrap());
+            assert_eq!(
+                sector.generate_circle_coordinates().unwrap(),
+                circle.generate_circle_coordinates().unwrap()
+            );
+        }
     }
 
     #[test]
-    fn test_builder_with_invalid_radius() {
-        let center = LatLng::new(0.0, 0.0).unwrap();
-        let result = SmallestEnclosingH3Builder::new(center, -1.0, Resolution::Nine).build();
+    fn test_bearing_range_validation() {
+        let center = LatLng::new(33.4484, -112.0740).unwrap();
+        let builder = || SmallestEnclosingH3Builder::new(center, 2000.0, Resolution::Ten);
+
+        for (start, end) in [
+            (f64::NAN, 20.0),
+            (0.0, f64::NAN),
+            (0.0, f64::INFINITY),
+            (90.0, 90.0),
+        ] {
+            assert!(matches!(
+                builder().bearing_range(start, end),
+                Err(SmallestEnclosingH3Error::InvalidBearing(_))
+            ));
+        }
+
+        let sector = builder()
+            .bearing_range(-10.0, 380.0)
+            .unwrap()
+            .build()
+            .unwrap();
+        assert_eq!(sector.bearing_range(), Some((350.0, 20.0)));
+    }
+
+    #[test]
+    fn test_annulus_validation() {
+        let center = LatLng::new(33.4484, -112.0740).unwrap();
+        let builder = || SmallestEnclosingH3Builder::new(center, 1000.0, Resolution::Ten);
+
         assert!(matches!(
-            result,
+            builder().inner_radius_meters(-1.0),
             Err(SmallestEnclosingH3Error::InvalidRadius(_))
         ));
+        for inner in [1000.0, 1500.0] {
+            assert!(matches!(
+                builder().inner_radius_meters(inner).unwrap().build(),
+                Err(SmallestEnclosingH3Error::InvalidRadius(_))
+            ));
+        }
+        // No inner radius is the whole disk
+        let disk = builder()
+            .inner_radius_meters(0.0)
+            .unwrap()
+            .build()
+            .unwrap()
+            .disk()
+            .unwrap();
+        assert_eq!(disk, builder().build().unwrap().disk().unwrap());
+    }
+
+    #[test]
+    fn test_expected_cell_count_away_from_pentagons() {
+        let center_cell = LatLng::new(33.4484, -112.0740)
+            .unwrap()
+            .to_cell(Resolution::Ten);
+        let disk: Vec<(CellIndex, u32)> = center_cell.grid_disk_distances(100);
+
+        let mut ring_counts = vec![0u64; 101];
+        for (_, distance) in &disk {
+            ring_counts[*distance as usize] += 1;
+        }
+        let mut within = 0;
+        for k in 0..=100 {
+            within += ring_counts[k as usize];
+            assert_eq!(
+                ring_counts[k as usize],
+                expected_cell_count(k, CoverageMode::Ring, false)
+            );
+            assert_eq!(within, expected_cell_count(k, CoverageMode::Disk, false));
+        }
+        assert_eq!(grid_disk(center_cell, 100).len(), disk.len());
+        assert_eq!(grid_ring(center_cell, 100).len(), 600);
+    }
+
+    #[test]
+    fn test_expected_cell_count_near_pentagons() {
+        for pentagon in Resolution::Four.pentagons() {
+            for k in 1..10 {
+                // Exact around the pentagon itself
+                assert_eq!(
+                    grid_ring(pentagon, k).len() as u64,
+                    expected_cell_count(k, CoverageMode::Ring, true)
+                );
+                assert_eq!(
+                    grid_disk(pentagon, k).len() as u64,
+                    expected_cell_count(k, CoverageMode::Disk, true)
+                );
+
+                // Between the two counts for its neighbours
+                for cell in pentagon.grid_disk::<Vec<_>>(2) {
+                    for (mode, len) in [
+                        (CoverageMode::Ring, grid_ring(cell, k).len()),
+                        (CoverageMode::Disk, grid_disk(cell, k).len()),
+                    ] {
+                        let len = len as u64;
+                        assert!(expected_cell_count(k, mode, true) <= len);
+                        assert!(len <= expected_cell_count(k, mode, false));
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_into_iterator() {
+        let center = LatLng::new(33.4484, -112.0740).unwrap();
+        let smallest_enclosing_h3 = SmallestEnclosingH3Builder::new(center, 500.0, Resolution::Ten)
+            .build()
+            .unwrap();
+
+        let mut cells = Vec::new();
+        for cell in &smallest_enclosing_h3 {
+            cells.push(cell);
+        }
+        assert_eq!(cells, smallest_enclosing_h3.hexagons().unwrap());
+
+        let mut iter = smallest_enclosing_h3.into_iter();
+        assert_eq!(iter.next(), Some(cells[0]));
+        assert_eq!(iter.count(), cells.len() - 1);
+    }
+
+    #[test]
+    fn test_hexagons_sorted() {
+        let center = LatLng::new(33.4484, -112.0740).unwrap();
+        let smallest_enclosing_h3 =
+            SmallestEnclosingH3Builder::new(center, 500.0, Resolution::Eleven)
+                .build()
+                .unwrap();
+        let mut expected = smallest_enclosing_h3.hexagons().unwrap();
+        expected.sort_unstable();
+
+        let by_index = smallest_enclosing_h3
+            .hexagons_sorted(CellOrder::Index)
+            .unwrap();
+        assert!(by_index.windows(2).all(|w| w[0] < w[1]));
+        assert_eq!(by_index, expected);
+
+        let clock = smallest_enclosing_h3
+            .hexagons_sorted(CellOrder::RingClock)
+            .unwrap();
+        let bearings: Vec<f64> = clock
+            .iter()
+            .map(|cell| bearing_degrees(center, LatLng::from(*cell)))
+            .collect();
+        assert!(bearings.windows(2).all(|w| w[0] <= w[1]));
+        assert!(bearings[0] < 360.0 / clock.len() as f64 * 2.0);
+        assert_eq!(
+            clock,
+            smallest_enclosing_h3
+                .hexagons_sorted(CellOrder::RingClock)
+                .unwrap()
+        );
+
+        let mut same_cells = clock.clone();
+        same_cells.sort_unstable();
+        assert_eq!(same_cells, expected);
+    }
+
+    #[test]
+    fn test_hilbert_order_is_local() {
+        let center = LatLng::new(33.4484, -112.0740).unwrap();
+        let smallest_enclosing_h3 =
+            SmallestEnclosingH3Builder::new(center, 1000.0, Resolution::Eleven)
+                .build()
+                .unwrap();
+        let spacing = resolution_info(Resolution::Eleven).avg_cell_spacing_km * 1000.0;
+
+        // Total distance walked scanning the cells in order, and the number of
+        // steps to a cell that isn't a neighbour
+        let scan = |cells: &[CellIndex]| {
+            cells.windows(2).fold((0.0, 0), |(walked, jumps), w| {
+                let step = haversine_distance(LatLng::from(w[0]), LatLng::from(w[1]));
+                (walked + step, jumps + usize::from(step > 1.5 * spacing))
+            })
+        };
+
+        let mut by_index = smallest_enclosing_h3.disk().unwrap();
+        let mut hilbert = by_index.clone();
+        smallest_enclosing_h3.sort_cells(&mut by_index, CellOrder::Index, 0);
+        smallest_enclosing_h3.sort_cells(&mut hilbert, CellOrder::Hilbert, 0);
+        let (index_walked, index_jumps) = scan(&by_index);
+        let (hilbert_walked, hilbert_jumps) = scan(&hilbert);
+        assert!(
+            hilbert_walked * 1.5 < index_walked,
+            "{hilbert_walked} {index_walked}"
+        );
+        assert!(
+            hilbert_jumps * 4 < index_jumps,
+            "{hilbert_jumps} {index_jumps}"
+        );
+
+        let mut same_cells = hilbert.clone();
+        same_cells.sort_unstable();
+        assert_eq!(same_cells, by_index);
+
+        let mut ring = smallest_enclosing_h3.hexagons_hilbert_ordered().unwrap();
+        ring.sort_unstable();
+        assert_eq!(
+            ring,
+            smallest_enclosing_h3
+                .hexagons_sorted(CellOrder::Index)
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_ring_clock_orders_rings_outwards() {
+        let center = LatLng::new(33.4484, -112.0740).unwrap();
+        let smallest_enclosing_h3 =
+            SmallestEnclosingH3Builder::new(center, 500.0, Resolution::Eleven)
+                .build()
+                .unwrap();
+        let center_cell = center.to_cell(Resolution::Eleven);
+
+        let mut disk = smallest_enclosing_h3.disk().unwrap();
+        smallest_enclosing_h3.sort_cells(&mut disk, CellOrder::RingClock, 0);
+        assert_eq!(disk[0], center_cell);
+
+        let rings: Vec<u32> = disk
+            .iter()
+            .map(|cell| center_cell.grid_distance(*cell).unwrap() as u32)
+            .collect();
+        assert!(rings.windows(2).all(|w| w[0] <= w[1]));
+        for ring in 1..=*rings.last().unwrap() {
+            let bearings: Vec<f64> = disk
+                .iter()
+                .zip(&rings)
+                .filter(|(_, r)| **r == ring)
+                .map(|(cell, _)| bearing_degrees(center, LatLng::from(*cell)))
+                .collect();
+            assert_eq!(bearings.len(), 6 * ring as usize);
+            assert!(bearings.windows(2).all(|w| w[0] <= w[1]));
+        }
+    }
+
+    // Whether `lng` lies within the west/east span of `bbox`
+    fn within_lng(bbox: &[f64; 4], lng: f64) -> bool {
+        if bbox[0] <= bbox[2] {
+            (bbox[0]..=bbox[2]).contains(&lng)
+        } else {
+            lng >= bbox[0] || lng <= bbox[2]
+        }
+    }
+
+    #[test]
+    fn test_bbox() {
+        let center = LatLng::new(33.4484, -112.0740).unwrap();
+        let smallest_enclosing_h3 = SmallestEnclosingH3Builder::new(center, 500.0, Resolution::Ten)
+            .build()
+            .unwrap();
+        let bbox = smallest_enclosing_h3.bbox().unwrap();
+        assert!(bbox[0] < bbox[2] && bbox[1] < bbox[3]);
+
+        for cell in smallest_enclosing_h3.hexagons().unwrap() {
+            for p in cell.boundary().iter() {
+                assert!(within_lng(&bbox, p.lng()));
+                assert!((bbox[1]..=bbox[3]).contains(&p.lat()));
+            }
+        }
+        // The circle sits inside the covering cells
+        for c in smallest_enclosing_h3.generate_circle_coordinates().unwrap() {
+            assert!(within_lng(&bbox, c[0]));
+            assert!((bbox[1]..=bbox[3]).contains(&c[1]));
+        }
+    }
+
+    #[test]
+    fn test_bbox_across_antimeridian() {
+        let center = LatLng::new(-16.5, 179.99).unwrap();
+        let smallest_enclosing_h3 =
+            SmallestEnclosingH3Builder::new(center, 5000.0, Resolution::Eight)
+                .build()
+                .unwrap();
+        let bbox = smallest_enclosing_h3.bbox().unwrap();
+        assert!(bbox[0] > bbox[2], "west should exceed east: {bbox:?}");
+        assert!(bbox[0] > 179.0 && bbox[2] < -179.0);
+
+        for cell in smallest_enclosing_h3.hexagons().unwrap() {
+            for p in cell.boundary().iter() {
+                assert!(within_lng(&bbox, p.lng()));
+            }
+        }
+    }
+
+    #[test]
+    fn test_truncate_closest() {
+        let center = LatLng::new(33.4484, -112.0740).unwrap();
+        let enclosing = SmallestEnclosingH3Builder::new(center, 50.0, Resolution::Twelve)
+            .build()
+            .unwrap();
+        let all = enclosing.disk().unwrap();
+
+        let mut by_distance: Vec<(f64, CellIndex)> = all
+            .iter()
+            .map(|cell| (haversine_distance(center, LatLng::from(*cell)), *cell))
+            .collect();
+        by_distance.sort_by(|a, b| a.0.total_cmp(&b.0).then_with(|| a.1.cmp(&b.1)));
+
+        let center_cell = center.to_cell(Resolution::Twelve);
+        let mut previous_fraction = f64::INFINITY;
+        for n in (1..=all.len()).rev() {
+            let kept = enclosing.truncate_closest(n).unwrap();
+            let expected: Vec<CellIndex> = by_distance[..n].iter().map(|(_, c)| *c).collect();
+            assert_eq!(kept.cells, expected);
+            assert!(kept.cells.contains(&center_cell));
+            assert_eq!(kept.truncated, n < all.len());
+            assert!(kept.circle_area_fraction <= previous_fraction);
+            previous_fraction = kept.circle_area_fraction;
+        }
+
+        let none = enclosing.truncate_closest(0).unwrap();
+        assert!(none.cells.is_empty() && none.truncated);
+        assert_eq!(none.circle_area_fraction.to_bits(), 0.0f64.to_bits());
+
+        let untouched = enclosing.truncate_closest(all.len() + 10).unwrap();
+        assert!(!untouched.truncated);
+        assert_eq!(untouched.cells.len(), all.len());
+        assert!((untouched.circle_area_fraction - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_hexagons_across_pentagon_distortion() {
+        // The grid distance between the center and edge cells can't be computed
+        // here, so the circle is flood-filled
+        let cases = [
+            (
+                LatLng::new(-67.0, -168.0).unwrap(),
+                500_000.0,
+                Resolution::Three,
+            ),
+            (
+                LatLng::new(-67.0, -168.0).unwrap(),
+                500_000.0,
+                Resolution::Four,
+            ),
+            (
+                LatLng::new(-67.0, -168.0).unwrap(),
+                500_000.0,
+                Resolution::Five,
+            ),
+            (
+                LatLng::new(40.0, 120.0).unwrap(),
+                400_000.0,
+                Resolution::Five,
+            ),
+        ];
+        for (center, radius, resolution) in cases {
+            let center_cell = center.to_cell(resolution);
+            let enclosing = SmallestEnclosingH3Builder::new(center, radius, resolution)
+                .build()
+                .unwrap();
+            let hexagons = enclosing.hexagons().unwrap();
+            assert!(!hexagons.is_empty());
+            assert_eq!(
+                enclosing.effective_parameters().unwrap().adjustments,
+                vec![Adjustment::ApproximateRingDistance {
+                    estimate: enclosing.estimate_k(center_cell)
+                }]
+            );
+
+            // Every cell may overlap the circle, and together they cover it
+            let disk = enclosing.disk().unwrap();
+            for cell in &disk {
+                let centroid = LatLng::from(*cell);
+                let circumradius = cell
+                    .boundary()
+                    .iter()
+                    .map(|vertex| haversine_distance(centroid, *vertex))
+                    .fold(0.0, f64::max);
+                assert!(haversine_distance(center, centroid) <= radius + circumradius);
+            }
+            assert!(enclosing.is_fully_covered().unwrap());
+
+            // The outline of the disk
+            let disk: HashSet<CellIndex> = disk.into_iter().collect();
+            assert!(hexagons.iter().all(|cell| disk.contains(cell)));
+            assert!(hexagons.iter().all(|cell| cell
+                .grid_disk::<Vec<_>>(1)
+                .iter()
+                .any(|neighbor| !disk.contains(neighbor))));
+        }
     }
 
     #[test]
@@ -175,8 +4501,8 @@ mod tests {
                 .build()
                 .unwrap();
 
-        let hexes: HashSet<String> = smallest_enclosing_h3
-            .hexagons()
+        let hexes: Vec<String> = smallest_enclosing_h3
+            .hexagons_sorted(CellOrder::Index)
             .unwrap()
             .into_iter()
             .map(|h| h.to_string())
@@ -188,7 +4514,7 @@ mod tests {
         // radius = 50
         // hexagons = mapper.get_hexagons(phoenix_center, radius)
         // print(hexagons)
-        let python_hexes: HashSet<String> = vec![
+        let mut python_hexes: Vec<String> = vec![
             "8c29b6d357aa7ff",
             "8c29b6d357a33ff",
             "8c29b6d357853ff",
@@ -211,19 +4537,41 @@ mod tests {
         .into_iter()
         .map(String::from)
         .collect();
+        // Same resolution, so the hex strings sort like the cell indexes
+        python_hexes.sort_unstable();
 
         assert_eq!(
             hexes.len(),
             python_hexes.len(),
             "Number of hexagons doesn't match"
         );
+        assert_eq!(hexes, python_hexes, "Hexagons don't match");
+    }
 
-        let diff: Vec<_> = hexes.symmetric_difference(&python_hexes).collect();
+    #[test]
+    fn test_k_value() {
+        let center = LatLng::new(33.4484, -112.0740).unwrap();
+        let smallest_enclosing_h3 =
+            SmallestEnclosingH3Builder::new(center, 50.0, Resolution::Twelve)
+                .build()
+                .unwrap();
 
-        assert!(
-            diff.is_empty(),
-            "Hexagon sets don't match. Difference: {:?}",
-            diff
-        );
+        // The Python implementation's 18 cells are the ring at k = 3
+        assert_eq!(smallest_enclosing_h3.k_value().unwrap(), 3);
+        assert!(smallest_enclosing_h3.ring.get().is_none());
+        assert_eq!(smallest_enclosing_h3.hexagons().unwrap().len(), 6 * 3);
+        assert_eq!(smallest_enclosing_h3.k_value().unwrap(), 3);
+
+        for (radius_meters, resolution) in [(500.0, Resolution::Nine), (100_000.0, Resolution::Six)]
+        {
+            let smallest_enclosing_h3 =
+                SmallestEnclosingH3Builder::new(center, radius_meters, resolution)
+                    .build()
+                    .unwrap();
+            assert_eq!(
+                smallest_enclosing_h3.k_value().unwrap(),
+                smallest_enclosing_h3.enclosing_ring().unwrap().0
+            );
+        }
     }
 }
@@ -0,0 +1,136 @@
+//! Trimming of the rim to the cells mostly within the circle, see
+//! [`crate::SmallestEnclosingH3Builder::min_coverage_fraction`].
+
+use crate::{Result, SmallestEnclosingH3, CIRCLE_VERTICES};
+use geo::{Area, BooleanOps, LineString, Polygon};
+use h3o::{CellIndex, LatLng};
+
+impl SmallestEnclosingH3 {
+    // `cells` less those with under `fraction` of their area within the
+    // circle. Only the cells reaching past the circle are measured, as
+    // polygons on the local plane around the center.
+    pub(crate) fn retain_min_coverage(
+        &self,
+        cells: &mut Vec<CellIndex>,
+        fraction: f64,
+    ) -> Result<()> {
+        let circle = self
+            .circle_coordinates(self.radius_meters, CIRCLE_VERTICES)?
+            .iter()
+            .map(|c| LatLng::new(c[1], c[0]))
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        let circle = self.planar_polygon(circle);
+
+        cells.retain(|cell| {
+            let boundary = cell.boundary();
+            if boundary
+                .iter()
+                .all(|vertex| self.distance_from_center(*vertex) <= self.radius_meters)
+            {
+                return true;
+            }
+            let polygon = self.planar_polygon(boundary.iter().copied());
+            polygon.intersection(&circle).unsigned_area() >= fraction * polygon.unsigned_area()
+        });
+        Ok(())
+    }
+
+    fn planar_polygon(&self, points: impl IntoIterator<Item = LatLng>) -> Polygon<f64> {
+        let ring: Vec<(f64, f64)> = points
+            .into_iter()
+            .map(|point| self.local_plane(point))
+            .collect();
+        Polygon::new(LineString::from(ring), vec![])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::SmallestEnclosingH3Error;
+    use crate::{Algorithm, SmallestEnclosingH3Builder};
+    use h3o::geom::ContainmentMode;
+    use h3o::{LatLng, Resolution};
+
+    fn builder() -> SmallestEnclosingH3Builder {
+        let center = LatLng::new(33.4484, -112.0740).unwrap();
+        SmallestEnclosingH3Builder::new(center, 500.0, Resolution::Ten)
+    }
+
+    #[test]
+    fn test_min_coverage_fraction_monotonic() {
+        let counts: Vec<usize> = [
+            0.01, 0.05, 0.1, 0.2, 0.3, 0.4, 0.5, 0.6, 0.7, 0.8, 0.9, 0.99,
+        ]
+        .into_iter()
+        .map(|fraction| {
+            let coverage = builder()
+                .min_coverage_fraction(fraction)
+                .unwrap()
+                .build()
+                .unwrap();
+            let disk = coverage.disk().unwrap();
+            // The ring is the rim of the trimmed disk
+            let ring = coverage.hexagons().unwrap();
+            assert!(!ring.is_empty() && ring.iter().all(|cell| disk.contains(cell)));
+            disk.len()
+        })
+        .collect();
+        assert!(
+            counts.windows(2).all(|pair| pair[0] >= pair[1]),
+            "{counts:?}"
+        );
+        assert!(counts[0] > counts[counts.len() - 1], "{counts:?}");
+
+        let untrimmed = builder().build().unwrap().disk().unwrap().len();
+        assert!(counts[0] <= untrimmed);
+    }
+
+    #[test]
+    fn test_min_coverage_fraction_near_zero_intersects() {
+        let intersects = || {
+            builder().algorithm(Algorithm::Polyfill {
+                containment: ContainmentMode::IntersectsBoundary,
+                vertices: 64,
+            })
+        };
+        let mut expected = intersects().build().unwrap().disk().unwrap();
+        let mut trimmed = intersects()
+            .min_coverage_fraction(1e-9)
+            .unwrap()
+            .build()
+            .unwrap()
+            .disk()
+            .unwrap();
+        expected.sort_unstable();
+        trimmed.sort_unstable();
+        assert_eq!(trimmed, expected);
+    }
+
+    #[test]
+    fn test_min_coverage_fraction_rejected() {
+        for fraction in [0.0, -0.1, 1.5, f64::NAN] {
+            assert!(matches!(
+                builder().min_coverage_fraction(fraction),
+                Err(SmallestEnclosingH3Error::InvalidFraction(_))
+            ));
+        }
+        let coverage = builder()
+            .min_coverage_fraction(1.0)
+            .unwrap()
+            .build()
+            .unwrap();
+        assert_eq!(coverage.min_coverage_fraction(), Some(1.0));
+
+        let center = LatLng::new(33.4484, -112.0740).unwrap();
+        for builder in [
+            SmallestEnclosingH3Builder::from_bbox(33.4, -112.1, 33.5, -112.0, Resolution::Eight)
+                .unwrap(),
+            SmallestEnclosingH3Builder::new(center, 5_000_000.0, Resolution::Two),
+        ] {
+            assert!(matches!(
+                builder.min_coverage_fraction(0.5).unwrap().build(),
+                Err(SmallestEnclosingH3Error::UnsupportedGeometry(_))
+            ));
+        }
+    }
+}
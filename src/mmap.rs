@@ -0,0 +1,325 @@
+//! Read-only, memory-mapped coverage sets.
+//!
+//! [`SmallestEnclosingH3::write_index_file`] stores a coverage as a sorted
+//! array of little-endian `u64` cell indexes behind a 32 byte header:
+//!
+//! | offset | size | field                                   |
+//! |--------|------|-----------------------------------------|
+//! | 0      | 8    | magic `SEH3IDX\0`                       |
+//! | 8      | 4    | format version (`1`)                    |
+//! | 12     | 1    | H3 resolution of the cells              |
+//! | 13     | 3    | reserved, zero                          |
+//! | 16     | 8    | cell count                              |
+//! | 24     | 8    | FNV-1a checksum of the cell bytes       |
+//!
+//! [`MappedCoverage::open`] maps such a file without copying it to the heap
+//! and answers membership queries by binary search over the mapping.
+//!
+//! The `mmap` feature is Unix-only: the module is left out on other targets.
+
+use crate::{error::SmallestEnclosingH3Error, Result, SmallestEnclosingH3};
+use h3o::{CellIndex, LatLng, Resolution};
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::os::unix::io::AsRawFd;
+use std::path::Path;
+use std::{ptr, slice};
+
+const MAGIC: &[u8; 8] = b"SEH3IDX\0";
+const VERSION: u32 = 1;
+const HEADER_LEN: usize = 32;
+const CELL_LEN: usize = 8;
+
+impl SmallestEnclosingH3 {
+    /// Write [`Self::hexagons`] to `path` in the index format read by
+    /// [`MappedCoverage::open`]
+    pub fn write_index_file(&self, path: impl AsRef<Path>) -> Result<()> {
+        let mut cells: Vec<u64> = self.hexagons()?.into_iter().map(u64::from).collect();
+        cells.sort_unstable();
+        cells.dedup();
+
+        let bytes: Vec<u8> = cells.iter().flat_map(|c| c.to_le_bytes()).collect();
+        let mut header = [0u8; HEADER_LEN];
+        header[0..8].copy_from_slice(MAGIC);
+        header[8..12].copy_from_slice(&VERSION.to_le_bytes());
+        header[12] = u8::from(self.resolution);
+        header[16..24].copy_from_slice(&(cells.len() as u64).to_le_bytes());
+        header[24..32].copy_from_slice(&checksum(&bytes).to_le_bytes());
+
+        let mut writer = BufWriter::new(File::create(path)?);
+        writer.write_all(&header)?;
+        writer.write_all(&bytes)?;
+        writer.flush()?;
+        Ok(())
+    }
+}
+
+/// A coverage index file mapped read-only into memory
+#[derive(Debug)]
+pub struct MappedCoverage {
+    ptr: *mut libc::c_void,
+    map_len: usize,
+    resolution: Resolution,
+    count: usize,
+}
+
+// The mapping is private and read-only, so sharing it between threads is safe
+unsafe impl Send for MappedCoverage {}
+unsafe impl Sync for MappedCoverage {}
+
+impl MappedCoverage {
+    /// Map the index file at `path`, validating its header and checksum, and
+    /// that its cells are valid, at the stored resolution and in ascending
+    /// order
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let corrupt = |reason: &str| {
+            SmallestEnclosingH3Error::CorruptIndexFile(format!("{}: {reason}", path.display()))
+        };
+
+        let file = File::open(path)?;
+        let len = file.metadata()?.len() as usize;
+        if len < HEADER_LEN {
+            return Err(corrupt("file is shorter than the header"));
+        }
+
+        // SAFETY: `len` is non-zero and the file stays open for the call; the
+        // mapping outlives the descriptor and is released in `Drop`.
+        let ptr = unsafe {
+            libc::mmap(
+                ptr::null_mut(),
+                len,
+                libc::PROT_READ,
+                libc::MAP_PRIVATE,
+                file.as_raw_fd(),
+                0,
+            )
+        };
+        if ptr == libc::MAP_FAILED {
+            return Err(std::io::Error::last_os_error().into());
+        }
+
+        // Constructed before validation so the mapping is released on error
+        let mut mapped = Self {
+            ptr,
+            map_len: len,
+            resolution: Resolution::Zero,
+            count: 0,
+        };
+
+        let header = &mapped.bytes()[..HEADER_LEN];
+        if &header[0..8] != MAGIC {
+            return Err(corrupt("bad magic"));
+        }
+        if read_u32(&header[8..12]) != VERSION {
+            return Err(corrupt("unsupported version"));
+        }
+        let resolution =
+            Resolution::try_from(header[12]).map_err(|_| corrupt("invalid resolution"))?;
+        let count = read_u64(&header[16..24]) as usize;
+        let expected = read_u64(&header[24..32]);
+        if count
+            .checked_mul(CELL_LEN)
+            .and_then(|n| n.checked_add(HEADER_LEN))
+            != Some(len)
+        {
+            return Err(corrupt("cell count does not match the file length"));
+        }
+        if checksum(&mapped.bytes()[HEADER_LEN..]) != expected {
+            return Err(corrupt("checksum mismatch"));
+        }
+
+        // The checksum only vouches for the bytes as written; `iter` and the
+        // binary search also rely on what they hold
+        mapped.count = count;
+        let mut previous = None;
+        for i in 0..count {
+            let raw = mapped.raw(i);
+            match CellIndex::try_from(raw) {
+                Ok(cell) if cell.resolution() == resolution => {}
+                _ => return Err(corrupt(&format!("invalid cell {raw:#x}"))),
+            }
+            if previous.is_some_and(|previous| previous >= raw) {
+                return Err(corrupt("cells are not in ascending order"));
+            }
+            previous = Some(raw);
+        }
+
+        mapped.resolution = resolution;
+        Ok(mapped)
+    }
+
+    /// Resolution of the stored cells
+    pub fn resolution(&self) -> Resolution {
+        self.resolution
+    }
+
+    /// Number of stored cells
+    pub fn len(&self) -> usize {
+        self.count
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    /// Whether `cell` is part of the coverage
+    pub fn contains_cell(&self, cell: CellIndex) -> bool {
+        let target = u64::from(cell);
+        let (mut low, mut high) = (0, self.count);
+        while low < high {
+            let mid = low + (high - low) / 2;
+            match self.raw(mid).cmp(&target) {
+                std::cmp::Ordering::Less => low = mid + 1,
+                std::cmp::Ordering::Greater => high = mid,
+                std::cmp::Ordering::Equal => return true,
+            }
+        }
+        false
+    }
+
+    /// Whether the cell containing `point` at the stored resolution is part
+    /// of the coverage
+    pub fn contains_point(&self, point: LatLng) -> bool {
+        self.contains_cell(point.to_cell(self.resolution))
+    }
+
+    /// Stream the stored cells in ascending index order
+    pub fn iter(&self) -> impl Iterator<Item = CellIndex> + '_ {
+        (0..self.count)
+            .map(|i| CellIndex::try_from(self.raw(i)).expect("cells are validated in `open`"))
+    }
+
+    fn raw(&self, i: usize) -> u64 {
+        let offset = HEADER_LEN + i * CELL_LEN;
+        read_u64(&self.bytes()[offset..offset + CELL_LEN])
+    }
+
+    fn bytes(&self) -> &[u8] {
+        // SAFETY: `ptr` maps `map_len` readable bytes until `self` is dropped
+        unsafe { slice::from_raw_parts(self.ptr as *const u8, self.map_len) }
+    }
+}
+
+impl Drop for MappedCoverage {
+    fn drop(&mut self) {
+        // SAFETY: `ptr` and `map_len` describe a mapping created in `open`
+        unsafe {
+            libc::munmap(self.ptr, self.map_len);
+        }
+    }
+}
+
+fn read_u32(bytes: &[u8]) -> u32 {
+    u32::from_le_bytes(bytes.try_into().expect("4 bytes"))
+}
+
+fn read_u64(bytes: &[u8]) -> u64 {
+    u64::from_le_bytes(bytes.try_into().expect("8 bytes"))
+}
+
+// 64-bit FNV-1a
+fn checksum(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x0000_0100_0000_01b3)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SmallestEnclosingH3Builder;
+    use std::collections::HashSet;
+
+    fn temp_path(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("seh3-{}-{name}.idx", std::process::id()))
+    }
+
+    #[test]
+    fn test_index_round_trip() {
+        let center = LatLng::new(33.4484, -112.0740).unwrap();
+        let smallest_enclosing_h3 =
+            SmallestEnclosingH3Builder::new(center, 2000.0, Resolution::Twelve)
+                .build()
+                .unwrap();
+        let path = temp_path("round-trip");
+        smallest_enclosing_h3.write_index_file(&path).unwrap();
+
+        let in_memory: HashSet<CellIndex> = smallest_enclosing_h3
+            .hexagons()
+            .unwrap()
+            .into_iter()
+            .collect();
+        let mapped = MappedCoverage::open(&path).unwrap();
+        assert_eq!(mapped.resolution(), Resolution::Twelve);
+        assert_eq!(mapped.len(), in_memory.len());
+
+        let streamed: Vec<CellIndex> = mapped.iter().collect();
+        assert!(streamed.windows(2).all(|w| w[0] < w[1]));
+        assert_eq!(streamed.iter().copied().collect::<HashSet<_>>(), in_memory);
+
+        // Compare membership over the ring and its neighbourhood
+        let candidates = in_memory
+            .iter()
+            .flat_map(|cell| cell.grid_disk::<Vec<_>>(1))
+            .collect::<HashSet<_>>();
+        for cell in candidates {
+            assert_eq!(mapped.contains_cell(cell), in_memory.contains(&cell));
+            let point = LatLng::from(cell);
+            assert_eq!(mapped.contains_point(point), in_memory.contains(&cell));
+        }
+
+        drop(mapped);
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_index_detects_corruption() {
+        let center = LatLng::new(33.4484, -112.0740).unwrap();
+        let smallest_enclosing_h3 =
+            SmallestEnclosingH3Builder::new(center, 50.0, Resolution::Twelve)
+                .build()
+                .unwrap();
+        let path = temp_path("corrupt");
+        smallest_enclosing_h3.write_index_file(&path).unwrap();
+        let original = std::fs::read(&path).unwrap();
+
+        let mut flipped = original.clone();
+        *flipped.last_mut().unwrap() ^= 1;
+        let truncated = original[..original.len() - 3].to_vec();
+        let short = original[..10].to_vec();
+
+        // Cells that aren't valid, at another resolution or out of order,
+        // each with a checksum that matches them
+        let resealed = |cells: &[u64]| {
+            let bytes: Vec<u8> = cells.iter().flat_map(|c| c.to_le_bytes()).collect();
+            let mut file = original[..HEADER_LEN].to_vec();
+            file[16..24].copy_from_slice(&(cells.len() as u64).to_le_bytes());
+            file[24..32].copy_from_slice(&checksum(&bytes).to_le_bytes());
+            file.extend(bytes);
+            file
+        };
+        let cells: Vec<u64> = smallest_enclosing_h3
+            .hexagons_sorted(crate::CellOrder::Index)
+            .unwrap()
+            .into_iter()
+            .map(u64::from)
+            .collect();
+        let coarser = u64::from(center.to_cell(Resolution::Eleven));
+        let invalid = resealed(&[cells[0], u64::MAX]);
+        assert!(coarser < cells[0]);
+        let mixed = resealed(&[coarser, cells[0]]);
+        let unsorted = resealed(&[cells[1], cells[0]]);
+        assert!(MappedCoverage::open(&path).is_ok());
+
+        for bytes in [flipped, truncated, short, invalid, mixed, unsorted] {
+            std::fs::write(&path, bytes).unwrap();
+            assert!(matches!(
+                MappedCoverage::open(&path),
+                Err(SmallestEnclosingH3Error::CorruptIndexFile(_))
+            ));
+        }
+
+        std::fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,150 @@
+//! The covered cells nearest to an arbitrary point.
+
+use crate::{grid_ring, haversine_distance, Result, SmallestEnclosingH3};
+use h3o::{CellIndex, LatLng};
+use std::collections::HashSet;
+
+impl SmallestEnclosingH3 {
+    /// The `k` cells of [`Self::disk`] whose centroids are nearest to
+    /// `point`, with their great-circle distances in meters, nearest first.
+    /// Ties are broken by index, and fewer than `k` cells come back when the
+    /// coverage is smaller.
+    ///
+    /// Rings of cells are searched outwards from `point`'s cell until no
+    /// unexplored cell can beat the `k`-th best distance: every cell beyond a
+    /// ring lies outside its outer edges, so is at least as far as the nearest
+    /// ring vertex less half the longest edge. The result is exact; a point so
+    /// far away that the search would outgrow the coverage scans the coverage
+    /// instead.
+    pub fn nearest_cells(&self, point: LatLng, k: usize) -> Result<Vec<(CellIndex, f64)>> {
+        let coverage: HashSet<CellIndex> = self.disk()?.into_iter().collect();
+        if k == 0 || coverage.is_empty() {
+            return Ok(Vec::new());
+        }
+        let distance = |cell: CellIndex| (cell, haversine_distance(point, LatLng::from(cell)));
+
+        let query_cell = point.to_cell(self.resolution);
+        let mut nearest = Vec::new();
+        let mut explored = 0;
+        for ring_k in 0.. {
+            let ring = grid_ring(query_cell, ring_k);
+            explored += ring.len();
+            if ring.is_empty() || explored > coverage.len() {
+                // Scanning everything is cheaper from here
+                nearest = coverage.iter().map(|cell| distance(*cell)).collect();
+                break;
+            }
+            nearest.extend(
+                ring.iter()
+                    .filter(|cell| coverage.contains(cell))
+                    .map(|cell| distance(*cell)),
+            );
+
+            if nearest.len() >= k {
+                sort_by_distance(&mut nearest);
+                nearest.truncate(k);
+                if nearest[k - 1].1 < beyond_ring(point, &ring) {
+                    break;
+                }
+            }
+        }
+
+        sort_by_distance(&mut nearest);
+        nearest.truncate(k);
+        Ok(nearest)
+    }
+}
+
+// A lower bound on the distance from `point`, inside the disk `ring` closes,
+// to any cell centroid beyond `ring`
+fn beyond_ring(point: LatLng, ring: &[CellIndex]) -> f64 {
+    let mut nearest_vertex = f64::INFINITY;
+    let mut longest_edge: f64 = 0.0;
+    for cell in ring {
+        let boundary = cell.boundary();
+        for (i, vertex) in boundary.iter().enumerate() {
+            let next = boundary[(i + 1) % boundary.len()];
+            nearest_vertex = nearest_vertex.min(haversine_distance(point, *vertex));
+            longest_edge = longest_edge.max(haversine_distance(*vertex, next));
+        }
+    }
+    nearest_vertex - longest_edge / 2.0
+}
+
+fn sort_by_distance(cells: &mut [(CellIndex, f64)]) {
+    cells.sort_unstable_by(|a, b| a.1.total_cmp(&b.1).then(a.0.cmp(&b.0)));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SmallestEnclosingH3Builder;
+    use h3o::Resolution;
+
+    fn brute_force(
+        smallest_enclosing_h3: &SmallestEnclosingH3,
+        point: LatLng,
+        k: usize,
+    ) -> Vec<(CellIndex, f64)> {
+        let mut cells: Vec<(CellIndex, f64)> = smallest_enclosing_h3
+            .disk()
+            .unwrap()
+            .into_iter()
+            .map(|cell| (cell, haversine_distance(point, LatLng::from(cell))))
+            .collect();
+        sort_by_distance(&mut cells);
+        cells.truncate(k);
+        cells
+    }
+
+    #[test]
+    fn test_nearest_cells_match_brute_force() {
+        let center = LatLng::new(33.4484, -112.0740).unwrap();
+        let smallest_enclosing_h3 =
+            SmallestEnclosingH3Builder::new(center, 1000.0, Resolution::Ten)
+                .build()
+                .unwrap();
+
+        for (lat, lng) in [
+            // Inside
+            (33.4484, -112.0740),
+            (33.4510, -112.0700),
+            // Near the edge, on both sides
+            (33.4570, -112.0740),
+            (33.4484, -112.0600),
+            // Far away
+            (33.5, -112.0),
+            (34.0, -111.0),
+        ] {
+            let point = LatLng::new(lat, lng).unwrap();
+            for k in [1, 5, 20, 200] {
+                assert_eq!(
+                    smallest_enclosing_h3.nearest_cells(point, k).unwrap(),
+                    brute_force(&smallest_enclosing_h3, point, k),
+                    "{lat} {lng} {k}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_nearest_cells_edge_cases() {
+        let center = LatLng::new(33.4484, -112.0740).unwrap();
+        let smallest_enclosing_h3 =
+            SmallestEnclosingH3Builder::new(center, 50.0, Resolution::Twelve)
+                .build()
+                .unwrap();
+        let disk = smallest_enclosing_h3.disk().unwrap();
+
+        assert!(smallest_enclosing_h3
+            .nearest_cells(center, 0)
+            .unwrap()
+            .is_empty());
+        let all = smallest_enclosing_h3
+            .nearest_cells(center, disk.len() + 10)
+            .unwrap();
+        assert_eq!(all.len(), disk.len());
+        assert!(all.windows(2).all(|pair| pair[0].1 <= pair[1].1));
+        assert_eq!(all[0].0, center.to_cell(Resolution::Twelve));
+    }
+}
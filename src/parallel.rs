@@ -0,0 +1,47 @@
+// Inputs shorter than this are mapped on the calling thread, spawning threads
+// costs more than it saves.
+#[cfg(all(feature = "parallel", not(target_arch = "wasm32")))]
+const MIN_PARALLEL_LEN: usize = 64;
+
+/// Map `f` over `items`, preserving input order.
+///
+/// With the `parallel` feature enabled the work is split into contiguous chunks
+/// handled by scoped threads; the output is identical to the serial path.
+/// `wasm32` has no threads to spawn, so it always takes the serial path.
+pub(crate) fn map<T, U, F>(items: &[T], f: F) -> Vec<U>
+where
+    T: Sync,
+    U: Send,
+    F: Fn(&T) -> U + Sync,
+{
+    #[cfg(all(feature = "parallel", not(target_arch = "wasm32")))]
+    if items.len() >= MIN_PARALLEL_LEN {
+        let threads = std::thread::available_parallelism()
+            .map(|n| n.get())
+            .unwrap_or(1);
+        let chunk_size = items.len().div_ceil(threads);
+        let f = &f;
+        return std::thread::scope(|scope| {
+            let handles: Vec<_> = items
+                .chunks(chunk_size)
+                .map(|chunk| scope.spawn(move || chunk.iter().map(f).collect::<Vec<U>>()))
+                .collect();
+            handles
+                .into_iter()
+                .flat_map(|handle| handle.join().expect("parallel map worker panicked"))
+                .collect()
+        });
+    }
+
+    items.iter().map(f).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_map_preserves_order() {
+        let items: Vec<u64> = (0..1000).collect();
+        let mapped = super::map(&items, |x| x * 2);
+        assert_eq!(mapped, items.iter().map(|x| x * 2).collect::<Vec<_>>());
+    }
+}
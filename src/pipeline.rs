@@ -0,0 +1,497 @@
+//! Declarative build → filter → transform → export sequences, see
+//! [`Pipeline`].
+
+use crate::error::SmallestEnclosingH3Error;
+use crate::{cell_polygon, pad_cells, EffectiveParameters, Result, SmallestEnclosingH3Builder};
+use geo::{Intersects, LineString, Polygon};
+use h3o::CellIndex;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+/// The output format of [`Step::Export`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum Format {
+    /// A `hex_id,resolution` row per cell under a header, in index order
+    Csv,
+    /// A compact FeatureCollection of hexagon features, as built by
+    /// [`crate::geojson_export::create_hex_feature`] with their `resolution`,
+    /// in index order, so equal cell sets serialise byte for byte the same
+    #[cfg(feature = "geojson_export")]
+    #[cfg_attr(feature = "serde", serde(rename = "canonical_geojson"))]
+    CanonicalGeoJson,
+}
+
+/// A step of a [`Pipeline`], run on the cells left by the previous one
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(tag = "step", rename_all = "snake_case")
+)]
+pub enum Step {
+    /// Keep the cells overlapping `polygon`, a ring of `[lng, lat]` degrees
+    Mask { polygon: Vec<[f64; 2]> },
+    /// Add `rings` rings of neighbours around the cells
+    Pad { rings: u32 },
+    /// Replace complete sets of siblings by their parent, see
+    /// [`CellIndex::compact`]
+    Compact,
+    /// Render the cells as the pipeline's artifact, leaving them as they are
+    Export { format: Format },
+}
+
+impl Step {
+    /// The step's name, as used in diagnostics and errors
+    pub fn name(&self) -> &'static str {
+        match self {
+            Step::Mask { .. } => "mask",
+            Step::Pad { .. } => "pad",
+            Step::Compact => "compact",
+            Step::Export { .. } => "export",
+        }
+    }
+
+    fn run(&self, cells: Vec<CellIndex>, artifact: &mut Option<String>) -> Result<Vec<CellIndex>> {
+        match self {
+            Step::Mask { polygon } => mask(cells, polygon),
+            Step::Pad { rings } => {
+                let mut padded = pad_cells(&cells, *rings);
+                padded.extend(cells);
+                padded.sort_unstable();
+                Ok(padded)
+            }
+            Step::Compact => {
+                let mut compacted: Vec<CellIndex> = CellIndex::compact(cells)?.collect();
+                compacted.sort_unstable();
+                Ok(compacted)
+            }
+            Step::Export { format } => {
+                *artifact = Some(export(&cells, *format)?);
+                Ok(cells)
+            }
+        }
+    }
+}
+
+/// The steps of a [`Pipeline`], as read from a configuration file
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PipelineSpec {
+    pub steps: Vec<Step>,
+}
+
+/// What one step of a [`Pipeline`] did
+#[derive(Debug, Clone, PartialEq)]
+pub struct StepDiagnostics {
+    /// 1-based position of the step, the build being step 1
+    pub index: usize,
+    /// See [`Step::name`]; `"build"` for the build
+    pub step: &'static str,
+    pub cells_in: usize,
+    pub cells_out: usize,
+    pub duration: Duration,
+}
+
+/// The outcome of [`Pipeline::run`]
+#[derive(Debug, Clone)]
+pub struct PipelineResult {
+    /// The cells left by the last step, in index order
+    pub cells: Vec<CellIndex>,
+    /// The output of the last [`Step::Export`], if any
+    pub artifact: Option<String>,
+    /// One entry per step, the build included
+    pub diagnostics: Vec<StepDiagnostics>,
+    /// The parameters the coverage was built with
+    pub effective_parameters: EffectiveParameters,
+}
+
+/// Builds a coverage, then runs a sequence of [`Step`]s on its
+/// [`crate::SmallestEnclosingH3::disk`].
+///
+/// ```
+/// use h3o::{LatLng, Resolution};
+/// use smallest_enclosing_h3::pipeline::{Format, Pipeline};
+/// use smallest_enclosing_h3::SmallestEnclosingH3Builder;
+///
+/// let center = LatLng::new(33.4484, -112.0740)?;
+/// let result = Pipeline::new(SmallestEnclosingH3Builder::new(center, 1000.0, Resolution::Ten))
+///     .pad(1)
+///     .compact()
+///     .export(Format::Csv)
+///     .run()?;
+/// assert!(result.artifact.unwrap().starts_with("hex_id,resolution\n"));
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+///
+/// A failing step is reported as a
+/// [`SmallestEnclosingH3Error::PipelineStep`] giving its index and name.
+#[derive(Debug)]
+pub struct Pipeline {
+    builder: SmallestEnclosingH3Builder,
+    steps: Vec<Step>,
+}
+
+impl Pipeline {
+    pub fn new(builder: SmallestEnclosingH3Builder) -> Self {
+        Self {
+            builder,
+            steps: Vec::new(),
+        }
+    }
+
+    /// A pipeline running the steps of `spec` on the coverage of `builder`
+    pub fn from_spec(builder: SmallestEnclosingH3Builder, spec: PipelineSpec) -> Self {
+        Self {
+            builder,
+            steps: spec.steps,
+        }
+    }
+
+    /// Append `step`
+    pub fn step(mut self, step: Step) -> Self {
+        self.steps.push(step);
+        self
+    }
+
+    /// Append a [`Step::Mask`]
+    pub fn mask(self, polygon: Vec<[f64; 2]>) -> Self {
+        self.step(Step::Mask { polygon })
+    }
+
+    /// Append a [`Step::Pad`]
+    pub fn pad(self, rings: u32) -> Self {
+        self.step(Step::Pad { rings })
+    }
+
+    /// Append a [`Step::Compact`]
+    pub fn compact(self) -> Self {
+        self.step(Step::Compact)
+    }
+
+    /// Append a [`Step::Export`]
+    pub fn export(self, format: Format) -> Self {
+        self.step(Step::Export { format })
+    }
+
+    /// Build the coverage and run every step in order
+    pub fn run(self) -> Result<PipelineResult> {
+        let in_step = |index: usize, step: &'static str| {
+            move |source| SmallestEnclosingH3Error::PipelineStep {
+                index,
+                step,
+                source: Box::new(source),
+            }
+        };
+
+        let start = Instant::now();
+        let (mut cells, effective_parameters) = self
+            .builder
+            .build()
+            .and_then(|smallest_enclosing_h3| {
+                Ok((
+                    smallest_enclosing_h3.disk()?,
+                    smallest_enclosing_h3.effective_parameters()?,
+                ))
+            })
+            .map_err(in_step(1, "build"))?;
+        cells.sort_unstable();
+        let mut diagnostics = vec![StepDiagnostics {
+            index: 1,
+            step: "build",
+            cells_in: 0,
+            cells_out: cells.len(),
+            duration: start.elapsed(),
+        }];
+
+        let mut artifact = None;
+        for (index, step) in (2..).zip(&self.steps) {
+            let start = Instant::now();
+            let cells_in = cells.len();
+            cells = step
+                .run(cells, &mut artifact)
+                .map_err(in_step(index, step.name()))?;
+            diagnostics.push(StepDiagnostics {
+                index,
+                step: step.name(),
+                cells_in,
+                cells_out: cells.len(),
+                duration: start.elapsed(),
+            });
+        }
+
+        Ok(PipelineResult {
+            cells,
+            artifact,
+            diagnostics,
+            effective_parameters,
+        })
+    }
+
+    /// [`Self::run`], then write the artifact to `path`. Nothing is written
+    /// unless every step succeeds, and the file is replaced in one rename, so
+    /// `path` never holds a partial artifact.
+    pub fn run_to_file(self, path: impl AsRef<Path>) -> Result<PipelineResult> {
+        let path = path.as_ref();
+        let result = self.run()?;
+        let Some(artifact) = &result.artifact else {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "the pipeline has no export step",
+            )
+            .into());
+        };
+
+        let mut partial = path.as_os_str().to_owned();
+        partial.push(".partial");
+        std::fs::write(&partial, artifact)?;
+        std::fs::rename(&partial, path)?;
+        Ok(result)
+    }
+}
+
+// The cells overlapping the polygon with exterior `ring`
+fn mask(cells: Vec<CellIndex>, ring: &[[f64; 2]]) -> Result<Vec<CellIndex>> {
+    let polygon = Polygon::new(
+        LineString::from(
+            ring.iter()
+                .map(|&[lng, lat]| (lng, lat))
+                .collect::<Vec<_>>(),
+        ),
+        vec![],
+    );
+    // Rejects non-finite coordinates, as `coverage_from_geo` would
+    h3o::geom::Polygon::from_degrees(polygon.clone())?;
+
+    Ok(cells
+        .into_iter()
+        .filter(|cell| cell_polygon(*cell).intersects(&polygon))
+        .collect())
+}
+
+fn export(cells: &[CellIndex], format: Format) -> Result<String> {
+    match format {
+        Format::Csv => {
+            let mut csv = String::from("hex_id,resolution\n");
+            for cell in cells {
+                csv.push_str(&format!("{cell},{}\n", u8::from(cell.resolution())));
+            }
+            Ok(csv)
+        }
+        #[cfg(feature = "geojson_export")]
+        Format::CanonicalGeoJson => {
+            let features = cells
+                .iter()
+                .map(|cell| {
+                    let mut feature = crate::geojson_export::create_hex_feature(*cell)?;
+                    feature.set_property("resolution", u8::from(cell.resolution()));
+                    Ok(feature)
+                })
+                .collect::<Result<Vec<_>>>()?;
+            let collection = geojson::FeatureCollection {
+                bbox: None,
+                features,
+                foreign_members: None,
+            };
+            Ok(serde_json::to_string(&collection)?)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SmallestEnclosingH3;
+    use h3o::{LatLng, Resolution};
+
+    fn builder() -> SmallestEnclosingH3Builder {
+        let center = LatLng::new(33.4484, -112.0740).unwrap();
+        SmallestEnclosingH3Builder::new(center, 1000.0, Resolution::Ten)
+    }
+
+    // The eastern half of the circle
+    const EAST: [[f64; 2]; 5] = [
+        [-112.0740, 33.40],
+        [-112.00, 33.40],
+        [-112.00, 33.50],
+        [-112.0740, 33.50],
+        [-112.0740, 33.40],
+    ];
+
+    #[test]
+    fn test_pipeline_diagnostics() {
+        let result = Pipeline::new(builder())
+            .mask(EAST.to_vec())
+            .pad(1)
+            .export(Format::Csv)
+            .run()
+            .unwrap();
+
+        let disk = builder().build().unwrap().disk().unwrap();
+        let masked = mask(disk.clone(), &EAST).unwrap();
+        assert!(!masked.is_empty() && masked.len() < disk.len());
+        let padding = pad_cells(&masked, 1);
+
+        let counts: Vec<(usize, &str, usize, usize)> = result
+            .diagnostics
+            .iter()
+            .map(|d| (d.index, d.step, d.cells_in, d.cells_out))
+            .collect();
+        assert_eq!(
+            counts,
+            [
+                (1, "build", 0, disk.len()),
+                (2, "mask", disk.len(), masked.len()),
+                (3, "pad", masked.len(), masked.len() + padding.len()),
+                (
+                    4,
+                    "export",
+                    masked.len() + padding.len(),
+                    masked.len() + padding.len()
+                ),
+            ]
+        );
+
+        let artifact = result.artifact.unwrap();
+        assert_eq!(artifact.lines().count(), result.cells.len() + 1);
+        assert!(result.cells.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    fn test_pipeline_effective_parameters() {
+        let requested = LatLng::new(10.0, 190.0).unwrap();
+        let builder = SmallestEnclosingH3Builder::new(requested, 1000.0, Resolution::Nine)
+            .snap_center_to_resolution(Resolution::Seven);
+        let result = Pipeline::new(builder).run().unwrap();
+        assert_eq!(result.effective_parameters.requested_center, requested);
+        assert_eq!(result.effective_parameters.adjustments.len(), 2);
+    }
+
+    #[test]
+    fn test_pipeline_compact() {
+        let result = Pipeline::new(builder()).compact().run().unwrap();
+        let smallest_enclosing_h3: SmallestEnclosingH3 = builder().build().unwrap();
+        let mut compacted = smallest_enclosing_h3.compact().unwrap();
+        compacted.sort_unstable();
+        assert_eq!(result.cells, compacted);
+        assert_eq!(result.artifact, None);
+    }
+
+    #[test]
+    fn test_failing_step_leaves_no_file() {
+        let path = std::env::temp_dir().join(format!("seh3-{}-pipeline.csv", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        let mut bad = EAST.to_vec();
+        bad[1] = [f64::NAN, 33.4];
+        let err = Pipeline::new(builder())
+            .pad(1)
+            .mask(bad)
+            .export(Format::Csv)
+            .run_to_file(&path)
+            .unwrap_err();
+        match &err {
+            SmallestEnclosingH3Error::PipelineStep {
+                index: 3,
+                step: "mask",
+                source,
+            } => assert!(matches!(
+                **source,
+                SmallestEnclosingH3Error::InvalidGeometry(_)
+            )),
+            other => panic!("Expected a mask step error, got {other:?}"),
+        }
+        assert!(err.to_string().starts_with("step 3: mask: "), "{err}");
+        assert!(!path.exists());
+
+        // Compacting twice mixes resolutions
+        let err = Pipeline::new(builder())
+            .compact()
+            .compact()
+            .run()
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            SmallestEnclosingH3Error::PipelineStep {
+                index: 3,
+                step: "compact",
+                ..
+            }
+        ));
+
+        let result = Pipeline::new(builder())
+            .export(Format::Csv)
+            .run_to_file(&path)
+            .unwrap();
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            result.artifact.unwrap()
+        );
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[cfg(feature = "geojson_export")]
+    #[test]
+    fn test_canonical_geojson() {
+        let export = |pipeline: Pipeline| pipeline.run().unwrap().artifact.unwrap();
+        let geojson = export(
+            Pipeline::new(builder())
+                .compact()
+                .export(Format::CanonicalGeoJson),
+        );
+        assert_eq!(
+            geojson,
+            export(
+                Pipeline::new(builder())
+                    .compact()
+                    .export(Format::CanonicalGeoJson)
+            )
+        );
+
+        let collection: geojson::FeatureCollection = geojson
+            .parse::<geojson::GeoJson>()
+            .unwrap()
+            .try_into()
+            .unwrap();
+        assert!(collection
+            .features
+            .iter()
+            .any(|f| f.property("resolution").and_then(|v| v.as_u64()) == Some(9)));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_spec_from_json() {
+        let json = r#"{"steps": [
+            {"step": "mask", "polygon": [[-112.074, 33.4], [-112.0, 33.4], [-112.0, 33.5], [-112.074, 33.5], [-112.074, 33.4]]},
+            {"step": "pad", "rings": 1},
+            {"step": "export", "format": "csv"}
+        ]}"#;
+        let spec: PipelineSpec = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            spec.steps,
+            [
+                Step::Mask {
+                    polygon: EAST.to_vec()
+                },
+                Step::Pad { rings: 1 },
+                Step::Export {
+                    format: Format::Csv
+                },
+            ]
+        );
+
+        let from_spec = Pipeline::from_spec(builder(), spec).run().unwrap();
+        let chained = Pipeline::new(builder())
+            .mask(EAST.to_vec())
+            .pad(1)
+            .export(Format::Csv)
+            .run()
+            .unwrap();
+        assert_eq!(from_spec.artifact, chained.artifact);
+    }
+}
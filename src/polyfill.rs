@@ -0,0 +1,146 @@
+//! Coverage of the circle's polygon with h3o's polygon-to-cells, see
+//! [`crate::Algorithm::Polyfill`].
+
+use crate::{
+    error::SmallestEnclosingH3Error, outer_cells, CoverageMode, Result, SmallestEnclosingH3,
+};
+use h3o::geom::{ContainmentMode, PolyfillConfig, ToCells};
+use h3o::CellIndex;
+
+impl SmallestEnclosingH3 {
+    // The cells h3o selects for the circle's polygon of `vertices` points
+    // under `containment`, in index order, or for `CoverageMode::Ring` only
+    // those with a neighbour outside them.
+    pub(crate) fn polyfill_cells(
+        &self,
+        containment: ContainmentMode,
+        vertices: usize,
+        mode: CoverageMode,
+    ) -> Result<Vec<CellIndex>> {
+        let polygon = h3o::geom::Polygon::from_degrees(self.circle_polygon(vertices)?)?;
+        let config = PolyfillConfig::new(self.resolution).containment_mode(containment);
+        let estimated = polygon.max_cells_count(config);
+        if estimated > self.max_cells {
+            return Err(SmallestEnclosingH3Error::TooManyCells {
+                estimated,
+                limit: self.max_cells,
+            });
+        }
+
+        let mut cells: Vec<CellIndex> = polygon.to_cells(config).collect();
+        cells.sort_unstable();
+        cells.dedup();
+
+        if mode == CoverageMode::Ring {
+            cells = outer_cells(cells);
+        }
+        Ok(cells)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Algorithm, CellOrder, SmallestEnclosingH3Builder};
+    use h3o::{LatLng, Resolution};
+
+    fn polyfill(containment: ContainmentMode) -> Algorithm {
+        Algorithm::Polyfill {
+            containment,
+            vertices: 64,
+        }
+    }
+
+    #[test]
+    fn test_polyfill_phoenix() {
+        // The Phoenix case of `test_matches_python_implementation`, polyfilled
+        let center = LatLng::new(33.4484, -112.0740).unwrap();
+        let builder = || SmallestEnclosingH3Builder::new(center, 50.0, Resolution::Twelve);
+        let polyfilled = builder()
+            .algorithm(polyfill(ContainmentMode::Covers))
+            .build()
+            .unwrap();
+        let hexes: Vec<String> = polyfilled
+            .hexagons_sorted(CellOrder::Index)
+            .unwrap()
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(
+            hexes,
+            [
+                "8c29b6d357853ff",
+                "8c29b6d357859ff",
+                "8c29b6d35785bff",
+                "8c29b6d357a13ff",
+                "8c29b6d357a17ff",
+                "8c29b6d357a33ff",
+                "8c29b6d357a83ff",
+                "8c29b6d357a89ff",
+                "8c29b6d357a8bff",
+                "8c29b6d357aa3ff",
+                "8c29b6d357aa5ff",
+                "8c29b6d357aa7ff",
+                "8c29b6d357ab9ff",
+                "8c29b6d357abdff",
+                "8c29b6d357ac1ff",
+                "8c29b6d357ac3ff",
+                "8c29b6d357ac5ff",
+                "8c29b6d357aedff",
+            ]
+        );
+
+        // Tighter than the k-ring, yet covering the whole circle
+        let k_ring = builder().build().unwrap();
+        assert!(polyfilled.disk().unwrap().len() < k_ring.disk().unwrap().len());
+        assert!(polyfilled.is_fully_covered().unwrap());
+
+        // Centroid containment is tighter still, but leaves the rim uncovered
+        let centroids = builder()
+            .algorithm(polyfill(ContainmentMode::ContainsCentroid))
+            .build()
+            .unwrap();
+        assert!(centroids.disk().unwrap().len() < polyfilled.disk().unwrap().len());
+        assert!(!centroids.is_fully_covered().unwrap());
+    }
+
+    #[test]
+    fn test_polyfill_across_antimeridian() {
+        let center = LatLng::new(0.0, 179.999).unwrap();
+        let polyfilled = SmallestEnclosingH3Builder::new(center, 2000.0, Resolution::Eight)
+            .algorithm(polyfill(ContainmentMode::Covers))
+            .build()
+            .unwrap();
+        let disk = polyfilled.disk().unwrap();
+        assert!(disk.iter().any(|cell| LatLng::from(*cell).lng() < 0.0));
+        assert!(disk.iter().any(|cell| LatLng::from(*cell).lng() > 0.0));
+        assert!(polyfilled.is_fully_covered().unwrap());
+        let ring = polyfilled.hexagons().unwrap();
+        assert!(!ring.is_empty() && ring.len() < disk.len());
+    }
+
+    #[test]
+    fn test_polyfill_rejected() {
+        let algorithm = polyfill(ContainmentMode::Covers);
+        let center = LatLng::new(33.4484, -112.0740).unwrap();
+        for builder in [
+            SmallestEnclosingH3Builder::from_bbox(33.4, -112.1, 33.5, -112.0, Resolution::Eight)
+                .unwrap(),
+            SmallestEnclosingH3Builder::new(center, 5_000_000.0, Resolution::Two),
+        ] {
+            assert!(matches!(
+                builder.algorithm(algorithm).build(),
+                Err(SmallestEnclosingH3Error::UnsupportedGeometry(_))
+            ));
+        }
+        assert!(matches!(
+            SmallestEnclosingH3Builder::new(center, 50.0, Resolution::Twelve)
+                .algorithm(Algorithm::Polyfill {
+                    containment: ContainmentMode::Covers,
+                    vertices: 2,
+                })
+                .build(),
+            Err(SmallestEnclosingH3Error::UnsupportedGeometry(_))
+        ));
+    }
+}
@@ -0,0 +1,161 @@
+//! Encoded polyline (Google / OSRM format) input.
+
+use crate::{corridor::corridor, error::SmallestEnclosingH3Error, Result};
+use h3o::{CellIndex, LatLng, Resolution};
+
+/// Decode an encoded polyline with `precision` decimal digits (5 for Google,
+/// 6 for OSRM / Valhalla).
+pub fn decode_polyline(encoded: &str, precision: u32) -> Result<Vec<LatLng>> {
+    if !(1..=10).contains(&precision) {
+        return Err(SmallestEnclosingH3Error::UnsupportedGeometry(format!(
+            "unsupported polyline precision {precision}"
+        )));
+    }
+    let factor = 10f64.powi(precision as i32);
+
+    let bytes = encoded.as_bytes();
+    let mut offset = 0;
+    let (mut lat, mut lng) = (0i64, 0i64);
+    let mut points = Vec::new();
+
+    while offset < bytes.len() {
+        lat += decode_value(bytes, &mut offset)?;
+        if offset == bytes.len() {
+            return Err(SmallestEnclosingH3Error::ParseError {
+                offset,
+                reason: "latitude without a longitude".to_string(),
+            });
+        }
+        lng += decode_value(bytes, &mut offset)?;
+        points.push(LatLng::new(lat as f64 / factor, lng as f64 / factor)?);
+    }
+
+    Ok(points)
+}
+
+/// Cells within `buffer_m` of the route encoded in `encoded`, see
+/// [`decode_polyline`] and [`corridor`]
+pub fn corridor_from_encoded_polyline(
+    encoded: &str,
+    precision: u32,
+    buffer_m: f64,
+    resolution: Resolution,
+) -> Result<Vec<CellIndex>> {
+    corridor(&decode_polyline(encoded, precision)?, buffer_m, resolution)
+}
+
+// One zigzag-encoded value of 5-bit chunks, least significant first
+fn decode_value(bytes: &[u8], offset: &mut usize) -> Result<i64> {
+    let (mut result, mut shift) = (0i64, 0);
+
+    loop {
+        let Some(&byte) = bytes.get(*offset) else {
+            return Err(SmallestEnclosingH3Error::ParseError {
+                offset: *offset,
+                reason: "truncated value".to_string(),
+            });
+        };
+        if !(63..=126).contains(&byte) || shift > 55 {
+            return Err(SmallestEnclosingH3Error::ParseError {
+                offset: *offset,
+                reason: format!("unexpected character {:?}", byte as char),
+            });
+        }
+
+        let chunk = i64::from(byte - 63);
+        result |= (chunk & 0x1f) << shift;
+        shift += 5;
+        *offset += 1;
+
+        if chunk < 0x20 {
+            break;
+        }
+    }
+
+    Ok(if result & 1 == 1 {
+        !(result >> 1)
+    } else {
+        result >> 1
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Google's reference polyline
+    const ENCODED: &str = "_p~iF~ps|U_ulLnnqC_mqNvxq`@";
+
+    fn encode(points: &[(f64, f64)], precision: u32) -> String {
+        let factor = 10f64.powi(precision as i32);
+        let mut encoded = String::new();
+        let (mut previous_lat, mut previous_lng) = (0, 0);
+
+        for &(lat, lng) in points {
+            let (lat, lng) = ((lat * factor).round() as i64, (lng * factor).round() as i64);
+            for delta in [lat - previous_lat, lng - previous_lng] {
+                let mut value = if delta < 0 { !(delta << 1) } else { delta << 1 };
+                while value >= 0x20 {
+                    encoded.push((((value & 0x1f) | 0x20) as u8 + 63) as char);
+                    value >>= 5;
+                }
+                encoded.push((value as u8 + 63) as char);
+            }
+            (previous_lat, previous_lng) = (lat, lng);
+        }
+
+        encoded
+    }
+
+    #[test]
+    fn test_decode_reference_polyline() {
+        let points = decode_polyline(ENCODED, 5).unwrap();
+        assert_eq!(points.len(), 3);
+        assert_eq!(points[0], LatLng::new(38.5, -120.2).unwrap());
+        assert_eq!(points[2], LatLng::new(43.252, -126.453).unwrap());
+        assert_eq!(
+            encode(&[(38.5, -120.2), (40.7, -120.95), (43.252, -126.453)], 5),
+            ENCODED
+        );
+    }
+
+    #[test]
+    fn test_decode_precision_six() {
+        let route = [(33.448412, -112.074037), (33.451234, -112.068765)];
+        let points = decode_polyline(&encode(&route, 6), 6).unwrap();
+
+        for (point, (lat, lng)) in points.iter().zip(route) {
+            assert!((point.lat() - lat).abs() < 1e-9);
+            assert!((point.lng() - lng).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_malformed_polyline_offset() {
+        // A space is outside the encoding alphabet
+        assert!(matches!(
+            decode_polyline("_p~iF ~ps|U", 5),
+            Err(SmallestEnclosingH3Error::ParseError { offset: 5, .. })
+        ));
+        // The last longitude is cut short
+        assert!(matches!(
+            decode_polyline(&ENCODED[..ENCODED.len() - 1], 5),
+            Err(SmallestEnclosingH3Error::ParseError { offset, .. }) if offset == ENCODED.len() - 1
+        ));
+        // A latitude with no longitude
+        assert!(matches!(
+            decode_polyline("_p~iF", 5),
+            Err(SmallestEnclosingH3Error::ParseError { offset: 5, .. })
+        ));
+    }
+
+    #[test]
+    fn test_corridor_from_encoded_polyline() {
+        let route = [(33.4484, -112.0740), (33.4484, -112.0)];
+        let cells =
+            corridor_from_encoded_polyline(&encode(&route, 6), 6, 300.0, Resolution::Ten).unwrap();
+
+        let midpoint = LatLng::new(33.4484, -112.037).unwrap();
+        assert!(cells.contains(&midpoint.to_cell(Resolution::Ten)));
+    }
+}
@@ -0,0 +1,214 @@
+//! SQL queries selecting the rows of a PostGIS table within the coverage.
+
+use crate::cell_coverage::CellCoverage;
+use crate::{error::SmallestEnclosingH3Error, quote_identifier, Result, SmallestEnclosingH3};
+use geo::{LineString, Polygon};
+use std::fmt::Write;
+
+impl SmallestEnclosingH3 {
+    /// `SELECT * FROM {table} WHERE ST_Within({geom_column}, ...)`, matching
+    /// the rows whose geometry lies within the union of [`Self::disk`],
+    /// given as a WKT `MULTIPOLYGON` in SRID 4326.
+    ///
+    /// `table`, optionally schema-qualified as `schema.table`, and
+    /// `geom_column` are quoted as identifiers, so give them as stored:
+    /// lowercase unless created quoted. A coverage across the antimeridian
+    /// can't be drawn as one planar outline, and is a
+    /// [`SmallestEnclosingH3Error::UnsupportedGeometry`]; see
+    /// [`Self::to_postgis_h3_query`] for those.
+    pub fn to_postgis_query(&self, table: &str, geom_column: &str) -> Result<String> {
+        let coverage = CellCoverage::from_cells(self.disk()?);
+        let [min_lng, _, max_lng, _] = coverage.bbox()?;
+        if min_lng > max_lng {
+            return Err(SmallestEnclosingH3Error::UnsupportedGeometry(
+                "a coverage across the antimeridian has no planar outline".to_string(),
+            ));
+        }
+        let mut wkt = String::from("MULTIPOLYGON(");
+        for (i, polygon) in coverage.outline().iter().enumerate() {
+            if i > 0 {
+                wkt.push(',');
+            }
+            write_polygon(&mut wkt, polygon);
+        }
+        wkt.push(')');
+
+        Ok(format!(
+            "SELECT * FROM {} WHERE ST_Within({}, ST_GeomFromText('{wkt}', 4326))",
+            quote_identifier(table),
+            quote_identifier(geom_column),
+        ))
+    }
+
+    /// `SELECT * FROM {table} WHERE h3_lat_lng_to_cell({geom_column}, r) =
+    /// ANY(ARRAY[...])`, matching the rows whose point geometry falls in a
+    /// cell of [`Self::disk`] at the coverage's resolution `r`, with the
+    /// functions of the `h3` and `h3_postgis` extensions.
+    ///
+    /// Unlike [`Self::to_postgis_query`] this works across the antimeridian,
+    /// and the database can use an index on the cell expression. Identifiers
+    /// are quoted the same way.
+    pub fn to_postgis_h3_query(&self, table: &str, geom_column: &str) -> Result<String> {
+        let cells: Vec<String> = self
+            .disk()?
+            .iter()
+            .map(|cell| format!("'{cell}'::h3index"))
+            .collect();
+        Ok(format!(
+            "SELECT * FROM {} WHERE h3_lat_lng_to_cell({}, {}) = ANY(ARRAY[{}])",
+            quote_identifier(table),
+            quote_identifier(geom_column),
+            u8::from(self.resolution),
+            cells.join(", ")
+        ))
+    }
+}
+
+// `polygon` as the WKT `((x y, ...), (x y, ...))` of its exterior and holes
+fn write_polygon(wkt: &mut String, polygon: &Polygon<f64>) {
+    wkt.push('(');
+    for (i, ring) in std::iter::once(polygon.exterior())
+        .chain(polygon.interiors())
+        .enumerate()
+    {
+        if i > 0 {
+            wkt.push(',');
+        }
+        write_ring(wkt, ring);
+    }
+    wkt.push(')');
+}
+
+fn write_ring(wkt: &mut String, ring: &LineString<f64>) {
+    wkt.push('(');
+    for (i, coord) in ring.coords().enumerate() {
+        if i > 0 {
+            wkt.push(',');
+        }
+        let _ = write!(wkt, "{} {}", coord.x, coord.y);
+    }
+    wkt.push(')');
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SmallestEnclosingH3Builder;
+    use h3o::{LatLng, Resolution};
+
+    // The string literals of `sql`, after checking that quotes are closed
+    // and that parentheses and brackets outside them balance
+    fn literals(sql: &str) -> Vec<&str> {
+        let mut literals = Vec::new();
+        let mut depth = Vec::new();
+        let mut chars = sql.char_indices();
+        while let Some((i, c)) = chars.next() {
+            match c {
+                '\'' | '"' => {
+                    let end = chars
+                        .by_ref()
+                        .find(|&(_, close)| close == c)
+                        .unwrap_or_else(|| panic!("unclosed {c} at {i} in {sql}"))
+                        .0;
+                    if c == '\'' {
+                        literals.push(&sql[i + 1..end]);
+                    }
+                }
+                '(' | '[' => depth.push(c),
+                ')' => assert_eq!(depth.pop(), Some('('), "{sql}"),
+                ']' => assert_eq!(depth.pop(), Some('['), "{sql}"),
+                _ => {}
+            }
+        }
+        assert!(depth.is_empty(), "{sql}");
+        literals
+    }
+
+    fn coverage(center: LatLng, radius: f64) -> SmallestEnclosingH3 {
+        SmallestEnclosingH3Builder::new(center, radius, Resolution::Nine)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_to_postgis_query() {
+        let center = LatLng::new(33.4484, -112.0740).unwrap();
+        let sql = coverage(center, 1000.0)
+            .to_postgis_query("public.places", "geom")
+            .unwrap();
+        assert!(sql.starts_with(
+            r#"SELECT * FROM "public"."places" WHERE ST_Within("geom", ST_GeomFromText('MULTIPOLYGON((("#
+        ));
+        assert!(sql.ends_with(")))', 4326))"));
+
+        // One polygon without holes, closed, around the center
+        let strings = literals(&sql);
+        assert_eq!(strings.len(), 1);
+        let wkt = strings[0];
+        assert_eq!(wkt.matches("((").count(), 1);
+        let points: Vec<(f64, f64)> = wkt
+            .trim_start_matches("MULTIPOLYGON(((")
+            .trim_end_matches(")))")
+            .split(',')
+            .map(|point| {
+                let (x, y) = point.split_once(' ').unwrap();
+                (x.parse().unwrap(), y.parse().unwrap())
+            })
+            .collect();
+        assert!(points.len() > 6);
+        assert_eq!(points.first(), points.last());
+        assert!(points
+            .iter()
+            .all(|&(lng, lat)| (lng - center.lng()).abs() < 0.05
+                && (lat - center.lat()).abs() < 0.05));
+
+        // An annulus has a hole
+        let annulus = SmallestEnclosingH3Builder::new(center, 1000.0, Resolution::Nine)
+            .inner_radius_meters(600.0)
+            .unwrap()
+            .build()
+            .unwrap();
+        let sql = annulus.to_postgis_query("places", "geom").unwrap();
+        assert!(literals(&sql)[0].contains("),("));
+    }
+
+    #[test]
+    fn test_to_postgis_h3_query() {
+        let center = LatLng::new(33.4484, -112.0740).unwrap();
+        let smallest_enclosing_h3 = coverage(center, 1000.0);
+        let sql = smallest_enclosing_h3
+            .to_postgis_h3_query("places", "geom")
+            .unwrap();
+        assert!(sql.starts_with(
+            r#"SELECT * FROM "places" WHERE h3_lat_lng_to_cell("geom", 9) = ANY(ARRAY['"#
+        ));
+        assert!(sql.ends_with("'::h3index])"));
+        let cells: Vec<String> = smallest_enclosing_h3
+            .disk()
+            .unwrap()
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(literals(&sql), cells);
+    }
+
+    #[test]
+    fn test_postgis_query_across_antimeridian() {
+        let across = coverage(LatLng::new(0.0, 179.999).unwrap(), 1000.0);
+        assert!(matches!(
+            across.to_postgis_query("places", "geom"),
+            Err(SmallestEnclosingH3Error::UnsupportedGeometry(_))
+        ));
+        assert!(across.to_postgis_h3_query("places", "geom").is_ok());
+    }
+
+    #[test]
+    fn test_quote_identifier() {
+        assert_eq!(quote_identifier("geom"), r#""geom""#);
+        assert_eq!(quote_identifier("gis.Places"), r#""gis"."Places""#);
+        assert_eq!(
+            quote_identifier(r#"x"); DROP TABLE places; --"#),
+            r#""x""); DROP TABLE places; --""#
+        );
+    }
+}
@@ -0,0 +1,138 @@
+//! Self-contained HTML preview of the coverage on a Leaflet map, to open
+//! locally without uploading anything.
+
+use crate::{Result, SmallestEnclosingH3};
+
+const TEMPLATE: &str = r##"<!DOCTYPE html>
+<html>
+<head>
+<meta charset="utf-8">
+<meta name="viewport" content="width=device-width, initial-scale=1">
+<title>smallest-enclosing-h3 coverage</title>
+<link rel="stylesheet" href="https://unpkg.com/leaflet@1.9.4/dist/leaflet.css"
+  integrity="sha256-p4NxAoJBhIIN+hmNHrzRCf9tD/miZyoHS5obTRR9BMY=" crossorigin="">
+<script src="https://unpkg.com/leaflet@1.9.4/dist/leaflet.js"
+  integrity="sha256-20nQCchB9co0qIjJZRGuk2/Z9VM+kNiyxNV1lvTlZBo=" crossorigin=""></script>
+<style>html, body, #map { height: 100%; margin: 0; }</style>
+</head>
+<body>
+<div id="map"></div>
+<script type="application/json" id="coverage">{geojson}</script>
+<script>
+const coverage = JSON.parse(document.getElementById("coverage").textContent);
+const map = L.map("map");
+L.tileLayer("https://tile.openstreetmap.org/{z}/{x}/{y}.png", {
+  maxZoom: 19,
+  attribution: "&copy; OpenStreetMap contributors",
+}).addTo(map);
+L.geoJSON(coverage, {
+  style: (feature) => feature.properties.type === "hexagon"
+    ? { color: "#3388ff", weight: 1, fillOpacity: 0.4 }
+    : { color: "#ff0000", weight: 2, fill: false },
+  onEachFeature: (feature, layer) => {
+    if (feature.properties.hex_id) layer.bindTooltip(feature.properties.hex_id);
+  },
+}).addTo(map);
+map.fitBounds({bounds});
+</script>
+</body>
+</html>
+"##;
+
+impl SmallestEnclosingH3 {
+    /// An HTML page showing [`Self::to_geojson`] on a Leaflet map, fitted to
+    /// the coverage's bbox: hexagons filled in blue with their hex id as a
+    /// tooltip, the circle outlined in red.
+    ///
+    /// The GeoJSON is embedded in the page; only Leaflet and the
+    /// OpenStreetMap tiles are loaded from the network.
+    pub fn to_html(&self) -> Result<String> {
+        let geojson = self.to_geojson()?;
+        let [min_lng, min_lat, mut max_lng, max_lat] = self.bbox()?;
+        // Leaflet takes a box across the antimeridian as one past 180°
+        if min_lng > max_lng {
+            max_lng += 360.0;
+        }
+        let bounds = format!("[[{min_lat}, {min_lng}], [{max_lat}, {max_lng}]]");
+
+        // The GeoJSON goes in last, so nothing in it is taken for a
+        // placeholder
+        Ok(TEMPLATE
+            .replace("{bounds}", &bounds)
+            .replace("{geojson}", &script_safe_json(&geojson.to_string())))
+    }
+}
+
+// `json` escaped to sit inside a `<script>` element: `<`, `>` and `&` can't
+// end the element or open a comment, and the line separators older
+// JavaScript engines reject in strings are escaped as well. JSON.parse reads
+// the escapes back as the original characters.
+fn script_safe_json(json: &str) -> String {
+    let mut escaped = String::with_capacity(json.len());
+    for c in json.chars() {
+        match c {
+            '<' => escaped.push_str("\\u003c"),
+            '>' => escaped.push_str("\\u003e"),
+            '&' => escaped.push_str("\\u0026"),
+            '\u{2028}' => escaped.push_str("\\u2028"),
+            '\u{2029}' => escaped.push_str("\\u2029"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SmallestEnclosingH3Builder;
+    use geojson::GeoJson;
+    use h3o::{LatLng, Resolution};
+
+    // The JSON embedded in `html`
+    fn embedded(html: &str) -> &str {
+        let start = html.find(r#"id="coverage">"#).unwrap() + r#"id="coverage">"#.len();
+        let end = start + html[start..].find("</script>").unwrap();
+        &html[start..end]
+    }
+
+    #[test]
+    fn test_to_html() {
+        let center = LatLng::new(33.4484, -112.0740).unwrap();
+        let smallest_enclosing_h3 = SmallestEnclosingH3Builder::new(center, 500.0, Resolution::Ten)
+            .build()
+            .unwrap();
+        let html = smallest_enclosing_h3.to_html().unwrap();
+        assert!(html.starts_with("<!DOCTYPE html>"));
+
+        let GeoJson::FeatureCollection(collection) = embedded(&html).parse::<GeoJson>().unwrap()
+        else {
+            panic!("not a FeatureCollection");
+        };
+        let hexagons = smallest_enclosing_h3.hexagons().unwrap();
+        let hex_ids = collection
+            .features
+            .iter()
+            .filter_map(|feature| feature.property("hex_id"))
+            .count();
+        assert_eq!(hex_ids, hexagons.len());
+        for cell in &hexagons {
+            assert!(html.contains(&cell.to_string()));
+        }
+
+        let [min_lng, min_lat, ..] = smallest_enclosing_h3.bbox().unwrap();
+        assert!(html.contains(&format!("map.fitBounds([[{min_lat}, {min_lng}]")));
+    }
+
+    #[test]
+    fn test_script_safe_json() {
+        let value = serde_json::json!({ "note": "</script><!-- & \u{2028}" });
+        let escaped = script_safe_json(&value.to_string());
+        assert!(!escaped.contains('<') && !escaped.contains('>') && !escaped.contains('&'));
+        assert!(!escaped.contains('\u{2028}'));
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(&escaped).unwrap(),
+            value
+        );
+    }
+}
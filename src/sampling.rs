@@ -0,0 +1,254 @@
+//! Uniform random points over the coverage, for load tests and Monte Carlo
+//! demand models.
+
+use crate::{error::SmallestEnclosingH3Error, Result, SmallestEnclosingH3};
+use h3o::{CellIndex, LatLng};
+
+// Draws allowed per accepted point before the random source is deemed stuck
+const MAX_DRAWS_PER_POINT: usize = 10_000;
+
+/// Where [`SmallestEnclosingH3::sample_points_in`] places its points
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SampleRegion {
+    /// Anywhere in the cells of [`SmallestEnclosingH3::disk`]
+    #[default]
+    Cells,
+    /// Only within the circle itself, outside any inner radius and within
+    /// any bearing range
+    Circle,
+}
+
+impl SmallestEnclosingH3 {
+    /// `n` points uniformly distributed by area over the cells of
+    /// [`Self::disk`], see [`Self::sample_points_in`]
+    pub fn sample_points(&self, n: usize, rng: &mut impl FnMut() -> f64) -> Result<Vec<LatLng>> {
+        self.sample_points_in(n, SampleRegion::Cells, rng)
+    }
+
+    /// `n` points uniformly distributed by area over `region`.
+    ///
+    /// `rng` returns uniform numbers in `[0, 1)`, such as `|| rng.gen()` with
+    /// the `rand` crate; a seeded source gives reproducible points. A cell is
+    /// chosen with probability proportional to its area, then a point within
+    /// its latitude and longitude bounds is drawn until one falls in the
+    /// cell (and in the circle for [`SampleRegion::Circle`]).
+    ///
+    /// A bounding box has no circle to sample, and is a
+    /// [`SmallestEnclosingH3Error::UnsupportedGeometry`] with
+    /// [`SampleRegion::Circle`], as is a source that never lands in the
+    /// region, or any points asked of an empty coverage.
+    pub fn sample_points_in(
+        &self,
+        n: usize,
+        region: SampleRegion,
+        rng: &mut impl FnMut() -> f64,
+    ) -> Result<Vec<LatLng>> {
+        if region == SampleRegion::Circle && self.rectangle.is_some() {
+            return Err(SmallestEnclosingH3Error::UnsupportedGeometry(
+                "a bounding box has no circle to sample".to_string(),
+            ));
+        }
+
+        let cells = self.disk()?;
+        if cells.is_empty() {
+            if n == 0 {
+                return Ok(Vec::new());
+            }
+            return Err(SmallestEnclosingH3Error::UnsupportedGeometry(
+                "the coverage is empty".to_string(),
+            ));
+        }
+        let bounds: Vec<CellBounds> = cells.iter().map(|cell| CellBounds::new(*cell)).collect();
+        let mut cumulative_area = Vec::with_capacity(cells.len());
+        let mut total_area = 0.0;
+        for cell in &cells {
+            total_area += cell.area_m2();
+            cumulative_area.push(total_area);
+        }
+
+        let mut points = Vec::with_capacity(n);
+        let mut draws = 0;
+        while points.len() < n {
+            if draws == MAX_DRAWS_PER_POINT * (points.len() + 1) {
+                return Err(SmallestEnclosingH3Error::UnsupportedGeometry(format!(
+                    "no point accepted after {draws} draws"
+                )));
+            }
+            draws += 1;
+
+            let target = rng() * total_area;
+            let i = cumulative_area
+                .partition_point(|area| *area <= target)
+                .min(cells.len() - 1);
+            let Some(point) = bounds[i].sample(rng) else {
+                continue;
+            };
+            if point.to_cell(self.resolution) == cells[i]
+                && (region == SampleRegion::Cells || self.within_circle(point))
+            {
+                points.push(point);
+            }
+        }
+        Ok(points)
+    }
+
+    // Whether `point` lies within the circle, outside any inner radius and
+    // within any bearing range
+    fn within_circle(&self, point: LatLng) -> bool {
+        let distance = self.distance_from_center(point);
+        distance <= self.radius_meters
+            && distance >= self.inner_radius_meters
+            && self.bearing_range.is_none_or(|(start, end)| {
+                let bearing = self.bearing_from_center(point);
+                (bearing - start).rem_euclid(360.0) <= (end - start).rem_euclid(360.0)
+            })
+    }
+}
+
+// A cell's latitude and longitude bounds, in degrees. Longitudes are
+// unwrapped around the centroid's, so a cell across the antimeridian has
+// bounds past ±180; a cell holding a pole spans every longitude.
+struct CellBounds {
+    min_sin_lat: f64,
+    max_sin_lat: f64,
+    min_lng: f64,
+    max_lng: f64,
+}
+
+impl CellBounds {
+    fn new(cell: CellIndex) -> Self {
+        let resolution = cell.resolution();
+        let centroid_lng = LatLng::from(cell).lng();
+        let (mut min_lat, mut max_lat) = (f64::INFINITY, f64::NEG_INFINITY);
+        let (mut min_lng, mut max_lng) = (f64::INFINITY, f64::NEG_INFINITY);
+        for vertex in cell.boundary().iter() {
+            let lng =
+                centroid_lng + (vertex.lng() - centroid_lng + 180.0).rem_euclid(360.0) - 180.0;
+            min_lat = min_lat.min(vertex.lat());
+            max_lat = max_lat.max(vertex.lat());
+            min_lng = min_lng.min(lng);
+            max_lng = max_lng.max(lng);
+        }
+
+        for pole in [90.0, -90.0] {
+            if LatLng::new(pole, 0.0).is_ok_and(|p| p.to_cell(resolution) == cell) {
+                (min_lat, max_lat) = (min_lat.min(pole), max_lat.max(pole));
+                (min_lng, max_lng) = (-180.0, 180.0);
+            }
+        }
+
+        Self {
+            min_sin_lat: min_lat.to_radians().sin(),
+            max_sin_lat: max_lat.to_radians().sin(),
+            min_lng,
+            max_lng,
+        }
+    }
+
+    // A point uniform by area within the bounds: uniform in longitude and in
+    // the sine of the latitude
+    fn sample(&self, rng: &mut impl FnMut() -> f64) -> Option<LatLng> {
+        let sin_lat = self.min_sin_lat + rng() * (self.max_sin_lat - self.min_sin_lat);
+        let lng = self.min_lng + rng() * (self.max_lng - self.min_lng);
+        let lng = (lng + 180.0).rem_euclid(360.0) - 180.0;
+        LatLng::new(sin_lat.clamp(-1.0, 1.0).asin().to_degrees(), lng).ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SmallestEnclosingH3Builder;
+    use h3o::Resolution;
+    use std::collections::HashSet;
+
+    // SplitMix64, uniform in [0, 1)
+    fn seeded(mut state: u64) -> impl FnMut() -> f64 {
+        move || {
+            state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+            let mut z = state;
+            z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+            ((z ^ (z >> 31)) >> 11) as f64 / (1u64 << 53) as f64
+        }
+    }
+
+    #[test]
+    fn test_sample_points() {
+        let center = LatLng::new(33.4484, -112.0740).unwrap();
+        let coverage = SmallestEnclosingH3Builder::new(center, 1000.0, Resolution::Nine)
+            .build()
+            .unwrap();
+        let disk: HashSet<CellIndex> = coverage.disk().unwrap().into_iter().collect();
+
+        let points = coverage.sample_points(20_000, &mut seeded(7)).unwrap();
+        assert_eq!(points.len(), 20_000);
+        assert!(points
+            .iter()
+            .all(|p| disk.contains(&p.to_cell(Resolution::Nine))));
+
+        // The share inside the circle is the circle's share of the area
+        let inside = points
+            .iter()
+            .filter(|p| coverage.distance_from_center(**p) <= 1000.0)
+            .count();
+        let disk_area: f64 = disk.iter().map(|cell| cell.area_m2()).sum();
+        let expected = std::f64::consts::PI * 1000.0 * 1000.0 / disk_area;
+        let actual = inside as f64 / points.len() as f64;
+        assert!((actual - expected).abs() < 0.02, "{actual} {expected}");
+
+        // Reproducible from the seed
+        assert_eq!(
+            coverage.sample_points(10, &mut seeded(7)).unwrap(),
+            points[..10]
+        );
+    }
+
+    #[test]
+    fn test_sample_points_in_circle() {
+        let center = LatLng::new(0.0, 179.99).unwrap();
+        let coverage = SmallestEnclosingH3Builder::new(center, 2000.0, Resolution::Nine)
+            .inner_radius_meters(500.0)
+            .unwrap()
+            .build()
+            .unwrap();
+        let points = coverage
+            .sample_points_in(1000, SampleRegion::Circle, &mut seeded(11))
+            .unwrap();
+        assert_eq!(points.len(), 1000);
+        for point in &points {
+            let distance = coverage.distance_from_center(*point);
+            assert!((500.0..=2000.0).contains(&distance), "{distance}");
+        }
+        // Both sides of the antimeridian
+        assert!(points.iter().any(|p| p.lng() < 0.0));
+        assert!(points.iter().any(|p| p.lng() > 0.0));
+
+        // A stuck source always draws a corner of the first cell's bounds
+        assert!(matches!(
+            coverage.sample_points(1, &mut || 0.0),
+            Err(SmallestEnclosingH3Error::UnsupportedGeometry(_))
+        ));
+    }
+
+    #[test]
+    fn test_sample_points_empty_coverage() {
+        // A circle too small to cover any cell to 90%
+        let center = LatLng::new(33.4484, -112.0740).unwrap();
+        let coverage = SmallestEnclosingH3Builder::new(center, 1.0, Resolution::Five)
+            .min_coverage_fraction(0.9)
+            .unwrap()
+            .build()
+            .unwrap();
+        assert!(coverage.disk().unwrap().is_empty());
+
+        assert!(coverage
+            .sample_points(0, &mut seeded(3))
+            .unwrap()
+            .is_empty());
+        assert!(matches!(
+            coverage.sample_points(3, &mut seeded(3)),
+            Err(SmallestEnclosingH3Error::UnsupportedGeometry(_))
+        ));
+    }
+}
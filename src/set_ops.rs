@@ -0,0 +1,271 @@
+//! Set operations on the disks of two coverages at the same resolution.
+
+use crate::{haversine_distance, Result, SmallestEnclosingH3};
+use h3o::{CellIndex, LatLng};
+use std::collections::HashSet;
+use std::ops::Add;
+
+impl SmallestEnclosingH3 {
+    /// Cells of [`Self::disk`] in either `self` or `other`, in index order.
+    ///
+    /// Both must share a resolution, or this is a
+    /// [`crate::error::SmallestEnclosingH3Error::InvalidResolution`].
+    pub fn union(&self, other: &SmallestEnclosingH3) -> Result<Vec<CellIndex>> {
+        let (a, b) = self.disk_sets(other)?;
+        Ok(sorted(a.union(&b)))
+    }
+
+    /// Cells of [`Self::disk`] in both `self` and `other`, in index order, see
+    /// [`Self::union`]
+    pub fn intersection(&self, other: &SmallestEnclosingH3) -> Result<Vec<CellIndex>> {
+        let (a, b) = self.disk_sets(other)?;
+        Ok(sorted(a.intersection(&b)))
+    }
+
+    /// Cells of [`Self::disk`] in `self` but not in `other`, in index order,
+    /// see [`Self::union`]
+    pub fn difference(&self, other: &SmallestEnclosingH3) -> Result<Vec<CellIndex>> {
+        let (a, b) = self.disk_sets(other)?;
+        Ok(sorted(a.difference(&b)))
+    }
+
+    /// Cells of [`Self::disk`] in exactly one of `self` and `other`, in index
+    /// order, see [`Self::union`]
+    pub fn symmetric_difference(&self, other: &SmallestEnclosingH3) -> Result<Vec<CellIndex>> {
+        let (a, b) = self.disk_sets(other)?;
+        Ok(sorted(a.symmetric_difference(&b)))
+    }
+
+    /// Whether [`Self::disk`] of `self` and `other` share a cell, see
+    /// [`Self::union`].
+    ///
+    /// Circles whose centers are further apart than both disks can reach are
+    /// disjoint without generating any cells; otherwise the smaller disk is
+    /// looked up in the larger.
+    pub fn intersects(&self, other: &SmallestEnclosingH3) -> Result<bool> {
+        self.check_same_resolution(other)?;
+        if let (Some(reach), Some(other_reach)) = (self.disk_reach()?, other.disk_reach()?) {
+            if haversine_distance(self.center, other.center) > reach + other_reach {
+                return Ok(false);
+            }
+        }
+
+        let (a, b) = (self.disk()?, other.disk()?);
+        let (smaller, larger) = if a.len() <= b.len() { (a, b) } else { (b, a) };
+        let larger: HashSet<CellIndex> = larger.into_iter().collect();
+        Ok(smaller.iter().any(|cell| larger.contains(cell)))
+    }
+
+    // An upper bound on the distance, in meters on the Earth, from the center
+    // to any point of the disk: the center cell's diameter for each of the
+    // k + 1 rings, padding included, doubled so cells growing away from the
+    // center stay within it. `None` for a bounding box or a flood-filled
+    // circle, whose cells aren't a disk of rings.
+    fn disk_reach(&self) -> Result<Option<f64>> {
+        if self.rectangle.is_some() || self.is_flood_filled()? {
+            return Ok(None);
+        }
+        let center_cell = self.center.to_cell(self.resolution);
+        let centroid = LatLng::from(center_cell);
+        let diameter = center_cell
+            .boundary()
+            .iter()
+            .map(|vertex| 2.0 * haversine_distance(centroid, *vertex))
+            .fold(0.0, f64::max);
+        let rings = self.k_value()? + self.padding_rings + 1;
+        Ok(Some(2.0 * f64::from(rings) * diameter))
+    }
+
+    fn check_same_resolution(&self, other: &SmallestEnclosingH3) -> Result<()> {
+        if other.resolution != self.resolution {
+            return Err(h3o::error::InvalidResolution {
+                value: Some(u8::from(other.resolution)),
+                reason: "must match the resolution of the other coverage",
+            }
+            .into());
+        }
+        Ok(())
+    }
+
+    fn disk_sets(
+        &self,
+        other: &SmallestEnclosingH3,
+    ) -> Result<(HashSet<CellIndex>, HashSet<CellIndex>)> {
+        self.check_same_resolution(other)?;
+        Ok((
+            self.disk()?.into_iter().collect(),
+            other.disk()?.into_iter().collect(),
+        ))
+    }
+}
+
+/// `&a + &b` is [`SmallestEnclosingH3::union`]
+///
+/// ```
+/// use h3o::{LatLng, Resolution};
+/// use smallest_enclosing_h3::SmallestEnclosingH3Builder;
+///
+/// let phoenix = LatLng::new(33.4484, -112.0740).unwrap();
+/// let tempe = LatLng::new(33.4255, -111.9400).unwrap();
+/// let circle = |center| {
+///     SmallestEnclosingH3Builder::new(center, 2000.0, Resolution::Nine)
+///         .build()
+///         .unwrap()
+/// };
+/// let (a, b) = (circle(phoenix), circle(tempe));
+///
+/// let combined = (&a + &b).unwrap();
+/// assert_eq!(combined, a.union(&b).unwrap());
+/// assert!(combined.len() > a.disk().unwrap().len());
+/// ```
+impl Add<&SmallestEnclosingH3> for &SmallestEnclosingH3 {
+    type Output = Result<Vec<CellIndex>>;
+
+    fn add(self, other: &SmallestEnclosingH3) -> Self::Output {
+        self.union(other)
+    }
+}
+
+fn sorted<'a>(cells: impl Iterator<Item = &'a CellIndex>) -> Vec<CellIndex> {
+    let mut cells: Vec<CellIndex> = cells.copied().collect();
+    cells.sort_unstable();
+    cells
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{error::SmallestEnclosingH3Error, SmallestEnclosingH3Builder};
+    use h3o::{LatLng, Resolution};
+
+    fn smallest_enclosing_h3(lng: f64, radius_meters: f64) -> SmallestEnclosingH3 {
+        let center = LatLng::new(33.4484, lng).unwrap();
+        SmallestEnclosingH3Builder::new(center, radius_meters, Resolution::Nine)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_set_operations() {
+        let a = smallest_enclosing_h3(-112.0740, 2000.0);
+        let b = smallest_enclosing_h3(-112.0540, 3000.0);
+        let (disk_a, disk_b) = (a.disk().unwrap(), b.disk().unwrap());
+
+        let union = a.union(&b).unwrap();
+        let intersection = a.intersection(&b).unwrap();
+        let difference = a.difference(&b).unwrap();
+        let symmetric_difference = a.symmetric_difference(&b).unwrap();
+
+        assert!(!intersection.is_empty());
+        assert_eq!(
+            union.len(),
+            disk_a.len() + disk_b.len() - intersection.len()
+        );
+        assert_eq!(difference.len(), disk_a.len() - intersection.len());
+        assert!(difference
+            .iter()
+            .all(|cell| disk_a.contains(cell) && intersection.binary_search(cell).is_err()));
+        assert_eq!(symmetric_difference.len(), union.len() - intersection.len());
+        assert!(intersection
+            .iter()
+            .all(|cell| union.binary_search(cell).is_ok()));
+        assert!(symmetric_difference
+            .iter()
+            .all(|cell| intersection.binary_search(cell).is_err()));
+
+        // The difference is one-sided, and the two sides make up the
+        // symmetric difference
+        let other_difference = b.difference(&a).unwrap();
+        assert_ne!(difference, other_difference);
+        let mut sides = [difference.clone(), other_difference].concat();
+        sides.sort_unstable();
+        assert_eq!(sides, symmetric_difference);
+
+        // Symmetric, and trivial with itself
+        assert_eq!(b.union(&a).unwrap(), union);
+        assert_eq!((&a + &b).unwrap(), union);
+        assert_eq!(b.intersection(&a).unwrap(), intersection);
+        assert_eq!(b.symmetric_difference(&a).unwrap(), symmetric_difference);
+        assert_eq!(a.intersection(&a).unwrap(), a.union(&a).unwrap());
+        assert!(a.difference(&a).unwrap().is_empty());
+        assert!(a.symmetric_difference(&a).unwrap().is_empty());
+
+        // Inclusion-exclusion
+        assert_eq!(
+            disk_a.len() + disk_b.len(),
+            union.len() + intersection.len()
+        );
+        assert!(a.intersects(&b).unwrap() && b.intersects(&a).unwrap());
+
+        // Disjoint
+        let far = smallest_enclosing_h3(-111.0, 2000.0);
+        assert!(a.intersection(&far).unwrap().is_empty());
+        assert!(!a.intersects(&far).unwrap());
+        let mut sorted_a = disk_a.clone();
+        sorted_a.sort_unstable();
+        assert_eq!(a.difference(&far).unwrap(), sorted_a);
+        assert_eq!(
+            a.symmetric_difference(&far).unwrap(),
+            a.union(&far).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_intersects_exits_early() {
+        // Too many cells to generate, but far enough apart not to need them
+        let circle = |lng| {
+            SmallestEnclosingH3Builder::new(
+                LatLng::new(33.4484, lng).unwrap(),
+                2000.0,
+                Resolution::Nine,
+            )
+            .max_cells(1)
+            .build()
+            .unwrap()
+        };
+        let (a, far) = (circle(-112.0740), circle(-111.0));
+        assert!(!a.intersects(&far).unwrap());
+        assert!(matches!(
+            a.intersects(&circle(-112.0540)),
+            Err(SmallestEnclosingH3Error::TooManyCells { .. })
+        ));
+
+        // A circle within the disk's outermost cell is never ruled out
+        let a = smallest_enclosing_h3(-112.0740, 2000.0);
+        let disk = a.disk().unwrap();
+        let edge = disk
+            .iter()
+            .max_by(|x, y| {
+                let distance =
+                    |cell: &&CellIndex| haversine_distance(a.center, LatLng::from(**cell));
+                distance(x).total_cmp(&distance(y))
+            })
+            .unwrap();
+        let touching = SmallestEnclosingH3Builder::new(LatLng::from(*edge), 1.0, Resolution::Nine)
+            .build()
+            .unwrap();
+        assert!(a.intersects(&touching).unwrap());
+    }
+
+    #[test]
+    fn test_set_operations_resolution_mismatch() {
+        let a = smallest_enclosing_h3(-112.0740, 2000.0);
+        let b = a.at_resolution(Resolution::Ten);
+        for result in [
+            a.union(&b),
+            &a + &b,
+            a.intersection(&b),
+            a.difference(&b),
+            a.symmetric_difference(&b),
+        ] {
+            assert!(matches!(
+                result,
+                Err(SmallestEnclosingH3Error::InvalidResolution(_))
+            ));
+        }
+        assert!(matches!(
+            a.intersects(&b),
+            Err(SmallestEnclosingH3Error::InvalidResolution(_))
+        ));
+    }
+}
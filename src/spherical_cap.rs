@@ -0,0 +1,190 @@
+//! Coverage of circles too large to treat as small, flat shapes.
+//!
+//! Past an angular radius of [`LARGE_CAP_DEGREES`] the circle is a sizeable
+//! spherical cap: grid distances from the center cell run across icosahedron
+//! faces and pentagons, and planar tilers read its polygon as the complement.
+//! Such caps are covered by flood filling from the center cell instead,
+//! testing each cell's great-circle distance to the center directly.
+
+use crate::{
+    error::SmallestEnclosingH3Error, geodesy::EARTH_RADIUS, haversine_distance, CoverageMode,
+    Result, SmallestEnclosingH3,
+};
+use h3o::{CellIndex, LatLng};
+use std::collections::{HashSet, VecDeque};
+
+/// Angular radius, in degrees, beyond which a circle is covered as a
+/// spherical cap
+pub const LARGE_CAP_DEGREES: f64 = 30.0;
+
+// Vertices of a large cap's polygon, one per degree of bearing
+pub(crate) const LARGE_CAP_VERTICES: usize = 360;
+
+impl SmallestEnclosingH3 {
+    /// Whether the circle's angular radius exceeds [`LARGE_CAP_DEGREES`], so
+    /// it is covered as a spherical cap
+    pub fn is_large_cap(&self) -> bool {
+        (self.radius_meters / self.sphere_radius_meters).to_degrees() > LARGE_CAP_DEGREES
+    }
+
+    // Every cell that may overlap the cap, in index order, or for
+    // `CoverageMode::Ring` only those with a neighbour outside the cover.
+    //
+    // A cell is kept when its centroid is within the radius plus the cell's
+    // own circumradius. The kept cells are connected, so a flood fill from
+    // the center cell reaches all of them.
+    pub(crate) fn spherical_cap_cells(&self, mode: CoverageMode) -> Result<Vec<CellIndex>> {
+        // The cap's area as the same angle on the Earth, whose cells cover it
+        let cap_area = 2.0
+            * std::f64::consts::PI
+            * EARTH_RADIUS.powi(2)
+            * (1.0 - (self.radius_meters / self.sphere_radius_meters).cos());
+        // The smallest cells at a resolution are about half the average area;
+        // a third leaves room for the cells straddling the rim as well, so
+        // the estimate never falls short
+        let cell_area = crate::resolution_info(self.resolution).avg_area_km2 * 1e6 / 3.0;
+        let estimated = (cap_area / cell_area).ceil() as usize;
+        if estimated > self.max_cells {
+            return Err(SmallestEnclosingH3Error::TooManyCells {
+                estimated,
+                limit: self.max_cells,
+            });
+        }
+
+        let center_cell = self.center.to_cell(self.resolution);
+        let mut cover = HashSet::from([center_cell]);
+        let mut queue = VecDeque::from([center_cell]);
+        while let Some(cell) = queue.pop_front() {
+            for neighbor in cell.grid_disk::<Vec<_>>(1) {
+                if !cover.contains(&neighbor) && self.cap_overlaps(neighbor) {
+                    cover.insert(neighbor);
+                    queue.push_back(neighbor);
+                }
+            }
+        }
+
+        let mut cells: Vec<CellIndex> = match mode {
+            CoverageMode::Disk => cover.iter().copied().collect(),
+            CoverageMode::Ring => cover
+                .iter()
+                .copied()
+                .filter(|cell| {
+                    cell.grid_disk::<Vec<_>>(1)
+                        .iter()
+                        .any(|neighbor| !cover.contains(neighbor))
+                })
+                .collect(),
+        };
+        cells.sort_unstable();
+        Ok(cells)
+    }
+
+    fn cap_overlaps(&self, cell: CellIndex) -> bool {
+        let centroid = LatLng::from(cell);
+        let circumradius = cell
+            .boundary()
+            .iter()
+            .map(|vertex| haversine_distance(centroid, *vertex))
+            .fold(0.0, f64::max);
+        haversine_distance(self.center, centroid)
+            <= self.to_earth_meters(self.radius_meters) + circumradius
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SmallestEnclosingH3Builder;
+    use h3o::Resolution;
+
+    #[test]
+    fn test_large_cap_against_brute_force() {
+        let center = LatLng::new(33.4484, -112.0740).unwrap();
+        let radius_meters = 4_000_000.0;
+        let smallest_enclosing_h3 =
+            SmallestEnclosingH3Builder::new(center, radius_meters, Resolution::Two)
+                .build()
+                .unwrap();
+        assert!(smallest_enclosing_h3.is_large_cap());
+
+        let disk: HashSet<CellIndex> = smallest_enclosing_h3.disk().unwrap().into_iter().collect();
+        let all_cells: Vec<CellIndex> = CellIndex::base_cells()
+            .flat_map(|base| base.children(Resolution::Two))
+            .collect();
+        assert_eq!(all_cells.len(), 5882);
+
+        for cell in all_cells {
+            let centroid_distance = haversine_distance(center, LatLng::from(cell));
+            let touches = centroid_distance <= radius_meters
+                || cell
+                    .boundary()
+                    .iter()
+                    .any(|vertex| haversine_distance(center, *vertex) <= radius_meters);
+            if touches {
+                assert!(disk.contains(&cell), "{cell} overlaps the cap");
+            }
+            assert_eq!(
+                disk.contains(&cell),
+                smallest_enclosing_h3.cap_overlaps(cell),
+                "{cell}"
+            );
+        }
+
+        // The outline is the cover's edge, far from the center
+        let hexagons = smallest_enclosing_h3.hexagons().unwrap();
+        assert!(hexagons.iter().all(|cell| disk.contains(cell)));
+        assert!(hexagons
+            .iter()
+            .all(|cell| haversine_distance(center, LatLng::from(*cell)) > 3_500_000.0));
+    }
+
+    #[test]
+    fn test_small_circles_are_not_caps() {
+        let center = LatLng::new(33.4484, -112.0740).unwrap();
+        let smallest_enclosing_h3 =
+            SmallestEnclosingH3Builder::new(center, 3_000_000.0, Resolution::Two)
+                .build()
+                .unwrap();
+        assert!(!smallest_enclosing_h3.is_large_cap());
+    }
+
+    #[test]
+    fn test_radius_beyond_antipode() {
+        let center = LatLng::new(33.4484, -112.0740).unwrap();
+        assert!(matches!(
+            SmallestEnclosingH3Builder::new(center, 20_100_000.0, Resolution::Two).build(),
+            Err(SmallestEnclosingH3Error::InvalidRadius(_))
+        ));
+        assert!(matches!(
+            SmallestEnclosingH3Builder::new(center, 21_000_000.0, Resolution::Zero).build(),
+            Err(SmallestEnclosingH3Error::InvalidRadius(_))
+        ));
+    }
+
+    #[test]
+    fn test_very_large_radii() {
+        let center = LatLng::new(33.4484, -112.0740).unwrap();
+        for (radius_meters, resolution) in [
+            (5_000_000.0, Resolution::Three),
+            // Short of the antipode, 20,015 km away, so nearly the whole globe
+            (19_000_000.0, Resolution::One),
+        ] {
+            let smallest_enclosing_h3 =
+                SmallestEnclosingH3Builder::new(center, radius_meters, resolution)
+                    .build()
+                    .unwrap();
+            assert!(!smallest_enclosing_h3.hexagons().unwrap().is_empty());
+            let disk = smallest_enclosing_h3.disk().unwrap();
+            assert!(disk.contains(&center.to_cell(resolution)));
+
+            // The boundary is the cap's edge, a closed ring at the radius
+            let ring = smallest_enclosing_h3.generate_circle_coordinates().unwrap();
+            assert_eq!(ring.first(), ring.last());
+            for coordinate in &ring {
+                let vertex = LatLng::new(coordinate[1], coordinate[0]).unwrap();
+                let distance = haversine_distance(center, vertex);
+                assert!((distance - radius_meters).abs() < 1.0, "{distance}");
+            }
+        }
+    }
+}
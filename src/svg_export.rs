@@ -0,0 +1,150 @@
+//! SVG rendering of the coverage, for a quick look without a map viewer.
+
+use crate::geodesy::{normalize_lng, EARTH_RADIUS};
+use crate::{Result, SmallestEnclosingH3};
+use std::fmt::Write;
+
+// Fraction of the drawing's extent left around it
+const MARGIN: f64 = 0.05;
+
+impl SmallestEnclosingH3 {
+    /// Render [`Self::hexagons`] and the circle as an SVG document of `width`
+    /// by `height` pixels.
+    ///
+    /// Coordinates are projected equirectangularly around the center, in
+    /// meters east and south of it, which is accurate enough at the scale of
+    /// a coverage. Each hexagon is a filled `<path class="hexagon">` and the
+    /// circle an unfilled `<path class="circle">`, both with hairline strokes.
+    /// The viewBox is their bounding box grown by a 5% margin.
+    pub fn to_svg(&self, width: u32, height: u32) -> Result<String> {
+        let (lat0, lng0) = (self.center.lat(), self.center.lng());
+        let project = |lng: f64, lat: f64| {
+            [
+                EARTH_RADIUS * normalize_lng(lng - lng0).to_radians() * lat0.to_radians().cos(),
+                EARTH_RADIUS * (lat0 - lat).to_radians(),
+            ]
+        };
+
+        let hexagons: Vec<Vec<[f64; 2]>> = self
+            .hexagons()?
+            .iter()
+            .map(|cell| {
+                cell.boundary()
+                    .iter()
+                    .map(|vertex| project(vertex.lng(), vertex.lat()))
+                    .collect()
+            })
+            .collect();
+        let circle: Vec<[f64; 2]> = self
+            .generate_circle_coordinates()?
+            .iter()
+            .map(|coordinate| project(coordinate[0], coordinate[1]))
+            .collect();
+
+        let (mut min, mut max) = ([f64::INFINITY; 2], [f64::NEG_INFINITY; 2]);
+        for point in hexagons.iter().flatten().chain(&circle) {
+            for axis in 0..2 {
+                min[axis] = min[axis].min(point[axis]);
+                max[axis] = max[axis].max(point[axis]);
+            }
+        }
+        let margin = MARGIN * (max[0] - min[0]).max(max[1] - min[1]).max(1.0);
+
+        let mut svg = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\" \
+             viewBox=\"{:.2} {:.2} {:.2} {:.2}\">\n",
+            min[0] - margin,
+            min[1] - margin,
+            max[0] - min[0] + 2.0 * margin,
+            max[1] - min[1] + 2.0 * margin,
+        );
+        // Writing to a String can't fail
+        for hexagon in &hexagons {
+            let _ = writeln!(
+                svg,
+                "  <path class=\"hexagon\" d=\"{}\" fill=\"#3388ff\" fill-opacity=\"0.4\" \
+                 stroke=\"#3388ff\" stroke-width=\"1\" vector-effect=\"non-scaling-stroke\"/>",
+                path_data(hexagon)
+            );
+        }
+        let _ = writeln!(
+            svg,
+            "  <path class=\"circle\" d=\"{}\" fill=\"none\" stroke=\"#ff0000\" \
+             stroke-width=\"1\" vector-effect=\"non-scaling-stroke\"/>",
+            path_data(&circle)
+        );
+        svg.push_str("</svg>\n");
+        Ok(svg)
+    }
+}
+
+// A closed path through `points`
+fn path_data(points: &[[f64; 2]]) -> String {
+    let mut d = String::new();
+    for (i, [x, y]) in points.iter().enumerate() {
+        let command = if i == 0 { 'M' } else { 'L' };
+        let _ = write!(d, "{command}{x:.2} {y:.2} ");
+    }
+    d.push('Z');
+    d
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::SmallestEnclosingH3Builder;
+    use h3o::{LatLng, Resolution};
+
+    // The numbers of a space-separated attribute
+    fn numbers(attribute: &str) -> Vec<f64> {
+        attribute
+            .split([' ', 'M', 'L', 'Z'])
+            .filter(|token| !token.is_empty())
+            .map(|token| token.parse().unwrap())
+            .collect()
+    }
+
+    fn attribute<'a>(element: &'a str, name: &str) -> &'a str {
+        let start = element.find(&format!(" {name}=\"")).unwrap() + name.len() + 3;
+        let end = start + element[start..].find('"').unwrap();
+        &element[start..end]
+    }
+
+    #[test]
+    fn test_svg_export() {
+        let center = LatLng::new(33.4484, -112.0740).unwrap();
+        let smallest_enclosing_h3 = SmallestEnclosingH3Builder::new(center, 500.0, Resolution::Ten)
+            .build()
+            .unwrap();
+        let svg = smallest_enclosing_h3.to_svg(800, 600).unwrap();
+
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"800\""));
+        assert!(svg.trim_end().ends_with("</svg>"));
+        let paths: Vec<&str> = svg.lines().filter(|line| line.contains("<path")).collect();
+        assert_eq!(
+            paths
+                .iter()
+                .filter(|p| p.contains("class=\"hexagon\""))
+                .count(),
+            smallest_enclosing_h3.hexagons().unwrap().len()
+        );
+        assert_eq!(
+            paths
+                .iter()
+                .filter(|p| p.contains("class=\"circle\""))
+                .count(),
+            1
+        );
+
+        let &[x, y, width, height] = numbers(attribute(&svg, "viewBox")).as_slice() else {
+            panic!("Expected 4 viewBox numbers");
+        };
+        for path in paths {
+            let coordinates = numbers(attribute(path, "d"));
+            assert!(coordinates.len() >= 12);
+            for point in coordinates.chunks(2) {
+                assert!((x..=x + width).contains(&point[0]), "{point:?}");
+                assert!((y..=y + height).contains(&point[1]), "{point:?}");
+            }
+        }
+    }
+}
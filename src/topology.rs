@@ -0,0 +1,310 @@
+//! Topological sanity metrics for a set of cells.
+//!
+//! The ring structure of the dissolved outline is cross-checked against the
+//! Euler characteristic (`V - E + F`) of the cells themselves. For a planar
+//! set of cells both must equal `components - holes`; a disagreement points
+//! at a bug in the dissolve rather than in the coverage.
+
+use crate::{cells::UniformCells, Result, SmallestEnclosingH3};
+use h3o::{geom::ToGeo, CellIndex, DirectedEdgeIndex};
+use std::collections::{HashMap, HashSet, VecDeque};
+
+/// Shape of a set of cells, as returned by [`analyze_topology`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TopologyStats {
+    /// Exterior rings of the dissolved outline
+    pub exterior_rings: usize,
+    /// Interior rings (holes) of the dissolved outline
+    pub holes: usize,
+    /// Groups of edge-adjacent cells
+    pub components: usize,
+    /// `V - E + F` over the cells' vertexes, edges and faces
+    pub euler_characteristic: i64,
+    /// Mismatches between the outline and the cells, empty when consistent
+    pub warnings: Vec<String>,
+}
+
+impl TopologyStats {
+    /// Whether the outline and cell structure agree
+    pub fn is_consistent(&self) -> bool {
+        self.warnings.is_empty()
+    }
+}
+
+impl SmallestEnclosingH3 {
+    /// [`analyze_topology`] of [`Self::hexagons`]
+    pub fn analyze_topology(&self) -> Result<TopologyStats> {
+        analyze_topology(&self.hexagons_uniform()?)
+    }
+}
+
+impl SmallestEnclosingH3 {
+    /// The directed edges along the outline of [`Self::disk`], in disk order:
+    /// every edge from a covered cell to a neighbour outside the disk. An
+    /// annulus's inner outline is included, its edges pointing into the hole.
+    ///
+    /// A pentagon contributes only its five edges, so routing adjacency built
+    /// on these never looks for a sixth neighbour.
+    pub fn boundary_edges(&self) -> Result<Vec<DirectedEdgeIndex>> {
+        let disk = self.disk()?;
+        let covered: HashSet<CellIndex> = disk.iter().copied().collect();
+        Ok(disk
+            .iter()
+            .flat_map(|cell| cell.edges())
+            .filter(|edge| !covered.contains(&edge.destination()))
+            .collect())
+    }
+
+    /// The cells of [`Self::disk`] with a neighbour outside it, in disk
+    /// order: the disk less its morphological erosion. Unlike
+    /// [`Self::hexagons`], the outer ring at the grid distance to the
+    /// circle's edge, this follows whatever the disk's shape is, so an
+    /// annulus's inner outline is included too.
+    pub fn perimeter_cells(&self) -> Result<Vec<CellIndex>> {
+        Ok(crate::outer_cells(self.disk()?))
+    }
+
+    /// The adjacency graph of [`Self::disk`], for connected components,
+    /// shortest paths or diffusion over the coverage: every cell of the disk
+    /// with its neighbours in the disk, in edge order.
+    ///
+    /// Like [`Self::boundary_edges`] this follows the cells' directed edges,
+    /// so a pentagon has at most five neighbours.
+    pub fn hexagon_neighbors_graph(&self) -> Result<HashMap<CellIndex, Vec<CellIndex>>> {
+        let disk = self.disk()?;
+        let covered: HashSet<CellIndex> = disk.iter().copied().collect();
+        Ok(disk
+            .iter()
+            .map(|cell| {
+                let neighbors = cell
+                    .edges()
+                    .map(|edge| edge.destination())
+                    .filter(|neighbor| covered.contains(neighbor))
+                    .collect();
+                (*cell, neighbors)
+            })
+            .collect())
+    }
+}
+
+/// Count rings, holes and components of `cells` and check them against the
+/// Euler characteristic. Outlines only exist for cells of one resolution;
+/// convert a slice with `UniformCells::try_from(cells)?`.
+pub fn analyze_topology(cells: &UniformCells) -> Result<TopologyStats> {
+    let cells: HashSet<CellIndex> = cells.iter().copied().collect();
+
+    let outline = cells.iter().copied().to_geom(true)?;
+    let exterior_rings = outline.0.len();
+    let holes = outline.iter().map(|p| p.interiors().len()).sum();
+
+    let components = count_components(&cells);
+    let euler_characteristic = euler_characteristic(&cells);
+
+    let mut warnings = Vec::new();
+    if exterior_rings != components {
+        warnings.push(format!(
+            "outline has {exterior_rings} exterior rings but cells form {components} components"
+        ));
+    }
+    if exterior_rings as i64 - holes as i64 != euler_characteristic {
+        warnings.push(format!(
+            "outline rings minus holes is {} but the Euler characteristic is {euler_characteristic}",
+            exterior_rings as i64 - holes as i64
+        ));
+    }
+
+    Ok(TopologyStats {
+        exterior_rings,
+        holes,
+        components,
+        euler_characteristic,
+        warnings,
+    })
+}
+
+fn count_components(cells: &HashSet<CellIndex>) -> usize {
+    let mut seen = HashSet::with_capacity(cells.len());
+    let mut components = 0;
+
+    for &start in cells {
+        if !seen.insert(start) {
+            continue;
+        }
+        components += 1;
+
+        let mut queue = VecDeque::from([start]);
+        while let Some(cell) = queue.pop_front() {
+            for edge in cell.edges() {
+                let neighbor = edge.destination();
+                if cells.contains(&neighbor) && seen.insert(neighbor) {
+                    queue.push_back(neighbor);
+                }
+            }
+        }
+    }
+
+    components
+}
+
+fn euler_characteristic(cells: &HashSet<CellIndex>) -> i64 {
+    let vertexes: HashSet<_> = cells.iter().flat_map(|cell| cell.vertexes()).collect();
+    // A shared edge appears once from each side, so key it by its cell pair
+    let edges: HashSet<_> = cells
+        .iter()
+        .flat_map(|cell| cell.edges())
+        .map(|edge| {
+            let (a, b) = edge.cells();
+            (a.min(b), a.max(b))
+        })
+        .collect();
+
+    vertexes.len() as i64 - edges.len() as i64 + cells.len() as i64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SmallestEnclosingH3Builder;
+    use h3o::{LatLng, Resolution};
+
+    #[test]
+    fn test_topology_of_disk() {
+        let center = LatLng::new(33.4484, -112.0740).unwrap();
+        let disk: Vec<CellIndex> = center.to_cell(Resolution::Nine).grid_disk(3);
+
+        let stats = analyze_topology(&disk.try_into().unwrap()).unwrap();
+        assert_eq!((stats.components, stats.holes), (1, 0));
+        assert_eq!(stats.exterior_rings, 1);
+        assert_eq!(stats.euler_characteristic, 1);
+        assert!(stats.is_consistent());
+    }
+
+    #[test]
+    fn test_boundary_edges() {
+        let center = LatLng::new(33.4484, -112.0740).unwrap();
+        let coverage = SmallestEnclosingH3Builder::new(center, 1000.0, Resolution::Nine)
+            .build()
+            .unwrap();
+        let disk: HashSet<CellIndex> = coverage.disk().unwrap().into_iter().collect();
+        let edges = coverage.boundary_edges().unwrap();
+
+        // Every perimeter cell's exposed sides, and nothing else
+        let k = coverage.k_value().unwrap();
+        let exposed: usize = center
+            .to_cell(Resolution::Nine)
+            .grid_ring_fast(k)
+            .flatten()
+            .map(|cell| {
+                cell.edges()
+                    .filter(|edge| !disk.contains(&edge.destination()))
+                    .count()
+            })
+            .sum();
+        assert_eq!(edges.len(), exposed);
+        // Six corners with three sides exposed, the other cells with two
+        assert_eq!(edges.len(), 12 * k as usize + 6);
+        for edge in &edges {
+            assert!(disk.contains(&edge.origin()), "{edge}");
+            assert!(!disk.contains(&edge.destination()), "{edge}");
+        }
+    }
+
+    #[test]
+    fn test_perimeter_cells() {
+        let center = LatLng::new(33.4484, -112.0740).unwrap();
+        let builder = || SmallestEnclosingH3Builder::new(center, 1000.0, Resolution::Nine);
+        let coverage = builder().build().unwrap();
+        let mut perimeter = coverage.perimeter_cells().unwrap();
+        let mut ring = coverage.hexagons().unwrap();
+        perimeter.sort_unstable();
+        ring.sort_unstable();
+        assert_eq!(perimeter, ring);
+
+        // An annulus has an inner outline as well
+        let annulus = builder()
+            .inner_radius_meters(500.0)
+            .unwrap()
+            .build()
+            .unwrap();
+        let disk: HashSet<CellIndex> = annulus.disk().unwrap().into_iter().collect();
+        let perimeter = annulus.perimeter_cells().unwrap();
+        assert!(perimeter.len() > annulus.hexagons().unwrap().len());
+        for cell in &perimeter {
+            assert!(disk.contains(cell));
+            assert!(cell
+                .grid_disk::<Vec<_>>(1)
+                .iter()
+                .any(|neighbor| !disk.contains(neighbor)));
+        }
+    }
+
+    #[test]
+    fn test_hexagon_neighbors_graph() {
+        let center = LatLng::new(33.4484, -112.0740).unwrap();
+        let coverage = SmallestEnclosingH3Builder::new(center, 600.0, Resolution::Nine)
+            .build()
+            .unwrap();
+        assert_eq!(coverage.k_value().unwrap(), 2);
+        let graph = coverage.hexagon_neighbors_graph().unwrap();
+        assert_eq!(graph.len(), 19);
+
+        let center_cell = center.to_cell(Resolution::Nine);
+        assert_eq!(graph[&center_cell].len(), 6);
+        // The ring's corners have three neighbours in the disk, its sides four
+        let mut degrees: Vec<usize> = graph.values().map(Vec::len).collect();
+        degrees.sort_unstable();
+        assert_eq!(
+            degrees[..12],
+            [3; 6].into_iter().chain([4; 6]).collect::<Vec<_>>()
+        );
+        // Adjacency is symmetric
+        for (cell, neighbors) in &graph {
+            assert!(neighbors
+                .iter()
+                .all(|neighbor| graph[neighbor].contains(cell)));
+        }
+    }
+
+    #[test]
+    fn test_boundary_edges_of_pentagon() {
+        let pentagon = Resolution::Five.pentagons().next().unwrap();
+        let coverage =
+            SmallestEnclosingH3Builder::new(LatLng::from(pentagon), 1.0, Resolution::Five)
+                .build()
+                .unwrap();
+        assert_eq!(coverage.disk().unwrap(), vec![pentagon]);
+
+        let edges = coverage.boundary_edges().unwrap();
+        assert_eq!(edges.len(), 5);
+        assert!(edges.iter().all(|edge| edge.origin() == pentagon));
+    }
+
+    #[test]
+    fn test_topology_of_annulus() {
+        let center = LatLng::new(33.4484, -112.0740).unwrap();
+        let stats = SmallestEnclosingH3Builder::new(center, 50.0, Resolution::Twelve)
+            .build()
+            .unwrap()
+            .analyze_topology()
+            .unwrap();
+
+        assert_eq!((stats.components, stats.holes), (1, 1));
+        assert_eq!(stats.euler_characteristic, 0);
+        assert!(stats.is_consistent());
+    }
+
+    #[test]
+    fn test_topology_of_disjoint_union() {
+        let phoenix = LatLng::new(33.4484, -112.0740).unwrap();
+        let tucson = LatLng::new(32.2226, -110.9747).unwrap();
+        let union: Vec<CellIndex> = [phoenix, tucson]
+            .into_iter()
+            .flat_map(|c| c.to_cell(Resolution::Nine).grid_disk::<Vec<_>>(2))
+            .collect();
+
+        let stats = analyze_topology(&union.try_into().unwrap()).unwrap();
+        assert_eq!((stats.components, stats.holes), (2, 0));
+        assert_eq!(stats.euler_characteristic, 2);
+        assert!(stats.is_consistent());
+    }
+}
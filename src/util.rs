@@ -0,0 +1,273 @@
+//! Conversions between H3 cells and coordinates, returning this crate's error
+//! type.
+
+use crate::{estimate_ring_distance, expected_cell_count, resolution_info, CoverageMode, Result};
+use h3o::{CellIndex, LatLng, Resolution};
+
+/// The centroid of `cell`. Conversion from a valid `CellIndex` can't fail
+/// today; the `Result` keeps [`crate::error::SmallestEnclosingH3Error`] the
+/// single error surface should that change.
+pub fn hexagon_centroid(cell: CellIndex) -> Result<LatLng> {
+    Ok(LatLng::from(cell))
+}
+
+/// The cell with index `value`, such as one read back from storage. A
+/// corrupted index is a [`crate::error::SmallestEnclosingH3Error::InvalidCellIndex`]
+/// naming the value and what is wrong with it.
+pub fn cell_from_u64(value: u64) -> Result<CellIndex> {
+    Ok(CellIndex::try_from(value)?)
+}
+
+/// The cells with raw `indices`, in the same order, such as those of
+/// [`crate::SmallestEnclosingH3::hexagon_u64s`] or from the H3 C library.
+///
+/// Every index must be a valid cell at `resolution`; the first that isn't is
+/// a [`crate::error::SmallestEnclosingH3Error::InvalidCellIndex`] naming it.
+pub fn from_h3_index_slice(indices: &[u64], resolution: Resolution) -> Result<Vec<CellIndex>> {
+    indices
+        .iter()
+        .map(|&value| {
+            let cell = cell_from_u64(value)?;
+            if cell.resolution() != resolution {
+                return Err(h3o::error::InvalidCellIndex {
+                    value: Some(value),
+                    reason: "not at the expected resolution",
+                }
+                .into());
+            }
+            Ok(cell)
+        })
+        .collect()
+}
+
+/// The cell holding the point (`lat`, `lng`) in degrees at `resolution`.
+///
+/// Coordinates outside [-90, 90] and [-180, 180], or not finite, are a
+/// [`crate::error::SmallestEnclosingH3Error::InvalidLatLng`] rather than
+/// wrapped around.
+pub fn hex_id_at_point(lat: f64, lng: f64, resolution: Resolution) -> Result<CellIndex> {
+    crate::validate_center(lat, lng)?;
+    Ok(LatLng::new(lat, lng)?.to_cell(resolution))
+}
+
+/// [`hex_id_at_point`] for every `(lat, lng)` of `points`, in the same order.
+/// The first invalid point is the error.
+pub fn hex_ids_at_points(points: &[(f64, f64)], resolution: Resolution) -> Result<Vec<CellIndex>> {
+    points
+        .iter()
+        .map(|&(lat, lng)| hex_id_at_point(lat, lng, resolution))
+        .collect()
+}
+
+/// The resolution at which a circle of `radius_meters` around `center` is
+/// covered by the number of cells closest to `target` without exceeding it,
+/// for requests like "about 100 hexagons".
+///
+/// Counts are estimated, as [`expected_cell_count`] of the disk at the grid
+/// distance estimated from the local cell size, without computing any cells.
+/// The disk count grows as O(k²), about sevenfold per finer resolution, so
+/// the count at the resolution returned can be well under `target`. When
+/// even resolution 0 exceeds `target`, that coarsest resolution is returned.
+pub fn resolution_for_target_cell_count(
+    center: LatLng,
+    radius_meters: f64,
+    target: usize,
+) -> Resolution {
+    let estimated_cell_count = |resolution| {
+        let k = estimate_ring_distance(center.to_cell(resolution), radius_meters);
+        expected_cell_count(k, CoverageMode::Disk, false)
+    };
+    Resolution::range(Resolution::Zero, Resolution::Fifteen)
+        .map(|resolution| (resolution, estimated_cell_count(resolution)))
+        .filter(|&(_, count)| count <= target as u64)
+        .max_by_key(|&(_, count)| count)
+        .map_or(Resolution::Zero, |(resolution, _)| resolution)
+}
+
+/// The physical distance, in kilometers, of the grid distance between the
+/// cells of `from` and `to` at `resolution`: the hops times the average
+/// spacing between neighbouring cell centers, √3 times the average edge
+/// length (see [`resolution_info`]). Useful to calibrate widths given in grid
+/// hops, such as a corridor's buffer.
+///
+/// Grid paths zigzag and cells vary in size, so this approximates the
+/// great-circle distance, within about 20% for points up to a couple of
+/// hundred kilometers apart at a resolution fine enough to tell them apart.
+/// Cells whose grid distance spans pentagon distortion are a
+/// [`crate::error::SmallestEnclosingH3Error::GridDistanceError`].
+pub fn grid_distance_km(from: LatLng, to: LatLng, resolution: Resolution) -> Result<f64> {
+    let hops = from
+        .to_cell(resolution)
+        .grid_distance(to.to_cell(resolution))?;
+    Ok(f64::from(hops) * resolution_info(resolution).avg_cell_spacing_km)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use geo::{Contains, Point};
+
+    #[test]
+    fn test_centroid_within_cell() {
+        let center = LatLng::new(33.4484, -112.0740).unwrap();
+        let pentagon = Resolution::Five.pentagons().next().unwrap();
+        for cell in [center.to_cell(Resolution::Nine), pentagon] {
+            let centroid = hexagon_centroid(cell).unwrap();
+            let point = Point::new(centroid.lng(), centroid.lat());
+            assert!(crate::cell_polygon(cell).contains(&point));
+            assert_eq!(centroid.to_cell(cell.resolution()), cell);
+        }
+    }
+
+    #[test]
+    fn test_cell_from_u64() {
+        let cell = LatLng::new(33.4484, -112.0740)
+            .unwrap()
+            .to_cell(Resolution::Nine);
+        assert_eq!(cell_from_u64(u64::from(cell)).unwrap(), cell);
+
+        // A flipped mode bit, as from a corrupted store
+        assert!(matches!(
+            cell_from_u64(u64::from(cell) ^ (1 << 59)),
+            Err(crate::error::SmallestEnclosingH3Error::InvalidCellIndex(_))
+        ));
+        assert!(cell_from_u64(0).is_err());
+    }
+
+    #[test]
+    fn test_h3_index_round_trip() {
+        // The resolution 12 Phoenix cells of the Python reference
+        let indices = [0x8c29b6d357aa7ff, 0x8c29b6d357a33ff, 0x8c29b6d357853ff];
+        let cells = from_h3_index_slice(&indices, Resolution::Twelve).unwrap();
+        assert_eq!(cells[0].to_string(), "8c29b6d357aa7ff");
+        assert_eq!(
+            cells.iter().map(|c| u64::from(*c)).collect::<Vec<_>>(),
+            indices
+        );
+
+        let center = LatLng::new(33.4484, -112.0740).unwrap();
+        let coverage = crate::SmallestEnclosingH3Builder::new(center, 50.0, Resolution::Twelve)
+            .build()
+            .unwrap();
+        let u64s = coverage.hexagon_u64s().unwrap();
+        assert_eq!(
+            from_h3_index_slice(&u64s, Resolution::Twelve).unwrap(),
+            coverage.hexagons().unwrap()
+        );
+        assert!(indices.iter().all(|index| u64s.contains(index)));
+
+        // A wrong resolution or a corrupted index
+        assert!(matches!(
+            from_h3_index_slice(&indices, Resolution::Eleven),
+            Err(crate::error::SmallestEnclosingH3Error::InvalidCellIndex(_))
+        ));
+        assert!(from_h3_index_slice(&[indices[0], 0], Resolution::Twelve).is_err());
+    }
+
+    #[test]
+    fn test_resolution_for_target_cell_count() {
+        let center = LatLng::new(33.4484, -112.0740).unwrap();
+        for (radius, target, expected) in [
+            (1000.0, 100, Resolution::Nine),
+            (500.0, 1000, Resolution::Eleven),
+            (50_000.0, 100, Resolution::Five),
+            (10.0, 7, Resolution::Thirteen),
+            (1000.0, 1, Resolution::Seven),
+        ] {
+            assert_eq!(
+                resolution_for_target_cell_count(center, radius, target),
+                expected,
+                "{radius} m for {target} cells"
+            );
+        }
+
+        // A larger target never asks for a coarser resolution
+        let resolutions: Vec<Resolution> = [1, 7, 19, 100, 1000, 10_000, 100_000]
+            .into_iter()
+            .map(|target| resolution_for_target_cell_count(center, 2000.0, target))
+            .collect();
+        assert!(resolutions.windows(2).all(|pair| pair[0] <= pair[1]));
+
+        // Even resolution 0 needs more than one cell for a continent
+        assert_eq!(
+            resolution_for_target_cell_count(center, 3_000_000.0, 1),
+            Resolution::Zero
+        );
+    }
+
+    #[test]
+    fn test_grid_distance_km() {
+        let phoenix = LatLng::new(33.4484, -112.0740).unwrap();
+        let destinations = [
+            (33.4255, -111.9400), // Tempe, 13 km
+            (32.2226, -110.9747), // Tucson, 170 km
+            (34.5400, -112.4685), // Prescott, 125 km
+            (33.4484, -113.5000), // due west, 132 km
+            (34.6000, -112.0740), // due north, 128 km
+        ];
+        for (lat, lng) in destinations {
+            let to = LatLng::new(lat, lng).unwrap();
+            let haversine_km = crate::haversine_distance(phoenix, to) / 1000.0;
+            for resolution in [Resolution::Six, Resolution::Seven, Resolution::Nine] {
+                let km = grid_distance_km(phoenix, to, resolution).unwrap();
+                assert!(
+                    (km - haversine_km).abs() <= 0.2 * haversine_km,
+                    "{km} km against {haversine_km} km at {resolution}"
+                );
+            }
+        }
+        assert_eq!(
+            grid_distance_km(phoenix, phoenix, Resolution::Nine).unwrap(),
+            0.0
+        );
+
+        // Across a pentagon, 300 km either side of it
+        let pentagon = LatLng::from(Resolution::Five.pentagons().next().unwrap());
+        let at = |bearing: f64| {
+            let (lat, lng) = crate::geodesy::destination_point(
+                (pentagon.lat(), pentagon.lng()),
+                300_000.0,
+                bearing.to_radians(),
+            );
+            LatLng::new(lat, lng).unwrap()
+        };
+        assert!(matches!(
+            grid_distance_km(at(90.0), at(270.0), Resolution::Seven),
+            Err(crate::error::SmallestEnclosingH3Error::GridDistanceError(_))
+        ));
+    }
+
+    #[test]
+    fn test_hex_id_at_point() {
+        let cell = hex_id_at_point(33.4484, -112.0740, Resolution::Nine).unwrap();
+        assert_eq!(
+            cell,
+            LatLng::new(33.4484, -112.0740)
+                .unwrap()
+                .to_cell(Resolution::Nine)
+        );
+        for (lat, lng) in [
+            (91.0, 0.0),
+            (0.0, 180.5),
+            (f64::NAN, 0.0),
+            (0.0, f64::INFINITY),
+        ] {
+            assert!(matches!(
+                hex_id_at_point(lat, lng, Resolution::Nine),
+                Err(crate::error::SmallestEnclosingH3Error::InvalidLatLng(_))
+            ));
+        }
+
+        let points = [(33.4484, -112.0740), (40.7128, -74.0060)];
+        let cells = hex_ids_at_points(&points, Resolution::Seven).unwrap();
+        assert_eq!(cells.len(), 2);
+        assert_eq!(
+            cells[1],
+            hex_id_at_point(40.7128, -74.0060, Resolution::Seven).unwrap()
+        );
+        assert!(hex_ids_at_points(&[(0.0, 0.0), (-90.5, 0.0)], Resolution::Seven).is_err());
+        assert!(hex_ids_at_points(&[], Resolution::Seven)
+            .unwrap()
+            .is_empty());
+    }
+}
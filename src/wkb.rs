@@ -0,0 +1,190 @@
+//! Well-known binary (WKB) polygon output, for bulk loading into PostGIS and
+//! other spatial databases without a GeoJSON round trip.
+
+use crate::{Result, SmallestEnclosingH3};
+use h3o::CellIndex;
+
+/// The SRID written by [`WkbOptions::srid`], for longitude and latitude in
+/// degrees (EPSG:4326)
+pub const WGS84_SRID: u32 = 4326;
+
+const WKB_POLYGON: u32 = 3;
+const EWKB_SRID_FLAG: u32 = 0x2000_0000;
+
+/// Output options for [`SmallestEnclosingH3::to_wkb_with_options`] and
+/// [`SmallestEnclosingH3::circle_wkb_with_options`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct WkbOptions {
+    /// Write PostGIS extended WKB (EWKB) carrying `SRID=4326`, so a
+    /// `geometry(Polygon, 4326)` column takes the bytes as they are
+    pub srid: bool,
+}
+
+impl SmallestEnclosingH3 {
+    /// One little-endian WKB polygon per cell of [`Self::hexagons`], in the
+    /// same order, with `[lng, lat]` vertices in degrees
+    pub fn to_wkb(&self) -> Result<Vec<Vec<u8>>> {
+        self.to_wkb_with_options(&WkbOptions::default())
+    }
+
+    /// [`Self::to_wkb`] with [`WkbOptions`]
+    pub fn to_wkb_with_options(&self, opts: &WkbOptions) -> Result<Vec<Vec<u8>>> {
+        Ok(self
+            .hexagons()?
+            .into_iter()
+            .map(|cell| cell_wkb(cell, opts))
+            .collect())
+    }
+
+    /// The circle of [`Self::generate_circle_coordinates`] as a little-endian
+    /// WKB polygon
+    pub fn circle_wkb(&self) -> Result<Vec<u8>> {
+        self.circle_wkb_with_options(&WkbOptions::default())
+    }
+
+    /// [`Self::circle_wkb`] with [`WkbOptions`]
+    pub fn circle_wkb_with_options(&self, opts: &WkbOptions) -> Result<Vec<u8>> {
+        let coordinates = self.generate_circle_coordinates()?;
+        Ok(polygon_wkb(
+            coordinates.iter().map(|c| (c[0], c[1])),
+            coordinates.len(),
+            opts,
+        ))
+    }
+}
+
+/// The boundary of `cell` as a little-endian WKB polygon, closed by
+/// repeating its first vertex
+pub fn cell_wkb(cell: CellIndex, opts: &WkbOptions) -> Vec<u8> {
+    let boundary = cell.boundary();
+    let ring = boundary.iter().chain(boundary.iter().take(1));
+    polygon_wkb(ring.map(|p| (p.lng(), p.lat())), boundary.len() + 1, opts)
+}
+
+// A polygon of the single closed ring `ring` of `len` points
+fn polygon_wkb(ring: impl Iterator<Item = (f64, f64)>, len: usize, opts: &WkbOptions) -> Vec<u8> {
+    let mut wkb = Vec::with_capacity(17 + 16 * len);
+    wkb.push(1); // little-endian
+    if opts.srid {
+        wkb.extend_from_slice(&(WKB_POLYGON | EWKB_SRID_FLAG).to_le_bytes());
+        wkb.extend_from_slice(&WGS84_SRID.to_le_bytes());
+    } else {
+        wkb.extend_from_slice(&WKB_POLYGON.to_le_bytes());
+    }
+    wkb.extend_from_slice(&1u32.to_le_bytes());
+    wkb.extend_from_slice(&(len as u32).to_le_bytes());
+    for (x, y) in ring {
+        wkb.extend_from_slice(&x.to_le_bytes());
+        wkb.extend_from_slice(&y.to_le_bytes());
+    }
+    wkb
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SmallestEnclosingH3Builder;
+    use h3o::{LatLng, Resolution};
+
+    // The SRID and the ring of a single-ring little-endian polygon, checking
+    // every header field on the way
+    fn decode(wkb: &[u8]) -> (Option<u32>, Vec<(f64, f64)>) {
+        let u32_at = |i: usize| u32::from_le_bytes(wkb[i..i + 4].try_into().unwrap());
+        let f64_at = |i: usize| f64::from_le_bytes(wkb[i..i + 8].try_into().unwrap());
+        assert_eq!(wkb[0], 1);
+
+        let geometry_type = u32_at(1);
+        let (srid, mut offset) = if geometry_type & EWKB_SRID_FLAG != 0 {
+            (Some(u32_at(5)), 9)
+        } else {
+            (None, 5)
+        };
+        assert_eq!(geometry_type & !EWKB_SRID_FLAG, WKB_POLYGON);
+        assert_eq!(u32_at(offset), 1);
+        let len = u32_at(offset + 4) as usize;
+        offset += 8;
+        assert_eq!(wkb.len(), offset + 16 * len);
+
+        let ring = (0..len)
+            .map(|i| (f64_at(offset + 16 * i), f64_at(offset + 16 * i + 8)))
+            .collect();
+        (srid, ring)
+    }
+
+    fn phoenix() -> SmallestEnclosingH3 {
+        let center = LatLng::new(33.4484, -112.0740).unwrap();
+        SmallestEnclosingH3Builder::new(center, 500.0, Resolution::Ten)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_hexagon_wkb() {
+        let coverage = phoenix();
+        let hexagons = coverage.hexagons().unwrap();
+        let polygons = coverage.to_wkb().unwrap();
+        assert_eq!(polygons.len(), hexagons.len());
+
+        for (cell, wkb) in hexagons.iter().zip(&polygons) {
+            let (srid, ring) = decode(wkb);
+            assert_eq!(srid, None);
+            assert_eq!(ring.len(), cell.boundary().len() + 1);
+            assert_eq!(ring.first(), ring.last());
+            for (vertex, (lng, lat)) in cell.boundary().iter().zip(ring) {
+                assert!((vertex.lng() - lng).abs() < 1e-12 && (vertex.lat() - lat).abs() < 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn test_circle_ewkb() {
+        let coverage = phoenix();
+        let (srid, ring) = decode(&coverage.circle_wkb().unwrap());
+        assert_eq!(srid, None);
+        let coordinates = coverage.generate_circle_coordinates().unwrap();
+        assert_eq!(ring.len(), coordinates.len());
+        for (c, (lng, lat)) in coordinates.iter().zip(ring) {
+            assert!((c[0] - lng).abs() < 1e-12 && (c[1] - lat).abs() < 1e-12);
+        }
+
+        // EWKB only adds the SRID after the geometry type
+        let opts = WkbOptions { srid: true };
+        let ewkb = coverage.circle_wkb_with_options(&opts).unwrap();
+        let wkb = coverage.circle_wkb().unwrap();
+        assert_eq!(decode(&ewkb).0, Some(WGS84_SRID));
+        assert_eq!(ewkb.len(), wkb.len() + 4);
+        assert_eq!(ewkb[9..], wkb[5..]);
+        assert_eq!(&ewkb[1..5], &[3, 0, 0, 0x20]);
+
+        for wkb in coverage.to_wkb_with_options(&opts).unwrap() {
+            assert_eq!(decode(&wkb).0, Some(WGS84_SRID));
+        }
+    }
+
+    #[cfg(feature = "geojson_export")]
+    #[test]
+    fn test_wkb_matches_geojson() {
+        use geojson::Value;
+
+        let coverage = phoenix();
+        let collection = coverage.to_geojson().unwrap();
+        let polygons = coverage.to_wkb().unwrap();
+        let features: Vec<_> = collection
+            .features
+            .iter()
+            .filter(|f| f.property("type").and_then(|t| t.as_str()) == Some("hexagon"))
+            .collect();
+        assert_eq!(features.len(), polygons.len());
+
+        for (feature, wkb) in features.into_iter().zip(&polygons) {
+            let Some(Value::Polygon(rings)) = feature.geometry.as_ref().map(|g| &g.value) else {
+                panic!("{feature:?} isn't a polygon");
+            };
+            let ring = decode(wkb).1;
+            assert_eq!(rings[0].len(), ring.len());
+            for (c, (lng, lat)) in rings[0].iter().zip(ring) {
+                assert!((c[0] - lng).abs() < 1e-9 && (c[1] - lat).abs() < 1e-9);
+            }
+        }
+    }
+}
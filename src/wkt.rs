@@ -0,0 +1,136 @@
+//! Well-known text (WKT) point input.
+
+use crate::{error::SmallestEnclosingH3Error, validate_center, Result, SmallestEnclosingH3Builder};
+use h3o::{LatLng, Resolution};
+
+impl SmallestEnclosingH3Builder {
+    /// A builder centered on the WKT point `wkt`, see [`parse_wkt_point`]
+    pub fn from_wkt_point(wkt: &str, radius_meters: f64, resolution: Resolution) -> Result<Self> {
+        Ok(Self::new(parse_wkt_point(wkt)?, radius_meters, resolution))
+    }
+}
+
+/// Parse a WKT `POINT(lng lat)`, as stored by most spatial databases.
+///
+/// The keyword is case-insensitive and whitespace is allowed around the
+/// parentheses. Malformed text is a [`SmallestEnclosingH3Error::ParseError`]
+/// quoting `wkt`, and coordinates outside [-90, 90] / [-180, 180] are a
+/// [`SmallestEnclosingH3Error::InvalidLatLng`].
+pub fn parse_wkt_point(wkt: &str) -> Result<LatLng> {
+    let error = |offset: usize, reason: &str| SmallestEnclosingH3Error::ParseError {
+        offset,
+        reason: format!("{reason} in WKT point {wkt:?}"),
+    };
+
+    let start = wkt.len() - wkt.trim_start().len();
+    let rest = &wkt[start..];
+    if !rest
+        .get(..5)
+        .is_some_and(|keyword| keyword.eq_ignore_ascii_case("POINT"))
+    {
+        return Err(error(start, "expected POINT"));
+    }
+
+    let open = start + 5 + (rest[5..].len() - rest[5..].trim_start().len());
+    if !wkt[open..].starts_with('(') {
+        return Err(error(open, "expected '('"));
+    }
+    let Some(close) = wkt[open..].find(')').map(|i| open + i) else {
+        return Err(error(wkt.len(), "expected ')'"));
+    };
+    if !wkt[close + 1..].trim().is_empty() {
+        return Err(error(close + 1, "unexpected trailing text"));
+    }
+
+    // Coordinates, with the byte offset each starts at
+    let body = &wkt[open + 1..close];
+    let coordinates: Vec<(usize, &str)> = body
+        .split_whitespace()
+        .map(|token| {
+            (
+                open + 1 + (token.as_ptr() as usize - body.as_ptr() as usize),
+                token,
+            )
+        })
+        .collect();
+    let &[(lng_offset, lng), (lat_offset, lat)] = coordinates.as_slice() else {
+        return Err(error(open + 1, "expected two coordinates"));
+    };
+    let lng: f64 = lng
+        .parse()
+        .map_err(|_| error(lng_offset, "invalid longitude"))?;
+    let lat: f64 = lat
+        .parse()
+        .map_err(|_| error(lat_offset, "invalid latitude"))?;
+
+    validate_center(lat, lng)?;
+    Ok(LatLng::new(lat, lng)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_wkt_point() {
+        let expected = LatLng::new(33.4484, -112.074).unwrap();
+        for wkt in [
+            "POINT(-112.074 33.4484)",
+            "point (-112.074 33.4484)",
+            "  POINT ( -112.074   33.4484 )  ",
+        ] {
+            assert_eq!(parse_wkt_point(wkt).unwrap(), expected, "{wkt}");
+        }
+
+        let builder = SmallestEnclosingH3Builder::from_wkt_point(
+            "POINT(-112.074 33.4484)",
+            50.0,
+            Resolution::Twelve,
+        )
+        .unwrap();
+        let from_lat_lng = SmallestEnclosingH3Builder::new(expected, 50.0, Resolution::Twelve);
+        assert_eq!(
+            builder.build().unwrap().hexagons().unwrap(),
+            from_lat_lng.build().unwrap().hexagons().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_malformed_wkt_point() {
+        for (wkt, expected_offset) in [
+            ("LINESTRING(0 0, 1 1)", 0),
+            ("POINT 0 0", 6),
+            ("POINT(0 0", 9),
+            ("POINT(0 0) extra", 10),
+            ("POINT(0)", 6),
+            ("POINT(0 0 0)", 6),
+            ("POINT(0 north)", 8),
+            ("POINT EMPTY", 6),
+        ] {
+            match parse_wkt_point(wkt) {
+                Err(SmallestEnclosingH3Error::ParseError { offset, reason }) => {
+                    assert_eq!(offset, expected_offset, "{wkt}");
+                    assert!(reason.contains(wkt), "{reason}");
+                }
+                other => panic!("Expected a parse error for {wkt}, got {other:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_wkt_point_out_of_range() {
+        // Latitude and longitude swapped
+        assert!(matches!(
+            parse_wkt_point("POINT(33.4484 -112.074)"),
+            Err(SmallestEnclosingH3Error::InvalidLatLng(_))
+        ));
+        assert!(matches!(
+            parse_wkt_point("POINT(181 0)"),
+            Err(SmallestEnclosingH3Error::InvalidLatLng(_))
+        ));
+        assert!(matches!(
+            parse_wkt_point("POINT(NaN 0)"),
+            Err(SmallestEnclosingH3Error::InvalidLatLng(_))
+        ));
+    }
+}
@@ -0,0 +1,113 @@
+#![cfg(feature = "cli")]
+
+use std::process::{Command, Output};
+
+fn run(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_smallest-enclosing-h3"))
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+const PHOENIX: [&str; 6] = ["--lat", "33.4484", "--lng", "-112.0740", "--radius-m", "50"];
+
+#[test]
+fn test_geojson_to_stdout() {
+    let output = run(&[&PHOENIX[..], &["--resolution", "12"]].concat());
+    assert!(output.status.success());
+
+    let geojson: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let features = geojson["features"].as_array().unwrap();
+    let hexagons = features
+        .iter()
+        .filter(|feature| feature["properties"]["type"] == "hexagon")
+        .count();
+    assert_eq!(hexagons, 18);
+    assert_eq!(features.len(), hexagons + 1);
+}
+
+#[test]
+fn test_txt_to_file() {
+    let path = std::env::temp_dir().join("smallest-enclosing-h3-cli-test.txt");
+    let path = path.to_str().unwrap();
+    let output = run(&[
+        &PHOENIX[..],
+        &["--resolution", "12", "--format", "txt", "--output", path],
+    ]
+    .concat());
+    assert!(output.status.success());
+    assert!(output.stdout.is_empty());
+
+    let cells = std::fs::read_to_string(path).unwrap();
+    std::fs::remove_file(path).unwrap();
+    assert_eq!(cells.lines().count(), 18);
+    assert!(cells
+        .lines()
+        .all(|cell| cell.parse::<h3o::CellIndex>().is_ok()));
+}
+
+#[test]
+fn test_csv() {
+    let output = run(&[&PHOENIX[..], &["--resolution", "12", "--format", "csv"]].concat());
+    assert!(output.status.success());
+    let csv = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(csv.lines().next(), Some("h3_index"));
+    assert_eq!(csv.lines().count(), 19);
+}
+
+#[test]
+fn test_invalid_resolution() {
+    for resolution in ["16", "300", "-1"] {
+        let output = run(&[&PHOENIX[..], &["--resolution", resolution]].concat());
+        assert_eq!(output.status.code(), Some(2));
+        let stderr = String::from_utf8(output.stderr).unwrap();
+        assert!(
+            stderr.starts_with("error: Invalid resolution: "),
+            "{stderr}"
+        );
+    }
+}
+
+#[test]
+fn test_validation_and_io_exit_codes() {
+    // Missing and malformed arguments
+    assert_eq!(run(&PHOENIX).status.code(), Some(2));
+    let output = run(&[
+        "--lat",
+        "north",
+        "--lng",
+        "0",
+        "--radius-m",
+        "1",
+        "--resolution",
+        "9",
+    ]);
+    assert_eq!(output.status.code(), Some(2));
+    let output = run(&[&PHOENIX[..], &["--resolution", "9", "--format", "kml"]].concat());
+    assert_eq!(output.status.code(), Some(2));
+
+    // A radius the builder rejects
+    let output = run(&[
+        "--lat",
+        "0",
+        "--lng",
+        "0",
+        "--radius-m",
+        "-5",
+        "--resolution",
+        "9",
+    ]);
+    assert_eq!(output.status.code(), Some(2));
+
+    let output = run(&[
+        &PHOENIX[..],
+        &[
+            "--resolution",
+            "9",
+            "--output",
+            "/nonexistent/dir/out.geojson",
+        ],
+    ]
+    .concat());
+    assert_eq!(output.status.code(), Some(3));
+}
@@ -0,0 +1,37 @@
+#![cfg(feature = "h3")]
+
+//! One circle covered at every resolution, from a single cell at resolution
+//! 0 to hundreds of thousands of ring cells at resolution 15.
+
+use h3o::{LatLng, Resolution};
+use smallest_enclosing_h3::SmallestEnclosingH3Builder;
+
+#[test]
+fn test_hexagons_at_every_resolution() {
+    // Phoenix, 50 km
+    let center = LatLng::new(33.4484, -112.0740).unwrap();
+    let counts: Vec<usize> = Resolution::range(Resolution::Zero, Resolution::Fifteen)
+        .map(|resolution| {
+            // The ring alone stays small, but the disk the limit is checked
+            // against runs into billions of cells at the finest resolutions
+            let hexagons = SmallestEnclosingH3Builder::new(center, 50_000.0, resolution)
+                .max_cells(usize::MAX)
+                .build()
+                .unwrap()
+                .hexagons()
+                .unwrap();
+            assert!(
+                hexagons.iter().all(|cell| cell.resolution() == resolution),
+                "{resolution}"
+            );
+            hexagons.len()
+        })
+        .collect();
+
+    assert_eq!(counts[0], 1, "{counts:?}");
+    assert!(
+        counts.windows(2).all(|pair| pair[0] <= pair[1]),
+        "{counts:?}"
+    );
+    assert!(counts[15] > 100_000, "{counts:?}");
+}
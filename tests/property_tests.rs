@@ -0,0 +1,130 @@
+#![cfg(feature = "h3")]
+
+//! Coverage invariants over generated `(lat, lng, radius_meters, resolution)`
+//! inputs, polar latitudes and sub-meter radii included.
+//!
+//! Inputs come from a small fixed-seed generator rather than a property
+//! testing crate, so every CI run checks the same cases; a failure message
+//! carries the case to reproduce it with.
+
+use h3o::{LatLng, Resolution};
+use smallest_enclosing_h3::{
+    expected_cell_count, geodesy::EARTH_RADIUS, resolution_info, CoverageMode,
+    SmallestEnclosingH3Builder,
+};
+
+const SEED: u64 = 0x5eed_0301;
+const CASES: usize = 256;
+
+// SplitMix64
+struct Generator(u64);
+
+impl Generator {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    // Uniform in [low, high)
+    fn range(&mut self, low: f64, high: f64) -> f64 {
+        let unit = (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64;
+        low + unit * (high - low)
+    }
+}
+
+#[derive(Debug)]
+struct Case {
+    lat: f64,
+    lng: f64,
+    radius_meters: f64,
+    resolution: Resolution,
+}
+
+// Resolutions 5 to 15, with radii log-uniform from 10 cm up to about 25 rings
+// of cells, so no case generates more than a couple of thousand cells. One
+// case in eight sits within a degree of a pole.
+fn cases() -> Vec<Case> {
+    let mut generator = Generator(SEED);
+    (0..CASES)
+        .map(|i| {
+            let resolution = Resolution::try_from(generator.range(5.0, 16.0) as u8).unwrap();
+            let max_radius = 25_000.0 * resolution_info(resolution).avg_cell_spacing_km;
+            let lat = if i % 8 == 0 {
+                generator
+                    .range(89.0, 90.0)
+                    .copysign(generator.range(-1.0, 1.0))
+            } else {
+                generator.range(-90.0, 90.0)
+            };
+            Case {
+                lat,
+                lng: generator.range(-180.0, 180.0),
+                radius_meters: generator.range(0.1f64.ln(), max_radius.ln()).exp(),
+                resolution,
+            }
+        })
+        .collect()
+}
+
+#[test]
+fn test_hexagons_never_panic() {
+    for case in cases() {
+        let center = LatLng::new(case.lat, case.lng).unwrap();
+        let builder = SmallestEnclosingH3Builder::new(center, case.radius_meters, case.resolution);
+        if let Ok(coverage) = builder.build() {
+            let _ = coverage.hexagons();
+        }
+    }
+}
+
+#[test]
+fn test_coverage_invariants() {
+    let mut checked = 0;
+    for case in cases() {
+        let center = LatLng::new(case.lat, case.lng).unwrap();
+        let Ok(coverage) =
+            SmallestEnclosingH3Builder::new(center, case.radius_meters, case.resolution).build()
+        else {
+            continue;
+        };
+        let Ok(disk) = coverage.disk() else {
+            continue;
+        };
+        checked += 1;
+
+        // The disk always contains the center cell
+        assert!(disk.contains(&center.to_cell(case.resolution)), "{case:?}");
+
+        // Its size is the closed form for k, bounded below by the count
+        // around a pentagon where one is nearby
+        let k = coverage.k_value().unwrap();
+        let cells = disk.len() as u64;
+        if disk.iter().any(|cell| cell.is_pentagon()) {
+            assert!(
+                (expected_cell_count(k, CoverageMode::Disk, true)
+                    ..=expected_cell_count(k, CoverageMode::Disk, false))
+                    .contains(&cells),
+                "{case:?}"
+            );
+        } else {
+            assert_eq!(
+                cells,
+                expected_cell_count(k, CoverageMode::Disk, false),
+                "{case:?}"
+            );
+        }
+
+        // The cells cover at least the area of the spherical cap
+        let cap_area = 2.0
+            * std::f64::consts::PI
+            * EARTH_RADIUS.powi(2)
+            * (1.0 - (case.radius_meters / EARTH_RADIUS).cos());
+        let covered_area: f64 = disk.iter().map(|cell| cell.area_m2()).sum();
+        assert!(covered_area / cap_area >= 1.0, "{case:?}");
+    }
+    // Guard against the generator drifting into only rejected inputs
+    assert!(checked > CASES / 2, "only {checked} cases checked");
+}